@@ -20,6 +20,7 @@ use crate::{
     core::{Core, CoreSignals},
     core_thread::{ChannelCoreThreadDispatcher, CoreThreadHandle},
     dag_state::DagState,
+    leader_schedule::LeaderSchedule,
     leader_timeout::{LeaderTimeoutTask, LeaderTimeoutTaskHandle},
     metrics::initialise_metrics,
     network::{
@@ -206,15 +207,18 @@ where
         let block_manager =
             BlockManager::new(context.clone(), dag_state.clone(), block_verifier.clone());
 
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
         let commit_observer = CommitObserver::new(
             context.clone(),
             commit_consumer,
             dag_state.clone(),
             store.clone(),
+            leader_schedule.clone(),
         );
 
         let core = Core::new(
             context.clone(),
+            leader_schedule,
             tx_consumer,
             block_manager,
             commit_observer,
@@ -351,7 +355,10 @@ mod tests {
         context::Context,
         core_thread::{CoreError, CoreThreadDispatcher},
         error::ConsensusResult,
-        network::{BlockStream, NetworkClient, NetworkService as _},
+        network::{
+            fault_injection::{FaultInjectingManager, FaultScenario, LatencyDistribution},
+            BlockStream, NetworkClient, NetworkService as _,
+        },
         storage::mem_store::MemStore,
         transaction::NoopTransactionVerifier,
     };
@@ -641,4 +648,101 @@ mod tests {
             authority.stop().await;
         }
     }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_authority_committee_with_network_faults() {
+        let (committee, keypairs) = local_committee_and_keys(0, vec![1, 1, 1, 1]);
+        let temp_dirs = (0..4).map(|_| TempDir::new().unwrap()).collect::<Vec<_>>();
+
+        let mut output_receivers = vec![];
+        let mut authorities = vec![];
+        for (index, _authority_info) in committee.authorities() {
+            let parameters = Parameters {
+                db_path: Some(temp_dirs[index.value()].path().to_path_buf()),
+                ..Default::default()
+            };
+            let (sender, receiver) = unbounded_channel();
+            let authority = AuthorityNode::<FaultInjectingManager<_, TonicManager>>::start(
+                index,
+                committee.clone(),
+                parameters,
+                ProtocolConfig::get_for_max_version_UNSAFE(),
+                keypairs[index].1.clone(),
+                keypairs[index].0.clone(),
+                Arc::new(NoopTransactionVerifier {}),
+                CommitConsumer::new(sender, 0, 0),
+                Registry::new(),
+            )
+            .await;
+            output_receivers.push(receiver);
+            authorities.push(authority);
+        }
+
+        // Authority 3 equivocates over lossy links with variable latencies, and the committee
+        // is partitioned into two halves that cannot form a quorum.
+        let scenario = FaultScenario::default()
+            .with_default_latency(LatencyDistribution::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(100),
+            })
+            .with_drop_probability(0.01)
+            .with_reordering(0.1, Duration::from_millis(200))
+            .with_equivocator(
+                committee.to_authority_index(3).unwrap(),
+                keypairs[3].1.clone(),
+            );
+        let partitioned_scenario = scenario.clone().with_partitions(
+            committee
+                .authorities()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|group| group.to_vec())
+                .collect(),
+        );
+        for authority in &authorities {
+            authority
+                .network_manager
+                .faults()
+                .set_scenario(partitioned_scenario.clone());
+        }
+        sleep(Duration::from_secs(10)).await;
+
+        // Heal the partition and let the committee make progress.
+        for authority in &authorities {
+            authority
+                .network_manager
+                .faults()
+                .set_scenario(scenario.clone());
+        }
+
+        // All authorities commit the same sub-dags, including the equivocating blocks.
+        const NUM_COMMITS: usize = 10;
+        let mut committed = vec![];
+        for receiver in &mut output_receivers {
+            let mut commits = vec![];
+            while commits.len() < NUM_COMMITS {
+                let committed_subdag =
+                    tokio::time::timeout(Duration::from_secs(60), receiver.recv())
+                        .await
+                        .unwrap()
+                        .unwrap();
+                commits.push((
+                    committed_subdag.commit_index,
+                    committed_subdag.leader,
+                    committed_subdag
+                        .blocks
+                        .iter()
+                        .map(|b| b.reference())
+                        .collect::<Vec<_>>(),
+                ));
+            }
+            committed.push(commits);
+        }
+        assert!(committed.windows(2).all(|pair| pair[0] == pair[1]));
+
+        for authority in authorities {
+            authority.stop().await;
+        }
+    }
 }
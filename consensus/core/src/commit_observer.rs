@@ -12,6 +12,7 @@ use crate::{
     context::Context,
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
+    leader_schedule::LeaderSchedule,
     linearizer::Linearizer,
     storage::Store,
     CommitConsumer,
@@ -47,10 +48,11 @@ impl CommitObserver {
         // last_processed_commit_index: CommitIndex,
         dag_state: Arc<RwLock<DagState>>,
         store: Arc<dyn Store>,
+        leader_schedule: LeaderSchedule,
     ) -> Self {
         let mut observer = Self {
             context,
-            commit_interpreter: Linearizer::new(dag_state.clone()),
+            commit_interpreter: Linearizer::new(dag_state.clone(), leader_schedule),
             sender: commit_consumer.sender,
             store,
         };
@@ -203,6 +205,7 @@ mod tests {
             ),
             dag_state.clone(),
             mem_store.clone(),
+            leader_schedule.clone(),
         );
 
         // Populate fully connected test blocks for round 0 ~ 10, authorities 0 ~ 3.
@@ -210,7 +213,7 @@ mod tests {
         build_dag(context.clone(), dag_state.clone(), None, num_rounds);
         let leaders = get_all_uncommitted_leader_blocks(
             dag_state.clone(),
-            leader_schedule,
+            leader_schedule.clone(),
             num_rounds,
             DEFAULT_WAVE_LENGTH,
             false,
@@ -293,6 +296,7 @@ mod tests {
             ),
             dag_state.clone(),
             mem_store.clone(),
+            leader_schedule.clone(),
         );
 
         // Populate fully connected test blocks for round 0 ~ 10, authorities 0 ~ 3.
@@ -300,7 +304,7 @@ mod tests {
         build_dag(context.clone(), dag_state.clone(), None, num_rounds);
         let leaders = get_all_uncommitted_leader_blocks(
             dag_state.clone(),
-            leader_schedule,
+            leader_schedule.clone(),
             num_rounds,
             DEFAULT_WAVE_LENGTH,
             false,
@@ -388,6 +392,7 @@ mod tests {
             ),
             dag_state.clone(),
             mem_store.clone(),
+            leader_schedule.clone(),
         );
 
         // Check commits sent over consensus output channel is accurate starting
@@ -430,6 +435,7 @@ mod tests {
             ),
             dag_state.clone(),
             mem_store.clone(),
+            leader_schedule.clone(),
         );
 
         // Populate fully connected test blocks for round 0 ~ 10, authorities 0 ~ 3.
@@ -437,7 +443,7 @@ mod tests {
         build_dag(context.clone(), dag_state.clone(), None, num_rounds);
         let leaders = get_all_uncommitted_leader_blocks(
             dag_state.clone(),
-            leader_schedule,
+            leader_schedule.clone(),
             num_rounds,
             DEFAULT_WAVE_LENGTH,
             false,
@@ -483,6 +489,7 @@ mod tests {
             ),
             dag_state.clone(),
             mem_store.clone(),
+            leader_schedule.clone(),
         );
 
         // No commits should be resubmitted as consensus store's last commit index
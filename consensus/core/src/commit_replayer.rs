@@ -0,0 +1,298 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use consensus_config::Committee;
+use parking_lot::RwLock;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::{
+    block::{BlockAPI, BlockRef, Round, Slot, VerifiedBlock},
+    commit::{CommitAPI, CommitIndex, CommittedSubDag, TrustedCommit},
+    context::Context,
+    core::NUM_LEADERS_PER_ROUND,
+    dag_state::DagState,
+    error::ConsensusResult,
+    leader_schedule::LeaderSchedule,
+    linearizer::Linearizer,
+    storage::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store},
+    universal_committer::{
+        universal_committer_builder::UniversalCommitterBuilder, UniversalCommitter,
+    },
+};
+
+/// Replays the commit rule over the blocks persisted by an authority, and checks the resulting
+/// commits against the ones recorded in its store.
+///
+/// Blocks are accepted round by round into a fresh `DagState` starting from genesis, so the
+/// replay is deterministic and does not depend on the order in which the authority originally
+/// received the blocks. All blocks of the epoch are loaded into memory.
+pub struct CommitReplayer {
+    context: Arc<Context>,
+    store: Arc<dyn Store>,
+    number_of_leaders: usize,
+    pipeline: bool,
+}
+
+impl CommitReplayer {
+    /// Opens the consensus store at `db_path` read-only. `committee` and `protocol_config` must
+    /// be of the epoch the store belongs to.
+    pub fn open(committee: Committee, protocol_config: ProtocolConfig, db_path: &Path) -> Self {
+        let context = Context::new_offline(committee, protocol_config, db_path);
+        let store = Arc::new(RocksDBStore::new_read_only(
+            db_path
+                .to_str()
+                .expect("Consensus db path should be valid UTF-8"),
+        ));
+        Self::new(Arc::new(context), store)
+    }
+
+    pub(crate) fn new(context: Arc<Context>, store: Arc<dyn Store>) -> Self {
+        Self {
+            context,
+            store,
+            number_of_leaders: NUM_LEADERS_PER_ROUND,
+            pipeline: true,
+        }
+    }
+
+    /// Overrides the number of leaders per round of the replayed committer.
+    pub fn with_number_of_leaders(mut self, number_of_leaders: usize) -> Self {
+        self.number_of_leaders = number_of_leaders;
+        self
+    }
+
+    /// Overrides whether the replayed committer is pipelined.
+    pub fn with_pipeline(mut self, pipeline: bool) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Replays all persisted blocks, and returns the committed sub-dags in commit order together
+    /// with the stored commits they diverge from.
+    pub fn replay(&self) -> ConsensusResult<ReplayResult> {
+        let mut blocks_by_round: BTreeMap<Round, Vec<VerifiedBlock>> = BTreeMap::new();
+        for (authority, _) in self.context.committee.authorities() {
+            for block in self.store.scan_blocks_by_author(authority, 1)? {
+                blocks_by_round
+                    .entry(block.round())
+                    .or_default()
+                    .push(block);
+            }
+        }
+
+        let replay_store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            self.context.clone(),
+            replay_store.clone(),
+        )));
+        let leader_schedule = LeaderSchedule::from_store(self.context.clone(), dag_state.clone());
+        let committer = UniversalCommitterBuilder::new(self.context.clone(), dag_state.clone())
+            .with_leader_schedule(leader_schedule.clone())
+            .with_number_of_leaders(self.number_of_leaders)
+            .with_pipeline(self.pipeline)
+            .build();
+        let mut linearizer = Linearizer::new(dag_state.clone(), leader_schedule.clone());
+        let mut last_decided_leader = dag_state.read().last_commit_leader();
+
+        let mut committed_sub_dags = vec![];
+        for (_round, blocks) in blocks_by_round {
+            dag_state.write().accept_blocks(blocks);
+            committed_sub_dags.extend(Self::try_commit(
+                &committer,
+                &mut linearizer,
+                &leader_schedule,
+                &dag_state,
+                &mut last_decided_leader,
+            ));
+            dag_state.write().flush();
+        }
+
+        let stored_commits = Self::scan_all_commits(self.store.as_ref())?;
+        let replayed_commits = Self::scan_all_commits(replay_store.as_ref())?;
+        let mut divergences = vec![];
+        for (index, stored) in stored_commits.iter().enumerate() {
+            match replayed_commits.get(index) {
+                Some(replayed) if replayed == stored => {}
+                Some(replayed) => divergences.push(CommitDivergence::Mismatch {
+                    commit_index: stored.index(),
+                    stored_leader: stored.leader(),
+                    replayed_leader: replayed.leader(),
+                    stored_blocks: stored.blocks().to_vec(),
+                    replayed_blocks: replayed.blocks().to_vec(),
+                }),
+                None => divergences.push(CommitDivergence::Missing {
+                    commit_index: stored.index(),
+                    stored_leader: stored.leader(),
+                }),
+            }
+        }
+
+        Ok(ReplayResult {
+            committed_sub_dags,
+            num_stored_commits: stored_commits.len(),
+            divergences,
+        })
+    }
+
+    /// Decides and linearizes leaders in the same way as `Core::try_commit()`, stopping at each
+    /// leader schedule update so that later leaders are elected by the updated schedule (see
+    /// `LeaderSchedule::truncate_to_leader_schedule_update()`).
+    fn try_commit(
+        committer: &UniversalCommitter,
+        linearizer: &mut Linearizer,
+        leader_schedule: &LeaderSchedule,
+        dag_state: &Arc<RwLock<DagState>>,
+        last_decided_leader: &mut Slot,
+    ) -> Vec<CommittedSubDag> {
+        let mut committed_sub_dags = vec![];
+        loop {
+            let mut sequenced_leaders = committer.try_commit(*last_decided_leader);
+            let schedule_updated = leader_schedule
+                .truncate_to_leader_schedule_update(dag_state.clone(), &mut sequenced_leaders);
+
+            let Some(last) = sequenced_leaders.last() else {
+                break;
+            };
+            *last_decided_leader = last.get_decided_slot();
+
+            let committed_leaders = sequenced_leaders
+                .into_iter()
+                .filter_map(|leader| leader.into_committed_block())
+                .collect::<Vec<_>>();
+            committed_sub_dags.extend(linearizer.handle_commit(committed_leaders));
+
+            if !schedule_updated {
+                break;
+            }
+        }
+        committed_sub_dags
+    }
+
+    fn scan_all_commits(store: &dyn Store) -> ConsensusResult<Vec<TrustedCommit>> {
+        let Some(last_commit) = store.read_last_commit()? else {
+            return Ok(vec![]);
+        };
+        store.scan_commits(1..(last_commit.index() + 1))
+    }
+}
+
+/// Result of replaying the persisted blocks of an authority.
+pub struct ReplayResult {
+    /// Sub-dags committed by the replay, in commit order.
+    pub committed_sub_dags: Vec<CommittedSubDag>,
+    /// Number of commits recorded in the store.
+    pub num_stored_commits: usize,
+    /// Stored commits that are not reproduced by the replay. Commits after the last stored
+    /// commit are not divergences, because their blocks may be persisted before the commits.
+    pub divergences: Vec<CommitDivergence>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitDivergence {
+    /// The replayed commit at the index differs from the stored one.
+    Mismatch {
+        commit_index: CommitIndex,
+        stored_leader: BlockRef,
+        replayed_leader: BlockRef,
+        stored_blocks: Vec<BlockRef>,
+        replayed_blocks: Vec<BlockRef>,
+    },
+    /// The replay produced no commit at the index.
+    Missing {
+        commit_index: CommitIndex,
+        stored_leader: BlockRef,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commit::CommitDigest, storage::WriteBatch, test_dag_builder::DagBuilder};
+
+    #[test]
+    fn test_replay_commits() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..10).build();
+        dag_builder.persist_all_blocks(dag_state.clone());
+        dag_state.write().flush();
+
+        // Without stored commits, every leader up to round 8 is committed with pipelining.
+        let result = CommitReplayer::new(context.clone(), store.clone())
+            .replay()
+            .unwrap();
+        assert_eq!(result.num_stored_commits, 0);
+        assert!(result.divergences.is_empty());
+        assert_eq!(
+            result
+                .committed_sub_dags
+                .iter()
+                .map(|sub_dag| (sub_dag.commit_index, sub_dag.leader.round))
+                .collect::<Vec<_>>(),
+            (1..=8).map(|round| (round, round)).collect::<Vec<_>>()
+        );
+
+        // Store the first commits as the authority would have, and replay again.
+        let mut previous_digest = CommitDigest::MIN;
+        let commits = result.committed_sub_dags[0..4]
+            .iter()
+            .map(|sub_dag| {
+                let commit = TrustedCommit::new_for_test(
+                    sub_dag.commit_index,
+                    previous_digest,
+                    sub_dag.leader,
+                    sub_dag
+                        .blocks
+                        .iter()
+                        .map(|block| block.reference())
+                        .collect(),
+                );
+                previous_digest = commit.digest();
+                commit
+            })
+            .collect::<Vec<_>>();
+        store
+            .write(WriteBatch::default().commits(commits.clone()))
+            .unwrap();
+        let result = CommitReplayer::new(context.clone(), store.clone())
+            .replay()
+            .unwrap();
+        assert_eq!(result.num_stored_commits, 4);
+        assert!(result.divergences.is_empty());
+
+        // A stored commit with another leader diverges from the replay.
+        let tampered = TrustedCommit::new_for_test(
+            5,
+            commits[3].digest(),
+            result.committed_sub_dags[5].leader,
+            vec![],
+        );
+        store
+            .write(WriteBatch::default().commits(vec![tampered]))
+            .unwrap();
+        let result = CommitReplayer::new(context.clone(), store.clone())
+            .replay()
+            .unwrap();
+        assert_eq!(result.num_stored_commits, 5);
+        assert_eq!(
+            result.divergences,
+            vec![CommitDivergence::Mismatch {
+                commit_index: 5,
+                stored_leader: result.committed_sub_dags[5].leader,
+                replayed_leader: result.committed_sub_dags[4].leader,
+                stored_blocks: vec![],
+                replayed_blocks: result.committed_sub_dags[4]
+                    .blocks
+                    .iter()
+                    .map(|block| block.reference())
+                    .collect(),
+            }]
+        );
+    }
+}
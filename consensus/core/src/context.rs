@@ -1,18 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use consensus_config::{AuthorityIndex, Committee, Parameters};
 #[cfg(test)]
 use consensus_config::{NetworkKeyPair, ProtocolKeyPair};
+use prometheus::Registry;
 use sui_protocol_config::ProtocolConfig;
 #[cfg(test)]
 use tempfile::TempDir;
 
 #[cfg(test)]
 use crate::metrics::test_metrics;
-use crate::metrics::Metrics;
+use crate::metrics::{initialise_metrics, Metrics};
 
 /// Context contains per-epoch configuration and metrics shared by all components
 /// of this authority.
@@ -47,6 +48,25 @@ impl Context {
         }
     }
 
+    /// Creates a context for inspecting the data persisted by an authority offline, outside of
+    /// the authority's own process.
+    pub(crate) fn new_offline(
+        committee: Committee,
+        protocol_config: ProtocolConfig,
+        db_path: &Path,
+    ) -> Self {
+        Context::new(
+            AuthorityIndex::ZERO,
+            committee,
+            Parameters {
+                db_path: Some(db_path.to_path_buf()),
+                ..Default::default()
+            },
+            protocol_config,
+            initialise_metrics(Registry::new()),
+        )
+    }
+
     /// Create a test context with a committee of given size and even stake
     #[cfg(test)]
     pub(crate) fn new_for_test(
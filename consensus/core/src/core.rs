@@ -25,6 +25,7 @@ use crate::{
     context::Context,
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
+    leader_schedule::LeaderSchedule,
     threshold_clock::ThresholdClock,
     transaction::TransactionConsumer,
     universal_committer::{
@@ -34,7 +35,7 @@ use crate::{
 };
 
 // TODO: Move to protocol config once initial value is finalized.
-const NUM_LEADERS_PER_ROUND: usize = 1;
+pub(crate) const NUM_LEADERS_PER_ROUND: usize = 1;
 
 // Maximum number of commit votes to include in a block.
 // TODO: Move to protocol config, and verify in BlockVerifier.
@@ -51,6 +52,8 @@ pub(crate) struct Core {
     block_manager: BlockManager,
     /// Used to make commit decisions for leader blocks in the dag.
     committer: UniversalCommitter,
+    /// The leader schedule, shared with the committer and the commit observer.
+    leader_schedule: LeaderSchedule,
     /// The last produced block
     last_proposed_block: VerifiedBlock,
     /// The blocks of the last included ancestors per authority. This vector is basically used as a
@@ -76,6 +79,7 @@ pub(crate) struct Core {
 impl Core {
     pub(crate) fn new(
         context: Arc<Context>,
+        leader_schedule: LeaderSchedule,
         transaction_consumer: TransactionConsumer,
         block_manager: BlockManager,
         commit_observer: CommitObserver,
@@ -86,6 +90,7 @@ impl Core {
         let last_decided_leader = dag_state.read().last_commit_leader();
 
         let committer = UniversalCommitterBuilder::new(context.clone(), dag_state.clone())
+            .with_leader_schedule(leader_schedule.clone())
             .with_number_of_leaders(NUM_LEADERS_PER_ROUND)
             .with_pipeline(true)
             .build();
@@ -116,6 +121,7 @@ impl Core {
             last_included_ancestors,
             block_manager,
             committer,
+            leader_schedule,
             last_decided_leader,
             commit_observer,
             signals,
@@ -347,24 +353,40 @@ impl Core {
             .with_label_values(&["Core::try_commit"])
             .start_timer();
 
-        // TODO: Add optimization to abort early without quorum for a round.
-        let sequenced_leaders = self.committer.try_commit(self.last_decided_leader);
+        let mut committed_sub_dags = Vec::new();
+        loop {
+            // TODO: Add optimization to abort early without quorum for a round.
+            let mut sequenced_leaders = self.committer.try_commit(self.last_decided_leader);
+
+            // Leaders after the next leader schedule update are elected by the new schedule, so
+            // only decide leaders up to the commit that triggers the update, then try again.
+            let schedule_updated = self
+                .leader_schedule
+                .truncate_to_leader_schedule_update(self.dag_state.clone(), &mut sequenced_leaders);
 
-        if let Some(last) = sequenced_leaders.last() {
+            let Some(last) = sequenced_leaders.last() else {
+                break;
+            };
             self.last_decided_leader = last.get_decided_slot();
             self.context
                 .metrics
                 .node_metrics
                 .last_decided_leader_round
                 .set(self.last_decided_leader.round as i64);
-        }
 
-        let committed_leaders = sequenced_leaders
-            .into_iter()
-            .filter_map(|leader| leader.into_committed_block())
-            .collect::<Vec<_>>();
+            let committed_leaders = sequenced_leaders
+                .into_iter()
+                .filter_map(|leader| leader.into_committed_block())
+                .collect::<Vec<_>>();
+
+            committed_sub_dags.extend(self.commit_observer.handle_commit(committed_leaders)?);
+
+            if !schedule_updated {
+                break;
+            }
+        }
 
-        self.commit_observer.handle_commit(committed_leaders)
+        Ok(committed_sub_dags)
     }
 
     pub(crate) fn get_missing_blocks(&self) -> BTreeSet<BlockRef> {
@@ -629,11 +651,13 @@ mod test {
         );
 
         let (sender, _receiver) = unbounded_channel();
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
         let commit_observer = CommitObserver::new(
             context.clone(),
             CommitConsumer::new(sender.clone(), 0, 0),
             dag_state.clone(),
             store.clone(),
+            leader_schedule.clone(),
         );
 
         // Check no commits have been persisted to dag_state or store.
@@ -647,6 +671,7 @@ mod test {
         let mut block_receiver = signal_receivers.block_broadcast_receiver();
         let mut core = Core::new(
             context.clone(),
+            leader_schedule,
             transaction_consumer,
             block_manager,
             commit_observer,
@@ -739,11 +764,13 @@ mod test {
         );
 
         let (sender, _receiver) = unbounded_channel();
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
         let commit_observer = CommitObserver::new(
             context.clone(),
             CommitConsumer::new(sender.clone(), 0, 0),
             dag_state.clone(),
             store.clone(),
+            leader_schedule.clone(),
         );
 
         // Check no commits have been persisted to dag_state & store
@@ -757,6 +784,7 @@ mod test {
         let mut block_receiver = signal_receivers.block_broadcast_receiver();
         let mut core = Core::new(
             context.clone(),
+            leader_schedule,
             transaction_consumer,
             block_manager,
             commit_observer,
@@ -828,15 +856,18 @@ mod test {
         let mut block_receiver = signal_receivers.block_broadcast_receiver();
 
         let (sender, _receiver) = unbounded_channel();
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
         let commit_observer = CommitObserver::new(
             context.clone(),
             CommitConsumer::new(sender.clone(), 0, 0),
             dag_state.clone(),
             store.clone(),
+            leader_schedule.clone(),
         );
 
         let mut core = Core::new(
             context.clone(),
+            leader_schedule,
             transaction_consumer,
             block_manager,
             commit_observer,
@@ -929,15 +960,18 @@ mod test {
         let _block_receiver = signal_receivers.block_broadcast_receiver();
 
         let (sender, _receiver) = unbounded_channel();
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
         let commit_observer = CommitObserver::new(
             context.clone(),
             CommitConsumer::new(sender.clone(), 0, 0),
             dag_state.clone(),
             store.clone(),
+            leader_schedule.clone(),
         );
 
         let mut core = Core::new(
             context.clone(),
+            leader_schedule,
             transaction_consumer,
             block_manager,
             commit_observer,
@@ -1234,17 +1268,20 @@ mod test {
             let block_receiver = signal_receivers.block_broadcast_receiver();
 
             let (commit_sender, commit_receiver) = unbounded_channel();
+            let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
             let commit_observer = CommitObserver::new(
                 context.clone(),
                 CommitConsumer::new(commit_sender.clone(), 0, 0),
                 dag_state.clone(),
                 store.clone(),
+                leader_schedule.clone(),
             );
 
             let block_signer = signers.remove(index).1;
 
             let core = Core::new(
                 context,
+                leader_schedule,
                 transaction_consumer,
                 block_manager,
                 commit_observer,
@@ -0,0 +1,483 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    path::Path,
+    sync::Arc,
+};
+
+use consensus_config::{AuthorityIndex, Committee};
+use serde::Serialize;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::{
+    block::{BlockAPI, BlockRef, BlockTimestampMs, Round, VerifiedBlock},
+    commit::{CommitAPI as _, CommitIndex, TrustedCommit},
+    context::Context,
+    error::ConsensusResult,
+    storage::{rocksdb_store::RocksDBStore, Store},
+};
+
+/// Number of rounds after the inspected range that are loaded to find the first references to
+/// its blocks.
+const REFERENCE_LOOKAHEAD_ROUNDS: Round = 30;
+
+/// Number of commits read at a time, when scanning back from the last commit.
+const COMMIT_SCAN_BATCH_SIZE: CommitIndex = 100;
+
+/// Inspects the DAG persisted by an authority, for offline analysis of what consensus did.
+///
+/// Leader decisions are read from the persisted commits, so they are the ones the authority
+/// made. Skipped leaders are not persisted: rounds without a committed leader are reported as
+/// skipped up to the last commit, and as undecided after it.
+pub struct DagInspector {
+    context: Arc<Context>,
+    store: Arc<dyn Store>,
+}
+
+impl DagInspector {
+    /// Opens the consensus store at `db_path` read-only. It is safe to use while the authority
+    /// is running. `committee` and `protocol_config` must be of the epoch the store belongs to.
+    pub fn open(committee: Committee, protocol_config: ProtocolConfig, db_path: &Path) -> Self {
+        let context = Context::new_offline(committee, protocol_config, db_path);
+        let store = Arc::new(RocksDBStore::new_read_only(
+            db_path
+                .to_str()
+                .expect("Consensus db path should be valid UTF-8"),
+        ));
+        Self::new(Arc::new(context), store)
+    }
+
+    pub(crate) fn new(context: Arc<Context>, store: Arc<dyn Store>) -> Self {
+        Self { context, store }
+    }
+
+    /// Exports the blocks of rounds `start_round..=end_round`, together with the decisions on
+    /// their leaders and the propagation delays of each authority.
+    pub fn inspect(&self, start_round: Round, end_round: Round) -> ConsensusResult<DagReport> {
+        let start_round = start_round.max(1);
+        let end_round = end_round.max(start_round);
+        let load_end_round = end_round.saturating_add(REFERENCE_LOOKAHEAD_ROUNDS);
+
+        let mut blocks = vec![];
+        for (authority, _) in self.context.committee.authorities() {
+            let author_blocks = self.store.scan_last_blocks_by_author(
+                authority,
+                (load_end_round - start_round + 1) as u64,
+                Some(load_end_round),
+            )?;
+            blocks.extend(
+                author_blocks
+                    .into_iter()
+                    .filter(|block| block.round() >= start_round),
+            );
+        }
+        blocks.sort_by_key(|block| block.reference());
+
+        let leaders = self.read_leaders(start_round, end_round)?;
+        let first_references = first_reference_timestamps(&blocks);
+
+        let mut delays_per_authority: BTreeMap<AuthorityIndex, Vec<u64>> = BTreeMap::new();
+        let mut block_summaries = vec![];
+        for block in blocks.iter().filter(|block| block.round() <= end_round) {
+            let propagation_delay_ms = first_references
+                .get(&block.reference())
+                .map(|first_reference_ms| first_reference_ms.saturating_sub(block.timestamp_ms()));
+            if let Some(delay) = propagation_delay_ms {
+                delays_per_authority
+                    .entry(block.author())
+                    .or_default()
+                    .push(delay);
+            }
+            block_summaries.push(BlockSummary {
+                reference: block.reference().to_string(),
+                round: block.round(),
+                author: block.author(),
+                timestamp_ms: block.timestamp_ms(),
+                ancestors: block
+                    .ancestors()
+                    .iter()
+                    .map(|ancestor| ancestor.to_string())
+                    .collect(),
+                num_transactions: block.transactions().len(),
+                propagation_delay_ms,
+            });
+        }
+
+        let propagation_delays = self
+            .context
+            .committee
+            .authorities()
+            .map(|(authority, info)| {
+                let delays = delays_per_authority.remove(&authority).unwrap_or_default();
+                PropagationDelay {
+                    authority,
+                    hostname: info.hostname.clone(),
+                    num_blocks: delays.len(),
+                    mean_ms: (!delays.is_empty())
+                        .then(|| delays.iter().sum::<u64>() / delays.len() as u64),
+                    max_ms: delays.iter().max().copied(),
+                }
+            })
+            .collect();
+
+        Ok(DagReport {
+            start_round,
+            end_round,
+            blocks: block_summaries,
+            leaders,
+            propagation_delays,
+        })
+    }
+
+    /// Returns the decision on the leaders of each round in `start_round..=end_round`, as recorded
+    /// by the persisted commits.
+    fn read_leaders(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Vec<LeaderSummary>> {
+        let commits = self.scan_commits_by_round(start_round, end_round)?;
+        let last_committed_round = self
+            .store
+            .read_last_commit()?
+            .map_or(0, |commit| commit.round());
+
+        let mut commits = commits.into_iter().peekable();
+        let mut leaders = vec![];
+        for round in start_round..=end_round {
+            let mut committed = false;
+            while let Some(commit) = commits.next_if(|commit| commit.round() == round) {
+                leaders.push(LeaderSummary::committed(&commit));
+                committed = true;
+            }
+            if !committed {
+                let decision = if round < last_committed_round {
+                    LeaderDecision::Skip
+                } else {
+                    LeaderDecision::Undecided
+                };
+                leaders.push(LeaderSummary::uncommitted(round, decision));
+            }
+        }
+        Ok(leaders)
+    }
+
+    /// Returns the persisted commits with leaders in `start_round..=end_round`, in commit order.
+    /// Commits are ordered by leader round, so they are scanned back from the last commit until
+    /// one is below `start_round`.
+    fn scan_commits_by_round(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> ConsensusResult<Vec<TrustedCommit>> {
+        let Some(last_commit) = self.store.read_last_commit()? else {
+            return Ok(vec![]);
+        };
+
+        let mut commits = vec![];
+        let mut end_index = last_commit.index() + 1;
+        while end_index > 1 {
+            let start_index = end_index.saturating_sub(COMMIT_SCAN_BATCH_SIZE).max(1);
+            let batch = self.store.scan_commits(start_index..end_index)?;
+            let reached_start_round = batch
+                .first()
+                .map_or(true, |commit| commit.round() < start_round);
+            commits.extend(
+                batch
+                    .into_iter()
+                    .rev()
+                    .filter(|commit| (start_round..=end_round).contains(&commit.round())),
+            );
+            if reached_start_round {
+                break;
+            }
+            end_index = start_index;
+        }
+        commits.reverse();
+        Ok(commits)
+    }
+}
+
+/// Returns the earliest timestamp at which each block is referenced by a block of another
+/// authority.
+fn first_reference_timestamps(blocks: &[VerifiedBlock]) -> BTreeMap<BlockRef, BlockTimestampMs> {
+    let mut first_references = BTreeMap::new();
+    for block in blocks {
+        for ancestor in block.ancestors() {
+            if ancestor.author == block.author() {
+                continue;
+            }
+            first_references
+                .entry(*ancestor)
+                .and_modify(|timestamp_ms: &mut BlockTimestampMs| {
+                    *timestamp_ms = (*timestamp_ms).min(block.timestamp_ms())
+                })
+                .or_insert(block.timestamp_ms());
+        }
+    }
+    first_references
+}
+
+/// The blocks, leader decisions and propagation delays over a range of rounds.
+#[derive(Debug, Serialize)]
+pub struct DagReport {
+    pub start_round: Round,
+    pub end_round: Round,
+    pub blocks: Vec<BlockSummary>,
+    pub leaders: Vec<LeaderSummary>,
+    pub propagation_delays: Vec<PropagationDelay>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockSummary {
+    pub reference: String,
+    pub round: Round,
+    pub author: AuthorityIndex,
+    pub timestamp_ms: BlockTimestampMs,
+    pub ancestors: Vec<String>,
+    pub num_transactions: usize,
+    /// Time between the block's timestamp and its first reference by another authority.
+    /// None when no block of the inspected rounds references it.
+    pub propagation_delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LeaderSummary {
+    pub round: Round,
+    /// The author of the committed leader block. None when no leader of the round is committed,
+    /// as the elected leaders of skipped rounds are not persisted.
+    pub authority: Option<AuthorityIndex>,
+    /// The committed leader block, if any.
+    pub block: Option<String>,
+    /// The index of the commit of the leader block, if any.
+    pub commit_index: Option<CommitIndex>,
+    pub decision: LeaderDecision,
+}
+
+impl LeaderSummary {
+    fn committed(commit: &TrustedCommit) -> Self {
+        Self {
+            round: commit.round(),
+            authority: Some(commit.leader().author),
+            block: Some(commit.leader().to_string()),
+            commit_index: Some(commit.index()),
+            decision: LeaderDecision::Commit,
+        }
+    }
+
+    fn uncommitted(round: Round, decision: LeaderDecision) -> Self {
+        Self {
+            round,
+            authority: None,
+            block: None,
+            commit_index: None,
+            decision,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderDecision {
+    Commit,
+    Skip,
+    Undecided,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropagationDelay {
+    pub authority: AuthorityIndex,
+    pub hostname: String,
+    /// Number of the authority's blocks referenced by other authorities.
+    pub num_blocks: usize,
+    pub mean_ms: Option<u64>,
+    pub max_ms: Option<u64>,
+}
+
+impl DagReport {
+    /// Renders the report as a Graphviz DOT graph. Blocks are grouped by round, committed leader
+    /// blocks are colored, and edges point from blocks to their ancestors within the inspected
+    /// rounds.
+    pub fn to_dot(&self) -> String {
+        let committed_leaders: BTreeSet<&str> = self
+            .leaders
+            .iter()
+            .filter_map(|leader| leader.block.as_deref())
+            .collect();
+        let references: BTreeSet<&str> = self
+            .blocks
+            .iter()
+            .map(|block| block.reference.as_str())
+            .collect();
+
+        let mut dot = String::new();
+        writeln!(dot, "digraph dag {{").unwrap();
+        writeln!(dot, "  rankdir=BT;").unwrap();
+        writeln!(dot, "  node [shape=box, style=filled, fillcolor=white];").unwrap();
+        for round in self.start_round..=self.end_round {
+            writeln!(dot, "  subgraph round_{round} {{").unwrap();
+            writeln!(dot, "    rank=same;").unwrap();
+            for block in self.blocks.iter().filter(|block| block.round == round) {
+                let fillcolor = if committed_leaders.contains(block.reference.as_str()) {
+                    "green"
+                } else {
+                    "white"
+                };
+                writeln!(
+                    dot,
+                    "    \"{}\" [label=\"{}\\n{} txns\\n{} ms\", fillcolor={}];",
+                    block.reference,
+                    block.reference,
+                    block.num_transactions,
+                    block.timestamp_ms,
+                    fillcolor,
+                )
+                .unwrap();
+            }
+            writeln!(dot, "  }}").unwrap();
+        }
+        for block in &self.blocks {
+            for ancestor in &block.ancestors {
+                if references.contains(ancestor.as_str()) {
+                    writeln!(dot, "  \"{}\" -> \"{}\";", block.reference, ancestor).unwrap();
+                }
+            }
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+
+    use super::*;
+    use crate::{
+        core::NUM_LEADERS_PER_ROUND, dag_state::DagState, leader_schedule::LeaderSchedule,
+        linearizer::Linearizer, storage::mem_store::MemStore, test_dag_builder::DagBuilder,
+        universal_committer::universal_committer_builder::UniversalCommitterBuilder,
+    };
+
+    #[test]
+    fn test_inspect_dag() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+
+        // The leader block of round 4 is missing.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..3).build();
+        dag_builder.layer(4).no_leader_block(vec![]).build();
+        dag_builder.layers(5..10).build();
+        dag_builder.persist_all_blocks(dag_state.clone());
+        dag_state.write().flush();
+
+        // Nothing is committed yet, so no leader is decided.
+        let inspector = DagInspector::new(context.clone(), store.clone());
+        let report = inspector.inspect(2, 6).unwrap();
+        assert!(report
+            .leaders
+            .iter()
+            .all(|leader| leader.decision == LeaderDecision::Undecided));
+
+        // Commit the leaders up to round 3 only. The inspector reports what was committed, even
+        // though the blocks are enough to decide more leaders.
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone());
+        let committer = UniversalCommitterBuilder::new(context.clone(), dag_state.clone())
+            .with_leader_schedule(leader_schedule.clone())
+            .with_number_of_leaders(NUM_LEADERS_PER_ROUND)
+            .with_pipeline(true)
+            .build();
+        let committed_leaders = committer
+            .try_commit(dag_state.read().last_commit_leader())
+            .into_iter()
+            .filter_map(|leader| leader.into_committed_block())
+            .collect::<Vec<_>>();
+        let mut linearizer = Linearizer::new(dag_state.clone(), leader_schedule);
+        let (first_leaders, last_leaders): (Vec<_>, Vec<_>) = committed_leaders
+            .into_iter()
+            .partition(|leader| leader.round() <= 3);
+        linearizer.handle_commit(first_leaders);
+
+        let report = inspector.inspect(2, 6).unwrap();
+        assert_eq!(
+            report
+                .leaders
+                .iter()
+                .map(|leader| (leader.round, leader.decision))
+                .collect::<Vec<_>>(),
+            vec![
+                (2, LeaderDecision::Commit),
+                (3, LeaderDecision::Commit),
+                (4, LeaderDecision::Undecided),
+                (5, LeaderDecision::Undecided),
+                (6, LeaderDecision::Undecided),
+            ]
+        );
+
+        linearizer.handle_commit(last_leaders);
+        let report = inspector.inspect(2, 6).unwrap();
+
+        assert_eq!(report.start_round, 2);
+        assert_eq!(report.end_round, 6);
+        assert_eq!(report.blocks.len(), 5 * 4 - 1);
+        assert!(report
+            .blocks
+            .iter()
+            .all(|block| (2..=6).contains(&block.round)));
+
+        // There is one leader per round with pipelining, and the leader of round 4 is skipped.
+        assert_eq!(
+            report
+                .leaders
+                .iter()
+                .map(|leader| (leader.round, leader.decision))
+                .collect::<Vec<_>>(),
+            vec![
+                (2, LeaderDecision::Commit),
+                (3, LeaderDecision::Commit),
+                (4, LeaderDecision::Skip),
+                (5, LeaderDecision::Commit),
+                (6, LeaderDecision::Commit),
+            ]
+        );
+        assert!(report.leaders[2].block.is_none());
+        assert!(report.leaders[2].authority.is_none());
+
+        // The commit indexes are those of the stored commits.
+        let stored_commits = store
+            .scan_commits(1..(store.read_last_commit().unwrap().unwrap().index() + 1))
+            .unwrap();
+        for leader in report
+            .leaders
+            .iter()
+            .filter(|leader| leader.block.is_some())
+        {
+            let commit = stored_commits
+                .iter()
+                .find(|commit| Some(commit.index()) == leader.commit_index)
+                .unwrap();
+            assert_eq!(Some(commit.leader().to_string()), leader.block);
+        }
+
+        // All blocks are referenced by later blocks of other authorities.
+        assert_eq!(
+            report
+                .propagation_delays
+                .iter()
+                .map(|delay| delay.num_blocks)
+                .collect::<Vec<_>>(),
+            vec![4, 5, 5, 5]
+        );
+
+        let dot = report.to_dot();
+        assert!(dot.starts_with("digraph dag {"));
+        assert!(dot.contains("subgraph round_2"));
+        assert!(dot.contains("fillcolor=green"));
+    }
+}
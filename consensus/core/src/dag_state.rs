@@ -16,8 +16,12 @@ use crate::{
     block::{
         genesis_blocks, BlockAPI, BlockDigest, BlockRef, Round, Slot, VerifiedBlock, GENESIS_ROUND,
     },
-    commit::{CommitAPI as _, CommitDigest, CommitIndex, CommitVote, TrustedCommit},
+    commit::{
+        load_committed_subdag_from_store, CommitAPI as _, CommitDigest, CommitIndex, CommitVote,
+        CommittedSubDag, TrustedCommit,
+    },
     context::Context,
+    leader_scoring::ReputationScores,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
     storage::{Store, WriteBatch},
 };
@@ -59,6 +63,12 @@ pub(crate) struct DagState {
     // TODO: limit to 1st commit per round with multi-leader.
     pending_commit_votes: VecDeque<CommitVote>,
 
+    // Committed sub-dags that have not been scored yet for the leader schedule.
+    unscored_committed_subdags: Vec<CommittedSubDag>,
+
+    // Reputation scores of the last leader schedule update.
+    reputation_scores: ReputationScores,
+
     // Data to be flushed to storage.
     blocks_to_write: Vec<VerifiedBlock>,
     commits_to_write: Vec<TrustedCommit>,
@@ -84,17 +94,36 @@ impl DagState {
         let last_commit = store
             .read_last_commit()
             .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e));
-        let last_committed_rounds = {
+        let (last_committed_rounds, reputation_scores) = {
             let commit_info = store
                 .read_last_commit_info()
                 .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e));
             if let Some(commit_info) = commit_info {
-                commit_info.last_committed_rounds
+                (
+                    commit_info.last_committed_rounds().to_vec(),
+                    commit_info.reputation_scores().cloned().unwrap_or_default(),
+                )
             } else {
-                vec![0; num_authorities]
+                (vec![0; num_authorities], ReputationScores::default())
             }
         };
 
+        // Recover the commits after the last leader schedule update, to be scored on the next one.
+        let unscored_committed_subdags = if context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule()
+        {
+            let last_commit_index = last_commit.as_ref().map_or(0, |commit| commit.index());
+            store
+                .scan_commits((reputation_scores.last_commit_index + 1)..(last_commit_index + 1))
+                .unwrap_or_else(|e| panic!("Failed to read from storage: {:?}", e))
+                .into_iter()
+                .map(|commit| load_committed_subdag_from_store(store.as_ref(), commit))
+                .collect()
+        } else {
+            vec![]
+        };
+
         let mut state = Self {
             context,
             genesis,
@@ -105,6 +134,8 @@ impl DagState {
             last_commit_round_advancement_time: None,
             last_committed_rounds: last_committed_rounds.clone(),
             pending_commit_votes: VecDeque::new(),
+            unscored_committed_subdags,
+            reputation_scores,
             blocks_to_write: vec![],
             commits_to_write: vec![],
             store,
@@ -583,6 +614,32 @@ impl DagState {
         self.last_committed_rounds.clone()
     }
 
+    /// Buffers committed sub-dags to be scored on the next leader schedule update.
+    pub(crate) fn add_unscored_committed_subdags(&mut self, subdags: Vec<CommittedSubDag>) {
+        self.unscored_committed_subdags.extend(subdags);
+    }
+
+    /// Number of committed sub-dags since the last leader schedule update.
+    pub(crate) fn unscored_committed_subdags_count(&self) -> u64 {
+        self.unscored_committed_subdags.len() as u64
+    }
+
+    /// Takes the committed sub-dags since the last leader schedule update, to be scored.
+    pub(crate) fn take_unscored_committed_subdags(&mut self) -> Vec<CommittedSubDag> {
+        std::mem::take(&mut self.unscored_committed_subdags)
+    }
+
+    /// Reputation scores of the last leader schedule update.
+    pub(crate) fn reputation_scores(&self) -> ReputationScores {
+        self.reputation_scores.clone()
+    }
+
+    /// Sets the reputation scores of a leader schedule update, to be persisted with the next
+    /// commits.
+    pub(crate) fn set_reputation_scores(&mut self, reputation_scores: ReputationScores) {
+        self.reputation_scores = reputation_scores;
+    }
+
     /// After each flush, DagState becomes persisted in storage and it expected to recover
     /// all internal states from storage after restarts.
     pub(crate) fn flush(&mut self) {
@@ -605,6 +662,10 @@ impl DagState {
                 commits,
                 // TODO: limit to write at most once per commit round with multi-leader.
                 self.last_committed_rounds.clone(),
+                self.context
+                    .protocol_config
+                    .mysticeti_leader_scoring_and_schedule()
+                    .then(|| self.reputation_scores.clone()),
             ))
             .unwrap_or_else(|e| panic!("Failed to write to storage: {:?}", e));
         self.context
@@ -1,43 +1,195 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{
+    collections::BTreeSet,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
 
-use rand::{prelude::SliceRandom, rngs::StdRng, SeedableRng};
+use consensus_config::{AuthorityIndex, Stake};
+use parking_lot::RwLock;
+use rand::{prelude::SliceRandom, rngs::StdRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use tracing::{debug, info};
 
-use consensus_config::AuthorityIndex;
+use crate::{
+    block::Round, commit::LeaderStatus, context::Context, dag_state::DagState,
+    leader_scoring::ReputationScores,
+};
 
-use crate::context::Context;
+/// The number of commits after which the reputation scores are recalculated and the leader
+/// schedule is updated.
+// TODO: Move to protocol config once initial value is finalized.
+pub(crate) const CONSENSUS_COMMITS_PER_SCHEDULE: u64 = 300;
 
 /// The LeaderSchedule is responsible for producing the leader schedule across
-/// an epoch. For now it is a simple wrapper around Context to provide a leader
-/// for a round deterministically.
-// TODO: complete full leader schedule changes
+/// an epoch. The leader of a round is elected deterministically, and then swapped
+/// for a well performing authority if it is one of the worst performing authorities
+/// according to the reputation scores of the last leader schedule update.
+///
+/// The leader schedule is updated every `CONSENSUS_COMMITS_PER_SCHEDULE` commits, from the
+/// reputation scores calculated over the committed sub-dags since the previous update. The
+/// scores are persisted with the commits, so the schedule can be restored after restarts.
+///
+/// Leader swapping is enabled by the `mysticeti_leader_scoring_and_schedule` protocol feature flag.
+/// Without it, leaders are never swapped and no reputation scores are computed or persisted.
+///
+/// This struct can be cloned and shared freely as the swap table is atomically updated.
 #[derive(Clone)]
 pub(crate) struct LeaderSchedule {
     context: Arc<Context>,
+    num_commits_per_schedule: u64,
+    leader_swap_table: Arc<RwLock<LeaderSwapTable>>,
 }
 
 impl LeaderSchedule {
     pub fn new(context: Arc<Context>) -> Self {
-        Self { context }
+        Self::with_leader_swap_table(context, LeaderSwapTable::default())
     }
 
-    pub fn elect_leader(&self, round: u32, leader_offset: u32) -> AuthorityIndex {
-        cfg_if::cfg_if! {
-            // TODO: we need to differentiate the leader strategy in tests, so for
-            // some type of testing (ex sim tests) we can use the staked approach.
-            if #[cfg(test)] {
-                AuthorityIndex::new_for_test((round + leader_offset) % self.context.committee.size() as u32)
-            } else {
-                self.elect_leader_stake_based(round, leader_offset)
+    fn with_leader_swap_table(context: Arc<Context>, leader_swap_table: LeaderSwapTable) -> Self {
+        Self {
+            context,
+            num_commits_per_schedule: CONSENSUS_COMMITS_PER_SCHEDULE,
+            leader_swap_table: Arc::new(RwLock::new(leader_swap_table)),
+        }
+    }
+
+    /// Restores the leader schedule from the reputation scores of the last leader schedule update
+    /// persisted in storage.
+    pub(crate) fn from_store(context: Arc<Context>, dag_state: Arc<RwLock<DagState>>) -> Self {
+        let reputation_scores = dag_state.read().reputation_scores();
+        let enabled = context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule();
+        let leader_swap_table = if !enabled || reputation_scores.is_empty() {
+            LeaderSwapTable::default()
+        } else {
+            LeaderSwapTable::new(
+                context.clone(),
+                &reputation_scores,
+                Self::bad_nodes_stake_threshold(&context),
+            )
+        };
+        info!("Restored leader schedule: {leader_swap_table:?}");
+        Self::with_leader_swap_table(context, leader_swap_table)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_num_commits_per_schedule(mut self, num_commits_per_schedule: u64) -> Self {
+        self.num_commits_per_schedule = num_commits_per_schedule;
+        self
+    }
+
+    /// Whether leaders are scored and swapped, according to the protocol config.
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule()
+    }
+
+    /// The number of commits left until the next leader schedule update. Commits past the update
+    /// must not be decided before the update, as their leaders are elected by the new schedule.
+    pub(crate) fn commits_until_leader_schedule_update(
+        &self,
+        dag_state: Arc<RwLock<DagState>>,
+    ) -> usize {
+        let unscored_committed_subdags_count = dag_state.read().unscored_committed_subdags_count();
+        assert!(
+            unscored_committed_subdags_count <= self.num_commits_per_schedule,
+            "Unscored committed subdags count {unscored_committed_subdags_count} exceeds the \
+            number of commits per schedule {}",
+            self.num_commits_per_schedule
+        );
+        (self.num_commits_per_schedule - unscored_committed_subdags_count) as usize
+    }
+
+    /// Truncates `sequenced_leaders` after the commit that triggers the next leader schedule
+    /// update, if leaders are scored and swapped. The leaders after it are elected by the updated
+    /// schedule, so they have to be decided again once the update is in effect. Returns whether
+    /// `sequenced_leaders` now ends at a leader schedule update.
+    pub(crate) fn truncate_to_leader_schedule_update(
+        &self,
+        dag_state: Arc<RwLock<DagState>>,
+        sequenced_leaders: &mut Vec<LeaderStatus>,
+    ) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+
+        let commits_until_update = self.commits_until_leader_schedule_update(dag_state);
+        let update_position = sequenced_leaders
+            .iter()
+            .enumerate()
+            .filter(|(_, leader)| matches!(leader, LeaderStatus::Commit(_)))
+            .nth(commits_until_update - 1)
+            .map(|(position, _)| position);
+
+        match update_position {
+            Some(position) => {
+                sequenced_leaders.truncate(position + 1);
+                true
             }
+            None => false,
         }
     }
 
+    /// Scores the committed sub-dags since the last update and updates the leader swap table from
+    /// the new scores. The scores are persisted with the next flush of `dag_state`.
+    pub(crate) fn update_leader_schedule(&self, dag_state: Arc<RwLock<DagState>>) {
+        let mut dag_state = dag_state.write();
+        let unscored_committed_subdags = dag_state.take_unscored_committed_subdags();
+        let reputation_scores =
+            ReputationScores::from_committed_subdags(&self.context, &unscored_committed_subdags);
+        reputation_scores.update_metrics(&self.context);
+
+        self.update_leader_swap_table(LeaderSwapTable::new(
+            self.context.clone(),
+            &reputation_scores,
+            Self::bad_nodes_stake_threshold(&self.context),
+        ));
+        dag_state.set_reputation_scores(reputation_scores);
+    }
+
+    /// Atomically updates the leader swap table. Any leader elected from now on is swapped
+    /// according to this table, until a new one is provided.
+    fn update_leader_swap_table(&self, table: LeaderSwapTable) {
+        debug!("Updating leader swap table: {table:?}");
+        self.context
+            .metrics
+            .node_metrics
+            .leader_swap_table_bad_nodes
+            .set(table.bad_nodes.len() as i64);
+        *self.leader_swap_table.write() = table;
+    }
+
+    pub fn elect_leader(&self, round: u32, leader_offset: u32) -> AuthorityIndex {
+        let leader = {
+            cfg_if::cfg_if! {
+                // TODO: we need to differentiate the leader strategy in tests, so for
+                // some type of testing (ex sim tests) we can use the staked approach.
+                if #[cfg(test)] {
+                    AuthorityIndex::new_for_test((round + leader_offset) % self.context.committee.size() as u32)
+                } else {
+                    self.elect_leader_stake_based(round, leader_offset)
+                }
+            }
+        };
+
+        self.leader_swap_table
+            .read()
+            .swap(leader, round, leader_offset)
+            .unwrap_or(leader)
+    }
+
     pub fn elect_leader_stake_based(&self, round: u32, offset: u32) -> AuthorityIndex {
         assert!((offset as usize) < self.context.committee.size());
 
+        if self.is_enabled() {
+            return self.elect_leader_stake_based_pinned(round, offset);
+        }
+
         // To ensure that we elect different leaders for the same round (using
         // different offset) we are using the round number as seed to shuffle in
         // a weighted way the results, but skip based on the offset.
@@ -63,6 +215,179 @@ impl LeaderSchedule {
 
         leader_index
     }
+
+    /// Same as the legacy stake based election, but with an rng and a weighted sampling algorithm
+    /// that are both specified here, so the schedule can not change with a dependency upgrade.
+    /// Authorities are drawn without replacement, each with a probability proportional to its
+    /// stake among the authorities not drawn yet, and the leader is the `offset`-th one drawn.
+    fn elect_leader_stake_based_pinned(&self, round: u32, offset: u32) -> AuthorityIndex {
+        let mut seed_bytes = [0u8; 32];
+        seed_bytes[32 - 4..].copy_from_slice(&(round).to_le_bytes());
+        let mut rng = ChaCha20Rng::from_seed(seed_bytes);
+
+        let mut candidates = self
+            .context
+            .committee
+            .authorities()
+            .map(|(index, authority)| (index, authority.stake))
+            .collect::<Vec<_>>();
+        let mut remaining_stake: Stake = candidates.iter().map(|(_, stake)| stake).sum();
+
+        for _ in 0..offset {
+            let position = Self::draw_weighted(&mut rng, &candidates, remaining_stake);
+            remaining_stake -= candidates.remove(position).1;
+        }
+        let position = Self::draw_weighted(&mut rng, &candidates, remaining_stake);
+        candidates[position].0
+    }
+
+    /// Returns the position of the candidate covering a point drawn uniformly from the cumulative
+    /// stake of `candidates`, in committee order.
+    fn draw_weighted(
+        rng: &mut ChaCha20Rng,
+        candidates: &[(AuthorityIndex, Stake)],
+        total_stake: Stake,
+    ) -> usize {
+        let mut point = uniform_below(rng, total_stake);
+        for (position, (_, stake)) in candidates.iter().enumerate() {
+            if point < *stake {
+                return position;
+            }
+            point -= stake;
+        }
+        unreachable!("The drawn point should be below the total stake of the candidates");
+    }
+
+    fn bad_nodes_stake_threshold(context: &Context) -> u64 {
+        // Leaders are never swapped when no threshold is configured.
+        context
+            .protocol_config
+            .consensus_bad_nodes_stake_threshold_as_option()
+            .unwrap_or(0)
+    }
+}
+
+/// Swaps the worst performing authorities in the leader schedule for the best performing ones,
+/// as ranked by a set of reputation scores.
+#[derive(Default, Clone)]
+pub(crate) struct LeaderSwapTable {
+    /// The `f` (by stake) authorities with the best scores. They take the place of the bad nodes
+    /// in the leader schedule.
+    good_nodes: Vec<AuthorityIndex>,
+    /// The `f` (by stake) authorities with the worst scores. Whenever one of them is elected as
+    /// leader, it is swapped for one of the good nodes.
+    bad_nodes: BTreeSet<AuthorityIndex>,
+    /// The scores that the table was built from.
+    reputation_scores: ReputationScores,
+}
+
+impl LeaderSwapTable {
+    /// Builds the table from `reputation_scores`. `bad_nodes_stake_threshold` is the percentage of
+    /// stake, in the range [0, 33], held by the authorities considered good and bad respectively.
+    pub(crate) fn new(
+        context: Arc<Context>,
+        reputation_scores: &ReputationScores,
+        bad_nodes_stake_threshold: u64,
+    ) -> Self {
+        assert!(
+            (0..=33).contains(&bad_nodes_stake_threshold),
+            "The bad_nodes_stake_threshold should be in range [0 - 33], out of bounds parameter \
+            detected {bad_nodes_stake_threshold}"
+        );
+
+        let authorities_by_score = reputation_scores.authorities_by_score_desc(&context);
+        let good_nodes = Self::retrieve_first_nodes(
+            &context,
+            authorities_by_score.iter().copied(),
+            bad_nodes_stake_threshold,
+        );
+        // Reverse the authorities to score ascending, to get the lowest scorers first.
+        let bad_nodes = Self::retrieve_first_nodes(
+            &context,
+            authorities_by_score.iter().rev().copied(),
+            bad_nodes_stake_threshold,
+        )
+        .into_iter()
+        .collect();
+
+        Self {
+            good_nodes,
+            bad_nodes,
+            reputation_scores: reputation_scores.clone(),
+        }
+    }
+
+    /// Returns the good node to swap `leader` with if it is a bad node, or None otherwise. The
+    /// good node is chosen uniformly at random, seeded by the round and leader offset, rather than
+    /// weighted by stake, to give all good nodes an equal share of the swapped rounds. The rng and
+    /// the choice are pinned, as every authority must make the same choice.
+    pub(crate) fn swap(
+        &self,
+        leader: AuthorityIndex,
+        leader_round: Round,
+        leader_offset: u32,
+    ) -> Option<AuthorityIndex> {
+        if !self.bad_nodes.contains(&leader) {
+            return None;
+        }
+
+        let mut seed_bytes = [0u8; 32];
+        seed_bytes[24..28].copy_from_slice(&leader_round.to_le_bytes());
+        seed_bytes[28..32].copy_from_slice(&leader_offset.to_le_bytes());
+        let mut rng = ChaCha20Rng::from_seed(seed_bytes);
+
+        assert!(
+            !self.good_nodes.is_empty(),
+            "There should be at least one good node available"
+        );
+        let good_node =
+            self.good_nodes[uniform_below(&mut rng, self.good_nodes.len() as u64) as usize];
+        debug!("Swapping bad leader {leader} -> {good_node} for round {leader_round}");
+        Some(good_node)
+    }
+
+    /// Retrieves the first authorities of `authorities`, until the next one would bring their
+    /// total stake above `stake_threshold` percent of the total stake.
+    fn retrieve_first_nodes(
+        context: &Context,
+        authorities: impl Iterator<Item = (AuthorityIndex, u64)>,
+        stake_threshold: u64,
+    ) -> Vec<AuthorityIndex> {
+        let mut filtered_authorities = Vec::new();
+        let mut stake: Stake = 0;
+        for (authority_index, _score) in authorities {
+            stake += context.committee.stake(authority_index);
+            if stake > (stake_threshold * context.committee.total_stake()) / 100 {
+                break;
+            }
+            filtered_authorities.push(authority_index);
+        }
+        filtered_authorities
+    }
+}
+
+/// Draws a number uniformly from `[0, bound)`, rejecting the draws that would bias the result
+/// towards small numbers.
+fn uniform_below(rng: &mut ChaCha20Rng, bound: u64) -> u64 {
+    assert!(bound > 0, "Cannot draw from an empty range");
+    // Every number below `bound` is the remainder of the same count of values below `zone`.
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+        let value = rng.next_u64();
+        if value < zone {
+            return value % bound;
+        }
+    }
+}
+
+impl Debug for LeaderSwapTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "LeaderSwapTable(good_nodes={:?}, bad_nodes={:?}, scores={:?})",
+            self.good_nodes, self.bad_nodes, self.reputation_scores
+        )
+    }
 }
 
 #[cfg(test)]
@@ -71,7 +396,15 @@ mod tests {
     use sui_protocol_config::ProtocolConfig;
 
     use super::*;
-    use crate::metrics::test_metrics;
+    use crate::{
+        block::{BlockAPI as _, Slot, TestBlock, VerifiedBlock},
+        commit::DEFAULT_WAVE_LENGTH,
+        linearizer::Linearizer,
+        metrics::test_metrics,
+        storage::{mem_store::MemStore, Store as _},
+        test_dag::get_all_uncommitted_leader_blocks,
+        test_dag_builder::DagBuilder,
+    };
 
     #[test]
     fn test_elect_leader() {
@@ -136,4 +469,276 @@ mod tests {
             leader_schedule.elect_leader_stake_based(1, 2)
         );
     }
+
+    #[test]
+    fn test_elect_leader_stake_based_pinned() {
+        let committee = local_committee_and_keys(0, vec![1, 2, 3, 4]).0;
+        let mut protocol_config = ProtocolConfig::get_for_min_version();
+        protocol_config.set_mysticeti_leader_scoring_and_schedule_for_testing(true);
+        let context = Arc::new(Context::new(
+            AuthorityIndex::new_for_test(0),
+            committee,
+            Parameters::default(),
+            protocol_config,
+            test_metrics(),
+        ));
+        let leader_schedule = LeaderSchedule::new(context);
+
+        // The election must never change, as all authorities must elect the same leaders.
+        let leaders = (0..6)
+            .map(|round| leader_schedule.elect_leader_stake_based(round, 0).value())
+            .collect::<Vec<_>>();
+        assert_eq!(leaders, vec![0, 2, 2, 0, 2, 2]);
+        let leaders = (0..3)
+            .map(|offset| leader_schedule.elect_leader_stake_based(0, offset).value())
+            .collect::<Vec<_>>();
+        assert_eq!(leaders, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_leader_swap_table() {
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_consensus_bad_nodes_stake_threshold_for_testing(33);
+        let context = Arc::new(context);
+        let reputation_scores = ReputationScores::new(vec![4, 1, 3, 2], 10);
+
+        // Only one authority fits in 33% of the stake, so authority 0 is the only good node and
+        // authority 1 the only bad node.
+        let table = LeaderSwapTable::new(context.clone(), &reputation_scores, 33);
+        assert_eq!(table.good_nodes, vec![AuthorityIndex::new_for_test(0)]);
+        assert_eq!(
+            table.bad_nodes,
+            BTreeSet::from([AuthorityIndex::new_for_test(1)])
+        );
+        for round in 1..10 {
+            assert_eq!(
+                table.swap(AuthorityIndex::new_for_test(1), round, 0),
+                Some(AuthorityIndex::new_for_test(0))
+            );
+            assert_eq!(table.swap(AuthorityIndex::new_for_test(2), round, 0), None);
+        }
+
+        // No authority is swapped without a threshold.
+        let table = LeaderSwapTable::new(context, &reputation_scores, 0);
+        assert!(table.good_nodes.is_empty());
+        assert!(table.bad_nodes.is_empty());
+        assert_eq!(table.swap(AuthorityIndex::new_for_test(1), 1, 0), None);
+    }
+
+    #[test]
+    fn test_leader_swap_table_pinned_choice() {
+        let context = Arc::new(Context::new_for_test(7).0);
+        let reputation_scores = ReputationScores::new(vec![7, 6, 5, 4, 3, 2, 1], 10);
+
+        // Two authorities fit in 33% of the stake.
+        let table = LeaderSwapTable::new(context, &reputation_scores, 33);
+        assert_eq!(
+            table.good_nodes,
+            vec![
+                AuthorityIndex::new_for_test(0),
+                AuthorityIndex::new_for_test(1)
+            ]
+        );
+
+        // The choice of good node must never change, as all authorities must make the same one.
+        let swapped = (1..10)
+            .map(|round| {
+                table
+                    .swap(AuthorityIndex::new_for_test(6), round, 0)
+                    .unwrap()
+                    .value()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(swapped, vec![0, 1, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_leader_schedule_update_from_committed_subdags() {
+        telemetry_subscribers::init_for_testing();
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_consensus_bad_nodes_stake_threshold_for_testing(33);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone())
+            .with_num_commits_per_schedule(2);
+
+        // Blocks of authority 3 are never referenced by other blocks.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder
+            .layers(1..9)
+            .authorities(
+                context
+                    .committee
+                    .authorities()
+                    .map(|(index, _)| index)
+                    .collect(),
+            )
+            .skip_ancestor_links(vec![AuthorityIndex::new_for_test(3)]);
+        dag_builder.persist_all_blocks(dag_state.clone());
+
+        // Leaders of rounds 3 and 6 are authorities 3 and 2.
+        let leaders = get_all_uncommitted_leader_blocks(
+            dag_state.clone(),
+            leader_schedule.clone(),
+            6,
+            DEFAULT_WAVE_LENGTH,
+            false,
+            1,
+        );
+        assert_eq!(leaders.len(), 2);
+        assert_eq!(leaders[0].author(), AuthorityIndex::new_for_test(3));
+        assert_eq!(
+            leader_schedule.commits_until_leader_schedule_update(dag_state.clone()),
+            2
+        );
+
+        // Committing the first leader does not update the schedule yet.
+        let mut linearizer = Linearizer::new(dag_state.clone(), leader_schedule.clone());
+        linearizer.handle_commit(leaders[..1].to_vec());
+        assert_eq!(
+            leader_schedule.commits_until_leader_schedule_update(dag_state.clone()),
+            1
+        );
+        assert_eq!(
+            leader_schedule.elect_leader(7, 0),
+            AuthorityIndex::new_for_test(3)
+        );
+
+        // Committing the second leader updates the schedule, and authority 3 is swapped for the
+        // best scoring authority.
+        linearizer.handle_commit(leaders[1..].to_vec());
+        assert_eq!(
+            leader_schedule.commits_until_leader_schedule_update(dag_state.clone()),
+            2
+        );
+        assert_eq!(
+            dag_state.read().reputation_scores(),
+            ReputationScores::new(vec![2, 2, 2, 0], 2)
+        );
+        assert_eq!(
+            leader_schedule.elect_leader(7, 0),
+            AuthorityIndex::new_for_test(0)
+        );
+        assert_eq!(
+            leader_schedule.elect_leader(6, 0),
+            AuthorityIndex::new_for_test(2)
+        );
+
+        // The schedule is restored from the persisted scores.
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store)));
+        assert_eq!(dag_state.read().unscored_committed_subdags_count(), 0);
+        let leader_schedule = LeaderSchedule::from_store(context, dag_state);
+        assert_eq!(
+            leader_schedule.elect_leader(7, 0),
+            AuthorityIndex::new_for_test(0)
+        );
+    }
+
+    #[test]
+    fn test_truncate_to_leader_schedule_update() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            context.clone(),
+            Arc::new(MemStore::new()),
+        )));
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone())
+            .with_num_commits_per_schedule(2);
+
+        let commit = |round| {
+            LeaderStatus::Commit(VerifiedBlock::new_for_test(
+                TestBlock::new(round, 0).build(),
+            ))
+        };
+        let skip = |round| LeaderStatus::Skip(Slot::new_for_test(round, 0));
+        let rounds = |leaders: &[LeaderStatus]| {
+            leaders
+                .iter()
+                .map(|leader| leader.round())
+                .collect::<Vec<_>>()
+        };
+
+        // The leaders are truncated after the second commit, skipped leaders aside.
+        let mut leaders = vec![commit(3), skip(6), commit(9), commit(12)];
+        let updated =
+            leader_schedule.truncate_to_leader_schedule_update(dag_state.clone(), &mut leaders);
+        assert!(updated);
+        assert_eq!(rounds(&leaders), vec![3, 6, 9]);
+
+        // Leaders that don't reach the update are left as they are.
+        let mut leaders = vec![commit(3), skip(6)];
+        assert!(
+            !leader_schedule.truncate_to_leader_schedule_update(dag_state.clone(), &mut leaders)
+        );
+        assert_eq!(rounds(&leaders), vec![3, 6]);
+
+        // Without leader scoring, there are no updates to stop at.
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_mysticeti_leader_scoring_and_schedule_for_testing(false);
+        let leader_schedule =
+            LeaderSchedule::new(Arc::new(context)).with_num_commits_per_schedule(2);
+        let mut leaders = vec![commit(3), commit(6), commit(9)];
+        assert!(!leader_schedule.truncate_to_leader_schedule_update(dag_state, &mut leaders));
+        assert_eq!(rounds(&leaders), vec![3, 6, 9]);
+    }
+
+    #[test]
+    fn test_leader_schedule_disabled() {
+        let mut context = Context::new_for_test(4).0;
+        context
+            .protocol_config
+            .set_consensus_bad_nodes_stake_threshold_for_testing(33);
+        context
+            .protocol_config
+            .set_mysticeti_leader_scoring_and_schedule_for_testing(false);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
+        let leader_schedule = LeaderSchedule::from_store(context.clone(), dag_state.clone())
+            .with_num_commits_per_schedule(2);
+
+        // Blocks of authority 3 are never referenced by other blocks.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder
+            .layers(1..9)
+            .authorities(
+                context
+                    .committee
+                    .authorities()
+                    .map(|(index, _)| index)
+                    .collect(),
+            )
+            .skip_ancestor_links(vec![AuthorityIndex::new_for_test(3)]);
+        dag_builder.persist_all_blocks(dag_state.clone());
+
+        let leaders = get_all_uncommitted_leader_blocks(
+            dag_state.clone(),
+            leader_schedule.clone(),
+            6,
+            DEFAULT_WAVE_LENGTH,
+            false,
+            1,
+        );
+        assert_eq!(leaders.len(), 2);
+
+        // Committing past the update point neither scores the commits nor swaps any leader.
+        let mut linearizer = Linearizer::new(dag_state.clone(), leader_schedule.clone());
+        linearizer.handle_commit(leaders);
+        assert_eq!(dag_state.read().unscored_committed_subdags_count(), 0);
+        assert!(dag_state.read().reputation_scores().is_empty());
+        assert_eq!(
+            leader_schedule.elect_leader(7, 0),
+            AuthorityIndex::new_for_test(3)
+        );
+
+        // Commit infos are written without reputation scores.
+        let commit_info = store.read_last_commit_info().unwrap().unwrap();
+        assert!(commit_info.reputation_scores().is_none());
+    }
 }
@@ -0,0 +1,162 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cmp::Reverse;
+
+use consensus_config::AuthorityIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    block::BlockAPI,
+    commit::{CommitIndex, CommittedSubDag},
+    context::Context,
+};
+
+/// Reputation scores of the authorities, calculated over a sequence of committed sub-dags.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct ReputationScores {
+    /// Score per authority. Vec index is the `AuthorityIndex`.
+    pub(crate) scores_per_authority: Vec<u64>,
+    /// Index of the last commit included in the scores, or 0 if no commit has been scored yet.
+    pub(crate) last_commit_index: CommitIndex,
+}
+
+impl ReputationScores {
+    pub(crate) fn new(scores_per_authority: Vec<u64>, last_commit_index: CommitIndex) -> Self {
+        Self {
+            scores_per_authority,
+            last_commit_index,
+        }
+    }
+
+    /// Scores the authorities over the provided committed sub-dags, which must be consecutive.
+    ///
+    /// Every leader block of a sub-dag gives one point to each authority whose block of the
+    /// previous round is referenced by the leader. Authorities that are slow to propose, or whose
+    /// blocks are slow to propagate, miss the leaders' ancestor lists and score lower. Only the
+    /// content of committed blocks is used, so all authorities calculate the same scores.
+    pub(crate) fn from_committed_subdags(
+        context: &Context,
+        committed_subdags: &[CommittedSubDag],
+    ) -> Self {
+        let mut scores_per_authority = vec![0_u64; context.committee.size()];
+        for subdag in committed_subdags {
+            let leader = subdag
+                .blocks
+                .iter()
+                .find(|block| block.reference() == subdag.leader)
+                .expect("Leader block must be in the sub-dag");
+            for ancestor in leader.ancestors() {
+                if ancestor.round + 1 == leader.round() {
+                    scores_per_authority[ancestor.author] += 1;
+                }
+            }
+        }
+
+        let last_commit_index = committed_subdags
+            .last()
+            .map(|subdag| subdag.commit_index)
+            .unwrap_or_default();
+        Self::new(scores_per_authority, last_commit_index)
+    }
+
+    /// Returns the authorities by score descending. Authorities with equal scores are ordered by
+    /// ascending index, so the order is deterministic.
+    pub(crate) fn authorities_by_score_desc(
+        &self,
+        context: &Context,
+    ) -> Vec<(AuthorityIndex, u64)> {
+        let mut authorities: Vec<_> = self
+            .scores_per_authority
+            .iter()
+            .enumerate()
+            .map(|(index, score)| {
+                (
+                    context
+                        .committee
+                        .to_authority_index(index)
+                        .expect("Should be a valid AuthorityIndex"),
+                    *score,
+                )
+            })
+            .collect();
+        authorities.sort_by_key(|(index, score)| (Reverse(*score), *index));
+        authorities
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.scores_per_authority.is_empty()
+    }
+
+    pub(crate) fn update_metrics(&self, context: &Context) {
+        for (index, score) in self.scores_per_authority.iter().enumerate() {
+            let authority = context
+                .committee
+                .to_authority_index(index)
+                .expect("Should be a valid AuthorityIndex");
+            let hostname = &context.committee.authority(authority).hostname;
+            context
+                .metrics
+                .node_metrics
+                .reputation_scores
+                .with_label_values(&[hostname])
+                .set(*score as i64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{TestBlock, VerifiedBlock};
+
+    #[test]
+    fn test_reputation_scores_from_committed_subdags() {
+        let context = Context::new_for_test(4).0;
+
+        // Leader of round 2 references the round 1 blocks of authorities 0, 1 and 2.
+        let round_1 = (0..4)
+            .map(|author| {
+                VerifiedBlock::new_for_test(TestBlock::new(1, author).build()).reference()
+            })
+            .collect::<Vec<_>>();
+        let leader_1 = VerifiedBlock::new_for_test(
+            TestBlock::new(2, 0)
+                .set_ancestors(round_1[0..3].to_vec())
+                .build(),
+        );
+        // Leader of round 3 references the round 2 blocks of authorities 0 and 1, and a round 1
+        // block of authority 3 which is not counted.
+        let round_2 = [
+            leader_1.reference(),
+            VerifiedBlock::new_for_test(TestBlock::new(2, 1).build()).reference(),
+        ];
+        let leader_2 = VerifiedBlock::new_for_test(
+            TestBlock::new(3, 1)
+                .set_ancestors(vec![round_2[0], round_2[1], round_1[3]])
+                .build(),
+        );
+
+        let subdags = vec![
+            CommittedSubDag::new(leader_1.reference(), vec![leader_1.clone()], 0, 1),
+            CommittedSubDag::new(leader_2.reference(), vec![leader_2.clone()], 0, 2),
+        ];
+        let scores = ReputationScores::from_committed_subdags(&context, &subdags);
+
+        assert_eq!(scores.scores_per_authority, vec![2, 2, 1, 0]);
+        assert_eq!(scores.last_commit_index, 2);
+        assert_eq!(
+            scores.authorities_by_score_desc(&context),
+            vec![
+                (AuthorityIndex::new_for_test(0), 2),
+                (AuthorityIndex::new_for_test(1), 2),
+                (AuthorityIndex::new_for_test(2), 1),
+                (AuthorityIndex::new_for_test(3), 0),
+            ]
+        );
+
+        let empty = ReputationScores::from_committed_subdags(&Context::new_for_test(4).0, &[]);
+        assert_eq!(empty.scores_per_authority, vec![0; 4]);
+        assert_eq!(empty.last_commit_index, 0);
+    }
+}
@@ -10,13 +10,16 @@ mod block_verifier;
 mod broadcaster;
 mod commit;
 mod commit_observer;
+mod commit_replayer;
 mod commit_syncer;
 mod context;
 mod core;
 mod core_thread;
+mod dag_inspector;
 mod dag_state;
 mod error;
 mod leader_schedule;
+mod leader_scoring;
 mod leader_timeout;
 mod linearizer;
 mod metrics;
@@ -39,4 +42,8 @@ mod test_dag_parser;
 pub use authority_node::{ConsensusAuthority, NetworkType};
 pub use block::{BlockAPI, Round};
 pub use commit::{CommitConsumer, CommitIndex, CommittedSubDag};
+pub use commit_replayer::{CommitDivergence, CommitReplayer, ReplayResult};
+pub use dag_inspector::{
+    BlockSummary, DagInspector, DagReport, LeaderDecision, LeaderSummary, PropagationDelay,
+};
 pub use transaction::{TransactionClient, TransactionVerifier, ValidationError};
@@ -9,6 +9,7 @@ use crate::{
     block::{BlockAPI, Round, VerifiedBlock},
     commit::{Commit, CommitIndex, CommittedSubDag, TrustedCommit},
     dag_state::DagState,
+    leader_schedule::LeaderSchedule,
 };
 
 /// Expand a committed sequence of leader into a sequence of sub-dags.
@@ -16,11 +17,16 @@ use crate::{
 pub(crate) struct Linearizer {
     /// In memory block store representing the dag state
     dag_state: Arc<RwLock<DagState>>,
+    /// The leader schedule, updated from the reputation scores of the committed sub-dags.
+    leader_schedule: LeaderSchedule,
 }
 
 impl Linearizer {
-    pub(crate) fn new(dag_state: Arc<RwLock<DagState>>) -> Self {
-        Self { dag_state }
+    pub(crate) fn new(dag_state: Arc<RwLock<DagState>>, leader_schedule: LeaderSchedule) -> Self {
+        Self {
+            dag_state,
+            leader_schedule,
+        }
     }
 
     /// Collect the sub-dag from a specific leader excluding any duplicates or
@@ -122,6 +128,22 @@ impl Linearizer {
                 .unwrap_or_else(|e| panic!("Failed to serialize commit: {}", e));
             let commit = TrustedCommit::new_trusted(commit, serialized);
             self.dag_state.write().add_commit(commit.clone());
+
+            // Update the leader schedule once enough commits have been scored, so the new
+            // schedule gets persisted together with the commit that triggered it.
+            if self.leader_schedule.is_enabled() {
+                self.dag_state
+                    .write()
+                    .add_unscored_committed_subdags(vec![sub_dag.clone()]);
+                if self
+                    .leader_schedule
+                    .commits_until_leader_schedule_update(self.dag_state.clone())
+                    == 0
+                {
+                    self.leader_schedule
+                        .update_leader_schedule(self.dag_state.clone());
+                }
+            }
             committed_sub_dags.push(sub_dag);
         }
         // Committed blocks must be persisted to storage before sending them to Sui and executing
@@ -156,8 +178,8 @@ mod tests {
             context.clone(),
             Arc::new(MemStore::new()),
         )));
-        let mut linearizer = Linearizer::new(dag_state.clone());
         let leader_schedule = LeaderSchedule::new(context.clone());
+        let mut linearizer = Linearizer::new(dag_state.clone(), leader_schedule.clone());
 
         // Populate fully connected test blocks for round 0 ~ 10, authorities 0 ~ 3.
         let num_rounds: u32 = 10;
@@ -208,7 +230,7 @@ mod tests {
             Arc::new(MemStore::new()),
         )));
         let leader_schedule = LeaderSchedule::new(context.clone());
-        let mut linearizer = Linearizer::new(dag_state.clone());
+        let mut linearizer = Linearizer::new(dag_state.clone(), leader_schedule.clone());
         let wave_length = DEFAULT_WAVE_LENGTH;
 
         let mut blocks = vec![];
@@ -97,6 +97,8 @@ pub(crate) struct NodeMetrics {
     pub commit_round_advancement_interval: Histogram,
     pub last_decided_leader_round: IntGauge,
     pub leader_timeout_total: IntCounter,
+    pub leader_swap_table_bad_nodes: IntGauge,
+    pub reputation_scores: IntGaugeVec,
     pub missing_blocks_total: IntGauge,
     pub quorum_receive_latency: Histogram,
     pub scope_processing_time: HistogramVec,
@@ -255,6 +257,17 @@ impl NodeMetrics {
                 "Total number of leader timeouts",
                 registry,
             ).unwrap(),
+            leader_swap_table_bad_nodes: register_int_gauge_with_registry!(
+                "leader_swap_table_bad_nodes",
+                "The number of authorities swapped out of the leader schedule for their low reputation scores",
+                registry,
+            ).unwrap(),
+            reputation_scores: register_int_gauge_vec_with_registry!(
+                "reputation_scores",
+                "The reputation score of each authority, as of the last leader schedule update",
+                &["authority"],
+                registry,
+            ).unwrap(),
             missing_blocks_total: register_int_gauge_with_registry!(
                 "missing_blocks_total",
                 "Total number of missing blocks",
@@ -0,0 +1,558 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scripted network faults for testing consensus under adversarial conditions.
+//!
+//! `FaultInjectingClient` wraps any `NetworkClient`, and applies the `FaultScenario` of a shared
+//! `NetworkFaults` handle to every message: partitions, per-link latencies, drops, reordering
+//! and equivocating authorities. Delays use `tokio::time`, so tests can run scenarios with paused
+//! time, and random choices come from a seeded rng so the same scenario produces the same faults.
+//! `FaultInjectingManager` wraps a `NetworkManager`, to inject faults into running authorities.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use consensus_config::{AuthorityIndex, NetworkKeyPair, ProtocolKeyPair};
+use futures::StreamExt as _;
+use parking_lot::{Mutex, RwLock};
+use rand::{rngs::StdRng, Rng as _, SeedableRng as _};
+use tokio::time::sleep;
+
+use crate::{
+    block::{Block, BlockAPI as _, BlockRef, BlockV1, SignedBlock, VerifiedBlock},
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+    network::{BlockStream, NetworkClient, NetworkManager, NetworkService},
+    CommitIndex, Round,
+};
+
+/// Distribution of the one-way latency of a link.
+#[derive(Clone, Debug)]
+pub(crate) enum LatencyDistribution {
+    Fixed(Duration),
+    Uniform { min: Duration, max: Duration },
+}
+
+impl LatencyDistribution {
+    fn sample(&self, rng: &mut StdRng) -> Duration {
+        match self {
+            LatencyDistribution::Fixed(latency) => *latency,
+            LatencyDistribution::Uniform { min, max } => rng.gen_range(*min..=*max),
+        }
+    }
+}
+
+/// Faults applied to the network. The default scenario has no fault.
+#[derive(Clone, Default)]
+pub(crate) struct FaultScenario {
+    /// When not empty, authorities can only reach authorities of the same group. Authorities
+    /// outside of all groups are isolated.
+    partitions: Vec<BTreeSet<AuthorityIndex>>,
+    /// Latency of links without a specific latency.
+    default_latency: Option<LatencyDistribution>,
+    /// Latency per (sender, receiver) link.
+    link_latencies: BTreeMap<(AuthorityIndex, AuthorityIndex), LatencyDistribution>,
+    /// Probability that a message is dropped.
+    drop_probability: f64,
+    /// Probability that a message is held back by `reorder_delay`, so that later messages on
+    /// the same link overtake it. Messages of a subscription stream are never reordered.
+    reorder_probability: f64,
+    reorder_delay: Duration,
+    /// Authorities that propose two different blocks per round, with their signing keys.
+    /// Receivers with an odd index get the second block.
+    equivocators: BTreeMap<AuthorityIndex, Arc<ProtocolKeyPair>>,
+}
+
+impl FaultScenario {
+    pub(crate) fn with_partitions(mut self, partitions: Vec<Vec<AuthorityIndex>>) -> Self {
+        self.partitions = partitions
+            .into_iter()
+            .map(|group| group.into_iter().collect())
+            .collect();
+        self
+    }
+
+    pub(crate) fn with_default_latency(mut self, latency: LatencyDistribution) -> Self {
+        self.default_latency = Some(latency);
+        self
+    }
+
+    pub(crate) fn with_link_latency(
+        mut self,
+        from: AuthorityIndex,
+        to: AuthorityIndex,
+        latency: LatencyDistribution,
+    ) -> Self {
+        self.link_latencies.insert((from, to), latency);
+        self
+    }
+
+    pub(crate) fn with_drop_probability(mut self, drop_probability: f64) -> Self {
+        self.drop_probability = drop_probability;
+        self
+    }
+
+    pub(crate) fn with_reordering(mut self, probability: f64, delay: Duration) -> Self {
+        self.reorder_probability = probability;
+        self.reorder_delay = delay;
+        self
+    }
+
+    pub(crate) fn with_equivocator(
+        mut self,
+        authority: AuthorityIndex,
+        protocol_keypair: ProtocolKeyPair,
+    ) -> Self {
+        self.equivocators
+            .insert(authority, Arc::new(protocol_keypair));
+        self
+    }
+
+    fn is_reachable(&self, from: AuthorityIndex, to: AuthorityIndex) -> bool {
+        self.partitions.is_empty()
+            || self
+                .partitions
+                .iter()
+                .any(|group| group.contains(&from) && group.contains(&to))
+    }
+}
+
+/// Shared handle to the fault scenario of a network. The scenario can be changed while the
+/// network is in use, e.g. to heal a partition.
+pub(crate) struct NetworkFaults {
+    scenario: RwLock<FaultScenario>,
+    rng: Mutex<StdRng>,
+}
+
+impl NetworkFaults {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            scenario: RwLock::new(FaultScenario::default()),
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    pub(crate) fn set_scenario(&self, scenario: FaultScenario) {
+        *self.scenario.write() = scenario;
+    }
+
+    /// Removes all faults.
+    pub(crate) fn heal(&self) {
+        self.set_scenario(FaultScenario::default());
+    }
+
+    /// Returns the delay of a message sent over the link, or None if the message is dropped.
+    fn delay(&self, from: AuthorityIndex, to: AuthorityIndex) -> Option<Duration> {
+        let scenario = self.scenario.read();
+        if !scenario.is_reachable(from, to) {
+            return None;
+        }
+        let mut rng = self.rng.lock();
+        if scenario.drop_probability > 0.0 && rng.gen_bool(scenario.drop_probability) {
+            return None;
+        }
+        let mut delay = scenario
+            .link_latencies
+            .get(&(from, to))
+            .or(scenario.default_latency.as_ref())
+            .map(|latency| latency.sample(&mut rng))
+            .unwrap_or_default();
+        if scenario.reorder_probability > 0.0 && rng.gen_bool(scenario.reorder_probability) {
+            delay += scenario.reorder_delay;
+        }
+        Some(delay)
+    }
+
+    /// Returns the block that the receiver gets instead of `block`, when its author equivocates.
+    fn equivocate(&self, block: &VerifiedBlock, receiver: AuthorityIndex) -> Option<VerifiedBlock> {
+        if receiver.value() % 2 == 0 {
+            return None;
+        }
+        let protocol_keypair = self
+            .scenario
+            .read()
+            .equivocators
+            .get(&block.author())?
+            .clone();
+        // Same slot and ancestors, but a different timestamp and no transaction.
+        let equivocating_block = Block::V1(BlockV1::new(
+            block.epoch(),
+            block.round(),
+            block.author(),
+            block.timestamp_ms() + 1,
+            block.ancestors().to_vec(),
+            vec![],
+            block.commit_votes().to_vec(),
+        ));
+        let signed_block =
+            SignedBlock::new(equivocating_block, &protocol_keypair).expect("Block signing failed");
+        let serialized = signed_block
+            .serialize()
+            .expect("Block serialization failed");
+        Some(VerifiedBlock::new_verified(signed_block, serialized))
+    }
+
+    /// Same as `equivocate()`, for a serialized block received from the network.
+    fn equivocate_serialized(&self, serialized: Bytes, receiver: AuthorityIndex) -> Bytes {
+        let Ok(signed_block) = bcs::from_bytes::<SignedBlock>(&serialized) else {
+            return serialized;
+        };
+        let block = VerifiedBlock::new_verified(signed_block, serialized.clone());
+        match self.equivocate(&block, receiver) {
+            Some(equivocating_block) => equivocating_block.serialized().clone(),
+            None => serialized,
+        }
+    }
+}
+
+/// A `NetworkClient` that injects the faults of a `NetworkFaults` handle into the messages of
+/// the wrapped client. Requests and responses are delayed or dropped separately. Dropped
+/// messages fail after the request timeout, as they would over a real network.
+pub(crate) struct FaultInjectingClient<C: NetworkClient> {
+    own_index: AuthorityIndex,
+    inner: Arc<C>,
+    faults: Arc<NetworkFaults>,
+}
+
+impl<C: NetworkClient> FaultInjectingClient<C> {
+    pub(crate) fn new(
+        own_index: AuthorityIndex,
+        inner: Arc<C>,
+        faults: Arc<NetworkFaults>,
+    ) -> Self {
+        Self {
+            own_index,
+            inner,
+            faults,
+        }
+    }
+
+    /// Waits for a request and its response to go over the link with the peer. Returns the
+    /// remaining timeout, or an error after the timeout if either message is dropped.
+    async fn round_trip(
+        &self,
+        peer: AuthorityIndex,
+        timeout: Duration,
+        request: &str,
+    ) -> ConsensusResult<Duration> {
+        let delay = self
+            .faults
+            .delay(self.own_index, peer)
+            .zip(self.faults.delay(peer, self.own_index))
+            .map(|(request_delay, response_delay)| request_delay + response_delay);
+        match delay {
+            Some(delay) if delay < timeout => {
+                sleep(delay).await;
+                Ok(timeout - delay)
+            }
+            _ => {
+                sleep(timeout).await;
+                Err(ConsensusError::NetworkError(format!(
+                    "{request} to {peer} timed out by fault injection"
+                )))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<C: NetworkClient> NetworkClient for FaultInjectingClient<C> {
+    const SUPPORT_STREAMING: bool = C::SUPPORT_STREAMING;
+
+    async fn send_block(
+        &self,
+        peer: AuthorityIndex,
+        block: &VerifiedBlock,
+        timeout: Duration,
+    ) -> ConsensusResult<()> {
+        let timeout = self.round_trip(peer, timeout, "send_block").await?;
+        match self.faults.equivocate(block, peer) {
+            Some(equivocating_block) => {
+                self.inner
+                    .send_block(peer, &equivocating_block, timeout)
+                    .await
+            }
+            None => self.inner.send_block(peer, block, timeout).await,
+        }
+    }
+
+    async fn subscribe_blocks(
+        &self,
+        peer: AuthorityIndex,
+        last_received: Round,
+        timeout: Duration,
+    ) -> ConsensusResult<BlockStream> {
+        let timeout = self.round_trip(peer, timeout, "subscribe_blocks").await?;
+        let stream = self
+            .inner
+            .subscribe_blocks(peer, last_received, timeout)
+            .await?;
+        let own_index = self.own_index;
+        let faults = self.faults.clone();
+        let stream = stream.filter_map(move |serialized| {
+            let faults = faults.clone();
+            async move {
+                let delay = faults.delay(peer, own_index)?;
+                sleep(delay).await;
+                Some(faults.equivocate_serialized(serialized, own_index))
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    async fn fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        timeout: Duration,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        // Fetched blocks are not equivocated, because they are requested by digest.
+        let timeout = self.round_trip(peer, timeout, "fetch_blocks").await?;
+        self.inner.fetch_blocks(peer, block_refs, timeout).await
+    }
+
+    async fn fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        start: CommitIndex,
+        end: CommitIndex,
+        timeout: Duration,
+    ) -> ConsensusResult<(Vec<Bytes>, Vec<Bytes>)> {
+        let timeout = self.round_trip(peer, timeout, "fetch_commits").await?;
+        self.inner.fetch_commits(peer, start, end, timeout).await
+    }
+}
+
+/// A `NetworkManager` whose client injects faults, for starting authorities in tests with
+/// `AuthorityNode::<FaultInjectingManager<_, M>>::start()`. The rng of each authority is seeded by
+/// its index.
+pub(crate) struct FaultInjectingManager<S: NetworkService, M: NetworkManager<S>> {
+    inner: M,
+    client: Arc<FaultInjectingClient<M::Client>>,
+    faults: Arc<NetworkFaults>,
+    _service: std::marker::PhantomData<S>,
+}
+
+impl<S: NetworkService, M: NetworkManager<S>> FaultInjectingManager<S, M> {
+    pub(crate) fn faults(&self) -> Arc<NetworkFaults> {
+        self.faults.clone()
+    }
+}
+
+impl<S: NetworkService, M: NetworkManager<S>> NetworkManager<S> for FaultInjectingManager<S, M> {
+    type Client = FaultInjectingClient<M::Client>;
+
+    fn new(context: Arc<Context>) -> Self {
+        let inner = M::new(context.clone());
+        let faults = Arc::new(NetworkFaults::new(context.own_index.value() as u64));
+        let client = Arc::new(FaultInjectingClient::new(
+            context.own_index,
+            inner.client(),
+            faults.clone(),
+        ));
+        Self {
+            inner,
+            client,
+            faults,
+            _service: std::marker::PhantomData,
+        }
+    }
+
+    fn client(&self) -> Arc<Self::Client> {
+        self.client.clone()
+    }
+
+    async fn install_service(&mut self, network_keypair: NetworkKeyPair, service: Arc<S>) {
+        self.inner.install_service(network_keypair, service).await
+    }
+
+    async fn stop(&mut self) {
+        self.inner.stop().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use tokio::time::Instant;
+
+    use super::*;
+    use crate::block::TestBlock;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[derive(Default)]
+    struct MockNetworkClient {
+        sent_blocks: Mutex<Vec<(AuthorityIndex, VerifiedBlock)>>,
+        own_blocks: Vec<VerifiedBlock>,
+    }
+
+    #[async_trait]
+    impl NetworkClient for MockNetworkClient {
+        const SUPPORT_STREAMING: bool = true;
+
+        async fn send_block(
+            &self,
+            peer: AuthorityIndex,
+            block: &VerifiedBlock,
+            _timeout: Duration,
+        ) -> ConsensusResult<()> {
+            self.sent_blocks.lock().push((peer, block.clone()));
+            Ok(())
+        }
+
+        async fn subscribe_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _last_received: Round,
+            _timeout: Duration,
+        ) -> ConsensusResult<BlockStream> {
+            let blocks = self
+                .own_blocks
+                .iter()
+                .map(|block| block.serialized().clone())
+                .collect::<Vec<_>>();
+            Ok(Box::pin(stream::iter(blocks)))
+        }
+
+        async fn fetch_blocks(
+            &self,
+            _peer: AuthorityIndex,
+            _block_refs: Vec<BlockRef>,
+            _timeout: Duration,
+        ) -> ConsensusResult<Vec<Bytes>> {
+            unimplemented!("Unimplemented")
+        }
+
+        async fn fetch_commits(
+            &self,
+            _peer: AuthorityIndex,
+            _start: CommitIndex,
+            _end: CommitIndex,
+            _timeout: Duration,
+        ) -> ConsensusResult<(Vec<Bytes>, Vec<Bytes>)> {
+            unimplemented!("Unimplemented")
+        }
+    }
+
+    fn authority(index: u32) -> AuthorityIndex {
+        AuthorityIndex::new_for_test(index)
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_partitions_and_latencies() {
+        let inner = Arc::new(MockNetworkClient::default());
+        let faults = Arc::new(NetworkFaults::new(0));
+        let client = FaultInjectingClient::new(authority(0), inner.clone(), faults.clone());
+        let block = VerifiedBlock::new_for_test(TestBlock::new(1, 0).build());
+
+        faults.set_scenario(
+            FaultScenario::default()
+                .with_partitions(vec![
+                    vec![authority(0), authority(1)],
+                    vec![authority(2), authority(3)],
+                ])
+                .with_default_latency(LatencyDistribution::Fixed(Duration::from_millis(100))),
+        );
+
+        // A request and its response each take the link latency.
+        let start = Instant::now();
+        client
+            .send_block(authority(1), &block, TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+
+        // Messages across partitions fail after the timeout.
+        let start = Instant::now();
+        assert!(client
+            .send_block(authority(2), &block, TIMEOUT)
+            .await
+            .is_err());
+        assert_eq!(start.elapsed(), TIMEOUT);
+
+        // All messages are dropped with a drop probability of 1.
+        faults.set_scenario(FaultScenario::default().with_drop_probability(1.0));
+        assert!(client
+            .send_block(authority(1), &block, TIMEOUT)
+            .await
+            .is_err());
+
+        faults.heal();
+        let start = Instant::now();
+        client
+            .send_block(authority(3), &block, TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        let sent = inner.sent_blocks.lock();
+        assert_eq!(
+            sent.iter().map(|(peer, _)| *peer).collect::<Vec<_>>(),
+            vec![authority(1), authority(3)]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_equivocation() {
+        let (context, mut keys) = Context::new_for_test(4);
+        let inner = Arc::new(MockNetworkClient {
+            own_blocks: (1..=3)
+                .map(|round| VerifiedBlock::new_for_test(TestBlock::new(round, 2).build()))
+                .collect(),
+            ..Default::default()
+        });
+        let faults = Arc::new(NetworkFaults::new(0));
+        faults.set_scenario(
+            FaultScenario::default()
+                .with_equivocator(authority(0), keys.remove(0).1)
+                .with_link_latency(
+                    authority(2),
+                    authority(0),
+                    LatencyDistribution::Fixed(Duration::from_millis(10)),
+                ),
+        );
+        let block = VerifiedBlock::new_for_test(TestBlock::new(1, 0).build());
+
+        // Receivers with an odd index get another block for the same slot.
+        let client = FaultInjectingClient::new(context.own_index, inner.clone(), faults.clone());
+        client
+            .send_block(authority(1), &block, TIMEOUT)
+            .await
+            .unwrap();
+        client
+            .send_block(authority(2), &block, TIMEOUT)
+            .await
+            .unwrap();
+        {
+            let sent = inner.sent_blocks.lock();
+            assert_eq!(sent[0].1.slot(), block.slot());
+            assert_ne!(sent[0].1.reference(), block.reference());
+            assert_eq!(sent[1].1.reference(), block.reference());
+        }
+
+        // Blocks of a subscription are delayed by the link latency, and are not equivocated
+        // when their author is not an equivocator.
+        let start = Instant::now();
+        let received = client
+            .subscribe_blocks(authority(2), 0, TIMEOUT)
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(start.elapsed(), Duration::from_millis(10 + 3 * 10));
+        assert_eq!(
+            received,
+            inner
+                .own_blocks
+                .iter()
+                .map(|block| block.serialized().clone())
+                .collect::<Vec<_>>()
+        );
+    }
+}
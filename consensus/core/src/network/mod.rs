@@ -43,6 +43,8 @@ mod tonic_gen {
 pub(crate) mod anemo_network;
 pub(crate) mod connection_monitor;
 pub(crate) mod epoch_filter;
+#[cfg(test)]
+pub(crate) mod fault_injection;
 pub(crate) mod metrics;
 #[cfg(test)]
 pub(crate) mod test_network;
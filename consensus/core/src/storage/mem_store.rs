@@ -19,7 +19,7 @@ use crate::{
     error::ConsensusResult,
 };
 
-/// In-memory storage for testing.
+/// In-memory storage for testing, and for replaying persisted blocks offline.
 pub(crate) struct MemStore {
     inner: RwLock<Inner>,
 }
@@ -33,7 +33,6 @@ struct Inner {
 }
 
 impl MemStore {
-    #[cfg(test)]
     pub(crate) fn new() -> Self {
         MemStore {
             inner: RwLock::new(Inner {
@@ -74,9 +73,10 @@ impl Store for MemStore {
                     .commits
                     .insert((commit.index(), commit.digest()), commit);
             }
-            let commit_info = CommitInfo {
-                last_committed_rounds: write_batch.last_committed_rounds,
-            };
+            let commit_info = CommitInfo::new(
+                write_batch.last_committed_rounds,
+                write_batch.reputation_scores,
+            );
             inner
                 .commit_info
                 .insert((last_commit.index(), last_commit.digest()), commit_info);
@@ -16,6 +16,7 @@ use crate::{
     block::{BlockRef, Round, Slot, VerifiedBlock},
     commit::{CommitIndex, TrustedCommit},
     error::ConsensusResult,
+    leader_scoring::ReputationScores,
 };
 
 /// A common interface for consensus storage.
@@ -68,6 +69,8 @@ pub(crate) struct WriteBatch {
     pub(crate) blocks: Vec<VerifiedBlock>,
     pub(crate) commits: Vec<TrustedCommit>,
     pub(crate) last_committed_rounds: Vec<Round>,
+    /// Only set when leader scoring is enabled, in which case `CommitInfo::V2` is written.
+    pub(crate) reputation_scores: Option<ReputationScores>,
 }
 
 impl WriteBatch {
@@ -75,11 +78,13 @@ impl WriteBatch {
         blocks: Vec<VerifiedBlock>,
         commits: Vec<TrustedCommit>,
         last_committed_rounds: Vec<Round>,
+        reputation_scores: Option<ReputationScores>,
     ) -> Self {
         WriteBatch {
             blocks,
             commits,
             last_committed_rounds,
+            reputation_scores,
         }
     }
 
@@ -101,8 +106,54 @@ impl WriteBatch {
 /// Per-commit properties that can be derived and do not need to be part of the Commit struct.
 /// Only the latest version is needed for CommitInfo, but more versions are stored for
 /// debugging and potential recovery.
-// TODO: version this struct.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub(crate) struct CommitInfo {
-    pub(crate) last_committed_rounds: Vec<Round>,
+pub(crate) enum CommitInfo {
+    V1(CommitInfoV1),
+    V2(CommitInfoV2),
+}
+
+impl CommitInfo {
+    /// Creates a `V2` commit info when there are reputation scores to persist, and a `V1` one
+    /// otherwise, so stores written without leader scoring keep the same format.
+    pub(crate) fn new(
+        last_committed_rounds: Vec<Round>,
+        reputation_scores: Option<ReputationScores>,
+    ) -> Self {
+        match reputation_scores {
+            Some(reputation_scores) => CommitInfo::V2(CommitInfoV2 {
+                last_committed_rounds,
+                reputation_scores,
+            }),
+            None => CommitInfo::V1(CommitInfoV1 {
+                last_committed_rounds,
+            }),
+        }
+    }
+
+    pub(crate) fn last_committed_rounds(&self) -> &[Round] {
+        match self {
+            CommitInfo::V1(info) => &info.last_committed_rounds,
+            CommitInfo::V2(info) => &info.last_committed_rounds,
+        }
+    }
+
+    /// The reputation scores of the last leader schedule update, which the leader schedule is
+    /// restored from on recovery. None if leader scoring was not enabled.
+    pub(crate) fn reputation_scores(&self) -> Option<&ReputationScores> {
+        match self {
+            CommitInfo::V1(_) => None,
+            CommitInfo::V2(info) => Some(&info.reputation_scores),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct CommitInfoV1 {
+    last_committed_rounds: Vec<Round>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct CommitInfoV2 {
+    last_committed_rounds: Vec<Round>,
+    reputation_scores: ReputationScores,
 }
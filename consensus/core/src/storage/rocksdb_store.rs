@@ -3,6 +3,7 @@
 
 use std::collections::VecDeque;
 use std::ops::Range;
+use std::sync::Arc;
 use std::{
     ops::Bound::{Excluded, Included},
     time::Duration,
@@ -13,7 +14,10 @@ use consensus_config::AuthorityIndex;
 use typed_store::{
     metrics::SamplingInterval,
     reopen,
-    rocks::{default_db_options, open_cf_opts, DBMap, MetricConf, ReadWriteOptions},
+    rocks::{
+        default_db_options, open_cf_opts, open_cf_opts_secondary, DBMap, DBOptions, MetricConf,
+        ReadWriteOptions, RocksDB,
+    },
     Map as _,
 };
 
@@ -53,33 +57,62 @@ impl RocksDBStore {
         // Consensus data has high write throughput (all transactions) and is rarely read
         // (only during recovery and when helping peers catch up).
         let db_options = default_db_options().optimize_db_for_write_throughput(2);
+        let rocksdb = open_cf_opts(
+            path,
+            Some(db_options.options),
+            Self::metric_conf(),
+            &Self::column_family_options()
+                .into_iter()
+                .map(|(cf, options)| (cf, options.options))
+                .collect::<Vec<_>>(),
+        )
+        .expect("Cannot open database");
+        Self::from_rocksdb(&rocksdb)
+    }
+
+    /// Opens an existing RocksDB storage as a secondary instance, which can be read while the
+    /// primary instance is still in use by a running authority. Writing to it is an error.
+    pub(crate) fn new_read_only(path: &str) -> Self {
+        let rocksdb = open_cf_opts_secondary(
+            path,
+            None,
+            None,
+            Self::metric_conf(),
+            &Self::column_family_options()
+                .into_iter()
+                .map(|(cf, options)| (cf, options.options))
+                .collect::<Vec<_>>(),
+        )
+        .expect("Cannot open database as secondary");
+        Self::from_rocksdb(&rocksdb)
+    }
+
+    fn metric_conf() -> MetricConf {
         let mut metrics_conf = MetricConf::new("consensus");
         metrics_conf.read_sample_interval = SamplingInterval::new(Duration::from_secs(60), 0);
-        let cf_options = default_db_options().optimize_for_write_throughput().options;
-        let column_family_options = vec![
+        metrics_conf
+    }
+
+    fn column_family_options() -> Vec<(&'static str, DBOptions)> {
+        let cf_options = default_db_options().optimize_for_write_throughput();
+        vec![
             (
                 Self::BLOCKS_CF,
                 default_db_options()
                     .optimize_for_write_throughput()
                     // Blocks can get large and they don't need to be compacted.
                     // So keep them in rocksdb blobstore.
-                    .optimize_for_large_values_no_scan(1 << 10)
-                    .options,
+                    .optimize_for_large_values_no_scan(1 << 10),
             ),
             (Self::DIGESTS_BY_AUTHORITIES_CF, cf_options.clone()),
             (Self::COMMITS_CF, cf_options.clone()),
             (Self::COMMIT_VOTES_CF, cf_options.clone()),
-            (Self::COMMIT_INFO_CF, cf_options.clone()),
-        ];
-        let rocksdb = open_cf_opts(
-            path,
-            Some(db_options.options),
-            metrics_conf,
-            &column_family_options,
-        )
-        .expect("Cannot open database");
+            (Self::COMMIT_INFO_CF, cf_options),
+        ]
+    }
 
-        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(&rocksdb,
+    fn from_rocksdb(rocksdb: &Arc<RocksDB>) -> Self {
+        let (blocks, digests_by_authorities, commits, commit_votes, commit_info) = reopen!(rocksdb,
             Self::BLOCKS_CF;<(Round, AuthorityIndex, BlockDigest), bytes::Bytes>,
             Self::DIGESTS_BY_AUTHORITIES_CF;<(AuthorityIndex, Round, BlockDigest), ()>,
             Self::COMMITS_CF;<(CommitIndex, CommitDigest), Bytes>,
@@ -135,9 +168,10 @@ impl Store for RocksDBStore {
                     )
                     .map_err(ConsensusError::RocksDBFailure)?;
             }
-            let commit_info = CommitInfo {
-                last_committed_rounds: write_batch.last_committed_rounds,
-            };
+            let commit_info = CommitInfo::new(
+                write_batch.last_committed_rounds,
+                write_batch.reputation_scores,
+            );
             batch
                 .insert_batch(
                     &self.commit_info,
@@ -157,6 +157,11 @@ pub(crate) mod universal_committer_builder {
             }
         }
 
+        pub(crate) fn with_leader_schedule(mut self, leader_schedule: LeaderSchedule) -> Self {
+            self.leader_schedule = leader_schedule;
+            self
+        }
+
         #[allow(unused)]
         pub(crate) fn with_wave_length(mut self, wave_length: Round) -> Self {
             self.wave_length = wave_length;
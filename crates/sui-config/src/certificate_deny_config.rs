@@ -40,6 +40,20 @@ impl CertificateDenyConfig {
                 .collect::<HashSet<_>>()
         })
     }
+
+    /// Checks a config before it replaces `current` on a running node. Certificates that may
+    /// have been denied already have to stay denied, so that they are replayed with the same
+    /// result.
+    pub fn validate_replacement(&self, current: &CertificateDenyConfig) -> anyhow::Result<()> {
+        if let Some(digest) = current
+            .certificate_deny_set()
+            .difference(self.certificate_deny_set())
+            .next()
+        {
+            anyhow::bail!("Certificate {digest} cannot be removed from the deny list");
+        }
+        Ok(())
+    }
 }
 
 #[derive(Default)]
@@ -17,6 +17,7 @@ pub mod node_config_metrics;
 pub mod object_storage_config;
 pub mod p2p;
 pub mod transaction_deny_config;
+pub mod transaction_validation_hook_config;
 
 pub use node::{ConsensusConfig, NodeConfig};
 use sui_types::multiaddr::Multiaddr;
@@ -5,6 +5,7 @@ use crate::genesis;
 use crate::object_storage_config::ObjectStoreConfig;
 use crate::p2p::P2pConfig;
 use crate::transaction_deny_config::TransactionDenyConfig;
+use crate::transaction_validation_hook_config::TransactionValidationHookConfig;
 use crate::Config;
 use anyhow::Result;
 use narwhal_config::Parameters as ConsensusParameters;
@@ -140,6 +141,9 @@ pub struct NodeConfig {
     #[serde(default)]
     pub certificate_deny_config: CertificateDenyConfig,
 
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transaction_validation_hooks: Vec<TransactionValidationHookConfig>,
+
     #[serde(default)]
     pub state_debug_dump_config: StateDebugDumpConfig,
 
@@ -179,6 +183,10 @@ pub struct NodeConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub firewall_config: Option<RemoteFirewallConfig>,
+
+    // When None, the transactions of each consensus commit are enqueued for execution at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_scheduler_config: Option<CommitSchedulerConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -752,6 +760,11 @@ pub struct AuthorityOverloadConfig {
     // is above the threshold.
     #[serde(default = "default_max_transaction_manager_per_object_queue_length")]
     pub max_transaction_manager_per_object_queue_length: usize,
+
+    // In load shedding mode, shared objects with at least this many pending transactions are
+    // considered hot. Transactions touching hot objects are shed before other transactions.
+    #[serde(default = "default_hot_object_queue_length_threshold")]
+    pub hot_object_queue_length_threshold: usize,
 }
 
 fn default_max_txn_age_in_queue() -> Duration {
@@ -794,6 +807,10 @@ fn default_max_transaction_manager_per_object_queue_length() -> usize {
     100
 }
 
+fn default_hot_object_queue_length_threshold() -> usize {
+    50
+}
+
 impl Default for AuthorityOverloadConfig {
     fn default() -> Self {
         Self {
@@ -810,6 +827,7 @@ impl Default for AuthorityOverloadConfig {
             max_transaction_manager_queue_length: default_max_transaction_manager_queue_length(),
             max_transaction_manager_per_object_queue_length:
                 default_max_transaction_manager_per_object_queue_length(),
+            hot_object_queue_length_threshold: default_hot_object_queue_length_threshold(),
         }
     }
 }
@@ -818,6 +836,30 @@ fn default_authority_overload_config() -> AuthorityOverloadConfig {
     AuthorityOverloadConfig::default()
 }
 
+/// Configurations of the scheduler that executes the transactions of a consensus commit
+/// following their shared object dependencies. Chains of transactions that do not share
+/// objects with each other are executed in parallel.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommitSchedulerConfig {
+    // Maximum number of dependency chains, over all commits, whose transactions are being
+    // executed at the same time.
+    #[serde(default = "default_max_concurrent_chains")]
+    pub max_concurrent_chains: usize,
+}
+
+fn default_max_concurrent_chains() -> usize {
+    64
+}
+
+impl Default for CommitSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_chains: default_max_concurrent_chains(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq)]
 pub struct Genesis {
     #[serde(flatten)]
@@ -6,6 +6,7 @@ use std::collections::HashSet;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::{MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -118,6 +119,27 @@ impl TransactionDenyConfig {
     pub fn zklogin_disabled_providers(&self) -> &HashSet<String> {
         &self.zklogin_disabled_providers
     }
+
+    /// Checks a config before it replaces the config of a running node.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        // Denying these packages denies almost every transaction, which is what
+        // `user_transaction_disabled` is for.
+        for id in &self.package_deny_list {
+            if [
+                MOVE_STDLIB_PACKAGE_ID,
+                SUI_FRAMEWORK_PACKAGE_ID,
+                SUI_SYSTEM_PACKAGE_ID,
+            ]
+            .contains(id)
+            {
+                anyhow::bail!("Package {id} cannot be denied, disable user transactions instead");
+            }
+        }
+        if self.zklogin_disabled_providers.iter().any(String::is_empty) {
+            anyhow::bail!("Disabled zkLogin providers cannot be empty");
+        }
+        Ok(())
+    }
 }
 
 #[derive(Default)]
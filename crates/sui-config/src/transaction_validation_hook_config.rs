@@ -0,0 +1,30 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::ObjectID;
+
+/// A custom check that transactions submitted to this node must pass, in addition to the
+/// checks of `TransactionDenyConfig`. Hooks are run in the order they are configured.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TransactionValidationHookConfig {
+    /// Rejects transactions calling into any of `packages` with a gas price below
+    /// `min_gas_price`.
+    #[serde(rename_all = "kebab-case")]
+    MinGasPriceForPackages {
+        packages: Vec<ObjectID>,
+        min_gas_price: u64,
+    },
+
+    /// A hook implemented in Rust and linked into the node binary, registered under `name`
+    /// before the node starts. `params` are passed to the plugin as they are.
+    #[serde(rename_all = "kebab-case")]
+    Plugin {
+        name: String,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        params: BTreeMap<String, String>,
+    },
+}
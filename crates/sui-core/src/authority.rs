@@ -78,6 +78,7 @@ use sui_storage::indexes::{CoinInfo, ObjectIndexChanges};
 use sui_storage::key_value_store::{TransactionKeyValueStore, TransactionKeyValueStoreTrait};
 use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
 use sui_storage::IndexStore;
+use sui_transaction_checks::validation_hooks::TransactionValidationHooks;
 use sui_types::authenticator_state::get_authenticator_state;
 use sui_types::committee::{EpochId, ProtocolVersion};
 use sui_types::crypto::{default_hash, AuthoritySignInfo, Signer};
@@ -150,7 +151,7 @@ use crate::execution_driver::execution_process;
 use crate::metrics::LatencyObserver;
 use crate::metrics::RateTracker;
 use crate::module_cache_metrics::ResolverMetrics;
-use crate::overload_monitor::{overload_monitor_accept_tx, AuthorityOverloadInfo};
+use crate::overload_monitor::AuthorityOverloadInfo;
 use crate::stake_aggregator::StakeAggregator;
 use crate::state_accumulator::{AccumulatorStore, StateAccumulator, WrappedObject};
 use crate::subscription_handler::SubscriptionHandler;
@@ -255,6 +256,8 @@ pub struct AuthorityMetrics {
 
     pub(crate) authority_overload_status: IntGauge,
     pub(crate) authority_load_shedding_percentage: IntGauge,
+    pub(crate) authority_hot_object_load_shedding_percentage: IntGauge,
+    pub(crate) authority_load_shedding_hot_objects: IntGauge,
 
     /// Post processing metrics
     post_processing_total_events_emitted: IntCounter,
@@ -274,6 +277,15 @@ pub struct AuthorityMetrics {
     pub consensus_calculated_throughput: IntGauge,
     pub consensus_calculated_throughput_profile: IntGauge,
 
+    /// Commit scheduler metrics
+    pub commit_scheduler_critical_path_length: Histogram,
+    pub commit_scheduler_chains: Histogram,
+    pub commit_scheduler_inflight_chains: IntGauge,
+
+    pub deny_config_reloads: IntCounterVec,
+    pub transaction_deny_list_len: IntGaugeVec,
+    pub certificate_deny_list_len: IntGauge,
+
     pub limits_metrics: Arc<LimitsMetrics>,
 
     /// bytecode verifier metrics for tracking timeouts
@@ -513,6 +525,16 @@ impl AuthorityMetrics {
                 "The percentage of transactions is shed when the authority is in load shedding mode.",
                 registry)
             .unwrap(),
+            authority_hot_object_load_shedding_percentage: register_int_gauge_with_registry!(
+                "authority_hot_object_load_shedding_percentage",
+                "The percentage of transactions on hot objects is shed when the authority is in load shedding mode.",
+                registry)
+            .unwrap(),
+            authority_load_shedding_hot_objects: register_int_gauge_with_registry!(
+                "authority_load_shedding_hot_objects",
+                "The number of hot objects whose transactions are shed first in load shedding mode.",
+                registry)
+            .unwrap(),
             transaction_manager_object_cache_misses: register_int_counter_with_registry!(
                 "transaction_manager_object_cache_misses",
                 "Number of object-availability cache misses in TransactionManager",
@@ -648,6 +670,40 @@ impl AuthorityMetrics {
                 "Number of transactions deferred by consensus handler",
                 registry,
             ).unwrap(),
+            commit_scheduler_critical_path_length: register_histogram_with_registry!(
+                "commit_scheduler_critical_path_length",
+                "Number of transactions on the longest shared object dependency chain of a commit",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
+            commit_scheduler_chains: register_histogram_with_registry!(
+                "commit_scheduler_chains",
+                "Number of independent shared object dependency chains of a commit",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
+            commit_scheduler_inflight_chains: register_int_gauge_with_registry!(
+                "commit_scheduler_inflight_chains",
+                "Number of dependency chains being executed by the commit scheduler",
+                registry,
+            ).unwrap(),
+            deny_config_reloads: register_int_counter_vec_with_registry!(
+                "deny_config_reloads",
+                "Number of deny config reloads, sliced by config and result",
+                &["config", "result"],
+                registry,
+            ).unwrap(),
+            transaction_deny_list_len: register_int_gauge_vec_with_registry!(
+                "transaction_deny_list_len",
+                "Number of entries in the transaction deny lists, sliced by list",
+                &["list"],
+                registry,
+            ).unwrap(),
+            certificate_deny_list_len: register_int_gauge_with_registry!(
+                "certificate_deny_list_len",
+                "Number of certificates in the certificate deny list",
+                registry,
+            ).unwrap(),
             consensus_committed_subdags: register_int_counter_vec_with_registry!(
                 "consensus_committed_subdags",
                 "Number of committed subdags, sliced by author",
@@ -701,6 +757,23 @@ impl AuthorityMetrics {
             execution_rate_tracker: Arc::new(Mutex::new(RateTracker::new(Duration::from_secs(10)))),
         }
     }
+
+    fn update_transaction_deny_config(&self, config: &TransactionDenyConfig) {
+        for (list, len) in [
+            ("object", config.get_object_deny_set().len()),
+            ("package", config.get_package_deny_set().len()),
+            ("address", config.get_address_deny_set().len()),
+        ] {
+            self.transaction_deny_list_len
+                .with_label_values(&[list])
+                .set(len as i64);
+        }
+    }
+
+    fn update_certificate_deny_config(&self, config: &CertificateDenyConfig) {
+        self.certificate_deny_list_len
+            .set(config.certificate_deny_set().len() as i64);
+    }
 }
 
 /// a Trait object for `Signer` that is:
@@ -789,9 +862,16 @@ pub struct AuthorityState {
     /// Config controlling what kind of expensive safety checks to perform.
     expensive_safety_check_config: ExpensiveSafetyCheckConfig,
 
-    transaction_deny_config: TransactionDenyConfig,
+    /// Deny configs can be replaced while the node is running.
+    transaction_deny_config: ArcSwap<TransactionDenyConfig>,
+
+    certificate_deny_config: ArcSwap<CertificateDenyConfig>,
 
-    certificate_deny_config: CertificateDenyConfig,
+    /// Certificate deny config to apply at the next epoch boundary.
+    pending_certificate_deny_config: Mutex<Option<CertificateDenyConfig>>,
+
+    /// Custom checks on the transactions submitted to this node.
+    transaction_validation_hooks: TransactionValidationHooks,
 
     /// Config for state dumping on forks
     debug_dump_config: StateDebugDumpConfig,
@@ -863,9 +943,11 @@ impl AuthorityState {
             transaction.tx_signatures(),
             &input_object_kinds,
             &receiving_objects_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
+        self.transaction_validation_hooks
+            .check(tx_data, transaction.tx_signatures())?;
 
         let (input_objects, receiving_objects) = self
             .input_loader
@@ -1003,11 +1085,14 @@ impl AuthorityState {
             return Ok(());
         }
 
-        let load_shedding_percentage = self
-            .overload_info
-            .load_shedding_percentage
-            .load(Ordering::Relaxed);
-        overload_monitor_accept_tx(load_shedding_percentage, tx_data.digest())
+        self.overload_info.accept_tx(
+            tx_data.digest(),
+            tx_data
+                .transaction_data()
+                .shared_input_objects()
+                .into_iter()
+                .map(|object| object.id),
+        )
     }
 
     /// Executes a transaction that's known to have correct effects.
@@ -1599,7 +1684,7 @@ impl AuthorityState {
                 // cyclic dependency w/ sui-adapter
                 self.expensive_safety_check_config
                     .enable_deep_per_tx_sui_conservation_check(),
-                self.certificate_deny_config.certificate_deny_set(),
+                self.certificate_deny_config.load().certificate_deny_set(),
                 &epoch_store.epoch_start_config().epoch_data().epoch_id(),
                 epoch_store
                     .epoch_start_config()
@@ -1709,7 +1794,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -1779,7 +1864,7 @@ impl AuthorityState {
                 protocol_config,
                 self.metrics.limits_metrics.clone(),
                 expensive_checks,
-                self.certificate_deny_config.certificate_deny_set(),
+                self.certificate_deny_config.load().certificate_deny_set(),
                 &epoch_store.epoch_start_config().epoch_data().epoch_id(),
                 epoch_store
                     .epoch_start_config()
@@ -1928,7 +2013,7 @@ impl AuthorityState {
             &[],
             &input_object_kinds,
             &receiving_object_refs,
-            &self.transaction_deny_config,
+            &self.transaction_deny_config.load(),
             self.get_backing_package_store().as_ref(),
         )?;
 
@@ -2019,7 +2104,7 @@ impl AuthorityState {
             protocol_config,
             self.metrics.limits_metrics.clone(),
             /* expensive checks */ false,
-            self.certificate_deny_config.certificate_deny_set(),
+            self.certificate_deny_config.load().certificate_deny_set(),
             &epoch_store.epoch_start_config().epoch_data().epoch_id(),
             epoch_store
                 .epoch_start_config()
@@ -2627,6 +2712,7 @@ impl AuthorityState {
         expensive_safety_check_config: ExpensiveSafetyCheckConfig,
         transaction_deny_config: TransactionDenyConfig,
         certificate_deny_config: CertificateDenyConfig,
+        transaction_validation_hooks: TransactionValidationHooks,
         indirect_objects_threshold: usize,
         debug_dump_config: StateDebugDumpConfig,
         authority_overload_config: AuthorityOverloadConfig,
@@ -2635,6 +2721,8 @@ impl AuthorityState {
         Self::check_protocol_version(supported_protocol_versions, epoch_store.protocol_version());
 
         let metrics = Arc::new(AuthorityMetrics::new(prometheus_registry));
+        metrics.update_transaction_deny_config(&transaction_deny_config);
+        metrics.update_certificate_deny_config(&certificate_deny_config);
         let (tx_ready_certificates, rx_ready_certificates) = unbounded_channel();
         let transaction_manager = Arc::new(TransactionManager::new(
             execution_cache.clone(),
@@ -2679,8 +2767,10 @@ impl AuthorityState {
             _authority_per_epoch_pruner,
             db_checkpoint_config: db_checkpoint_config.clone(),
             expensive_safety_check_config,
-            transaction_deny_config,
-            certificate_deny_config,
+            transaction_deny_config: ArcSwap::from_pointee(transaction_deny_config),
+            certificate_deny_config: ArcSwap::from_pointee(certificate_deny_config),
+            pending_certificate_deny_config: Mutex::new(None),
+            transaction_validation_hooks,
             debug_dump_config,
             authority_overload_config: authority_overload_config.clone(),
             overload_info: AuthorityOverloadInfo::default(),
@@ -2778,6 +2868,69 @@ impl AuthorityState {
         &self.transaction_manager
     }
 
+    pub fn transaction_validation_hooks(&self) -> &TransactionValidationHooks {
+        &self.transaction_validation_hooks
+    }
+
+    pub fn transaction_deny_config(&self) -> Arc<TransactionDenyConfig> {
+        self.transaction_deny_config.load_full()
+    }
+
+    /// Replaces the transaction deny config, which applies to transactions handled from now on.
+    pub fn reload_transaction_deny_config(
+        &self,
+        config: TransactionDenyConfig,
+    ) -> anyhow::Result<()> {
+        let result = config.validate();
+        self.metrics
+            .deny_config_reloads
+            .with_label_values(&["transaction", if result.is_ok() { "ok" } else { "invalid" }])
+            .inc();
+        result?;
+        self.metrics.update_transaction_deny_config(&config);
+        self.transaction_deny_config.store(Arc::new(config));
+        info!("Transaction deny config reloaded");
+        Ok(())
+    }
+
+    pub fn certificate_deny_config(&self) -> Arc<CertificateDenyConfig> {
+        self.certificate_deny_config.load_full()
+    }
+
+    /// Stages a certificate deny config, which is applied at the next epoch boundary. Denying a
+    /// certificate changes its execution result, so the config of a validator must not change
+    /// while the certificates of an epoch are executed.
+    pub fn reload_certificate_deny_config(
+        &self,
+        config: CertificateDenyConfig,
+    ) -> anyhow::Result<()> {
+        let result = config.validate_replacement(&self.certificate_deny_config.load());
+        self.metrics
+            .deny_config_reloads
+            .with_label_values(&[
+                "certificate",
+                if result.is_ok() { "pending" } else { "invalid" },
+            ])
+            .inc();
+        result?;
+        *self.pending_certificate_deny_config.lock() = Some(config);
+        info!("Certificate deny config will be applied at the next epoch");
+        Ok(())
+    }
+
+    pub(crate) fn apply_pending_certificate_deny_config(&self) {
+        let Some(config) = self.pending_certificate_deny_config.lock().take() else {
+            return;
+        };
+        self.metrics
+            .deny_config_reloads
+            .with_label_values(&["certificate", "ok"])
+            .inc();
+        self.metrics.update_certificate_deny_config(&config);
+        self.certificate_deny_config.store(Arc::new(config));
+        info!("Certificate deny config applied");
+    }
+
     /// Adds certificates to transaction manager for ordered execution.
     /// It is unnecessary to persist the certificates into the pending_execution table,
     /// because only Narwhal output needs to be persisted.
@@ -2929,6 +3082,9 @@ impl AuthorityState {
             .await?;
         assert_eq!(new_epoch_store.epoch(), new_epoch);
         self.transaction_manager.reconfigure(new_epoch);
+        // No certificate is executed while the execution lock is held, so all certificates of the
+        // new epoch are executed with the same deny config.
+        self.apply_pending_certificate_deny_config();
         *execution_lock = new_epoch;
         // drop execution_lock after epoch store was updated
         // see also assert in AuthorityState::process_certificate
@@ -28,7 +28,7 @@ use sui_types::base_types::{ConciseableName, ObjectRef};
 use sui_types::committee::Committee;
 use sui_types::committee::CommitteeTrait;
 use sui_types::crypto::{AuthoritySignInfo, AuthorityStrongQuorumSignInfo, RandomnessRound};
-use sui_types::digests::ChainIdentifier;
+use sui_types::digests::{ChainIdentifier, ConsensusCommitDigest};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::signature::GenericSignature;
 use sui_types::storage::InputKey;
@@ -81,7 +81,7 @@ use prometheus::IntCounter;
 use std::str::FromStr;
 use sui_execution::{self, Executor};
 use sui_macros::fail_point;
-use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion};
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 use sui_storage::mutex_table::{MutexGuard, MutexTable};
 use sui_types::effects::TransactionEffects;
 use sui_types::executable_transaction::{
@@ -1701,27 +1701,18 @@ impl AuthorityPerEpochStore {
         }
 
         // Defer transaction if it uses shared objects that are congested.
-        match self.protocol_config().per_object_congestion_control_mode() {
-            PerObjectCongestionControlMode::None => None,
-            PerObjectCongestionControlMode::TotalGasBudget => {
-                if let Some((deferral_key, congested_objects)) = shared_object_congestion_tracker
-                    .should_defer_due_to_object_congestion(
-                        cert,
-                        self.protocol_config()
-                            .max_accumulated_txn_cost_per_object_in_checkpoint(),
-                        previously_deferred_tx_digests,
-                        commit_round,
-                    )
-                {
-                    Some((
-                        deferral_key,
-                        DeferralReason::SharedObjectCongestion(congested_objects),
-                    ))
-                } else {
-                    None
-                }
-            }
-        }
+        let (deferral_key, congested_objects) = shared_object_congestion_tracker
+            .should_defer_due_to_object_congestion(
+                cert,
+                self.protocol_config()
+                    .max_accumulated_txn_cost_per_object_in_checkpoint(),
+                previously_deferred_tx_digests,
+                commit_round,
+            )?;
+        Some((
+            deferral_key,
+            DeferralReason::SharedObjectCongestion(congested_objects),
+        ))
     }
 
     // Update shared objects' execution cost used in `cert` using `cert`'s execution cost.
@@ -1731,14 +1722,11 @@ impl AuthorityPerEpochStore {
         cert: &VerifiedExecutableTransaction,
         shared_object_congestion_tracker: &mut SharedObjectCongestionTracker,
     ) {
-        match self.protocol_config().per_object_congestion_control_mode() {
-            PerObjectCongestionControlMode::None => {}
-            PerObjectCongestionControlMode::TotalGasBudget => {
-                shared_object_congestion_tracker.bump_object_execution_cost(
-                    &cert.shared_input_objects().collect::<Vec<_>>(),
-                    cert.gas_budget(),
-                );
-            }
+        if let Some(tx_cost) = shared_object_congestion_tracker.get_tx_cost(cert) {
+            shared_object_congestion_tracker.bump_object_execution_cost(
+                &cert.shared_input_objects().collect::<Vec<_>>(),
+                tx_cost,
+            );
         }
     }
 
@@ -2432,6 +2420,7 @@ impl AuthorityPerEpochStore {
             .expect("test should not be write past end of epoch")
     }
 
+    #[allow(clippy::too_many_arguments)]
     #[instrument(level = "debug", skip_all)]
     pub(crate) async fn process_consensus_transactions_and_commit_boundary<
         'a,
@@ -2444,6 +2433,7 @@ impl AuthorityPerEpochStore {
         cache_reader: &dyn ExecutionCacheRead,
         commit_round: Round,
         commit_timestamp: TimestampMs,
+        commit_digest: Option<ConsensusCommitDigest>,
         authority_metrics: &Arc<AuthorityMetrics>,
     ) -> SuiResult<Vec<VerifiedExecutableTransaction>> {
         // Split transactions into different types for processing.
@@ -2588,10 +2578,12 @@ impl AuthorityPerEpochStore {
         PostConsensusTxReorder::reorder(
             &mut sequenced_transactions,
             self.protocol_config.consensus_transaction_ordering(),
+            commit_digest.as_ref(),
         );
         PostConsensusTxReorder::reorder(
             &mut sequenced_randomness_transactions,
             self.protocol_config.consensus_transaction_ordering(),
+            commit_digest.as_ref(),
         );
         let consensus_transactions: Vec<_> = system_transactions
             .into_iter()
@@ -2802,6 +2794,7 @@ impl AuthorityPerEpochStore {
             cache_reader,
             self.get_highest_pending_checkpoint_height() + 1,
             0,
+            None,
             authority_metrics,
         )
         .await
@@ -2869,10 +2862,11 @@ impl AuthorityPerEpochStore {
 
         // We track transaction execution cost separately for regular transactions and transactions using randomness, since
         // they will be in different checkpoints.
-        let mut shared_object_congestion_tracker: SharedObjectCongestionTracker =
-            Default::default();
-        let mut shared_object_using_randomness_congestion_tracker: SharedObjectCongestionTracker =
-            Default::default();
+        let congestion_control_mode = self.protocol_config().per_object_congestion_control_mode();
+        let mut shared_object_congestion_tracker =
+            SharedObjectCongestionTracker::new(congestion_control_mode);
+        let mut shared_object_using_randomness_congestion_tracker =
+            SharedObjectCongestionTracker::new(congestion_control_mode);
 
         let mut randomness_state_updated = false;
         for tx in transactions {
@@ -4,9 +4,10 @@
 use crate::authority::authority_per_epoch_store::DeferralKey;
 use narwhal_types::Round;
 use std::collections::HashMap;
+use sui_protocol_config::PerObjectCongestionControlMode;
 use sui_types::base_types::{ObjectID, TransactionDigest};
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
-use sui_types::transaction::SharedInputObject;
+use sui_types::transaction::{SharedInputObject, TransactionDataAPI, TransactionKind};
 
 // SharedObjectCongestionTracker stores the accumulated cost of executing transactions on an object, for
 // all transactions in a consensus commit.
@@ -17,19 +18,48 @@ use sui_types::transaction::SharedInputObject;
 //
 // The goal of this data structure is to capture the critical path of transaction execution latency on each
 // objects.
-#[derive(Default, PartialEq, Eq, Clone, Debug)]
+//
+// The cost of a transaction is estimated according to the congestion control mode. The estimate must only
+// depend on the transaction itself, so that every validator makes the same deferral decisions.
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct SharedObjectCongestionTracker {
     object_execution_cost: HashMap<ObjectID, u64>,
+    mode: PerObjectCongestionControlMode,
 }
 
 impl SharedObjectCongestionTracker {
-    pub fn new_with_initial_value_for_test(init_values: &[(ObjectID, u64)]) -> Self {
+    pub fn new(mode: PerObjectCongestionControlMode) -> Self {
+        Self {
+            object_execution_cost: HashMap::new(),
+            mode,
+        }
+    }
+
+    pub fn new_with_initial_value_for_test(
+        init_values: &[(ObjectID, u64)],
+        mode: PerObjectCongestionControlMode,
+    ) -> Self {
         let mut object_execution_cost = HashMap::new();
         for (object_id, total_cost) in init_values {
             object_execution_cost.insert(*object_id, *total_cost);
         }
         Self {
             object_execution_cost,
+            mode,
+        }
+    }
+
+    // Returns the estimated execution cost of `cert`, or None if congestion control is disabled.
+    pub fn get_tx_cost(&self, cert: &VerifiedExecutableTransaction) -> Option<u64> {
+        match self.mode {
+            PerObjectCongestionControlMode::None => None,
+            PerObjectCongestionControlMode::TotalGasBudget => Some(cert.gas_budget()),
+            PerObjectCongestionControlMode::TotalCommandCount => {
+                match cert.transaction_data().kind() {
+                    TransactionKind::ProgrammableTransaction(pt) => Some(pt.commands.len() as u64),
+                    _ => Some(1),
+                }
+            }
         }
     }
 
@@ -54,9 +84,10 @@ impl SharedObjectCongestionTracker {
         previously_deferred_tx_digests: &HashMap<TransactionDigest, DeferralKey>,
         commit_round: Round,
     ) -> Option<(DeferralKey, Vec<ObjectID>)> {
+        let tx_cost = self.get_tx_cost(cert)?;
         let shared_input_objects: Vec<_> = cert.shared_input_objects().collect();
         let start_cost = self.compute_tx_start_at_cost(&shared_input_objects);
-        if start_cost + cert.gas_budget() <= max_accumulated_txn_cost_per_object_in_checkpoint {
+        if start_cost + tx_cost <= max_accumulated_txn_cost_per_object_in_checkpoint {
             return None;
         }
 
@@ -142,10 +173,10 @@ mod object_cost_tests {
         let object_id_2 = ObjectID::random();
 
         let shared_object_congestion_tracker =
-            SharedObjectCongestionTracker::new_with_initial_value_for_test(&[
-                (object_id_0, 5),
-                (object_id_1, 10),
-            ]);
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(object_id_0, 5), (object_id_1, 10)],
+                PerObjectCongestionControlMode::TotalGasBudget,
+            );
 
         let shared_input_objects = construct_shared_input_objects(&[(object_id_0, false)]);
         assert_eq!(
@@ -211,6 +242,45 @@ mod object_cost_tests {
         )
     }
 
+    #[test]
+    fn test_get_tx_cost() {
+        let tx = build_transaction(&[(ObjectID::random(), true)]);
+
+        let tracker = SharedObjectCongestionTracker::new(PerObjectCongestionControlMode::None);
+        assert_eq!(tracker.get_tx_cost(&tx), None);
+
+        let tracker =
+            SharedObjectCongestionTracker::new(PerObjectCongestionControlMode::TotalGasBudget);
+        assert_eq!(tracker.get_tx_cost(&tx), Some(tx.gas_budget()));
+
+        // The transaction is a PTB with a single move call.
+        let tracker =
+            SharedObjectCongestionTracker::new(PerObjectCongestionControlMode::TotalCommandCount);
+        assert_eq!(tracker.get_tx_cost(&tx), Some(1));
+    }
+
+    #[test]
+    fn test_should_defer_by_command_count() {
+        let shared_obj_0 = ObjectID::random();
+        let shared_obj_1 = ObjectID::random();
+        let tx_0 = build_transaction(&[(shared_obj_0, true)]);
+        let tx_1 = build_transaction(&[(shared_obj_1, true)]);
+
+        // Allow two commands per object, regardless of the transactions' gas budgets.
+        let shared_object_congestion_tracker =
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(shared_obj_0, 2), (shared_obj_1, 1)],
+                PerObjectCongestionControlMode::TotalCommandCount,
+            );
+
+        assert!(shared_object_congestion_tracker
+            .should_defer_due_to_object_congestion(&tx_0, 2, &HashMap::new(), 0)
+            .is_some());
+        assert!(shared_object_congestion_tracker
+            .should_defer_due_to_object_congestion(&tx_1, 2, &HashMap::new(), 0)
+            .is_none());
+    }
+
     #[test]
     fn test_should_defer_return_correct_congested_objects() {
         // Creates two shared objects and three transactions that operate on these objects.
@@ -229,10 +299,10 @@ mod object_cost_tests {
         // object 0:            |
         // object 1:      |
         let shared_object_congestion_tracker =
-            SharedObjectCongestionTracker::new_with_initial_value_for_test(&[
-                (shared_obj_0, 10),
-                (shared_obj_1, 1),
-            ]);
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(shared_obj_0, 10), (shared_obj_1, 1)],
+                PerObjectCongestionControlMode::TotalGasBudget,
+            );
 
         // Read/write to object 0 should be deferred.
         for mutable in [true, false].iter() {
@@ -293,7 +363,8 @@ mod object_cost_tests {
         let tx = build_transaction(&[(shared_obj_0, true)]);
         // Make should_defer_due_to_object_congestion always defer transactions.
         let max_accumulated_txn_cost_per_object_in_checkpoint = 1;
-        let shared_object_congestion_tracker: SharedObjectCongestionTracker = Default::default();
+        let shared_object_congestion_tracker =
+            SharedObjectCongestionTracker::new(PerObjectCongestionControlMode::TotalGasBudget);
 
         // Insert a random pre-existing transaction.
         let mut previously_deferred_tx_digests = HashMap::new();
@@ -387,10 +458,10 @@ mod object_cost_tests {
         let object_id_2 = ObjectID::random();
 
         let mut shared_object_congestion_tracker =
-            SharedObjectCongestionTracker::new_with_initial_value_for_test(&[
-                (object_id_0, 5),
-                (object_id_1, 10),
-            ]);
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(object_id_0, 5), (object_id_1, 10)],
+                PerObjectCongestionControlMode::TotalGasBudget,
+            );
 
         // Read two objects should not change the object execution cost.
         let shared_input_objects =
@@ -398,10 +469,10 @@ mod object_cost_tests {
         shared_object_congestion_tracker.bump_object_execution_cost(&shared_input_objects, 10);
         assert_eq!(
             shared_object_congestion_tracker,
-            SharedObjectCongestionTracker::new_with_initial_value_for_test(&[
-                (object_id_0, 5),
-                (object_id_1, 10)
-            ])
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(object_id_0, 5), (object_id_1, 10)],
+                PerObjectCongestionControlMode::TotalGasBudget
+            )
         );
 
         // Write to object 0 should only bump object 0's execution cost. The start cost should be object 1's cost.
@@ -410,10 +481,10 @@ mod object_cost_tests {
         shared_object_congestion_tracker.bump_object_execution_cost(&shared_input_objects, 10);
         assert_eq!(
             shared_object_congestion_tracker,
-            SharedObjectCongestionTracker::new_with_initial_value_for_test(&[
-                (object_id_0, 20),
-                (object_id_1, 10)
-            ])
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(object_id_0, 20), (object_id_1, 10)],
+                PerObjectCongestionControlMode::TotalGasBudget
+            )
         );
 
         // Write to all objects should bump all objects' execution cost, including objects that are seen for the first time.
@@ -425,11 +496,10 @@ mod object_cost_tests {
         shared_object_congestion_tracker.bump_object_execution_cost(&shared_input_objects, 10);
         assert_eq!(
             shared_object_congestion_tracker,
-            SharedObjectCongestionTracker::new_with_initial_value_for_test(&[
-                (object_id_0, 30),
-                (object_id_1, 30),
-                (object_id_2, 30)
-            ])
+            SharedObjectCongestionTracker::new_with_initial_value_for_test(
+                &[(object_id_0, 30), (object_id_1, 30), (object_id_2, 30)],
+                PerObjectCongestionControlMode::TotalGasBudget
+            )
         );
     }
 }
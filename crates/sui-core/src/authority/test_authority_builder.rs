@@ -28,6 +28,7 @@ use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
 use sui_storage::IndexStore;
 use sui_swarm_config::genesis_config::AccountConfig;
 use sui_swarm_config::network_config::NetworkConfig;
+use sui_transaction_checks::validation_hooks::TransactionValidationHooks;
 use sui_types::base_types::{AuthorityName, ObjectID};
 use sui_types::crypto::AuthorityKeyPair;
 use sui_types::digests::ChainIdentifier;
@@ -43,6 +44,7 @@ pub struct TestAuthorityBuilder<'a> {
     store: Option<Arc<AuthorityStore>>,
     transaction_deny_config: Option<TransactionDenyConfig>,
     certificate_deny_config: Option<CertificateDenyConfig>,
+    transaction_validation_hooks: Option<TransactionValidationHooks>,
     protocol_config: Option<ProtocolConfig>,
     reference_gas_price: Option<u64>,
     node_keypair: Option<&'a AuthorityKeyPair>,
@@ -86,6 +88,11 @@ impl<'a> TestAuthorityBuilder<'a> {
         self
     }
 
+    pub fn with_transaction_validation_hooks(mut self, hooks: TransactionValidationHooks) -> Self {
+        assert!(self.transaction_validation_hooks.replace(hooks).is_none());
+        self
+    }
+
     pub fn with_protocol_config(mut self, config: ProtocolConfig) -> Self {
         assert!(self.protocol_config.replace(config).is_none());
         self
@@ -261,6 +268,7 @@ impl<'a> TestAuthorityBuilder<'a> {
         };
         let transaction_deny_config = self.transaction_deny_config.unwrap_or_default();
         let certificate_deny_config = self.certificate_deny_config.unwrap_or_default();
+        let transaction_validation_hooks = self.transaction_validation_hooks.unwrap_or_default();
         let authority_overload_config = self.authority_overload_config.unwrap_or_default();
         let mut pruning_config = AuthorityStorePruningConfig::default();
         if !epoch_store
@@ -287,6 +295,7 @@ impl<'a> TestAuthorityBuilder<'a> {
             ExpensiveSafetyCheckConfig::new_enable_all(),
             transaction_deny_config,
             certificate_deny_config,
+            transaction_validation_hooks,
             usize::MAX,
             StateDebugDumpConfig {
                 dump_file_directory: Some(tempdir().unwrap().into_path()),
@@ -280,6 +280,10 @@ impl ValidatorService {
         &self.state
     }
 
+    pub fn traffic_controller(&self) -> Option<Arc<TrafficController>> {
+        self.traffic_controller.clone()
+    }
+
     pub async fn execute_certificate_for_testing(
         &self,
         cert: CertifiedTransaction,
@@ -355,7 +359,8 @@ impl ValidatorService {
                 .inc();
             // TODO: consider change the behavior for other types of overload errors.
             match error {
-                SuiError::ValidatorOverloadedRetryAfter { .. } => {
+                SuiError::ValidatorOverloadedRetryAfter { .. }
+                | SuiError::ValidatorOverloadedOnObjectsRetryAfter { .. } => {
                     validator_pushback_error = Some(error)
                 }
                 _ => return Err(error.into()),
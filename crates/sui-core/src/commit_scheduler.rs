@@ -0,0 +1,490 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    future::Future,
+    sync::Arc,
+};
+
+use mysten_metrics::{monitored_scope, spawn_monitored_task, GaugeGuard};
+use sui_config::node::CommitSchedulerConfig;
+use sui_types::{
+    base_types::ObjectID, error::SuiResult, executable_transaction::VerifiedExecutableTransaction,
+    transaction::TransactionDataAPI,
+};
+use tokio::{
+    sync::{mpsc, OwnedSemaphorePermit, Semaphore},
+    task::JoinSet,
+};
+use tracing::{debug, error};
+
+use crate::{
+    authority::{authority_per_epoch_store::AuthorityPerEpochStore, AuthorityMetrics},
+    execution_cache::ExecutionCacheRead,
+    transaction_manager::TransactionManager,
+};
+
+/// Dependencies between the transactions of a consensus commit, derived from the shared objects
+/// they read and write in commit order.
+///
+/// A transaction that writes a shared object depends on the previous writer of the object and on
+/// all of its readers since then. A transaction that only reads a shared object depends on the
+/// previous writer, so readers of the same version do not depend on each other. Transactions
+/// without shared objects have no dependencies.
+pub(crate) struct CommitDependencyGraph {
+    /// Indices of the earlier transactions that each transaction depends on.
+    dependencies: Vec<BTreeSet<usize>>,
+    /// Length of the longest dependency chain ending at each transaction, minus one.
+    depths: Vec<usize>,
+    /// Transactions connected through dependencies, each in commit order. Chains are ordered by
+    /// their first transaction.
+    chains: Vec<Vec<usize>>,
+}
+
+#[derive(Default)]
+struct SharedObjectAccess {
+    last_writer: Option<usize>,
+    readers: Vec<usize>,
+}
+
+impl CommitDependencyGraph {
+    pub fn new(transactions: &[VerifiedExecutableTransaction]) -> Self {
+        Self::from_shared_inputs(transactions.iter().map(|transaction| {
+            transaction
+                .data()
+                .transaction_data()
+                .shared_input_objects()
+                .into_iter()
+                .map(|object| (object.id, object.mutable))
+        }))
+    }
+
+    /// Builds the graph from the `(object id, mutable)` shared inputs of each transaction, in
+    /// commit order.
+    pub fn from_shared_inputs<T, I>(shared_inputs: T) -> Self
+    where
+        T: IntoIterator<Item = I>,
+        I: IntoIterator<Item = (ObjectID, bool)>,
+    {
+        let mut accesses: HashMap<ObjectID, SharedObjectAccess> = HashMap::new();
+        let mut dependencies = vec![];
+        for (index, objects) in shared_inputs.into_iter().enumerate() {
+            let mut transaction_dependencies = BTreeSet::new();
+            for (id, mutable) in objects {
+                let access = accesses.entry(id).or_default();
+                transaction_dependencies.extend(access.last_writer);
+                if mutable {
+                    transaction_dependencies.extend(access.readers.drain(..));
+                    access.last_writer = Some(index);
+                } else {
+                    access.readers.push(index);
+                }
+            }
+            // An object can be listed more than once by the same transaction.
+            transaction_dependencies.remove(&index);
+            dependencies.push(transaction_dependencies);
+        }
+
+        let mut depths: Vec<usize> = Vec::with_capacity(dependencies.len());
+        for transaction_dependencies in &dependencies {
+            let depth = transaction_dependencies
+                .iter()
+                .map(|dependency| depths[*dependency] + 1)
+                .max()
+                .unwrap_or(0);
+            depths.push(depth);
+        }
+
+        let mut roots: Vec<usize> = (0..dependencies.len()).collect();
+        for (index, transaction_dependencies) in dependencies.iter().enumerate() {
+            for dependency in transaction_dependencies {
+                let root = find_root(&mut roots, index);
+                let dependency_root = find_root(&mut roots, *dependency);
+                // The smaller index becomes the root, so that roots are first transactions.
+                roots[root.max(dependency_root)] = root.min(dependency_root);
+            }
+        }
+        let mut chain_positions = HashMap::new();
+        let mut chains: Vec<Vec<usize>> = vec![];
+        for index in 0..dependencies.len() {
+            let root = find_root(&mut roots, index);
+            let position = *chain_positions.entry(root).or_insert_with(|| {
+                chains.push(vec![]);
+                chains.len() - 1
+            });
+            chains[position].push(index);
+        }
+
+        Self {
+            dependencies,
+            depths,
+            chains,
+        }
+    }
+
+    pub fn dependencies(&self, index: usize) -> &BTreeSet<usize> {
+        &self.dependencies[index]
+    }
+
+    pub fn chains(&self) -> &[Vec<usize>] {
+        &self.chains
+    }
+
+    /// Number of transactions on the longest dependency chain of the commit, which bounds how
+    /// many transactions have to be executed one after another.
+    pub fn critical_path_length(&self) -> usize {
+        self.depths.iter().max().map_or(0, |depth| depth + 1)
+    }
+
+    /// Splits the chain at `position` into levels. Transactions of a level only depend on
+    /// transactions of earlier levels.
+    pub fn chain_levels(&self, position: usize) -> Vec<Vec<usize>> {
+        let mut levels: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for index in &self.chains[position] {
+            levels.entry(self.depths[*index]).or_default().push(*index);
+        }
+        levels.into_values().collect()
+    }
+}
+
+fn find_root(roots: &mut [usize], mut index: usize) -> usize {
+    while roots[index] != index {
+        roots[index] = roots[roots[index]];
+        index = roots[index];
+    }
+    index
+}
+
+/// Hands the transactions of each consensus commit to the `TransactionManager` following their
+/// `CommitDependencyGraph`. Transactions of a dependency chain are enqueued one level at a time,
+/// after the previous level has been executed, while independent chains proceed in parallel.
+/// At most `max_concurrent_chains` chains are in flight at once.
+///
+/// The `TransactionManager` still waits for shared object versions before executing a
+/// transaction, so the scheduler only limits what is enqueued and cannot reorder execution.
+pub(crate) struct CommitScheduler {
+    sender: mpsc::Sender<Vec<VerifiedExecutableTransaction>>,
+}
+
+impl CommitScheduler {
+    pub fn start(
+        config: &CommitSchedulerConfig,
+        transaction_manager: Arc<TransactionManager>,
+        cache_reader: Arc<dyn ExecutionCacheRead>,
+        epoch_store: Arc<AuthorityPerEpochStore>,
+        metrics: Arc<AuthorityMetrics>,
+    ) -> Self {
+        let (sender, recv) = mpsc::channel(16);
+        let inner = CommitSchedulerInner {
+            transaction_manager,
+            cache_reader,
+            epoch_store,
+            metrics,
+        };
+        let semaphore = Arc::new(Semaphore::new(config.max_concurrent_chains));
+        spawn_monitored_task!(inner.run(recv, semaphore));
+        Self { sender }
+    }
+
+    pub async fn schedule(&self, transactions: Vec<VerifiedExecutableTransaction>) {
+        self.sender.send(transactions).await.ok();
+    }
+}
+
+#[derive(Clone)]
+struct CommitSchedulerInner {
+    transaction_manager: Arc<TransactionManager>,
+    cache_reader: Arc<dyn ExecutionCacheRead>,
+    epoch_store: Arc<AuthorityPerEpochStore>,
+    metrics: Arc<AuthorityMetrics>,
+}
+
+impl CommitSchedulerInner {
+    async fn run(
+        self,
+        mut recv: mpsc::Receiver<Vec<VerifiedExecutableTransaction>>,
+        semaphore: Arc<Semaphore>,
+    ) {
+        // Chains still in flight are aborted when the scheduler of the epoch is dropped.
+        let mut chains = JoinSet::new();
+        // Dependent chains waiting for a permit, in commit order. Permits are only acquired here,
+        // so chains of earlier commits, which later transactions may wait for, are never starved
+        // of permits, and waiting for a permit does not hold back receiving later commits.
+        let mut pending_chains = VecDeque::new();
+        loop {
+            tokio::select! {
+                transactions = recv.recv() => {
+                    let Some(transactions) = transactions else {
+                        break;
+                    };
+                    pending_chains.extend(self.schedule_commit(transactions));
+                }
+                permit = semaphore.clone().acquire_owned(), if !pending_chains.is_empty() => {
+                    let permit = permit.expect("Semaphore is never closed");
+                    let levels = pending_chains
+                        .pop_front()
+                        .expect("Permits are only acquired for pending chains");
+                    chains.spawn(self.clone().execute_chain(levels, permit));
+                }
+                Some(result) = chains.join_next() => {
+                    if let Err(err) = result {
+                        if err.is_panic() {
+                            std::panic::resume_unwind(err.into_panic());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Enqueues the transactions of the commit that have nothing to wait for, and returns the
+    /// levels of each dependent chain.
+    fn schedule_commit(
+        &self,
+        transactions: Vec<VerifiedExecutableTransaction>,
+    ) -> Vec<Vec<Vec<VerifiedExecutableTransaction>>> {
+        let graph = {
+            let _scope = monitored_scope("CommitScheduler::build_graph");
+            CommitDependencyGraph::new(&transactions)
+        };
+        self.metrics
+            .commit_scheduler_critical_path_length
+            .observe(graph.critical_path_length() as f64);
+        self.metrics
+            .commit_scheduler_chains
+            .observe(graph.chains().len() as f64);
+
+        let mut transactions: Vec<_> = transactions.into_iter().map(Some).collect();
+        let mut take = |indices: Vec<usize>| -> Vec<VerifiedExecutableTransaction> {
+            indices
+                .into_iter()
+                .map(|index| {
+                    transactions[index]
+                        .take()
+                        .expect("Each transaction belongs to exactly one chain")
+                })
+                .collect()
+        };
+
+        // Chains of a single level have nothing to wait for, so they are enqueued at once.
+        let mut ready = vec![];
+        let mut dependent_chains = vec![];
+        for position in 0..graph.chains().len() {
+            let mut levels = graph.chain_levels(position);
+            if levels.len() == 1 {
+                ready.extend(take(levels.pop().unwrap()));
+            } else {
+                dependent_chains.push(levels.into_iter().map(&mut take).collect::<Vec<_>>());
+            }
+        }
+        debug!(
+            "Scheduling {} ready transactions and {} dependent chains, critical path length {}",
+            ready.len(),
+            dependent_chains.len(),
+            graph.critical_path_length(),
+        );
+        if !ready.is_empty() {
+            let _scope = monitored_scope("CommitScheduler::enqueue");
+            self.transaction_manager.enqueue(ready, &self.epoch_store);
+        }
+        dependent_chains
+    }
+
+    async fn execute_chain(
+        self,
+        levels: Vec<Vec<VerifiedExecutableTransaction>>,
+        _permit: OwnedSemaphorePermit,
+    ) {
+        let _guard = GaugeGuard::acquire(&self.metrics.commit_scheduler_inflight_chains);
+        enqueue_levels(
+            levels,
+            |level| self.transaction_manager.enqueue(level, &self.epoch_store),
+            |level| {
+                let digests: Vec<_> = level.iter().map(|tx| *tx.digest()).collect();
+                let cache_reader = self.cache_reader.clone();
+                async move {
+                    cache_reader
+                        .notify_read_executed_effects_digests(&digests)
+                        .await
+                        .map_err(|err| {
+                            error!(
+                                "Failed to wait for the execution of {:?}, enqueueing the rest of \
+                                the chain: {:?}",
+                                digests, err
+                            );
+                            err
+                        })?;
+                    Ok(())
+                }
+            },
+        )
+        .await
+    }
+}
+
+/// Enqueues the `levels` of a dependency chain one at a time, waiting for the future returned by
+/// `wait` for each level before enqueueing the next. If waiting fails, the rest of the chain is
+/// enqueued at once rather than dropped, as the `TransactionManager` still executes it in shared
+/// object version order.
+async fn enqueue_levels<T, F>(
+    levels: Vec<Vec<T>>,
+    mut enqueue: impl FnMut(Vec<T>),
+    mut wait: impl FnMut(&[T]) -> F,
+) where
+    F: Future<Output = SuiResult<()>>,
+{
+    let mut levels = levels.into_iter();
+    while let Some(level) = levels.next() {
+        let executed = wait(&level);
+        enqueue(level);
+        if executed.await.is_err() {
+            let rest: Vec<_> = levels.flatten().collect();
+            if !rest.is_empty() {
+                enqueue(rest);
+            }
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_graph(shared_inputs: Vec<Vec<(u8, bool)>>) -> CommitDependencyGraph {
+        let object_ids: Vec<_> = (0..8).map(|_| ObjectID::random()).collect();
+        CommitDependencyGraph::from_shared_inputs(shared_inputs.into_iter().map(|objects| {
+            objects
+                .into_iter()
+                .map(|(object, mutable)| (object_ids[object as usize], mutable))
+                .collect::<Vec<_>>()
+        }))
+    }
+
+    #[test]
+    fn test_disjoint_shared_objects() {
+        // Swaps on two pools, interleaved, and a transaction without shared objects.
+        let graph = build_graph(vec![
+            vec![(0, true)],
+            vec![(1, true)],
+            vec![],
+            vec![(0, true)],
+            vec![(1, true)],
+            vec![(0, true)],
+        ]);
+        assert_eq!(graph.chains(), &[vec![0, 3, 5], vec![1, 4], vec![2]]);
+        assert_eq!(graph.critical_path_length(), 3);
+        assert!(graph.dependencies(2).is_empty());
+        assert_eq!(graph.dependencies(3), &BTreeSet::from([0]));
+        assert_eq!(graph.dependencies(4), &BTreeSet::from([1]));
+        assert_eq!(graph.chain_levels(0), vec![vec![0], vec![3], vec![5]]);
+        assert_eq!(graph.chain_levels(2), vec![vec![2]]);
+    }
+
+    #[test]
+    fn test_read_only_shared_objects() {
+        // Readers of the same version do not depend on each other, but the next writer waits for
+        // all of them, and later readers wait for that writer.
+        let graph = build_graph(vec![
+            vec![(0, true)],
+            vec![(0, false)],
+            vec![(0, false), (1, true)],
+            vec![(0, true)],
+            vec![(0, false)],
+        ]);
+        assert_eq!(graph.dependencies(1), &BTreeSet::from([0]));
+        assert_eq!(graph.dependencies(2), &BTreeSet::from([0]));
+        assert_eq!(graph.dependencies(3), &BTreeSet::from([1, 2]));
+        assert_eq!(graph.dependencies(4), &BTreeSet::from([3]));
+        assert_eq!(graph.chains(), &[vec![0, 1, 2, 3, 4]]);
+        assert_eq!(graph.critical_path_length(), 4);
+        assert_eq!(
+            graph.chain_levels(0),
+            vec![vec![0], vec![1, 2], vec![3], vec![4]]
+        );
+
+        // Only readers, which can all run in parallel.
+        let graph = build_graph(vec![vec![(0, false)], vec![(0, false)], vec![(0, false)]]);
+        assert_eq!(graph.chains(), &[vec![0], vec![1], vec![2]]);
+        assert_eq!(graph.critical_path_length(), 1);
+    }
+
+    #[test]
+    fn test_chains_joined_by_transaction() {
+        // A transaction on both pools joins their chains.
+        let graph = build_graph(vec![
+            vec![(0, true)],
+            vec![(1, true)],
+            vec![(2, true)],
+            vec![(1, true), (0, true)],
+            vec![(1, true)],
+        ]);
+        assert_eq!(graph.chains(), &[vec![0, 1, 3, 4], vec![2]]);
+        assert_eq!(graph.dependencies(3), &BTreeSet::from([0, 1]));
+        assert_eq!(graph.critical_path_length(), 3);
+        assert_eq!(graph.chain_levels(0), vec![vec![0, 1], vec![3], vec![4]]);
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_levels() {
+        use std::cell::RefCell;
+        use sui_types::error::SuiError;
+
+        // Each level is enqueued after the previous one has been executed.
+        let log = RefCell::new(vec![]);
+        enqueue_levels(
+            vec![vec![0], vec![1, 2], vec![3]],
+            |level| log.borrow_mut().push(format!("enqueue {level:?}")),
+            |level| {
+                let wait = format!("wait {level:?}");
+                let log = &log;
+                async move {
+                    log.borrow_mut().push(wait);
+                    Ok(())
+                }
+            },
+        )
+        .await;
+        assert_eq!(
+            log.into_inner(),
+            vec![
+                "enqueue [0]",
+                "wait [0]",
+                "enqueue [1, 2]",
+                "wait [1, 2]",
+                "enqueue [3]",
+                "wait [3]",
+            ]
+        );
+
+        // Once waiting fails, the rest of the chain is enqueued at once.
+        let log = RefCell::new(vec![]);
+        enqueue_levels(
+            vec![vec![0], vec![1], vec![2, 3], vec![4]],
+            |level| log.borrow_mut().push(format!("enqueue {level:?}")),
+            |level| {
+                let failed = level == [1];
+                async move {
+                    if failed {
+                        Err(SuiError::Unknown("storage error".to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        )
+        .await;
+        assert_eq!(
+            log.into_inner(),
+            vec!["enqueue [0]", "enqueue [1]", "enqueue [2, 3, 4]"]
+        );
+    }
+
+    #[test]
+    fn test_empty_commit() {
+        let graph = build_graph(vec![]);
+        assert!(graph.chains().is_empty());
+        assert_eq!(graph.critical_path_length(), 0);
+    }
+}
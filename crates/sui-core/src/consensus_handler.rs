@@ -16,6 +16,7 @@ use narwhal_config::Committee;
 use narwhal_executor::{ExecutionIndices, ExecutionState};
 use narwhal_types::ConsensusOutput;
 use serde::{Deserialize, Serialize};
+use sui_config::node::CommitSchedulerConfig;
 use sui_macros::{fail_point_async, fail_point_if};
 use sui_types::{
     authenticator_state::ActiveJwk,
@@ -37,6 +38,7 @@ use crate::{
         AuthorityMetrics, AuthorityState,
     },
     checkpoints::{CheckpointService, CheckpointServiceNotify},
+    commit_scheduler::CommitScheduler,
     consensus_throughput_calculator::ConsensusThroughputCalculator,
     consensus_types::{
         committee_api::CommitteeAPI, consensus_output_api::ConsensusOutputAPI, AuthorityIndex,
@@ -52,6 +54,7 @@ pub struct ConsensusHandlerInitializer {
     epoch_store: Arc<AuthorityPerEpochStore>,
     low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
     throughput_calculator: Arc<ConsensusThroughputCalculator>,
+    commit_scheduler_config: Option<CommitSchedulerConfig>,
 }
 
 impl ConsensusHandlerInitializer {
@@ -61,6 +64,7 @@ impl ConsensusHandlerInitializer {
         epoch_store: Arc<AuthorityPerEpochStore>,
         low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
         throughput_calculator: Arc<ConsensusThroughputCalculator>,
+        commit_scheduler_config: Option<CommitSchedulerConfig>,
     ) -> Self {
         Self {
             state,
@@ -68,6 +72,7 @@ impl ConsensusHandlerInitializer {
             epoch_store,
             low_scoring_authorities,
             throughput_calculator,
+            commit_scheduler_config,
         }
     }
 
@@ -84,6 +89,7 @@ impl ConsensusHandlerInitializer {
                 None,
                 state.metrics.clone(),
             )),
+            commit_scheduler_config: None,
         }
     }
     pub fn new_consensus_handler(&self) -> ConsensusHandler<CheckpointService> {
@@ -99,6 +105,7 @@ impl ConsensusHandlerInitializer {
             committee,
             self.state.metrics.clone(),
             self.throughput_calculator.clone(),
+            self.commit_scheduler_config.as_ref(),
         )
     }
 }
@@ -123,7 +130,7 @@ pub struct ConsensusHandler<C> {
     metrics: Arc<AuthorityMetrics>,
     /// Lru cache to quickly discard transactions processed by consensus
     processed_cache: LruCache<SequencedConsensusTransactionKey, ()>,
-    transaction_scheduler: AsyncTransactionScheduler,
+    transaction_scheduler: TransactionScheduler,
     /// Using the throughput calculator to record the current consensus throughput
     throughput_calculator: Arc<ConsensusThroughputCalculator>,
 }
@@ -140,6 +147,7 @@ impl<C> ConsensusHandler<C> {
         committee: Committee,
         metrics: Arc<AuthorityMetrics>,
         throughput_calculator: Arc<ConsensusThroughputCalculator>,
+        commit_scheduler_config: Option<&CommitSchedulerConfig>,
     ) -> Self {
         // Recover last_consensus_stats so it is consistent across validators.
         let mut last_consensus_stats = epoch_store
@@ -149,8 +157,19 @@ impl<C> ConsensusHandler<C> {
         if !last_consensus_stats.stats.is_initialized() {
             last_consensus_stats.stats = ConsensusStats::new(committee.size());
         }
-        let transaction_scheduler =
-            AsyncTransactionScheduler::start(transaction_manager, epoch_store.clone());
+        let transaction_scheduler = match commit_scheduler_config {
+            Some(config) => TransactionScheduler::Commit(CommitScheduler::start(
+                config,
+                transaction_manager,
+                cache_reader.clone(),
+                epoch_store.clone(),
+                metrics.clone(),
+            )),
+            None => TransactionScheduler::Async(AsyncTransactionScheduler::start(
+                transaction_manager,
+                epoch_store.clone(),
+            )),
+        };
         Self {
             epoch_store,
             last_consensus_stats,
@@ -271,10 +290,11 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             .protocol_config()
             .include_consensus_digest_in_prologue()
         {
+            // Commits without a digest use the default one in their prologue.
             true => self.consensus_commit_prologue_v2_transaction(
                 round,
                 timestamp,
-                consensus_output.consensus_digest(),
+                consensus_output.consensus_digest().unwrap_or_default(),
             ),
             false => self.consensus_commit_prologue_transaction(round, timestamp),
         };
@@ -452,6 +472,7 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
                 self.cache_reader.as_ref(),
                 round,
                 timestamp,
+                consensus_output.consensus_digest(),
                 &self.metrics,
             )
             .await
@@ -476,6 +497,22 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
     }
 }
 
+/// Hands the transactions of each commit over for execution, either at once or following their
+/// shared object dependencies.
+enum TransactionScheduler {
+    Async(AsyncTransactionScheduler),
+    Commit(CommitScheduler),
+}
+
+impl TransactionScheduler {
+    async fn schedule(&self, transactions: Vec<VerifiedExecutableTransaction>) {
+        match self {
+            Self::Async(scheduler) => scheduler.schedule(transactions).await,
+            Self::Commit(scheduler) => scheduler.schedule(transactions).await,
+        }
+    }
+}
+
 struct AsyncTransactionScheduler {
     sender: tokio::sync::mpsc::Sender<Vec<VerifiedExecutableTransaction>>,
 }
@@ -826,15 +863,16 @@ mod tests {
     use shared_crypto::intent::Intent;
     use sui_protocol_config::{ConsensusTransactionOrdering, SupportedProtocolVersions};
     use sui_types::{
-        base_types::{random_object_ref, AuthorityName, SuiAddress},
+        base_types::{random_object_ref, AuthorityName, ObjectID, SequenceNumber, SuiAddress},
         committee::Committee,
         messages_consensus::{
             AuthorityCapabilities, ConsensusTransaction, ConsensusTransactionKind,
         },
         object::Object,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
         sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait,
         transaction::{
-            CertifiedTransaction, SenderSignedData, TransactionData, TransactionDataAPI,
+            CertifiedTransaction, ObjectArg, SenderSignedData, TransactionData, TransactionDataAPI,
         },
     };
 
@@ -884,6 +922,7 @@ mod tests {
             committee.clone(),
             metrics,
             Arc::new(throughput_calculator),
+            None,
         );
 
         // AND
@@ -1004,7 +1043,7 @@ mod tests {
     #[test]
     fn test_order_by_gas_price() {
         let mut v = vec![cap_txn(10), user_txn(42), user_txn(100), cap_txn(1)];
-        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::ByGasPrice);
+        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::ByGasPrice, None);
         assert_eq!(
             extract(v),
             vec![
@@ -1025,7 +1064,7 @@ mod tests {
             cap_txn(1),
             user_txn(1000),
         ];
-        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::ByGasPrice);
+        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::ByGasPrice, None);
         assert_eq!(
             extract(v),
             vec![
@@ -1048,7 +1087,7 @@ mod tests {
             cap_txn(1),
             eop_txn(11),
         ];
-        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::ByGasPrice);
+        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::ByGasPrice, None);
         assert_eq!(
             extract(v),
             vec![
@@ -1061,6 +1100,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_order_by_sender_round_robin() {
+        let senders: Vec<_> = (0..3)
+            .map(|_| SuiAddress::random_for_testing_only())
+            .collect();
+        let mut v = vec![
+            user_txn_from(senders[0], 1),
+            user_txn_from(senders[0], 2),
+            user_txn_from(senders[0], 3),
+            cap_txn(10),
+            user_txn_from(senders[1], 4),
+            user_txn_from(senders[2], 5),
+            user_txn_from(senders[1], 6),
+        ];
+        PostConsensusTxReorder::reorder(
+            &mut v,
+            ConsensusTransactionOrdering::BySenderRoundRobin,
+            None,
+        );
+        assert_eq!(
+            extract(v),
+            vec![
+                "cap(10)".to_string(),
+                "user(1)".to_string(),
+                "user(4)".to_string(),
+                "user(5)".to_string(),
+                "user(2)".to_string(),
+                "user(6)".to_string(),
+                "user(3)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_order_by_shared_object() {
+        let objects: Vec<_> = (0..2).map(|_| ObjectID::random()).collect();
+        let mut v = vec![
+            shared_object_txn(objects[0], 1),
+            user_txn(2),
+            shared_object_txn(objects[1], 3),
+            cap_txn(10),
+            shared_object_txn(objects[0], 4),
+            user_txn(5),
+            shared_object_txn(objects[1], 6),
+        ];
+        PostConsensusTxReorder::reorder(&mut v, ConsensusTransactionOrdering::BySharedObject, None);
+        assert_eq!(
+            extract(v),
+            vec![
+                "cap(10)".to_string(),
+                "user(1)".to_string(),
+                "user(4)".to_string(),
+                "user(2)".to_string(),
+                "user(3)".to_string(),
+                "user(6)".to_string(),
+                "user(5)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_order_by_random_shuffle() {
+        let mut v: Vec<_> = (1..=20).map(user_txn).collect();
+        v.insert(10, cap_txn(10));
+        let shuffle = |digest: Option<&ConsensusCommitDigest>| {
+            let mut shuffled = v.clone();
+            PostConsensusTxReorder::reorder(
+                &mut shuffled,
+                ConsensusTransactionOrdering::ByRandomShuffle,
+                digest,
+            );
+            extract(shuffled)
+        };
+
+        // The order is deterministic for the same commit, and differs across commits.
+        let digest = ConsensusCommitDigest::random();
+        let shuffled = shuffle(Some(&digest));
+        assert_eq!(shuffled, shuffle(Some(&digest)));
+        assert_ne!(shuffled, shuffle(Some(&ConsensusCommitDigest::random())));
+
+        // Without a commit digest, the shuffle is rejected and the consensus order is kept,
+        // apart from system transactions.
+        let mut expected = extract(v.clone());
+        let cap = expected.remove(10);
+        expected.insert(0, cap);
+        assert_eq!(shuffle(None), expected);
+
+        // System transactions are still put to the beginning.
+        assert_eq!(shuffled[0], "cap(10)");
+        let mut sorted = shuffled[1..].to_vec();
+        sorted.sort_by_key(|s| s[5..s.len() - 1].parse::<u64>().unwrap());
+        assert_eq!(
+            sorted,
+            (1..=20).map(|i| format!("user({i})")).collect::<Vec<_>>()
+        );
+        assert_ne!(shuffled[1..], sorted[..]);
+    }
+
     fn extract(v: Vec<VerifiedSequencedConsensusTransaction>) -> Vec<String> {
         v.into_iter().map(extract_one).collect()
     }
@@ -1101,19 +1238,44 @@ mod tests {
     }
 
     fn user_txn(gas_price: u64) -> VerifiedSequencedConsensusTransaction {
+        user_txn_from(SuiAddress::default(), gas_price)
+    }
+
+    fn user_txn_from(sender: SuiAddress, gas_price: u64) -> VerifiedSequencedConsensusTransaction {
+        certified_txn(TransactionData::new_transfer(
+            SuiAddress::default(),
+            random_object_ref(),
+            sender,
+            random_object_ref(),
+            1000 * gas_price,
+            gas_price,
+        ))
+    }
+
+    fn shared_object_txn(
+        object_id: ObjectID,
+        gas_price: u64,
+    ) -> VerifiedSequencedConsensusTransaction {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .obj(ObjectArg::SharedObject {
+                id: object_id,
+                initial_shared_version: SequenceNumber::from_u64(1),
+                mutable: true,
+            })
+            .unwrap();
+        certified_txn(TransactionData::new_programmable(
+            SuiAddress::default(),
+            vec![random_object_ref()],
+            builder.finish(),
+            1000 * gas_price,
+            gas_price,
+        ))
+    }
+
+    fn certified_txn(data: TransactionData) -> VerifiedSequencedConsensusTransaction {
         let (committee, keypairs) = Committee::new_simple_test_committee();
-        let data = SenderSignedData::new(
-            TransactionData::new_transfer(
-                SuiAddress::default(),
-                random_object_ref(),
-                SuiAddress::default(),
-                random_object_ref(),
-                1000 * gas_price,
-                gas_price,
-            ),
-            Intent::sui_transaction(),
-            vec![],
-        );
+        let data = SenderSignedData::new(data, Intent::sui_transaction(), vec![]);
         txn(ConsensusTransactionKind::UserTransaction(Box::new(
             CertifiedTransaction::new_from_keypairs_for_testing(data, &keypairs, &committee),
         )))
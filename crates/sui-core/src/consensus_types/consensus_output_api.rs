@@ -27,8 +27,9 @@ pub(crate) trait ConsensusOutputAPI: Display {
     /// Returns all transactions in the commit.
     fn transactions(&self) -> ConsensusOutputTransactions<'_>;
 
-    /// Returns the digest of consensus output.
-    fn consensus_digest(&self) -> ConsensusCommitDigest;
+    /// Returns the digest of consensus output, or None if the consensus protocol does not
+    /// produce one.
+    fn consensus_digest(&self) -> Option<ConsensusCommitDigest>;
 }
 
 impl ConsensusOutputAPI for narwhal_types::ConsensusOutput {
@@ -94,11 +95,11 @@ impl ConsensusOutputAPI for narwhal_types::ConsensusOutput {
             }).collect()
     }
 
-    fn consensus_digest(&self) -> ConsensusCommitDigest {
+    fn consensus_digest(&self) -> Option<ConsensusCommitDigest> {
         // We port ConsensusOutputDigest, a narwhal space object, into ConsensusCommitDigest, a sui-core space object.
         // We assume they always have the same format.
         static_assertions::assert_eq_size!(ConsensusCommitDigest, ConsensusOutputDigest);
-        ConsensusCommitDigest::new(self.digest().into_inner())
+        Some(ConsensusCommitDigest::new(self.digest().into_inner()))
     }
 }
 
@@ -153,8 +154,8 @@ impl ConsensusOutputAPI for consensus_core::CommittedSubDag {
             .collect()
     }
 
-    fn consensus_digest(&self) -> ConsensusCommitDigest {
+    fn consensus_digest(&self) -> Option<ConsensusCommitDigest> {
         // TODO(mysticeti): implement consensus output digest.
-        ConsensusCommitDigest::default()
+        None
     }
 }
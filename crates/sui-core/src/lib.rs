@@ -9,6 +9,7 @@ pub mod authority_aggregator;
 pub mod authority_client;
 pub mod authority_server;
 pub mod checkpoints;
+mod commit_scheduler;
 pub mod consensus_adapter;
 pub mod consensus_handler;
 pub mod consensus_manager;
@@ -23,7 +24,7 @@ pub mod metrics;
 pub mod module_cache_metrics;
 pub mod mysticeti_adapter;
 pub mod overload_monitor;
-pub(crate) mod post_consensus_tx_reorder;
+pub mod post_consensus_tx_reorder;
 pub mod quorum_driver;
 pub mod safe_client;
 mod scoring_decision;
@@ -2,13 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::authority::AuthorityState;
+use parking_lot::RwLock;
 use std::cmp::{max, min};
+use std::collections::HashSet;
 use std::hash::Hasher;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Weak;
 use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
 use sui_config::node::AuthorityOverloadConfig;
+use sui_types::base_types::ObjectID;
 use sui_types::digests::TransactionDigest;
 use sui_types::error::SuiError;
 use sui_types::error::SuiResult;
@@ -28,18 +31,88 @@ pub struct AuthorityOverloadInfo {
 
     /// The calculated percentage of transactions to drop.
     pub load_shedding_percentage: AtomicU32,
+
+    /// The percentage of transactions touching `hot_objects` to drop.
+    pub hot_object_load_shedding_percentage: AtomicU32,
+
+    /// The percentage of transactions not touching `hot_objects` to drop.
+    pub uncongested_load_shedding_percentage: AtomicU32,
+
+    /// Shared objects with the most pending transactions, whose transactions are shed first.
+    pub hot_objects: RwLock<HashSet<ObjectID>>,
 }
 
 impl AuthorityOverloadInfo {
     pub fn set_overload(&self, load_shedding_percentage: u32) {
+        self.set_overload_by_class(
+            load_shedding_percentage,
+            load_shedding_percentage,
+            load_shedding_percentage,
+            HashSet::new(),
+        );
+    }
+
+    // Sets the overload with separate load shedding percentages for transactions touching
+    // `hot_objects` and for other transactions.
+    pub fn set_overload_by_class(
+        &self,
+        load_shedding_percentage: u32,
+        hot_object_load_shedding_percentage: u32,
+        uncongested_load_shedding_percentage: u32,
+        hot_objects: HashSet<ObjectID>,
+    ) {
         self.is_overload.store(true, Ordering::Relaxed);
         self.load_shedding_percentage
             .store(min(load_shedding_percentage, 100), Ordering::Relaxed);
+        self.hot_object_load_shedding_percentage.store(
+            min(hot_object_load_shedding_percentage, 100),
+            Ordering::Relaxed,
+        );
+        self.uncongested_load_shedding_percentage.store(
+            min(uncongested_load_shedding_percentage, 100),
+            Ordering::Relaxed,
+        );
+        *self.hot_objects.write() = hot_objects;
     }
 
     pub fn clear_overload(&self) {
         self.is_overload.store(false, Ordering::Relaxed);
         self.load_shedding_percentage.store(0, Ordering::Relaxed);
+        self.hot_object_load_shedding_percentage
+            .store(0, Ordering::Relaxed);
+        self.uncongested_load_shedding_percentage
+            .store(0, Ordering::Relaxed);
+        self.hot_objects.write().clear();
+    }
+
+    // Checks if we can accept the transaction with `tx_digest`, which touches `shared_objects`.
+    // The load shedding percentage of hot objects applies when any of them is hot.
+    pub fn accept_tx(
+        &self,
+        tx_digest: TransactionDigest,
+        shared_objects: impl IntoIterator<Item = ObjectID>,
+    ) -> SuiResult {
+        let hot_objects: Vec<_> = {
+            let hot_objects = self.hot_objects.read();
+            shared_objects
+                .into_iter()
+                .filter(|object_id| hot_objects.contains(object_id))
+                .collect()
+        };
+        if hot_objects.is_empty() {
+            overload_monitor_accept_tx(
+                self.uncongested_load_shedding_percentage
+                    .load(Ordering::Relaxed),
+                tx_digest,
+            )
+        } else {
+            overload_monitor_accept_hot_object_tx(
+                self.hot_object_load_shedding_percentage
+                    .load(Ordering::Relaxed),
+                tx_digest,
+                hot_objects,
+            )
+        }
     }
 }
 
@@ -108,9 +181,33 @@ fn check_authority_overload(
     );
 
     if is_overload {
-        authority
-            .overload_info
-            .set_overload(load_shedding_percentage);
+        // Shed the load of the hot objects first, so that transactions on other objects keep
+        // going through when a few objects are the bottleneck.
+        let hot_objects = authority
+            .transaction_manager()
+            .congested_objects(config.hot_object_queue_length_threshold);
+        let (hot_object_load_shedding_percentage, uncongested_load_shedding_percentage) =
+            split_load_shedding_percentage(
+                load_shedding_percentage,
+                config.max_load_shedding_percentage,
+                hot_objects.iter().map(|(_, queue_len)| queue_len).sum(),
+                authority.transaction_manager().inflight_queue_len(),
+            );
+        debug!(
+            "Load shedding {}% of transactions on {} hot objects, {}% of other transactions.",
+            hot_object_load_shedding_percentage,
+            hot_objects.len(),
+            uncongested_load_shedding_percentage
+        );
+        authority.overload_info.set_overload_by_class(
+            load_shedding_percentage,
+            hot_object_load_shedding_percentage,
+            uncongested_load_shedding_percentage,
+            hot_objects
+                .into_iter()
+                .map(|(object_id, _)| object_id)
+                .collect(),
+        );
     } else {
         authority.overload_info.clear_overload();
     }
@@ -123,6 +220,19 @@ fn check_authority_overload(
         .metrics
         .authority_load_shedding_percentage
         .set(load_shedding_percentage as i64);
+    authority
+        .metrics
+        .authority_hot_object_load_shedding_percentage
+        .set(
+            authority
+                .overload_info
+                .hot_object_load_shedding_percentage
+                .load(Ordering::Relaxed) as i64,
+        );
+    authority
+        .metrics
+        .authority_load_shedding_hot_objects
+        .set(authority.overload_info.hot_objects.read().len() as i64);
     true
 }
 
@@ -151,6 +261,41 @@ fn calculate_load_shedding_percentage(txn_ready_rate: f64, execution_rate: f64)
         .round() as u32
 }
 
+// Splits the percentage of all transactions to drop into the percentages of transactions touching
+// hot objects and of other transactions. The share of hot objects in the execution queue is used
+// as the share of their transactions in the load. Transactions on hot objects are dropped first,
+// up to `max_load_shedding_percentage`, and other transactions are dropped only when this is not
+// enough to drop `load_shedding_percentage` of all transactions.
+// Returns the percentages for transactions on hot objects and for other transactions.
+fn split_load_shedding_percentage(
+    load_shedding_percentage: u32,
+    max_load_shedding_percentage: u32,
+    hot_object_queue_len: usize,
+    queue_len: usize,
+) -> (u32, u32) {
+    if hot_object_queue_len == 0 || queue_len == 0 {
+        return (load_shedding_percentage, load_shedding_percentage);
+    }
+
+    // Transactions touching several hot objects are counted once per object, so the share can
+    // exceed 1.
+    let hot_object_share = (hot_object_queue_len as f64 / queue_len as f64).min(1.0);
+    let hot_object_capacity = hot_object_share * max_load_shedding_percentage as f64;
+    if hot_object_capacity >= load_shedding_percentage as f64 {
+        let hot_object_percentage =
+            (load_shedding_percentage as f64 / hot_object_share).round() as u32;
+        return (min(hot_object_percentage, max_load_shedding_percentage), 0);
+    }
+
+    let uncongested_percentage = ((load_shedding_percentage as f64 - hot_object_capacity)
+        / (1.0 - hot_object_share))
+        .round() as u32;
+    (
+        max_load_shedding_percentage,
+        min(uncongested_percentage, max_load_shedding_percentage),
+    )
+}
+
 // Given overload signals (`queueing_latency`, `txn_ready_rate`, `execution_rate`), return whether
 // the authority server should enter load shedding mode, and how much percentage of transactions to drop.
 // Note that the final load shedding percentage should also take the current load shedding percentage
@@ -232,23 +377,25 @@ fn should_reject_tx(
     value % 100 < load_shedding_percentage as u64
 }
 
+// Derives a random seed from the epoch time for transaction selection. Changing the seed every
+// `SEED_UPDATE_DURATION_SECS` interval allows rejected transaction's retry to have a chance
+// to go through in the future.
+// Also, using the epoch time instead of randomly generating a seed allows that all validators
+// makes the same decision.
+fn temporal_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Sui did not exist prior to 1970")
+        .as_secs()
+        / SEED_UPDATE_DURATION_SECS
+}
+
 // Checks if we can accept the transaction with `tx_digest`.
 pub fn overload_monitor_accept_tx(
     load_shedding_percentage: u32,
     tx_digest: TransactionDigest,
 ) -> SuiResult {
-    // Derive a random seed from the epoch time for transaction selection. Changing the seed every
-    // `SEED_UPDATE_DURATION_SECS` interval allows rejected transaction's retry to have a chance
-    // to go through in the future.
-    // Also, using the epoch time instead of randomly generating a seed allows that all validators
-    // makes the same decision.
-    let temporal_seed = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Sui did not exist prior to 1970")
-        .as_secs()
-        / SEED_UPDATE_DURATION_SECS;
-
-    if should_reject_tx(load_shedding_percentage, tx_digest, temporal_seed) {
+    if should_reject_tx(load_shedding_percentage, tx_digest, temporal_seed()) {
         // TODO: using `SEED_UPDATE_DURATION_SECS` is a safe suggestion that the time based seed
         // is definitely different by then. However, a shorter suggestion may be available.
         fp_bail!(SuiError::ValidatorOverloadedRetryAfter {
@@ -258,6 +405,22 @@ pub fn overload_monitor_accept_tx(
     Ok(())
 }
 
+// Checks if we can accept the transaction with `tx_digest` touching `hot_objects`. The hot objects
+// are reported when the transaction is rejected.
+pub fn overload_monitor_accept_hot_object_tx(
+    load_shedding_percentage: u32,
+    tx_digest: TransactionDigest,
+    hot_objects: Vec<ObjectID>,
+) -> SuiResult {
+    if should_reject_tx(load_shedding_percentage, tx_digest, temporal_seed()) {
+        fp_bail!(SuiError::ValidatorOverloadedOnObjectsRetryAfter {
+            object_ids: hot_objects,
+            retry_after_secs: SEED_UPDATE_DURATION_SECS
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[allow(clippy::disallowed_methods)] // allow unbounded_channel() since tests are simulating txn manager execution driver interaction.
 mod tests {
@@ -334,6 +497,54 @@ mod tests {
         assert_eq!(calculate_load_shedding_percentage(0.0, 1.0), 0);
     }
 
+    #[test]
+    pub fn test_split_load_shedding_percentage() {
+        // Without hot objects, all transactions are shed equally.
+        assert_eq!(split_load_shedding_percentage(40, 90, 0, 100), (40, 40));
+        assert_eq!(split_load_shedding_percentage(40, 90, 10, 0), (40, 40));
+
+        // Transactions on hot objects absorb all the load shedding when they can.
+        assert_eq!(split_load_shedding_percentage(40, 90, 50, 100), (80, 0));
+        assert_eq!(split_load_shedding_percentage(40, 90, 200, 100), (40, 0));
+
+        // Otherwise, other transactions are shed for the remaining percentage.
+        assert_eq!(split_load_shedding_percentage(60, 90, 50, 100), (90, 30));
+        assert_eq!(split_load_shedding_percentage(90, 90, 10, 100), (90, 90));
+    }
+
+    #[test]
+    pub fn test_accept_tx_by_class() {
+        let overload_info = AuthorityOverloadInfo::default();
+        let hot_object = ObjectID::random();
+        let other_object = ObjectID::random();
+        overload_info.set_overload_by_class(50, 100, 0, HashSet::from([hot_object]));
+        assert!(overload_info.is_overload.load(Ordering::Relaxed));
+
+        for _ in 0..100 {
+            let digest = TransactionDigest::random();
+            // Transactions not touching the hot object are accepted.
+            assert!(overload_info.accept_tx(digest, vec![]).is_ok());
+            assert!(overload_info.accept_tx(digest, vec![other_object]).is_ok());
+
+            // Transactions touching the hot object are rejected, reporting the hot object.
+            assert_eq!(
+                overload_info
+                    .accept_tx(digest, vec![other_object, hot_object])
+                    .unwrap_err(),
+                SuiError::ValidatorOverloadedOnObjectsRetryAfter {
+                    object_ids: vec![hot_object],
+                    retry_after_secs: SEED_UPDATE_DURATION_SECS,
+                }
+            );
+        }
+
+        overload_info.clear_overload();
+        assert!(overload_info.hot_objects.read().is_empty());
+        assert!(overload_info
+            .accept_tx(TransactionDigest::random(), vec![hot_object])
+            .is_ok());
+    }
+
     #[test]
     pub fn test_check_overload_signals() {
         let config = AuthorityOverloadConfig {
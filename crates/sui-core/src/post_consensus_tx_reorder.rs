@@ -4,41 +4,223 @@
 use crate::consensus_handler::{
     SequencedConsensusTransactionKind, VerifiedSequencedConsensusTransaction,
 };
+use fastcrypto::hash::HashFunction;
 use mysten_metrics::monitored_scope;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::collections::HashMap;
 use sui_protocol_config::ConsensusTransactionOrdering;
+use sui_types::crypto::DefaultHash;
+use sui_types::digests::ConsensusCommitDigest;
 use sui_types::messages_consensus::{ConsensusTransaction, ConsensusTransactionKind};
+use sui_types::transaction::CertifiedTransaction;
+use tracing::warn;
+
+/// A deterministic policy to order the user transactions of a consensus commit. All validators
+/// must produce the same order from the same input.
+pub trait TransactionOrderingPolicy {
+    /// Name of the policy, used as the monitored scope of `order()`.
+    fn name(&self) -> &'static str;
+
+    /// Orders `transactions`, which are all user transactions.
+    fn order(&self, transactions: &mut [VerifiedSequencedConsensusTransaction]);
+}
 
 pub struct PostConsensusTxReorder {}
 
 impl PostConsensusTxReorder {
+    /// Orders `transactions` with the policy of `kind`. `commit_digest` is the digest of the
+    /// consensus commit, if the consensus protocol produces one. Policies that are seeded by the
+    /// commit digest are rejected without it, and the consensus order is kept.
     pub fn reorder(
         transactions: &mut [VerifiedSequencedConsensusTransaction],
         kind: ConsensusTransactionOrdering,
+        commit_digest: Option<&ConsensusCommitDigest>,
     ) {
-        // TODO: make the reordering algorithm richer and depend on object hotness as well.
-        // Order transactions based on their gas prices. System transactions without gas price
-        // are put to the beginning of the sequenced_transactions vector.
-        match kind {
-            ConsensusTransactionOrdering::ByGasPrice => Self::order_by_gas_price(transactions),
-            ConsensusTransactionOrdering::None => (),
-        }
+        let policy: Box<dyn TransactionOrderingPolicy> = match kind {
+            ConsensusTransactionOrdering::ByGasPrice => Box::new(ByGasPrice),
+            ConsensusTransactionOrdering::BySenderRoundRobin => Box::new(BySenderRoundRobin),
+            ConsensusTransactionOrdering::BySharedObject => Box::new(BySharedObject),
+            ConsensusTransactionOrdering::ByRandomShuffle => match commit_digest {
+                Some(commit_digest) => Box::new(ByRandomShuffle::new(*commit_digest)),
+                None => {
+                    // Seeding with a digest known before the commit would make the order
+                    // predictable, so the consensus order is kept instead.
+                    warn!("Not shuffling transactions, as the commit has no digest");
+                    return;
+                }
+            },
+            ConsensusTransactionOrdering::None => return,
+        };
+        Self::reorder_with_policy(transactions, policy.as_ref());
     }
 
-    fn order_by_gas_price(transactions: &mut [VerifiedSequencedConsensusTransaction]) {
-        let _scope = monitored_scope("HandleConsensusOutput::order_by_gas_price");
-        transactions.sort_by_key(|txn| {
-            // Reverse order, so that transactions with higher gas price are put to the beginning.
-            std::cmp::Reverse({
-                match &txn.0.transaction {
-                    SequencedConsensusTransactionKind::External(ConsensusTransaction {
-                        tracking_id: _,
-                        kind: ConsensusTransactionKind::UserTransaction(cert),
-                    }) => cert.gas_price(),
-                    // Non-user transactions are considered to have gas price of MAX u64 and are
-                    // put to the beginning.
-                    _ => u64::MAX,
+    /// Orders the user transactions with `policy`. System transactions without gas price are
+    /// put to the beginning of `transactions`, in their original order.
+    pub fn reorder_with_policy(
+        transactions: &mut [VerifiedSequencedConsensusTransaction],
+        policy: &dyn TransactionOrderingPolicy,
+    ) {
+        let _scope = monitored_scope(policy.name());
+        // The sort is stable, so both partitions keep their order.
+        transactions.sort_by_key(|txn| user_transaction(txn).is_some());
+        let num_system_transactions = transactions
+            .iter()
+            .take_while(|txn| user_transaction(txn).is_none())
+            .count();
+        policy.order(&mut transactions[num_system_transactions..]);
+    }
+}
+
+fn user_transaction(txn: &VerifiedSequencedConsensusTransaction) -> Option<&CertifiedTransaction> {
+    match &txn.0.transaction {
+        SequencedConsensusTransactionKind::External(ConsensusTransaction {
+            tracking_id: _,
+            kind: ConsensusTransactionKind::UserTransaction(cert),
+        }) => Some(cert),
+        _ => None,
+    }
+}
+
+fn expect_user_transaction(txn: &VerifiedSequencedConsensusTransaction) -> &CertifiedTransaction {
+    user_transaction(txn).expect("Ordering policies only order user transactions")
+}
+
+// Stably sorts `transactions` by `keys`, where each key belongs to the transaction at the same
+// position.
+fn sort_by_keys<K: Ord>(transactions: &mut [VerifiedSequencedConsensusTransaction], keys: Vec<K>) {
+    let mut keyed: Vec<_> = keys.into_iter().zip(transactions.iter().cloned()).collect();
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (slot, (_, txn)) in transactions.iter_mut().zip(keyed) {
+        *slot = txn;
+    }
+}
+
+struct ByGasPrice;
+
+impl TransactionOrderingPolicy for ByGasPrice {
+    fn name(&self) -> &'static str {
+        "HandleConsensusOutput::order_by_gas_price"
+    }
+
+    fn order(&self, transactions: &mut [VerifiedSequencedConsensusTransaction]) {
+        // Reverse order, so that transactions with higher gas price are put to the beginning.
+        transactions.sort_by_key(|txn| std::cmp::Reverse(expect_user_transaction(txn).gas_price()));
+    }
+}
+
+struct BySenderRoundRobin;
+
+impl TransactionOrderingPolicy for BySenderRoundRobin {
+    fn name(&self) -> &'static str {
+        "HandleConsensusOutput::order_by_sender_round_robin"
+    }
+
+    fn order(&self, transactions: &mut [VerifiedSequencedConsensusTransaction]) {
+        // The n-th transaction of each sender goes in the n-th pass over the senders.
+        let mut senders = HashMap::new();
+        let keys = transactions
+            .iter()
+            .map(|txn| {
+                let num_senders = senders.len();
+                let (sender_position, num_transactions) = senders
+                    .entry(expect_user_transaction(txn).sender_address())
+                    .or_insert((num_senders, 0));
+                *num_transactions += 1;
+                (*num_transactions, *sender_position)
+            })
+            .collect();
+        sort_by_keys(transactions, keys);
+    }
+}
+
+struct BySharedObject;
+
+impl TransactionOrderingPolicy for BySharedObject {
+    fn name(&self) -> &'static str {
+        "HandleConsensusOutput::order_by_shared_object"
+    }
+
+    fn order(&self, transactions: &mut [VerifiedSequencedConsensusTransaction]) {
+        // Transactions are grouped by their first shared object, and each group is placed at
+        // the position of its first transaction. Transactions without shared objects stay in
+        // place relative to the groups.
+        let mut groups = HashMap::new();
+        let keys = transactions
+            .iter()
+            .enumerate()
+            .map(|(position, txn)| {
+                match expect_user_transaction(txn).shared_input_objects().next() {
+                    Some(object) => *groups.entry(object.id).or_insert(position),
+                    None => position,
                 }
             })
-        })
+            .collect();
+        sort_by_keys(transactions, keys);
+    }
+}
+
+struct ByRandomShuffle {
+    commit_digest: ConsensusCommitDigest,
+}
+
+impl ByRandomShuffle {
+    fn new(commit_digest: ConsensusCommitDigest) -> Self {
+        Self { commit_digest }
+    }
+}
+
+impl TransactionOrderingPolicy for ByRandomShuffle {
+    fn name(&self) -> &'static str {
+        "HandleConsensusOutput::order_by_random_shuffle"
+    }
+
+    fn order(&self, transactions: &mut [VerifiedSequencedConsensusTransaction]) {
+        let mut hasher = DefaultHash::default();
+        hasher.update(self.commit_digest.inner());
+        for txn in transactions.iter() {
+            hasher.update(expect_user_transaction(txn).digest().inner());
+        }
+        let mut rng = ChaCha20Rng::from_seed(hasher.finalize().digest);
+        shuffle(transactions, &mut rng);
+    }
+}
+
+// Fisher-Yates shuffle. Both the rng and the algorithm are pinned here, as all validators must
+// produce the same order regardless of the version of the `rand` crate.
+fn shuffle<T>(items: &mut [T], rng: &mut ChaCha20Rng) {
+    for i in (1..items.len()).rev() {
+        let j = uniform_below(rng, i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+}
+
+// Draws a number uniformly from `[0, bound)`, rejecting the draws that would bias the result
+// towards small numbers.
+fn uniform_below(rng: &mut ChaCha20Rng, bound: u64) -> u64 {
+    // Every number below `bound` is the remainder of the same count of values below `zone`.
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+        let value = rng.next_u64();
+        if value < zone {
+            return value % bound;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_is_pinned() {
+        // The order must never change for the same seed, as all validators must agree on it.
+        let mut items: Vec<u32> = (0..10).collect();
+        shuffle(&mut items, &mut ChaCha20Rng::from_seed([7; 32]));
+        assert_eq!(items, vec![6, 9, 7, 3, 0, 5, 2, 4, 1, 8]);
+
+        let mut items: Vec<u32> = vec![];
+        shuffle(&mut items, &mut ChaCha20Rng::from_seed([7; 32]));
+        assert!(items.is_empty());
     }
 }
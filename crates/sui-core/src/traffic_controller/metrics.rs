@@ -15,6 +15,7 @@ pub struct TrafficControllerMetrics {
     pub blocks_delegated_to_firewall: IntCounter,
     pub firewall_delegation_request_fail: IntCounter,
     pub tally_channel_overflow: IntCounter,
+    pub policy_config_updates: IntCounter,
 }
 
 impl TrafficControllerMetrics {
@@ -64,6 +65,12 @@ impl TrafficControllerMetrics {
                 registry
             )
             .unwrap(),
+            policy_config_updates: register_int_counter_with_registry!(
+                "traffic_control_policy_config_updates",
+                "Number of policy config updates applied by the traffic controller",
+                registry
+            )
+            .unwrap(),
         }
     }
 
@@ -24,8 +24,8 @@ use std::fmt::Debug;
 use std::time::{Duration, SystemTime};
 use sui_types::error::SuiError;
 use sui_types::traffic_control::{PolicyConfig, RemoteFirewallConfig, ServiceResponse};
-use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, watch};
 use tracing::{debug, error, info, warn};
 
 type BlocklistT = Arc<DashMap<IpAddr, SystemTime>>;
@@ -39,6 +39,7 @@ struct Blocklists {
 #[derive(Clone)]
 pub struct TrafficController {
     tally_channel: mpsc::Sender<TrafficTally>,
+    policy_config: Arc<watch::Sender<PolicyConfig>>,
     blocklists: Blocklists,
     metrics: Arc<TrafficControllerMetrics>,
 }
@@ -71,6 +72,7 @@ impl TrafficController {
     ) -> Self {
         let metrics = Arc::new(metrics);
         let (tx, rx) = mpsc::channel(policy_config.channel_capacity);
+        let (policy_tx, policy_rx) = watch::channel(policy_config);
         // Memoized drainfile existence state. This is passed into delegation
         // funtions to prevent them from continuing to populate blocklists
         // if drain is set, as otherwise it will grow without bounds
@@ -82,6 +84,7 @@ impl TrafficController {
 
         let ret = Self {
             tally_channel: tx,
+            policy_config: Arc::new(policy_tx),
             blocklists: Blocklists {
                 connection_ips: Arc::new(DashMap::new()),
                 proxy_ips: Arc::new(DashMap::new()),
@@ -91,7 +94,7 @@ impl TrafficController {
         let blocklists = ret.blocklists.clone();
         spawn_monitored_task!(run_tally_loop(
             rx,
-            policy_config,
+            policy_rx,
             fw_config,
             blocklists,
             metrics,
@@ -108,6 +111,25 @@ impl TrafficController {
         Self::spawn(policy_config, metrics, fw_config)
     }
 
+    pub fn policy_config(&self) -> PolicyConfig {
+        self.policy_config.borrow().clone()
+    }
+
+    /// Replaces the spam and error policies. The tallies collected by the current policies
+    /// are dropped, while the blocklists are kept.
+    pub fn update_policy_config(&self, policy_config: PolicyConfig) -> anyhow::Result<()> {
+        let channel_capacity = self.policy_config.borrow().channel_capacity;
+        if policy_config.channel_capacity != channel_capacity {
+            anyhow::bail!(
+                "Channel capacity cannot be changed on a running node (current {}, requested {})",
+                channel_capacity,
+                policy_config.channel_capacity
+            );
+        }
+        self.policy_config.send_replace(policy_config);
+        Ok(())
+    }
+
     pub fn tally(&self, tally: TrafficTally) {
         // Use try_send rather than send mainly to avoid creating backpressure
         // on the caller if the channel is full, which may slow down the critical
@@ -206,12 +228,13 @@ fn is_tallyable_error(response: &ServiceResponse) -> bool {
 
 async fn run_tally_loop(
     mut receiver: mpsc::Receiver<TrafficTally>,
-    policy_config: PolicyConfig,
+    mut policy_receiver: watch::Receiver<PolicyConfig>,
     fw_config: Option<RemoteFirewallConfig>,
     blocklists: Blocklists,
     metrics: Arc<TrafficControllerMetrics>,
     mut mem_drainfile_present: bool,
 ) {
+    let mut policy_config = policy_receiver.borrow_and_update().clone();
     let mut spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
     let mut error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
     let spam_blocklists = Arc::new(blocklists.clone());
@@ -265,6 +288,13 @@ async fn run_tally_loop(
                     }
                 }
             }
+            Ok(()) = policy_receiver.changed() => {
+                policy_config = policy_receiver.borrow_and_update().clone();
+                spam_policy = TrafficControlPolicy::from_spam_config(policy_config.clone()).await;
+                error_policy = TrafficControlPolicy::from_error_config(policy_config.clone()).await;
+                metrics.policy_config_updates.inc();
+                info!("TrafficController policy config updated: {:?}", policy_config);
+            }
             // Dead man's switch - if we suspect something is sinking all traffic to node, disable nodefw
             _ = tokio::time::sleep(tokio::time::Duration::from_secs(timeout)) => {
                 if let Some(fw_config) = &fw_config {
@@ -795,6 +795,36 @@ impl TransactionManager {
             .collect()
     }
 
+    // Returns the shared objects with at least `min_queue_len` transactions waiting on them, with
+    // the number of waiting transactions. Transactions waiting on owned objects are not counted,
+    // as they only delay their own sender.
+    pub(crate) fn congested_objects(&self, min_queue_len: usize) -> Vec<(ObjectID, usize)> {
+        let inner = self.inner.read();
+        inner
+            .input_objects
+            .iter()
+            // The queue length bounds the number of transactions waiting on the shared object.
+            .filter(|(_, txns)| txns.len() >= min_queue_len)
+            .map(|(object_id, txns)| {
+                let shared_queue_len = txns
+                    .keys()
+                    .filter(|digest| {
+                        inner.pending_certificates.get(digest).is_some_and(|cert| {
+                            cert.certificate
+                                .data()
+                                .transaction_data()
+                                .shared_input_objects()
+                                .iter()
+                                .any(|shared| shared.id == *object_id)
+                        })
+                    })
+                    .count();
+                (*object_id, shared_queue_len)
+            })
+            .filter(|(_, shared_queue_len)| *shared_queue_len >= min_queue_len)
+            .collect()
+    }
+
     // Returns the number of transactions pending or being executed right now.
     pub(crate) fn inflight_queue_len(&self) -> usize {
         let inner = self.inner.read();
@@ -167,6 +167,13 @@ where
         let transaction = epoch_store
             .verify_transaction(request.transaction)
             .map_err(QuorumDriverError::InvalidUserSignature)?;
+        self.validator_state
+            .transaction_validation_hooks()
+            .check(
+                transaction.data().transaction_data(),
+                transaction.tx_signatures(),
+            )
+            .map_err(QuorumDriverError::TransactionRejected)?;
         let (_in_flight_metrics_guards, good_response_metrics) = self.update_metrics(&transaction);
         let tx_digest = *transaction.digest();
         debug!(?tx_digest, "TO Received transaction execution request.");
@@ -26,6 +26,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::{convert::TryInto, env};
 
+use sui_config::node::CommitSchedulerConfig;
 use sui_json_rpc_types::{
     SuiArgument, SuiExecutionResult, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTypeTag,
 };
@@ -63,6 +64,7 @@ use crate::authority::authority_per_epoch_store::DeferralKey;
 use crate::authority::authority_store_tables::AuthorityPerpetualTables;
 use crate::authority::move_integration_tests::build_and_publish_test_package_with_upgrade_cap;
 use crate::authority::test_authority_builder::TestAuthorityBuilder;
+use crate::commit_scheduler::CommitScheduler;
 use crate::{
     authority_client::{AuthorityAPI, NetworkAuthorityClient},
     authority_server::AuthorityServer,
@@ -5892,3 +5894,73 @@ async fn test_per_object_congestion_control() {
         .unwrap()
         .is_empty());
 }
+
+#[tokio::test]
+async fn test_commit_scheduler_executes_dependency_chains() {
+    let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+    let shared_objects = create_shared_objects(2);
+    let gas_objects = create_gas_objects(6, sender);
+
+    let authority = TestAuthorityBuilder::new().build().await;
+    let mut genesis_objects = gas_objects.clone();
+    genesis_objects.extend(shared_objects.clone());
+    authority.insert_genesis_objects(&genesis_objects).await;
+
+    // Each commit has a dependency chain of two transactions on one shared object and a single
+    // transaction on the other one. The single transaction of the second commit waits for the
+    // chain of the first commit.
+    let mut commits = vec![];
+    for (objects, gas_objects) in [[0, 0, 1], [0, 1, 1]].iter().zip(gas_objects.chunks(3)) {
+        let mut certificates = vec![];
+        for (object, gas_object) in objects.iter().zip(gas_objects) {
+            let certificate = make_test_transaction(
+                &sender,
+                &keypair,
+                shared_objects[*object].id(),
+                OBJECT_START_VERSION,
+                &gas_object.compute_object_reference(),
+                &[&authority],
+                12345,
+                None,
+                None,
+            )
+            .await;
+            certificates.push(certificate);
+        }
+        commits.push(send_batch_consensus_no_execution(&authority, &certificates).await);
+    }
+
+    // With a single permit, the dependent chains of both commits are executed one after the other.
+    let scheduler = CommitScheduler::start(
+        &CommitSchedulerConfig {
+            max_concurrent_chains: 1,
+        },
+        authority.transaction_manager().clone(),
+        authority.get_cache_reader().clone(),
+        authority.epoch_store_for_testing().clone(),
+        authority.metrics.clone(),
+    );
+    let mut digests = vec![];
+    for transactions in commits {
+        assert_eq!(transactions.len(), 3);
+        digests.extend(transactions.iter().map(|transaction| *transaction.digest()));
+        scheduler.schedule(transactions).await;
+    }
+    tokio::time::timeout(
+        std::time::Duration::from_secs(60),
+        authority
+            .get_cache_reader()
+            .notify_read_executed_effects_digests(&digests),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+
+    let critical_path_length = &authority.metrics.commit_scheduler_critical_path_length;
+    assert_eq!(critical_path_length.get_sample_count(), 2);
+    assert_eq!(critical_path_length.get_sample_sum(), 4.0);
+    assert_eq!(
+        authority.metrics.commit_scheduler_chains.get_sample_sum(),
+        4.0
+    );
+}
@@ -14,22 +14,29 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::certificate_deny_config::CertificateDenyConfigBuilder;
 use sui_config::transaction_deny_config::{TransactionDenyConfig, TransactionDenyConfigBuilder};
+use sui_config::transaction_validation_hook_config::TransactionValidationHookConfig;
 use sui_swarm_config::genesis_config::{AccountConfig, DEFAULT_GAS_AMOUNT};
 use sui_swarm_config::network_config::NetworkConfig;
 use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_transaction_checks::validation_hooks::{
+    register_transaction_validation_plugin, RejectionReason, TransactionValidationHook,
+    TransactionValidationHooks,
+};
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_types::effects::TransactionEffectsAPI;
-use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::error::{SuiError, SuiResult, TransactionRejection, UserInputError};
 use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
 use sui_types::messages_grpc::HandleTransactionResponse;
+use sui_types::signature::GenericSignature;
 use sui_types::transaction::{
-    CallArg, CertifiedTransaction, Transaction, TransactionData, VerifiedCertificate,
-    VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    CallArg, CertifiedTransaction, Transaction, TransactionData, TransactionDataAPI,
+    VerifiedCertificate, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
 };
 use sui_types::utils::get_zklogin_user_address;
 use sui_types::utils::{
     make_zklogin_tx, to_sender_signed_transaction, to_sender_signed_transaction_with_multi_signers,
 };
+use sui_types::SUI_SYSTEM_PACKAGE_ID;
 
 const ACCOUNT_NUM: usize = 5;
 const GAS_OBJECT_COUNT: usize = 15;
@@ -240,6 +247,41 @@ async fn test_signer_denied() {
     assert_denied(&transfer_with_account(&accounts[2], &accounts[1], &state).await);
 }
 
+#[tokio::test]
+async fn test_reload_transaction_deny_config() {
+    let (network_config, state) = setup_test(TransactionDenyConfigBuilder::new().build()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+
+    state
+        .reload_transaction_deny_config(
+            TransactionDenyConfigBuilder::new()
+                .add_denied_address(accounts[0].0)
+                .build(),
+        )
+        .unwrap();
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+
+    // An invalid config is rejected and the current one is kept.
+    assert!(state
+        .reload_transaction_deny_config(
+            TransactionDenyConfigBuilder::new()
+                .add_denied_package(SUI_SYSTEM_PACKAGE_ID)
+                .build(),
+        )
+        .is_err());
+    assert_denied(&transfer_with_account(&accounts[0], &accounts[0], &state).await);
+
+    state
+        .reload_transaction_deny_config(TransactionDenyConfig::default())
+        .unwrap();
+    assert!(transfer_with_account(&accounts[0], &accounts[0], &state)
+        .await
+        .is_ok());
+}
+
 #[tokio::test]
 async fn test_shared_object_transaction_disabled() {
     let (network_config, state) = setup_test(
@@ -454,6 +496,65 @@ async fn test_certificate_deny() {
         )
         .build()
         .await;
+    let cert = certify_on_single_authority(&state, tx).await;
+    let (effects, _) = state.try_execute_for_test(&cert).await.unwrap();
+    assert!(matches!(
+        effects.status(),
+        &ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::CertificateDenied,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_reload_certificate_deny_config() {
+    let (network_config, state) = setup_test(TransactionDenyConfig::default()).await;
+    let (sender, key, gas_objects) = get_accounts_and_coins(&network_config, &state)
+        .pop()
+        .unwrap();
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+    let txs: Vec<_> = gas_objects[..2]
+        .iter()
+        .map(|gas_object| {
+            make_transfer_sui_transaction(*gas_object, sender, None, sender, &key, rgp)
+        })
+        .collect();
+    let deny_config = CertificateDenyConfigBuilder::new()
+        .add_certificate_deny(*txs[0].digest())
+        .add_certificate_deny(*txs[1].digest())
+        .build();
+    state
+        .reload_certificate_deny_config(deny_config.clone())
+        .unwrap();
+
+    // The config only applies from the next epoch on.
+    let cert = certify_on_single_authority(&state, txs[0].clone()).await;
+    let (effects, _) = state.try_execute_for_test(&cert).await.unwrap();
+    assert!(effects.status().is_ok());
+
+    state.apply_pending_certificate_deny_config();
+    let cert = certify_on_single_authority(&state, txs[1].clone()).await;
+    let (effects, _) = state.try_execute_for_test(&cert).await.unwrap();
+    assert!(matches!(
+        effects.status(),
+        &ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::CertificateDenied,
+            ..
+        }
+    ));
+
+    // Denied certificates cannot be removed from the list.
+    assert!(state
+        .reload_certificate_deny_config(CertificateDenyConfigBuilder::new().build())
+        .is_err());
+    state.reload_certificate_deny_config(deny_config).unwrap();
+}
+
+async fn certify_on_single_authority(
+    state: &AuthorityState,
+    tx: Transaction,
+) -> VerifiedCertificate {
     let epoch_store = state.epoch_store_for_testing();
     let tx = epoch_store.verify_transaction(tx).unwrap();
     let signature = state
@@ -462,16 +563,124 @@ async fn test_certificate_deny() {
         .unwrap()
         .status
         .into_signed_for_testing();
-    let cert = VerifiedCertificate::new_unchecked(
+    VerifiedCertificate::new_unchecked(
         CertifiedTransaction::new(tx.into_message(), vec![signature], epoch_store.committee())
             .unwrap(),
-    );
-    let (effects, _) = state.try_execute_for_test(&cert).await.unwrap();
-    assert!(matches!(
-        effects.status(),
-        &ExecutionStatus::Failure {
-            error: ExecutionFailureStatus::CertificateDenied,
-            ..
+    )
+}
+
+struct DenySender(SuiAddress);
+
+impl TransactionValidationHook for DenySender {
+    fn name(&self) -> &str {
+        "deny-sender"
+    }
+
+    fn validate(
+        &self,
+        tx_data: &TransactionData,
+        _tx_signatures: &[GenericSignature],
+    ) -> Result<(), RejectionReason> {
+        if tx_data.sender() == self.0 {
+            return Err(RejectionReason::new(
+                "sender-denied",
+                format!("Sender {} is denied", self.0),
+            ));
         }
-    ));
+        Ok(())
+    }
+}
+
+fn assert_rejected_by<T: std::fmt::Debug>(result: &SuiResult<T>, hook: &str, code: &str) {
+    match result.as_ref().unwrap_err() {
+        SuiError::UserInputError {
+            error:
+                UserInputError::TransactionRejected(TransactionRejection {
+                    hook: h, code: c, ..
+                }),
+        } => {
+            assert_eq!(h, hook);
+            assert_eq!(c, code);
+        }
+        err => panic!("Unexpected error: {err:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_transaction_validation_hooks() {
+    let (network_config, state) = setup_test(TransactionDenyConfig::default()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let rgp = state.reference_gas_price_for_testing().unwrap();
+
+    // Calls into the system package require a gas price above the reference gas price.
+    let hooks = TransactionValidationHooks::from_config(&[
+        TransactionValidationHookConfig::MinGasPriceForPackages {
+            packages: vec![SUI_SYSTEM_PACKAGE_ID],
+            min_gas_price: rgp + 1,
+        },
+    ])
+    .unwrap();
+    let state = TestAuthorityBuilder::new()
+        .with_network_config(&network_config)
+        .with_store(state.database_for_testing().clone())
+        .with_transaction_validation_hooks(hooks)
+        .build()
+        .await;
+    let epoch_store = state.epoch_store_for_testing();
+    let account = &accounts[0];
+    let stake = |gas_price| {
+        let tx = TestTransactionBuilder::new(account.0, account.2[0], gas_price)
+            .call_staking(account.2[1], SuiAddress::default())
+            .build_and_sign(&account.1);
+        epoch_store.verify_transaction(tx).unwrap()
+    };
+    let result = state.handle_transaction(&epoch_store, stake(rgp)).await;
+    assert_rejected_by(&result, "min-gas-price-for-packages", "gas-price-too-low");
+    let result = state.handle_transaction(&epoch_store, stake(rgp + 1)).await;
+    assert!(result.is_ok());
+    // Transactions without calls into the package are not affected.
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
+
+    // Hooks registered at runtime apply to later transactions.
+    state
+        .transaction_validation_hooks()
+        .register(Arc::new(DenySender(accounts[2].0)));
+    let result = transfer_with_account(&accounts[2], &accounts[2], &state).await;
+    assert_rejected_by(&result, "deny-sender", "sender-denied");
+    assert!(transfer_with_account(&accounts[3], &accounts[3], &state)
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_transaction_validation_plugin() {
+    let (network_config, state) = setup_test(TransactionDenyConfig::default()).await;
+    let accounts = get_accounts_and_coins(&network_config, &state);
+    let plugin_config = TransactionValidationHookConfig::Plugin {
+        name: "deny-sender".to_string(),
+        params: [("sender".to_string(), accounts[0].0.to_string())].into(),
+    };
+    assert!(TransactionValidationHooks::from_config(&[plugin_config.clone()]).is_err());
+
+    register_transaction_validation_plugin("deny-sender", |params| {
+        let sender = params
+            .get("sender")
+            .ok_or_else(|| anyhow::anyhow!("Missing sender"))?
+            .parse()?;
+        Ok(Arc::new(DenySender(sender)))
+    });
+    let hooks = TransactionValidationHooks::from_config(&[plugin_config]).unwrap();
+    let state = TestAuthorityBuilder::new()
+        .with_network_config(&network_config)
+        .with_store(state.database_for_testing().clone())
+        .with_transaction_validation_hooks(hooks)
+        .build()
+        .await;
+    let result = transfer_with_account(&accounts[0], &accounts[0], &state).await;
+    assert_rejected_by(&result, "deny-sender", "sender-denied");
+    assert!(transfer_with_account(&accounts[1], &accounts[1], &state)
+        .await
+        .is_ok());
 }
@@ -715,3 +715,85 @@ async fn transaction_manager_receiving_object_ready_if_current_version_greater()
     rx_ready_certificates.recv().await.unwrap();
     assert!(rx_ready_certificates.try_recv().is_err());
 }
+
+// Tests that only transactions waiting on shared objects count towards congested objects.
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn transaction_manager_congested_objects() {
+    // Initialize an authority state, with gas objects and a shared object.
+    let (owner, _keypair) = deterministic_random_account_key();
+    let gas_objects: Vec<Object> = (0..10)
+        .map(|_| {
+            let gas_object_id = ObjectID::random();
+            Object::with_id_owner_for_testing(gas_object_id, owner)
+        })
+        .collect();
+    let shared_object = Object::shared_for_testing();
+    let state =
+        init_state_with_objects([gas_objects.clone(), vec![shared_object.clone()]].concat()).await;
+
+    let (transaction_manager, mut rx_ready_certificates) = make_transaction_manager(&state);
+
+    // Enqueue three transactions waiting on a version of the shared object that is not
+    // available yet.
+    let shared_version = 1000.into();
+    let shared_object_arg = ObjectArg::SharedObject {
+        id: shared_object.id(),
+        initial_shared_version: 0.into(),
+        mutable: true,
+    };
+    let shared_transactions: Vec<_> = gas_objects[0..3]
+        .iter()
+        .map(|gas_object| {
+            make_transaction(gas_object.clone(), vec![CallArg::Object(shared_object_arg)])
+        })
+        .collect();
+    for transaction in &shared_transactions {
+        state
+            .epoch_store_for_testing()
+            .set_shared_object_versions_for_testing(
+                transaction.digest(),
+                &vec![(shared_object.id(), shared_version)],
+            )
+            .unwrap();
+    }
+
+    // Enqueue three transactions waiting on an owned object that is not available yet.
+    let owned_object =
+        Object::with_id_owner_version_for_testing(ObjectID::random(), 5.into(), owner);
+    let owned_object_arg = ObjectArg::ImmOrOwnedObject(owned_object.compute_object_reference());
+    let owned_transactions: Vec<_> = gas_objects[3..6]
+        .iter()
+        .map(|gas_object| {
+            make_transaction(gas_object.clone(), vec![CallArg::Object(owned_object_arg)])
+        })
+        .collect();
+
+    transaction_manager.enqueue(
+        [shared_transactions, owned_transactions].concat(),
+        &state.epoch_store_for_testing(),
+    );
+    sleep(Duration::from_secs(1)).await;
+    assert!(rx_ready_certificates.try_recv().is_err());
+    assert_eq!(transaction_manager.inflight_queue_len(), 6);
+
+    // Only the shared object is congested, even though as many transactions wait on the owned
+    // object.
+    assert_eq!(
+        transaction_manager.congested_objects(3),
+        vec![(shared_object.id(), 3)]
+    );
+    assert!(transaction_manager.congested_objects(4).is_empty());
+
+    // Once the shared object is available, it is no longer congested.
+    transaction_manager.objects_available(
+        vec![InputKey::VersionedObject {
+            id: shared_object.id(),
+            version: shared_version,
+        }],
+        &state.epoch_store_for_testing(),
+    );
+    for _ in 0..3 {
+        rx_ready_certificates.recv().await.unwrap();
+    }
+    assert!(transaction_manager.congested_objects(1).is_empty());
+}
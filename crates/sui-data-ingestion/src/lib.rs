@@ -7,4 +7,6 @@ mod workers;
 pub use progress_store::DynamoDBProgressStore;
 pub use workers::{
     ArchivalConfig, ArchivalWorker, BlobTaskConfig, BlobWorker, KVStoreTaskConfig, KVStoreWorker,
+    WebhookFilter, WebhookNotification, WebhookPayload, WebhookRule, WebhookTaskConfig,
+    WebhookWorker, ID_HEADER, SIGNATURE_HEADER,
 };
@@ -8,7 +8,7 @@ use std::env;
 use std::path::PathBuf;
 use sui_data_ingestion::{
     ArchivalConfig, ArchivalWorker, BlobTaskConfig, BlobWorker, DynamoDBProgressStore,
-    KVStoreTaskConfig, KVStoreWorker,
+    KVStoreTaskConfig, KVStoreWorker, WebhookTaskConfig, WebhookWorker,
 };
 use sui_data_ingestion_core::{DataIngestionMetrics, ReaderOptions};
 use sui_data_ingestion_core::{IndexerExecutor, WorkerPool};
@@ -21,6 +21,7 @@ enum Task {
     Archival(ArchivalConfig),
     Blob(BlobTaskConfig),
     KV(KVStoreTaskConfig),
+    Webhook(WebhookTaskConfig),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -141,6 +142,14 @@ async fn main() -> Result<()> {
                 );
                 executor.register(worker_pool).await?;
             }
+            Task::Webhook(webhook_config) => {
+                let worker_pool = WorkerPool::new(
+                    WebhookWorker::new(webhook_config)?,
+                    task_config.name,
+                    task_config.concurrency,
+                );
+                executor.register(worker_pool).await?;
+            }
         };
     }
     let reader_options = ReaderOptions {
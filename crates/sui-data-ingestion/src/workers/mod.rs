@@ -4,6 +4,11 @@
 mod archival;
 mod blob;
 mod kv_store;
+mod webhook;
 pub use archival::{ArchivalConfig, ArchivalWorker};
 pub use blob::{BlobTaskConfig, BlobWorker};
 pub use kv_store::{KVStoreTaskConfig, KVStoreWorker};
+pub use webhook::{
+    WebhookFilter, WebhookNotification, WebhookPayload, WebhookRule, WebhookTaskConfig,
+    WebhookWorker, ID_HEADER, SIGNATURE_HEADER,
+};
@@ -0,0 +1,899 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use backoff::backoff::Backoff;
+use backoff::ExponentialBackoff;
+use fastcrypto::hmac::{hmac_sha3_256, HmacKey};
+use fastcrypto::traits::ToFromBytes;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_data_ingestion_core::Worker;
+use sui_json_rpc_types::{
+    EffectsWithInput, EventFilter, Filter, SuiEvent, SuiTransactionBlockEffects, TransactionFilter,
+};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::event::EventID;
+use sui_types::full_checkpoint_content::CheckpointData;
+use tracing::{info, warn};
+
+/// Header carrying the hex encoded HMAC-SHA3-256 of the request body, keyed by the rule's secret.
+pub const SIGNATURE_HEADER: &str = "X-Sui-Webhook-Signature";
+/// Header carrying the notification's id, which is the same across redeliveries.
+pub const ID_HEADER: &str = "X-Sui-Webhook-Id";
+
+const OUTBOX_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+/// Directory under a rule's outbox that notifications which could not be delivered are moved to.
+const DEAD_LETTER_DIR: &str = "dead-letter";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebhookTaskConfig {
+    /// Directory of the outbox, holding the notifications that have not been delivered yet.
+    pub outbox_path: PathBuf,
+    pub rules: Vec<WebhookRule>,
+    #[serde(default = "default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+    /// Number of times delivery of a notification is attempted before it is dead-lettered.
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+}
+
+/// Notifies `url` of every event or transaction matching `filter`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebhookRule {
+    /// Unique name of the rule, made of ASCII letters, digits, `-` and `_`.
+    pub name: String,
+    pub url: String,
+    /// Key that requests to `url` are signed with.
+    pub secret: String,
+    #[serde(flatten)]
+    pub filter: WebhookFilter,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFilter {
+    Event(EventFilter),
+    Transaction(TransactionFilter),
+}
+
+/// Body of a webhook request.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookNotification {
+    pub id: String,
+    pub rule: String,
+    pub checkpoint: u64,
+    pub timestamp_ms: u64,
+    #[serde(flatten)]
+    pub payload: WebhookPayload,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum WebhookPayload {
+    /// The matching event. Its `parsedJson` is always null, as event layouts are not resolved.
+    Event(SuiEvent),
+    /// The effects of the matching transaction.
+    Transaction(SuiTransactionBlockEffects),
+}
+
+/// Matches every checkpoint against the configured rules, and delivers a signed HTTP POST to the
+/// rule's URL for each match.
+///
+/// Matches are first written to a durable outbox on disk, one directory per rule, and a checkpoint
+/// is only reported as processed once all of its matches are in the outbox, so the progress store
+/// never moves past a notification that could be lost. Each rule's notifications are then
+/// delivered in the background, retrying with exponential backoff until the endpoint responds
+/// with a success status, after which they are removed from the outbox. Delivery is therefore
+/// at-least-once: a notification is redelivered if the worker restarts before removing it, or if
+/// its checkpoint is re-processed, and receivers should use the notification's id to deduplicate.
+/// Notifications of a rule are delivered one at a time, but not necessarily in checkpoint order.
+///
+/// A notification that is still not delivered after `max_attempts` attempts, or that the endpoint
+/// rejects with a status that retrying won't change (a 4xx other than 408 or 429), is moved to the
+/// rule's `dead-letter` directory in the outbox instead, so that it doesn't hold up the rest.
+pub struct WebhookWorker {
+    outbox: Arc<Outbox>,
+    rules: Vec<WebhookRule>,
+}
+
+impl WebhookWorker {
+    pub fn new(config: WebhookTaskConfig) -> Result<Self> {
+        validate_rules(&config.rules)?;
+        if config.max_attempts == 0 {
+            bail!("Webhook max_attempts must be at least 1");
+        }
+        let outbox = Arc::new(Outbox::new(config.outbox_path)?);
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.request_timeout_ms))
+            .build()?;
+
+        for rule in &config.rules {
+            tokio::spawn(deliver(
+                outbox.clone(),
+                client.clone(),
+                rule.clone(),
+                config.max_attempts,
+            ));
+        }
+        for name in outbox.rule_names()? {
+            if !config.rules.iter().any(|rule| rule.name == name) {
+                warn!("Outbox has notifications for unknown webhook rule {name}, skipping them");
+            }
+        }
+
+        Ok(Self {
+            outbox,
+            rules: config.rules,
+        })
+    }
+
+    fn notifications(&self, checkpoint: &CheckpointData) -> Result<Vec<WebhookNotification>> {
+        let summary = &checkpoint.checkpoint_summary;
+        let mut notifications = vec![];
+
+        let has_transaction_rules = self
+            .rules
+            .iter()
+            .any(|rule| matches!(rule.filter, WebhookFilter::Transaction(_)));
+
+        for transaction in &checkpoint.transactions {
+            let tx_digest = *transaction.effects.transaction_digest();
+            let effects = if has_transaction_rules {
+                Some(EffectsWithInput {
+                    effects: SuiTransactionBlockEffects::try_from(transaction.effects.clone())?,
+                    input: transaction.transaction.transaction_data().clone(),
+                })
+            } else {
+                None
+            };
+
+            for rule in &self.rules {
+                match &rule.filter {
+                    WebhookFilter::Event(filter) => {
+                        let events = transaction.events.iter().flat_map(|events| &events.data);
+                        for (event_seq, event) in events.enumerate() {
+                            let event = SuiEvent {
+                                id: EventID {
+                                    tx_digest,
+                                    event_seq: event_seq as u64,
+                                },
+                                package_id: event.package_id,
+                                transaction_module: event.transaction_module.clone(),
+                                sender: event.sender,
+                                type_: event.type_.clone(),
+                                parsed_json: serde_json::Value::Null,
+                                bcs: event.contents.clone(),
+                                timestamp_ms: Some(summary.timestamp_ms),
+                            };
+                            if filter.matches(&event) {
+                                notifications.push(WebhookNotification {
+                                    id: format!("{}:{tx_digest}:{event_seq}", rule.name),
+                                    rule: rule.name.clone(),
+                                    checkpoint: summary.sequence_number,
+                                    timestamp_ms: summary.timestamp_ms,
+                                    payload: WebhookPayload::Event(event),
+                                });
+                            }
+                        }
+                    }
+                    WebhookFilter::Transaction(filter) => {
+                        let Some(effects) = &effects else {
+                            continue;
+                        };
+                        if filter.matches(effects) {
+                            notifications.push(WebhookNotification {
+                                id: format!("{}:{tx_digest}", rule.name),
+                                rule: rule.name.clone(),
+                                checkpoint: summary.sequence_number,
+                                timestamp_ms: summary.timestamp_ms,
+                                payload: WebhookPayload::Transaction(effects.effects.clone()),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(notifications)
+    }
+}
+
+#[async_trait]
+impl Worker for WebhookWorker {
+    async fn process_checkpoint(&self, checkpoint: CheckpointData) -> Result<()> {
+        let notifications = self.notifications(&checkpoint)?;
+        if notifications.is_empty() {
+            return Ok(());
+        }
+        let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+        let outbox = self.outbox.clone();
+        tokio::task::spawn_blocking(move || outbox.put(sequence_number, &notifications)).await?
+    }
+}
+
+fn default_request_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_max_attempts() -> u32 {
+    10
+}
+
+fn validate_rules(rules: &[WebhookRule]) -> Result<()> {
+    let mut names = HashSet::new();
+    for rule in rules {
+        let valid_name = !rule.name.is_empty()
+            && rule
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name {
+            bail!("Invalid webhook rule name: {:?}", rule.name);
+        }
+        if !names.insert(rule.name.as_str()) {
+            bail!("Duplicate webhook rule name: {}", rule.name);
+        }
+        let supported = match &rule.filter {
+            WebhookFilter::Event(filter) => is_supported_event_filter(filter),
+            WebhookFilter::Transaction(filter) => !matches!(
+                filter,
+                TransactionFilter::Checkpoint(_) | TransactionFilter::FromOrToAddress { .. }
+            ),
+        };
+        if !supported {
+            bail!("Unsupported filter in webhook rule {}", rule.name);
+        }
+    }
+    Ok(())
+}
+
+/// Events are matched without their layouts, so filters on their fields can't be supported.
+fn is_supported_event_filter(filter: &EventFilter) -> bool {
+    match filter {
+        EventFilter::MoveEventField { .. } => false,
+        EventFilter::All(filters) | EventFilter::Any(filters) => {
+            filters.iter().all(is_supported_event_filter)
+        }
+        EventFilter::And(f1, f2) | EventFilter::Or(f1, f2) => {
+            is_supported_event_filter(f1) && is_supported_event_filter(f2)
+        }
+        _ => true,
+    }
+}
+
+/// Delivers the notifications of `rule` from the outbox, forever.
+async fn deliver(
+    outbox: Arc<Outbox>,
+    client: reqwest::Client,
+    rule: WebhookRule,
+    max_attempts: u32,
+) {
+    let key = HmacKey::from_bytes(rule.secret.as_bytes())
+        .expect("HMAC key can be of any length and from_bytes should always succeed");
+    let mut backoff = ExponentialBackoff {
+        max_interval: MAX_RETRY_INTERVAL,
+        max_elapsed_time: None,
+        ..Default::default()
+    };
+
+    loop {
+        let pending = match outbox.pending(&rule.name) {
+            Ok(pending) => pending,
+            Err(err) => {
+                warn!(
+                    "Failed to list outbox of webhook rule {}: {err:?}",
+                    rule.name
+                );
+                vec![]
+            }
+        };
+        if pending.is_empty() {
+            tokio::time::sleep(OUTBOX_POLL_INTERVAL).await;
+            continue;
+        }
+
+        for path in pending {
+            let mut attempts = 0;
+            let delivered = loop {
+                attempts += 1;
+                match post(&client, &rule.url, &key, &path).await {
+                    Ok(()) => break true,
+                    Err(DeliveryError::Permanent(err)) => {
+                        warn!(
+                            "Failed to deliver {} for rule {}, not retrying: {err:?}",
+                            path.display(),
+                            rule.name,
+                        );
+                        break false;
+                    }
+                    Err(DeliveryError::Transient(err)) if attempts >= max_attempts => {
+                        warn!(
+                            "Failed to deliver {} for rule {} after {attempts} attempts: {err:?}",
+                            path.display(),
+                            rule.name,
+                        );
+                        break false;
+                    }
+                    Err(DeliveryError::Transient(err)) => {
+                        let delay = backoff.next_backoff().unwrap_or(MAX_RETRY_INTERVAL);
+                        warn!(
+                            "Failed to deliver {} for rule {}, retrying in {delay:?}: {err:?}",
+                            path.display(),
+                            rule.name,
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+            };
+            backoff.reset();
+
+            if delivered {
+                if let Err(err) = fs::remove_file(&path) {
+                    warn!(
+                        "Failed to remove delivered webhook {}: {err:?}",
+                        path.display()
+                    );
+                }
+            } else if let Err(err) = outbox.dead_letter(&rule.name, &path) {
+                warn!("Failed to dead-letter webhook {}: {err:?}", path.display());
+            }
+        }
+    }
+}
+
+enum DeliveryError {
+    /// Delivery may succeed if it is attempted again.
+    Transient(anyhow::Error),
+    /// Delivery will fail the same way however many times it is attempted.
+    Permanent(anyhow::Error),
+}
+
+async fn post(
+    client: &reqwest::Client,
+    url: &str,
+    key: &HmacKey,
+    path: &Path,
+) -> Result<(), DeliveryError> {
+    let body = tokio::fs::read(path)
+        .await
+        .map_err(|e| DeliveryError::Transient(e.into()))?;
+    let notification: WebhookNotification =
+        serde_json::from_slice(&body).map_err(|e| DeliveryError::Permanent(e.into()))?;
+    let signature = hex::encode(hmac_sha3_256(key, &body).to_vec());
+
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(ID_HEADER, &notification.id)
+        .header(SIGNATURE_HEADER, signature)
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| DeliveryError::Transient(e.into()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        info!("Delivered webhook {}", notification.id);
+        Ok(())
+    } else if is_retryable(status) {
+        Err(DeliveryError::Transient(anyhow!(
+            "endpoint responded with {status}"
+        )))
+    } else {
+        Err(DeliveryError::Permanent(anyhow!(
+            "endpoint responded with {status}"
+        )))
+    }
+}
+
+/// Whether a request that the endpoint responded to with `status` is worth retrying: the endpoint
+/// may be unavailable or overloaded, as opposed to rejecting the request.
+fn is_retryable(status: reqwest::StatusCode) -> bool {
+    use reqwest::StatusCode;
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Notifications waiting to be delivered, stored as one JSON file each, under a directory per
+/// rule. Files are named after the checkpoint of the notification and its position among the
+/// rule's notifications in that checkpoint, so re-processing a checkpoint overwrites rather than
+/// duplicates its pending notifications.
+struct Outbox {
+    path: PathBuf,
+}
+
+impl Outbox {
+    fn new(path: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create outbox at {}", path.display()))?;
+        Ok(Self { path })
+    }
+
+    fn put(&self, checkpoint: u64, notifications: &[WebhookNotification]) -> Result<()> {
+        let mut dirs = HashSet::new();
+        let mut positions = HashMap::new();
+        for notification in notifications {
+            let dir = self.path.join(&notification.rule);
+            if dirs.insert(dir.clone()) {
+                fs::create_dir_all(&dir)?;
+            }
+            let position = positions.entry(&notification.rule).or_insert(0u64);
+            let name = format!("{checkpoint:020}-{position:06}");
+            *position += 1;
+
+            // Write to a temporary file first, so that a partially written notification is never
+            // picked up for delivery.
+            let tmp = dir.join(format!("{name}.tmp"));
+            let contents = serde_json::to_vec(notification)?;
+            let mut file = fs::File::create(&tmp)?;
+            file.write_all(&contents)?;
+            file.sync_all()?;
+            fs::rename(&tmp, dir.join(format!("{name}.json")))?;
+        }
+
+        // Persist the renames before the checkpoint is reported as processed.
+        for dir in dirs {
+            fs::File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// The notifications of `rule` waiting to be delivered, oldest checkpoint first.
+    fn pending(&self, rule: &str) -> Result<Vec<PathBuf>> {
+        let dir = self.path.join(rule);
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut pending = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                pending.push(path);
+            }
+        }
+        pending.sort();
+        Ok(pending)
+    }
+
+    /// Moves `path`, one of the pending notifications of `rule`, to the rule's dead-letter
+    /// directory, where it is no longer retried.
+    fn dead_letter(&self, rule: &str, path: &Path) -> Result<()> {
+        let dir = self.path.join(rule).join(DEAD_LETTER_DIR);
+        fs::create_dir_all(&dir)?;
+        let name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("Not a notification: {}", path.display()))?;
+        fs::rename(path, dir.join(name))?;
+        fs::File::open(dir)?.sync_all()?;
+        Ok(())
+    }
+
+    fn rule_names(&self) -> Result<Vec<String>> {
+        let mut names = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+        }
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::VecDeque;
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+    use sui_types::base_types::{random_object_ref, ObjectID, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair, KeypairTraits};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::effects::{TransactionEffects, TransactionEvents};
+    use sui_types::event::Event;
+    use sui_types::full_checkpoint_content::CheckpointTransaction;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, SignedCheckpointSummary,
+    };
+    use sui_types::transaction::TransactionData;
+    use sui_types::utils::{make_committee_key, to_sender_signed_transaction};
+    use sui_types::{parse_sui_struct_tag, Identifier};
+
+    const SECRET: &str = "secret";
+
+    fn rule(name: &str, url: &str, filter: WebhookFilter) -> WebhookRule {
+        WebhookRule {
+            name: name.to_string(),
+            url: url.to_string(),
+            secret: SECRET.to_string(),
+            filter,
+        }
+    }
+
+    fn notification(rule: &str, id: &str) -> WebhookNotification {
+        WebhookNotification {
+            id: id.to_string(),
+            rule: rule.to_string(),
+            checkpoint: 0,
+            timestamp_ms: 0,
+            payload: WebhookPayload::Event(SuiEvent {
+                id: EventID {
+                    tx_digest: TransactionDigest::random(),
+                    event_seq: 0,
+                },
+                package_id: ObjectID::ZERO,
+                transaction_module: Identifier::new("m").unwrap(),
+                sender: SuiAddress::ZERO,
+                type_: parse_sui_struct_tag("0x42::m::Foo").unwrap(),
+                parsed_json: serde_json::Value::Null,
+                bcs: vec![],
+                timestamp_ms: None,
+            }),
+        }
+    }
+
+    fn read_notification(path: &Path) -> WebhookNotification {
+        serde_json::from_slice(&fs::read(path).unwrap()).unwrap()
+    }
+
+    /// A checkpoint with a single transaction from `sender`, emitting an event of each of
+    /// `event_types`.
+    fn checkpoint(
+        sender: SuiAddress,
+        key: &AccountKeyPair,
+        event_types: &[&str],
+    ) -> CheckpointData {
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::ZERO,
+            sender,
+            None,
+            random_object_ref(),
+            1_000_000,
+            1_000,
+        );
+        let module = Identifier::new("m").unwrap();
+        let events = event_types
+            .iter()
+            .map(|t| {
+                let type_ = parse_sui_struct_tag(t).unwrap();
+                let package = type_.address;
+                Event::new(&package, &module, sender, type_, vec![])
+            })
+            .collect();
+
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (keys, committee) = make_committee_key(&mut rng);
+        let contents = CheckpointContents::new_with_digests_only_for_tests(vec![]);
+        let summary = CheckpointSummary::new(
+            0,
+            7,
+            0,
+            &contents,
+            None,
+            GasCostSummary::default(),
+            None,
+            1_000,
+        );
+        let sign_infos: Vec<_> = keys
+            .iter()
+            .map(|k| SignedCheckpointSummary::sign(committee.epoch, &summary, k, k.public().into()))
+            .collect();
+
+        CheckpointData {
+            checkpoint_summary: CertifiedCheckpointSummary::new(summary, sign_infos, &committee)
+                .unwrap(),
+            checkpoint_contents: contents,
+            transactions: vec![CheckpointTransaction {
+                transaction: to_sender_signed_transaction(data, key),
+                effects: TransactionEffects::default(),
+                events: Some(TransactionEvents { data: events }),
+                input_objects: vec![],
+                output_objects: vec![],
+            }],
+        }
+    }
+
+    /// Endpoint that records the requests it receives, and responds to them with the statuses in
+    /// `responses`, in order, followed by 200 OK.
+    #[derive(Clone, Default)]
+    struct Endpoint {
+        requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+        responses: Arc<Mutex<VecDeque<StatusCode>>>,
+    }
+
+    impl Endpoint {
+        fn serve(responses: impl IntoIterator<Item = StatusCode>) -> (Self, String) {
+            let endpoint = Endpoint::default();
+            endpoint.responses.lock().unwrap().extend(responses);
+
+            let app = Router::new()
+                .route("/", post(Self::handle))
+                .with_state(endpoint.clone());
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/", listener.local_addr().unwrap());
+            let server = axum::Server::from_tcp(listener)
+                .unwrap()
+                .serve(app.into_make_service());
+            tokio::spawn(server);
+
+            (endpoint, url)
+        }
+
+        async fn handle(
+            State(endpoint): State<Endpoint>,
+            headers: HeaderMap,
+            body: Bytes,
+        ) -> StatusCode {
+            endpoint.requests.lock().unwrap().push((headers, body));
+            let response = endpoint.responses.lock().unwrap().pop_front();
+            response.unwrap_or(StatusCode::OK)
+        }
+
+        fn requests(&self) -> Vec<(HeaderMap, Bytes)> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    /// Puts `ids` in the outbox of a rule delivering to `url`, and runs delivery until the outbox
+    /// is empty.
+    async fn deliver_all(url: &str, ids: &[&str], max_attempts: u32) -> (Arc<Outbox>, PathBuf) {
+        let dir = tempfile::tempdir().unwrap().into_path();
+        let outbox = Arc::new(Outbox::new(dir.clone()).unwrap());
+        let notifications: Vec<_> = ids.iter().map(|id| notification("r", id)).collect();
+        outbox.put(1, &notifications).unwrap();
+
+        let rule = rule("r", url, WebhookFilter::Event(EventFilter::All(vec![])));
+        let delivery = tokio::spawn(deliver(
+            outbox.clone(),
+            reqwest::Client::new(),
+            rule,
+            max_attempts,
+        ));
+        tokio::time::timeout(Duration::from_secs(30), async {
+            while !outbox.pending("r").unwrap().is_empty() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("Timed out waiting for delivery");
+        delivery.abort();
+
+        (outbox, dir)
+    }
+
+    fn dead_letters(dir: &Path) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(dir.join("r").join(DEAD_LETTER_DIR)) else {
+            return vec![];
+        };
+        let mut ids: Vec<_> = entries
+            .map(|entry| read_notification(&entry.unwrap().path()).id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn outbox_put_and_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::new(dir.path().to_owned()).unwrap();
+
+        outbox
+            .put(
+                5,
+                &[
+                    notification("a", "a1"),
+                    notification("b", "b1"),
+                    notification("a", "a2"),
+                ],
+            )
+            .unwrap();
+        outbox.put(3, &[notification("a", "a0")]).unwrap();
+
+        // Oldest checkpoint first, then in the order they were matched in.
+        let pending: Vec<_> = outbox
+            .pending("a")
+            .unwrap()
+            .iter()
+            .map(|p| read_notification(p).id)
+            .collect();
+        assert_eq!(pending, vec!["a0", "a1", "a2"]);
+        assert_eq!(outbox.pending("b").unwrap().len(), 1);
+        assert!(outbox.pending("c").unwrap().is_empty());
+
+        let mut names = outbox.rule_names().unwrap();
+        names.sort();
+        assert_eq!(names, vec!["a", "b"]);
+
+        // No temporary files are left behind.
+        for entry in fs::read_dir(dir.path().join("a")).unwrap() {
+            let path = entry.unwrap().path();
+            assert_eq!(path.extension().unwrap(), "json", "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn outbox_put_overwrites_reprocessed_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let outbox = Outbox::new(dir.path().to_owned()).unwrap();
+
+        outbox
+            .put(5, &[notification("a", "old1"), notification("a", "old2")])
+            .unwrap();
+        outbox
+            .put(5, &[notification("a", "new1"), notification("a", "new2")])
+            .unwrap();
+
+        let pending: Vec<_> = outbox
+            .pending("a")
+            .unwrap()
+            .iter()
+            .map(|p| read_notification(p).id)
+            .collect();
+        assert_eq!(pending, vec!["new1", "new2"]);
+    }
+
+    #[test]
+    fn validate_rules_rejects_unsupported_rules() {
+        let url = "http://localhost/";
+        let event = |f| rule("r", url, WebhookFilter::Event(f));
+        let tx = |f| rule("r", url, WebhookFilter::Transaction(f));
+
+        assert!(validate_rules(&[event(EventFilter::Sender(SuiAddress::ZERO))]).is_ok());
+        assert!(validate_rules(&[tx(TransactionFilter::FromAddress(SuiAddress::ZERO))]).is_ok());
+
+        let field = EventFilter::MoveEventField {
+            path: "/value".to_string(),
+            value: serde_json::Value::Null,
+        };
+        assert!(validate_rules(&[event(field.clone())]).is_err());
+        assert!(validate_rules(&[event(EventFilter::Any(vec![field]))]).is_err());
+        assert!(validate_rules(&[tx(TransactionFilter::Checkpoint(0))]).is_err());
+
+        let mut invalid = event(EventFilter::All(vec![]));
+        invalid.name = "../r".to_string();
+        assert!(validate_rules(&[invalid]).is_err());
+        assert!(validate_rules(&[
+            event(EventFilter::All(vec![])),
+            event(EventFilter::All(vec![]))
+        ])
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn notifications_match_rules() {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let checkpoint = checkpoint(sender, &key, &["0x42::m::Foo", "0x42::m::Bar"]);
+
+        let url = "http://localhost/";
+        let foo = parse_sui_struct_tag("0x42::m::Foo").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let worker = WebhookWorker::new(WebhookTaskConfig {
+            outbox_path: dir.path().to_owned(),
+            rules: vec![
+                rule(
+                    "foo",
+                    url,
+                    WebhookFilter::Event(EventFilter::MoveEventType(foo)),
+                ),
+                rule(
+                    "other-sender",
+                    url,
+                    WebhookFilter::Event(EventFilter::Sender(SuiAddress::ZERO)),
+                ),
+                rule(
+                    "from",
+                    url,
+                    WebhookFilter::Transaction(TransactionFilter::FromAddress(sender)),
+                ),
+                rule(
+                    "to",
+                    url,
+                    WebhookFilter::Transaction(TransactionFilter::ToAddress(sender)),
+                ),
+            ],
+            request_timeout_ms: default_request_timeout_ms(),
+            max_attempts: default_max_attempts(),
+        })
+        .unwrap();
+
+        let notifications = worker.notifications(&checkpoint).unwrap();
+        let tx_digest = *checkpoint.transactions[0].effects.transaction_digest();
+        let ids: Vec<_> = notifications.iter().map(|n| n.id.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![format!("foo:{tx_digest}:0"), format!("from:{tx_digest}")]
+        );
+
+        let WebhookPayload::Event(event) = &notifications[0].payload else {
+            panic!("Expected an event notification");
+        };
+        assert_eq!(event.type_.name.as_str(), "Foo");
+        assert_eq!(event.sender, sender);
+        assert_eq!(event.parsed_json, serde_json::Value::Null);
+        assert_eq!(notifications[0].checkpoint, 7);
+        assert_eq!(notifications[0].timestamp_ms, 1_000);
+        assert!(matches!(
+            notifications[1].payload,
+            WebhookPayload::Transaction(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn deliver_signs_requests() {
+        let (endpoint, url) = Endpoint::serve([]);
+        let (_, dir) = deliver_all(&url, &["n1", "n2"], 1).await;
+
+        let key = HmacKey::from_bytes(SECRET.as_bytes()).unwrap();
+        let requests = endpoint.requests();
+        assert_eq!(requests.len(), 2);
+        for ((headers, body), id) in requests.iter().zip(["n1", "n2"]) {
+            let notification: WebhookNotification = serde_json::from_slice(body).unwrap();
+            assert_eq!(notification.id, id);
+            assert_eq!(headers[ID_HEADER], id);
+            assert_eq!(
+                headers[SIGNATURE_HEADER],
+                hex::encode(hmac_sha3_256(&key, body).to_vec())
+            );
+        }
+        assert!(dead_letters(&dir).is_empty());
+    }
+
+    #[tokio::test]
+    async fn deliver_retries_transient_failures() {
+        let (endpoint, url) = Endpoint::serve([
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::TOO_MANY_REQUESTS,
+        ]);
+        let (_, dir) = deliver_all(&url, &["n1"], 3).await;
+
+        // The same notification is delivered each time.
+        let requests = endpoint.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|(_, body)| body == &requests[0].1));
+        assert!(dead_letters(&dir).is_empty());
+    }
+
+    #[tokio::test]
+    async fn deliver_dead_letters_after_max_attempts() {
+        let (endpoint, url) = Endpoint::serve([
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ]);
+        let (_, dir) = deliver_all(&url, &["n1", "n2"], 2).await;
+
+        // The first notification is given up on, and doesn't hold up the second.
+        assert_eq!(endpoint.requests().len(), 3);
+        assert_eq!(dead_letters(&dir), vec!["n1"]);
+    }
+
+    #[tokio::test]
+    async fn deliver_dead_letters_rejected_requests() {
+        let (endpoint, url) = Endpoint::serve([StatusCode::BAD_REQUEST]);
+        let (outbox, dir) = deliver_all(&url, &["n1", "n2"], 10).await;
+
+        // Rejected requests are not retried.
+        assert_eq!(endpoint.requests().len(), 2);
+        assert_eq!(dead_letters(&dir), vec!["n1"]);
+
+        // Dead letters are not delivered again.
+        assert!(outbox.pending("r").unwrap().is_empty());
+        assert_eq!(outbox.rule_names().unwrap(), vec!["r"]);
+    }
+}
@@ -10,7 +10,11 @@ use jsonrpsee::{
     rpc_params,
 };
 use std::fs::File;
-use sui_core::traffic_controller::{nodefw_test_server::NodeFwTestServer, TrafficController};
+use std::net::SocketAddr;
+use std::time::SystemTime;
+use sui_core::traffic_controller::{
+    nodefw_test_server::NodeFwTestServer, policies::TrafficTally, TrafficController,
+};
 use sui_json_rpc_types::{
     SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
@@ -18,7 +22,7 @@ use sui_swarm_config::network_config_builder::ConfigBuilder;
 use sui_test_transaction_builder::batch_make_transfer_transactions;
 use sui_types::{
     quorum_driver_types::ExecuteTransactionRequestType,
-    traffic_control::{PolicyConfig, PolicyType, RemoteFirewallConfig},
+    traffic_control::{PolicyConfig, PolicyType, RemoteFirewallConfig, ServiceResponse},
 };
 use test_cluster::{TestCluster, TestClusterBuilder};
 
@@ -212,6 +216,57 @@ async fn test_traffic_control_dead_mans_switch() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_traffic_control_policy_update() -> Result<(), anyhow::Error> {
+    let policy_config = PolicyConfig {
+        connection_blocklist_ttl_sec: 5,
+        channel_capacity: 100,
+        ..Default::default()
+    };
+    let tc = TrafficController::spawn_for_test(policy_config.clone(), None);
+    let ip: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    let tally = || TrafficTally {
+        connection_ip: Some(ip.ip()),
+        proxy_ip: None,
+        result: ServiceResponse::Validator(Ok(())),
+        timestamp: SystemTime::now(),
+    };
+
+    // The initial policy never blocks.
+    for _ in 0..10 {
+        tc.tally(tally());
+    }
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    assert!(tc.check(Some(ip), None).await, "Expected IP to be allowed");
+
+    // The tally channel is created with the controller, so its capacity cannot change.
+    assert!(tc
+        .update_policy_config(PolicyConfig {
+            channel_capacity: 200,
+            ..policy_config.clone()
+        })
+        .is_err());
+
+    tc.update_policy_config(PolicyConfig {
+        spam_policy_type: PolicyType::TestNConnIP(3),
+        ..policy_config
+    })?;
+    assert!(matches!(
+        tc.policy_config().spam_policy_type,
+        PolicyType::TestNConnIP(3)
+    ));
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    for _ in 0..3 {
+        tc.tally(tally());
+    }
+    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    assert!(
+        !tc.check(Some(ip), None).await,
+        "Expected updated policy to block IP"
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_traffic_control_manual_set_dead_mans_switch() -> Result<(), anyhow::Error> {
     let drain_path = tempfile::tempdir().unwrap().into_path().join("drain");
@@ -0,0 +1,15 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// The schema exported by `sui-graphql-rpc`, which typed queries are checked against.
+const SCHEMA: &str = "../sui-graphql-rpc/schema/current_progress_schema.graphql";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SCHEMA}");
+
+    cynic_codegen::register_schema("sui")
+        .from_sdl_file(SCHEMA)
+        .expect("Failed to find GraphQL schema")
+        .as_default()
+        .expect("Failed to register GraphQL schema");
+}
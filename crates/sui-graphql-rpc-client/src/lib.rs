@@ -5,9 +5,17 @@ use async_graphql::Value;
 use hyper::header::ToStrError;
 use serde_json::Number;
 
+pub mod pagination;
+pub mod queries;
 pub mod response;
+pub mod scalars;
 pub mod simple_client;
 
+/// Schema types generated from `sui-graphql-rpc`'s exported schema, which queries are checked
+/// against at compile time (see `build.rs`).
+#[cynic::schema("sui")]
+pub mod schema {}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ClientError {
     #[error("Service version header not found")]
@@ -45,6 +53,10 @@ pub enum ClientError {
         var_val_prev: serde_json::Value,
         var_val_curr: serde_json::Value,
     },
+    #[error("GraphQL request failed: {errors:?}")]
+    GraphqlErrors { errors: Vec<cynic::GraphQlError> },
+    #[error("GraphQL response contained no data")]
+    MissingData,
     #[error(transparent)]
     InnerClientError(#[from] reqwest::Error),
 }
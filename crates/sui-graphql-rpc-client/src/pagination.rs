@@ -0,0 +1,170 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema;
+use crate::ClientError;
+use cynic::{QueryBuilder, QueryVariables};
+use futures::{stream, Future, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Information the service returns alongside each page of a connection, to fetch further pages.
+#[derive(cynic::QueryFragment, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageInfo {
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+/// One page of results from a paginated query.
+#[derive(Clone, Debug)]
+pub struct Page<T> {
+    pub page_info: PageInfo,
+    pub nodes: Vec<T>,
+}
+
+/// A query whose response is a page of a connection, which can be paginated forwards by re-running
+/// it with its `after` cursor set to the previous page's `endCursor`.
+pub trait PaginatedQuery: QueryBuilder<Self::Variables> + DeserializeOwned + 'static {
+    type Variables: QueryVariables + Serialize + Clone;
+    type Node;
+
+    /// Set the cursor that the query fetches the page after.
+    fn set_after(vars: &mut Self::Variables, cursor: Option<String>);
+
+    /// The page of nodes in this response.
+    fn into_page(self) -> Page<Self::Node>;
+}
+
+impl<T> Page<T> {
+    /// An empty last page, for queries whose connection's parent was not found.
+    pub fn empty() -> Self {
+        Self {
+            page_info: PageInfo::default(),
+            nodes: vec![],
+        }
+    }
+
+    /// The cursor to fetch the next page after, or `None` if this is the last page.
+    pub fn next_cursor(&self) -> Option<&str> {
+        if self.page_info.has_next_page {
+            self.page_info.end_cursor.as_deref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Stream the nodes of every page of query `Q`, starting from the page selected by `vars`, and
+/// using `fetch` to run the query for each page. Stops after the last page, or the first error.
+pub(crate) fn paginate<Q, F, Fut>(
+    vars: Q::Variables,
+    mut fetch: F,
+) -> impl Stream<Item = Result<Q::Node, ClientError>>
+where
+    Q: PaginatedQuery,
+    F: FnMut(Q::Variables) -> Fut,
+    Fut: Future<Output = Result<Q, ClientError>>,
+{
+    stream::try_unfold(Some(vars), move |vars| {
+        let response = vars.clone().map(&mut fetch);
+        async move {
+            let (Some(mut vars), Some(response)) = (vars, response) else {
+                return Ok(None);
+            };
+
+            let page = response.await?.into_page();
+            let next = page.next_cursor().map(|cursor| {
+                Q::set_after(&mut vars, Some(cursor.to_string()));
+                vars
+            });
+
+            Ok(Some((
+                stream::iter(page.nodes.into_iter().map(Ok::<_, ClientError>)),
+                next,
+            )))
+        }
+    })
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queries::{Checkpoint, CheckpointConnection, CheckpointsQuery, PageArgs};
+    use crate::scalars::DateTime;
+    use futures::StreamExt;
+    use std::sync::{Arc, Mutex};
+
+    fn checkpoint(sequence_number: u64) -> Checkpoint {
+        Checkpoint {
+            digest: format!("digest{sequence_number}"),
+            sequence_number,
+            timestamp: DateTime("1970-01-01T00:00:00Z".to_string()),
+            previous_checkpoint_digest: None,
+            network_total_transactions: None,
+            epoch: None,
+        }
+    }
+
+    /// Serves pages of two checkpoints, out of five, using sequence numbers as cursors.
+    async fn fetch(vars: PageArgs) -> Result<CheckpointsQuery, ClientError> {
+        let start = vars.after.map_or(0, |c| c.parse::<u64>().unwrap() + 1);
+        let end = (start + vars.first.unwrap_or(2)).min(5);
+
+        Ok(CheckpointsQuery {
+            checkpoints: CheckpointConnection {
+                page_info: PageInfo {
+                    has_previous_page: start > 0,
+                    has_next_page: end < 5,
+                    start_cursor: Some(start.to_string()),
+                    end_cursor: Some((end - 1).to_string()),
+                },
+                nodes: (start..end).map(checkpoint).collect(),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_paginate_follows_cursors() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let seen = requests.clone();
+
+        let nodes: Vec<_> = paginate::<CheckpointsQuery, _, _>(PageArgs::default(), |vars| {
+            seen.lock().unwrap().push(vars.after.clone());
+            fetch(vars)
+        })
+        .map(|c| c.unwrap().sequence_number)
+        .collect()
+        .await;
+
+        assert_eq!(nodes, vec![0, 1, 2, 3, 4]);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![None, Some("1".to_string()), Some("3".to_string())],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paginate_stops_on_error() {
+        let mut calls = 0;
+        let nodes: Vec<_> = paginate::<CheckpointsQuery, _, _>(PageArgs::default(), |vars| {
+            calls += 1;
+            let fail = calls > 1;
+            async move {
+                if fail {
+                    Err(ClientError::MissingData)
+                } else {
+                    fetch(vars).await
+                }
+            }
+        })
+        .collect()
+        .await;
+
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes[..2].iter().all(|n| n.is_ok()));
+        assert!(matches!(nodes[2], Err(ClientError::MissingData)));
+    }
+}
@@ -0,0 +1,403 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed queries against the Sui GraphQL service. Each query is a fragment on the schema's `Query`
+//! type, with its variables in a separate struct, and is checked against the service's schema at
+//! compile time. Queries can be run with `SimpleClient::query`, and queries over connections can
+//! be paginated with `SimpleClient::paginate`.
+
+use crate::pagination::{Page, PageInfo, PaginatedQuery};
+use crate::scalars::{Base64, BigInt, DateTime, Json, SuiAddress};
+use crate::schema;
+
+// Variables
+
+/// Variables for queries that fetch a page of a connection.
+#[derive(cynic::QueryVariables, Clone, Debug, Default)]
+pub struct PageArgs {
+    pub first: Option<u64>,
+    pub after: Option<String>,
+}
+
+#[derive(cynic::QueryVariables, Clone, Debug, Default)]
+pub struct CheckpointArgs {
+    /// Defaults to the latest checkpoint.
+    pub id: Option<CheckpointId>,
+}
+
+#[derive(cynic::QueryVariables, Clone, Debug)]
+pub struct TransactionBlockArgs {
+    pub digest: String,
+}
+
+#[derive(cynic::QueryVariables, Clone, Debug, Default)]
+pub struct TransactionBlocksArgs {
+    pub first: Option<u64>,
+    pub after: Option<String>,
+    pub filter: Option<TransactionBlockFilter>,
+}
+
+#[derive(cynic::QueryVariables, Clone, Debug)]
+pub struct ObjectArgs {
+    pub address: SuiAddress,
+    /// Defaults to the latest version.
+    pub version: Option<u64>,
+}
+
+#[derive(cynic::QueryVariables, Clone, Debug)]
+pub struct OwnedObjectsArgs {
+    pub owner: SuiAddress,
+    pub first: Option<u64>,
+    pub after: Option<String>,
+    pub filter: Option<ObjectFilter>,
+}
+
+#[derive(cynic::QueryVariables, Clone, Debug)]
+pub struct BalanceArgs {
+    pub owner: SuiAddress,
+    /// Defaults to `0x2::sui::SUI`.
+    pub coin_type: Option<String>,
+}
+
+// Inputs
+
+#[derive(cynic::InputObject, Clone, Debug, Default)]
+pub struct CheckpointId {
+    pub digest: Option<String>,
+    pub sequence_number: Option<u64>,
+}
+
+#[derive(cynic::InputObject, Clone, Debug, Default)]
+pub struct TransactionBlockFilter {
+    pub function: Option<String>,
+    pub kind: Option<TransactionBlockKindInput>,
+    pub after_checkpoint: Option<u64>,
+    pub at_checkpoint: Option<u64>,
+    pub before_checkpoint: Option<u64>,
+    pub sign_address: Option<SuiAddress>,
+    pub recv_address: Option<SuiAddress>,
+    pub input_object: Option<SuiAddress>,
+    pub changed_object: Option<SuiAddress>,
+    pub transaction_ids: Option<Vec<String>>,
+}
+
+#[derive(cynic::InputObject, Clone, Debug, Default)]
+pub struct ObjectFilter {
+    #[cynic(rename = "type")]
+    pub type_: Option<String>,
+    pub owner: Option<SuiAddress>,
+    pub object_ids: Option<Vec<SuiAddress>>,
+    pub object_keys: Option<Vec<ObjectKey>>,
+    /// Only fields that the indexer projects can be filtered on.
+    pub fields: Option<Vec<ObjectFieldFilter>>,
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+pub struct ObjectKey {
+    pub object_id: SuiAddress,
+    pub version: u64,
+}
+
+#[derive(cynic::InputObject, Clone, Debug)]
+pub struct ObjectFieldFilter {
+    pub path: String,
+    pub op: FieldComparison,
+    pub value: String,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldComparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionBlockKindInput {
+    SystemTx,
+    ProgrammableTx,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    Success,
+    Failure,
+}
+
+// Queries
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query")]
+pub struct ChainIdentifierQuery {
+    pub chain_identifier: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "CheckpointArgs")]
+pub struct CheckpointQuery {
+    #[arguments(id: $id)]
+    pub checkpoint: Option<Checkpoint>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "PageArgs")]
+pub struct CheckpointsQuery {
+    #[arguments(first: $first, after: $after)]
+    pub checkpoints: CheckpointConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "TransactionBlockArgs")]
+pub struct TransactionBlockQuery {
+    #[arguments(digest: $digest)]
+    pub transaction_block: Option<TransactionBlock>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "TransactionBlocksArgs")]
+pub struct TransactionBlocksQuery {
+    #[arguments(first: $first, after: $after, filter: $filter)]
+    pub transaction_blocks: TransactionBlockConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "ObjectArgs")]
+pub struct ObjectQuery {
+    #[arguments(address: $address, version: $version)]
+    pub object: Option<Object>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "OwnedObjectsArgs")]
+pub struct OwnedObjectsQuery {
+    #[arguments(address: $owner)]
+    pub address: Option<OwnedObjects>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "BalanceArgs")]
+pub struct BalanceQuery {
+    #[arguments(address: $owner)]
+    pub address: Option<AddressBalance>,
+}
+
+// Fragments
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct Checkpoint {
+    pub digest: String,
+    pub sequence_number: u64,
+    pub timestamp: DateTime,
+    pub previous_checkpoint_digest: Option<String>,
+    pub network_total_transactions: Option<u64>,
+    pub epoch: Option<EpochId>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+pub struct CheckpointConnection {
+    pub page_info: PageInfo,
+    pub nodes: Vec<Checkpoint>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(graphql_type = "Epoch")]
+pub struct EpochId {
+    pub epoch_id: u64,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+#[cynic(graphql_type = "Checkpoint")]
+pub struct CheckpointSequenceNumber {
+    pub sequence_number: u64,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct TransactionBlock {
+    pub digest: Option<String>,
+    pub sender: Option<Address>,
+    pub effects: Option<TransactionBlockEffects>,
+    /// BCS-encoded `SenderSignedData`.
+    pub bcs: Option<Base64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+pub struct TransactionBlockConnection {
+    pub page_info: PageInfo,
+    pub nodes: Vec<TransactionBlock>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct TransactionBlockEffects {
+    pub status: Option<ExecutionStatus>,
+    pub lamport_version: u64,
+    pub errors: Option<String>,
+    pub timestamp: Option<DateTime>,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    /// BCS-encoded `TransactionEffects`.
+    pub bcs: Base64,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct Address {
+    pub address: SuiAddress,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct Object {
+    pub address: SuiAddress,
+    pub version: u64,
+    pub digest: Option<String>,
+    pub storage_rebate: Option<BigInt>,
+    /// BCS-encoded `Object`.
+    pub bcs: Option<Base64>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Address", variables = "OwnedObjectsArgs")]
+pub struct OwnedObjects {
+    #[arguments(first: $first, after: $after, filter: $filter)]
+    pub objects: MoveObjectConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+pub struct MoveObjectConnection {
+    pub page_info: PageInfo,
+    pub nodes: Vec<MoveObject>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct MoveObject {
+    pub address: SuiAddress,
+    pub version: u64,
+    pub digest: Option<String>,
+    pub contents: Option<MoveValue>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct MoveValue {
+    #[cynic(rename = "type")]
+    pub type_: MoveType,
+    pub bcs: Base64,
+    pub json: Json,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct MoveType {
+    pub repr: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Address", variables = "BalanceArgs")]
+pub struct AddressBalance {
+    #[arguments(type: $coin_type)]
+    pub balance: Option<Balance>,
+}
+
+#[derive(cynic::QueryFragment, Clone, Debug)]
+pub struct Balance {
+    pub coin_type: MoveType,
+    pub coin_object_count: Option<u64>,
+    pub total_balance: Option<BigInt>,
+}
+
+// Pagination
+
+impl PaginatedQuery for CheckpointsQuery {
+    type Variables = PageArgs;
+    type Node = Checkpoint;
+
+    fn set_after(vars: &mut PageArgs, cursor: Option<String>) {
+        vars.after = cursor;
+    }
+
+    fn into_page(self) -> Page<Checkpoint> {
+        let CheckpointConnection { page_info, nodes } = self.checkpoints;
+        Page { page_info, nodes }
+    }
+}
+
+impl PaginatedQuery for TransactionBlocksQuery {
+    type Variables = TransactionBlocksArgs;
+    type Node = TransactionBlock;
+
+    fn set_after(vars: &mut TransactionBlocksArgs, cursor: Option<String>) {
+        vars.after = cursor;
+    }
+
+    fn into_page(self) -> Page<TransactionBlock> {
+        let TransactionBlockConnection { page_info, nodes } = self.transaction_blocks;
+        Page { page_info, nodes }
+    }
+}
+
+impl PaginatedQuery for OwnedObjectsQuery {
+    type Variables = OwnedObjectsArgs;
+    type Node = MoveObject;
+
+    fn set_after(vars: &mut OwnedObjectsArgs, cursor: Option<String>) {
+        vars.after = cursor;
+    }
+
+    fn into_page(self) -> Page<MoveObject> {
+        let Some(owned) = self.address else {
+            return Page::empty();
+        };
+
+        let MoveObjectConnection { page_info, nodes } = owned.objects;
+        Page { page_info, nodes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cynic::QueryBuilder;
+
+    #[test]
+    fn test_operation_variables() {
+        let operation = TransactionBlocksQuery::build(TransactionBlocksArgs {
+            first: Some(10),
+            after: Some("cursor".to_string()),
+            filter: Some(TransactionBlockFilter {
+                kind: Some(TransactionBlockKindInput::ProgrammableTx),
+                sign_address: Some("0x1".into()),
+                ..Default::default()
+            }),
+        });
+
+        assert!(operation.query.contains("transactionBlocks("));
+        assert!(operation.query.contains("pageInfo"));
+
+        let body = serde_json::to_value(&operation).unwrap();
+        let variables = &body["variables"];
+        assert_eq!(variables["first"], 10);
+        assert_eq!(variables["after"], "cursor");
+        assert_eq!(variables["filter"]["kind"], "PROGRAMMABLE_TX");
+        assert_eq!(variables["filter"]["signAddress"], "0x1");
+    }
+
+    #[test]
+    fn test_deserialize_response() {
+        let response: cynic::GraphQlResponse<BalanceQuery> =
+            serde_json::from_value(serde_json::json!({
+                "data": {
+                    "address": {
+                        "balance": {
+                            "coinType": { "repr": "0x2::sui::SUI" },
+                            "coinObjectCount": 2,
+                            "totalBalance": "1000000000000"
+                        }
+                    }
+                }
+            }))
+            .unwrap();
+
+        let balance = response.data.unwrap().address.unwrap().balance.unwrap();
+        assert_eq!(balance.coin_type.repr, "0x2::sui::SUI");
+        assert_eq!(balance.coin_object_count, Some(2));
+        assert_eq!(balance.total_balance, Some(BigInt("1000000000000".into())));
+    }
+}
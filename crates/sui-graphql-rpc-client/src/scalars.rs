@@ -0,0 +1,53 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rust representations of the custom scalars in the GraphQL schema. Scalars are kept in the
+//! format the service sends them in, so that clients can choose how to parse them.
+
+use crate::schema;
+
+// `Int`s in the schema hold sequence numbers, versions and counts, which are all unsigned.
+cynic::impl_scalar!(u64, schema::Int);
+
+/// A 32-byte Sui address, as a `0x`-prefixed hex string.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SuiAddress(pub String);
+
+/// An arbitrary precision integer, as a decimal string.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq, Eq)]
+pub struct BigInt(pub String);
+
+/// Base64-encoded bytes.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq, Eq)]
+pub struct Base64(pub String);
+
+/// An ISO-8601 timestamp.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq, Eq)]
+pub struct DateTime(pub String);
+
+/// An arbitrary JSON value.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq)]
+#[cynic(graphql_type = "JSON")]
+pub struct Json(pub serde_json::Value);
+
+/// The structured representation of a Move value.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq)]
+pub struct MoveData(pub serde_json::Value);
+
+/// The structured representation of the layout of a Move type.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq)]
+pub struct MoveTypeLayout(pub serde_json::Value);
+
+/// The structured representation of a concrete Move type signature.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq)]
+pub struct MoveTypeSignature(pub serde_json::Value);
+
+/// The structured representation of a Move type signature that may refer to type parameters.
+#[derive(cynic::Scalar, Clone, Debug, PartialEq)]
+pub struct OpenMoveTypeSignature(pub serde_json::Value);
+
+impl From<&str> for SuiAddress {
+    fn from(address: &str) -> Self {
+        SuiAddress(address.to_string())
+    }
+}
@@ -1,10 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::pagination::{self, PaginatedQuery};
 use crate::ClientError;
 use axum::http::HeaderValue;
+use cynic::{GraphQlResponse, Operation, QueryBuilder};
+use futures::Stream;
 use hyper::header;
 use reqwest::Response;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::Value;
 use std::collections::BTreeMap;
 use sui_graphql_rpc_headers::LIMITS_HEADER;
@@ -104,6 +109,48 @@ impl SimpleClient {
             .await
     }
 
+    /// Run typed query `Q` with variables `vars`, returning its response data, or an error if the
+    /// service responded with any errors.
+    pub async fn query<Q, V>(&self, vars: V) -> Result<Q, ClientError>
+    where
+        Q: QueryBuilder<V> + DeserializeOwned + 'static,
+        V: Serialize,
+    {
+        self.execute_operation(Q::build(vars), vec![]).await
+    }
+
+    /// Run a typed GraphQL operation, with additional `headers`.
+    pub async fn execute_operation<Q, V>(
+        &self,
+        operation: Operation<Q, V>,
+        headers: Vec<(header::HeaderName, header::HeaderValue)>,
+    ) -> Result<Q, ClientError>
+    where
+        Q: DeserializeOwned + 'static,
+        V: Serialize,
+    {
+        let mut builder = self.inner.post(&self.url).json(&operation);
+        for (key, value) in headers {
+            builder = builder.header(key, value);
+        }
+
+        let response: GraphQlResponse<Q> = builder.send().await?.json().await?;
+        if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+            return Err(ClientError::GraphqlErrors { errors });
+        }
+
+        response.data.ok_or(ClientError::MissingData)
+    }
+
+    /// Stream the nodes of every page of paginated query `Q`, starting from the page selected by
+    /// `vars`, and following each page's `endCursor` until the last page.
+    pub fn paginate<Q: PaginatedQuery>(
+        &self,
+        vars: Q::Variables,
+    ) -> impl Stream<Item = Result<Q::Node, ClientError>> + '_ {
+        pagination::paginate::<Q, _, _>(vars, move |vars| self.query::<Q, _>(vars))
+    }
+
     /// Send a request to the GraphQL server to check if it is alive.
     pub async fn ping(&self) -> Result<(), ClientError> {
         self.inner
@@ -6,7 +6,11 @@ use crate::types::big_int::BigInt;
 use async_graphql::*;
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    time::Duration,
+};
 use sui_json_rpc::name_service::NameServiceConfig;
 
 // TODO: calculate proper cost limits
@@ -31,6 +35,12 @@ pub(crate) const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 40_000;
 
 const DEFAULT_IDE_TITLE: &str = "Sui GraphQL IDE";
 
+const DEFAULT_MAX_AUTOMATIC_PERSISTED_QUERIES: usize = 10_000;
+const DEFAULT_QUOTA_WINDOW_MS: u64 = 60_000;
+
+const DEFAULT_CACHE_LRU_CAPACITY: usize = 100_000;
+const DEFAULT_CACHE_REDIS_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+
 pub(crate) const RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD: Duration = Duration::from_millis(10_000);
 pub(crate) const MAX_CONCURRENT_REQUESTS: usize = 1_000;
 
@@ -97,6 +107,15 @@ pub struct ServiceConfig {
 
     #[serde(default)]
     pub(crate) zklogin: ZkLoginConfig,
+
+    #[serde(default)]
+    pub(crate) persisted_queries: PersistedQueriesConfig,
+
+    #[serde(default)]
+    pub(crate) api_keys: ApiKeysConfig,
+
+    #[serde(default)]
+    pub(crate) cache: CacheConfig,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Copy)]
@@ -135,6 +154,120 @@ pub struct BackgroundTasksConfig {
     pub watermark_update_ms: u64,
 }
 
+/// Configuration for persisted queries: query documents that clients can refer to by the SHA-256
+/// hash of their text, following the Apollo Automatic Persisted Queries protocol.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PersistedQueriesConfig {
+    /// Whether clients can persist a query by sending its text along with its hash, so that later
+    /// requests only need to send the hash.
+    #[serde(default)]
+    pub(crate) automatic: bool,
+    /// Maximum number of automatically persisted queries remembered by each instance of the
+    /// service. The least recently used queries are forgotten first.
+    #[serde(default = "default_max_automatic_persisted_queries")]
+    pub(crate) max_automatic: usize,
+    /// Whether to reject any query that is not in `registered`. Clients can still send the text
+    /// of a registered query, rather than its hash.
+    #[serde(default)]
+    pub(crate) allow_list_only: bool,
+    /// Queries registered by the operator, by name. The name is only used for the operator's
+    /// benefit: clients refer to registered queries by their hash.
+    #[serde(default)]
+    pub(crate) registered: BTreeMap<String, String>,
+}
+
+/// Configuration for identifying callers by the API key in their requests, and applying limits
+/// and request quotas to them according to the profile their key is assigned to.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApiKeysConfig {
+    /// Whether requests without an API key are rejected. Requests with an API key that is not in
+    /// `keys` are always rejected.
+    #[serde(default)]
+    pub(crate) require_key: bool,
+    /// Length of the window that request quotas apply to, in milliseconds.
+    #[serde(default = "default_quota_window_ms")]
+    pub(crate) quota_window_ms: u64,
+    /// Where the number of requests made with each key in the current window is tracked.
+    #[serde(default)]
+    pub(crate) quota_store: QuotaStoreConfig,
+    /// Limit profiles, by name.
+    #[serde(default)]
+    pub(crate) profiles: BTreeMap<String, LimitProfile>,
+    /// The name of the profile each API key is assigned to, by API key.
+    #[serde(default)]
+    pub(crate) keys: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuotaStoreConfig {
+    /// Each instance of the service counts the requests it serves separately.
+    #[default]
+    Memory,
+    /// Requests are counted in the service's database, so that the quota is shared by all
+    /// instances of the service that are connected to it. The database must have been set up by
+    /// the indexer's migrations.
+    Postgres,
+}
+
+/// Limits for requests made with an API key assigned to this profile. Limits that are not set are
+/// inherited from the service-wide `Limits`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct LimitProfile {
+    #[serde(default)]
+    pub max_query_depth: Option<u32>,
+    #[serde(default)]
+    pub max_query_nodes: Option<u32>,
+    #[serde(default)]
+    pub max_output_nodes: Option<u64>,
+    #[serde(default)]
+    pub max_query_payload_size: Option<u32>,
+    #[serde(default)]
+    pub max_db_query_cost: Option<u64>,
+    #[serde(default)]
+    pub request_timeout_ms: Option<u64>,
+    /// Maximum number of requests each API key assigned to this profile can make per quota
+    /// window. Unlimited if not set.
+    #[serde(default)]
+    pub max_requests: Option<u64>,
+}
+
+/// Configuration for caching data that cannot change once it has been checkpointed (checkpoints,
+/// transaction blocks, and objects at a fixed version), to serve repeated reads without going to
+/// the database.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CacheConfig {
+    #[serde(default)]
+    pub(crate) backend: CacheBackendConfig,
+    /// Maximum number of entries held by the `lru` backend.
+    #[serde(default = "default_cache_lru_capacity")]
+    pub(crate) lru_capacity: usize,
+    /// URL of the Redis instance used by the `redis` backend.
+    #[serde(default)]
+    pub(crate) redis_url: Option<String>,
+    /// How long entries are kept for by the `redis` backend, in milliseconds.
+    #[serde(default = "default_cache_redis_ttl_ms")]
+    pub(crate) redis_ttl_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CacheBackendConfig {
+    /// Nothing is cached.
+    #[default]
+    None,
+    /// Entries are cached in memory by each instance of the service, evicting the least recently
+    /// used entries first.
+    Lru,
+    /// Entries are cached in Redis, shared by all instances of the service that are connected to
+    /// it.
+    Redis,
+}
+
 /// The Version of the service. `year.month` represents the major release.
 /// New `patch` versions represent backwards compatible fixes for their major release.
 /// The `full` version is `year.month.patch-sha`.
@@ -410,6 +543,23 @@ impl Limits {
     }
 }
 
+impl LimitProfile {
+    /// The limits for requests under this profile, given the service-wide `limits`.
+    pub fn limits(&self, limits: &Limits) -> Limits {
+        Limits {
+            max_query_depth: self.max_query_depth.unwrap_or(limits.max_query_depth),
+            max_query_nodes: self.max_query_nodes.unwrap_or(limits.max_query_nodes),
+            max_output_nodes: self.max_output_nodes.unwrap_or(limits.max_output_nodes),
+            max_query_payload_size: self
+                .max_query_payload_size
+                .unwrap_or(limits.max_query_payload_size),
+            max_db_query_cost: self.max_db_query_cost.unwrap_or(limits.max_db_query_cost),
+            request_timeout_ms: self.request_timeout_ms.unwrap_or(limits.request_timeout_ms),
+            ..*limits
+        }
+    }
+}
+
 impl Ide {
     pub fn new(ide_title: Option<String>) -> Self {
         Self {
@@ -481,6 +631,56 @@ impl Default for InternalFeatureConfig {
     }
 }
 
+impl Default for PersistedQueriesConfig {
+    fn default() -> Self {
+        Self {
+            automatic: false,
+            max_automatic: DEFAULT_MAX_AUTOMATIC_PERSISTED_QUERIES,
+            allow_list_only: false,
+            registered: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for ApiKeysConfig {
+    fn default() -> Self {
+        Self {
+            require_key: false,
+            quota_window_ms: DEFAULT_QUOTA_WINDOW_MS,
+            quota_store: QuotaStoreConfig::Memory,
+            profiles: BTreeMap::new(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            backend: CacheBackendConfig::None,
+            lru_capacity: DEFAULT_CACHE_LRU_CAPACITY,
+            redis_url: None,
+            redis_ttl_ms: DEFAULT_CACHE_REDIS_TTL_MS,
+        }
+    }
+}
+
+fn default_max_automatic_persisted_queries() -> usize {
+    DEFAULT_MAX_AUTOMATIC_PERSISTED_QUERIES
+}
+
+fn default_quota_window_ms() -> u64 {
+    DEFAULT_QUOTA_WINDOW_MS
+}
+
+fn default_cache_lru_capacity() -> usize {
+    DEFAULT_CACHE_LRU_CAPACITY
+}
+
+fn default_cache_redis_ttl_ms() -> u64 {
+    DEFAULT_CACHE_REDIS_TTL_MS
+}
+
 impl Default for BackgroundTasksConfig {
     fn default() -> Self {
         Self {
@@ -578,6 +778,129 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_persisted_queries_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [persisted-queries]
+                automatic = true
+                allow-list-only = true
+
+                [persisted-queries.registered]
+                chain-id = "{ chainIdentifier }"
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            persisted_queries: PersistedQueriesConfig {
+                automatic: true,
+                allow_list_only: true,
+                registered: BTreeMap::from([(
+                    "chain-id".to_string(),
+                    "{ chainIdentifier }".to_string(),
+                )]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_api_keys_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [api-keys]
+                require-key = true
+                quota-window-ms = 1000
+                quota-store = "postgres"
+
+                [api-keys.profiles.heavy]
+                max-query-nodes = 1000
+                max-db-query-cost = 50000
+
+                [api-keys.profiles.light]
+                max-requests = 10
+
+                [api-keys.keys]
+                indexer-team = "heavy"
+                wallet-team = "light"
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            api_keys: ApiKeysConfig {
+                require_key: true,
+                quota_window_ms: 1000,
+                quota_store: QuotaStoreConfig::Postgres,
+                profiles: BTreeMap::from([
+                    (
+                        "heavy".to_string(),
+                        LimitProfile {
+                            max_query_nodes: Some(1000),
+                            max_db_query_cost: Some(50000),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "light".to_string(),
+                        LimitProfile {
+                            max_requests: Some(10),
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                keys: BTreeMap::from([
+                    ("indexer-team".to_string(), "heavy".to_string()),
+                    ("wallet-team".to_string(), "light".to_string()),
+                ]),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_cache_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [cache]
+                backend = "redis"
+                redis-url = "redis://127.0.0.1:6379"
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            cache: CacheConfig {
+                backend: CacheBackendConfig::Redis,
+                redis_url: Some("redis://127.0.0.1:6379".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_limit_profile_inherits_limits() {
+        let limits = Limits::default();
+        let profile = LimitProfile {
+            max_query_nodes: Some(1000),
+            max_requests: Some(10),
+            ..Default::default()
+        };
+
+        let expect = Limits {
+            max_query_nodes: 1000,
+            ..limits
+        };
+
+        assert_eq!(profile.limits(&limits), expect)
+    }
+
     #[test]
     fn test_read_everything_in_service_config() {
         let actual = ServiceConfig::read(
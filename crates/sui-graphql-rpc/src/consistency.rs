@@ -101,6 +101,31 @@ pub(crate) fn build_objects_query(
     page: &Page<Cursor>,
     filter_fn: impl Fn(RawQuery) -> RawQuery,
     newer_criteria: impl Fn(RawQuery) -> RawQuery,
+) -> RawQuery {
+    build_objects_query_impl(view, lhs, rhs, Some(page), filter_fn, newer_criteria)
+}
+
+/// Like `build_objects_query`, but without applying a page's cursors and limit to the inner
+/// queries. This is for queries that paginate by something other than the object ID (such as the
+/// value of one of the objects' fields), where the inner queries cannot be bounded by a page of
+/// object IDs. Callers are responsible for paginating the resulting query.
+pub(crate) fn build_unpaginated_objects_query(
+    view: View,
+    lhs: i64,
+    rhs: i64,
+    filter_fn: impl Fn(RawQuery) -> RawQuery,
+    newer_criteria: impl Fn(RawQuery) -> RawQuery,
+) -> RawQuery {
+    build_objects_query_impl(view, lhs, rhs, None, filter_fn, newer_criteria)
+}
+
+fn build_objects_query_impl(
+    view: View,
+    lhs: i64,
+    rhs: i64,
+    page: Option<&Page<Cursor>>,
+    filter_fn: impl Fn(RawQuery) -> RawQuery,
+    newer_criteria: impl Fn(RawQuery) -> RawQuery,
 ) -> RawQuery {
     // Subquery to be used in `LEFT JOIN` against the inner queries for more recent object versions
     let newer = newer_criteria(filter!(
@@ -133,10 +158,12 @@ pub(crate) fn build_objects_query(
         }
     };
 
-    // Always apply cursor pagination and limit to constrain the number of rows returned, ensure
+    // When paginating by object ID, apply cursor pagination and limit to constrain the number of rows returned, ensure
     // that the inner queries are in step, and to handle the scenario where a user provides more
     // `objectKeys` than allowed by the maximum page size.
-    snapshot_objs = page.apply::<StoredHistoryObject>(snapshot_objs);
+    if let Some(page) = page {
+        snapshot_objs = page.apply::<StoredHistoryObject>(snapshot_objs);
+    }
 
     // Similar to the snapshot query, construct the filtered inner query for the history table.
     let mut history_objs_inner = query!("SELECT * FROM objects_history");
@@ -169,10 +196,12 @@ pub(crate) fn build_objects_query(
         }
     };
 
-    // Always apply cursor pagination and limit to constrain the number of rows returned, ensure
+    // When paginating by object ID, apply cursor pagination and limit to constrain the number of rows returned, ensure
     // that the inner queries are in step, and to handle the scenario where a user provides more
     // `objectKeys` than allowed by the maximum page size.
-    history_objs = page.apply::<StoredHistoryObject>(history_objs);
+    if let Some(page) = page {
+        history_objs = page.apply::<StoredHistoryObject>(history_objs);
+    }
 
     // Combine the two queries, and select the most recent version of each object. The result set is
     // the most recent version of objects from `objects_snapshot` and `objects_history` that match
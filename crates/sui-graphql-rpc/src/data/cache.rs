@@ -0,0 +1,462 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use lru::LruCache;
+use redis::aio::{ConnectionLike, ConnectionManager};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::{CacheBackendConfig, CacheConfig};
+use crate::error::Error;
+use crate::metrics::Metrics;
+
+/// Cache for data that cannot change once it has been included in a checkpoint. Each entry
+/// remembers the checkpoint its data was included in, and is only served to requests whose
+/// consistent checkpoint (`checkpoint_viewed_at`) is at or after it, so that a cache hit never
+/// reveals data that the equivalent database query would not have.
+///
+/// Entries also remember the first checkpoint whose data they depend on, and stop being served once
+/// the indexer's pruner has moved past it, so that a cache hit never reveals data that has since
+/// been pruned from the database.
+///
+/// Cache hits are served without touching the database, so they do not count towards a request's
+/// DB query cost.
+#[derive(Clone)]
+pub(crate) struct Cache {
+    backend: Arc<dyn CacheBackend>,
+    /// The first checkpoint that no table has been pruned past, or `u64::MAX` until it is known
+    /// (in which case entries for prunable data are never served).
+    pruned_watermark: Arc<AtomicU64>,
+    metrics: Metrics,
+}
+
+/// Storage for cache entries, as opaque bytes.
+#[async_trait]
+pub(crate) trait CacheBackend: Send + Sync {
+    async fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+
+    async fn set(&self, key: &[u8], value: Vec<u8>) -> Result<(), Error>;
+}
+
+/// The kinds of data that are cached, used to namespace keys.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum CacheKind {
+    /// Checkpoints, by sequence number.
+    CheckpointBySeqNum,
+    /// Checkpoints, by digest.
+    CheckpointByDigest,
+    /// Transaction blocks, by digest.
+    TransactionByDigest,
+    /// Objects, by ID and version.
+    ObjectAtVersion,
+    /// Pages of events, by filter and page bounds, at a particular checkpoint.
+    EventPage,
+}
+
+/// Caches entries in memory, evicting the least recently used entry when it is full.
+pub(crate) struct LruBackend {
+    entries: Mutex<LruCache<Vec<u8>, Vec<u8>>>,
+}
+
+/// Caches entries in Redis, where they expire after a fixed amount of time.
+pub(crate) struct RedisBackend<C> {
+    conn: C,
+    ttl_ms: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    /// The first checkpoint whose data `value` depends on.
+    first_checkpoint: u64,
+    /// The checkpoint that `value` was included in, or read at.
+    checkpoint: u64,
+    value: T,
+}
+
+impl Cache {
+    pub(crate) fn new(backend: impl CacheBackend + 'static, metrics: Metrics) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            pruned_watermark: Arc::new(AtomicU64::new(u64::MAX)),
+            metrics,
+        }
+    }
+
+    /// Create the cache described by `config`, or `None` if caching is disabled.
+    pub(crate) async fn from_config(
+        config: &CacheConfig,
+        metrics: Metrics,
+    ) -> Result<Option<Self>, Error> {
+        Ok(match config.backend {
+            CacheBackendConfig::None => None,
+
+            CacheBackendConfig::Lru => {
+                let backend = LruBackend::new(config.lru_capacity).ok_or_else(|| {
+                    Error::Internal("Cache LRU capacity must be non-zero".to_string())
+                })?;
+
+                Some(Cache::new(backend, metrics))
+            }
+
+            CacheBackendConfig::Redis => {
+                let Some(url) = &config.redis_url else {
+                    return Err(Error::Internal(
+                        "Redis cache backend requires a redis-url".to_string(),
+                    ));
+                };
+
+                let client = redis::Client::open(url.as_str())
+                    .map_err(|e| Error::Internal(format!("Invalid Redis URL: {e}")))?;
+
+                let conn = ConnectionManager::new(client)
+                    .await
+                    .map_err(|e| Error::Internal(format!("Failed to connect to Redis: {e}")))?;
+
+                Some(Cache::new(
+                    RedisBackend::new(conn, config.redis_ttl_ms),
+                    metrics,
+                ))
+            }
+        })
+    }
+
+    /// Record that the indexer has pruned data from before `checkpoint`. Entries for prunable data
+    /// that depends on earlier checkpoints are no longer served.
+    pub(crate) fn set_pruned_watermark(&self, checkpoint: u64) {
+        self.pruned_watermark.store(checkpoint, Ordering::Relaxed);
+    }
+
+    /// Look up the value cached for `key`, if it was included in a checkpoint at or before
+    /// `checkpoint_viewed_at` (or in any checkpoint, if `checkpoint_viewed_at` is `None`), and the
+    /// data it depends on has not been pruned since. Failures to read from the cache are treated as
+    /// misses.
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &self,
+        kind: CacheKind,
+        key: &[u8],
+        checkpoint_viewed_at: Option<u64>,
+    ) -> Option<T> {
+        let bytes = match self.backend.get(&kind.key(key)).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to read {kind:?} from cache: {e}");
+                None
+            }
+        };
+
+        let pruned_watermark = self.pruned_watermark.load(Ordering::Relaxed);
+        let value = bytes
+            .and_then(|bytes| bcs::from_bytes::<Entry<T>>(&bytes).ok())
+            .filter(|entry| checkpoint_viewed_at.map_or(true, |cp| entry.checkpoint <= cp))
+            .filter(|entry| !kind.is_prunable() || entry.first_checkpoint >= pruned_watermark)
+            .map(|entry| entry.value);
+
+        self.metrics
+            .observe_cache_lookup(kind.label(), value.is_some());
+        value
+    }
+
+    /// Cache `value` under `key`, as data that was included in `checkpoint`. Failures to write to
+    /// the cache are logged, but otherwise ignored.
+    pub(crate) async fn set<T: Serialize>(
+        &self,
+        kind: CacheKind,
+        key: &[u8],
+        checkpoint: u64,
+        value: &T,
+    ) {
+        self.set_range(kind, key, checkpoint..=checkpoint, value)
+            .await
+    }
+
+    /// Cache `value` under `key`, as data that was read at the end of `checkpoints`, and that
+    /// depends on data from as early as its start. Failures to write to the cache are logged, but
+    /// otherwise ignored.
+    pub(crate) async fn set_range<T: Serialize>(
+        &self,
+        kind: CacheKind,
+        key: &[u8],
+        checkpoints: RangeInclusive<u64>,
+        value: &T,
+    ) {
+        let entry = Entry {
+            first_checkpoint: *checkpoints.start(),
+            checkpoint: *checkpoints.end(),
+            value,
+        };
+
+        let bytes = match bcs::to_bytes(&entry) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to serialize {kind:?} for cache: {e}");
+                return;
+            }
+        };
+
+        if let Err(e) = self.backend.set(&kind.key(key), bytes).await {
+            warn!("Failed to write {kind:?} to cache: {e}");
+        }
+    }
+}
+
+impl CacheKind {
+    fn label(&self) -> &'static str {
+        match self {
+            CacheKind::CheckpointBySeqNum => "checkpoint_by_seq_num",
+            CacheKind::CheckpointByDigest => "checkpoint_by_digest",
+            CacheKind::TransactionByDigest => "transaction_by_digest",
+            CacheKind::ObjectAtVersion => "object_at_version",
+            CacheKind::EventPage => "event_page",
+        }
+    }
+
+    /// Whether the indexer may prune the data that entries of this kind are read from.
+    fn is_prunable(&self) -> bool {
+        match self {
+            CacheKind::CheckpointBySeqNum | CacheKind::CheckpointByDigest => false,
+            CacheKind::TransactionByDigest | CacheKind::ObjectAtVersion | CacheKind::EventPage => {
+                true
+            }
+        }
+    }
+
+    /// The key that an entry for `key` of this kind is stored under in the backend.
+    fn key(&self, key: &[u8]) -> Vec<u8> {
+        let label = self.label().as_bytes();
+        let mut namespaced = Vec::with_capacity(label.len() + 1 + key.len());
+        namespaced.extend_from_slice(label);
+        namespaced.push(b':');
+        namespaced.extend_from_slice(key);
+        namespaced
+    }
+}
+
+impl LruBackend {
+    /// An LRU backend that holds up to `capacity` entries, or `None` if `capacity` is zero.
+    pub(crate) fn new(capacity: usize) -> Option<Self> {
+        Some(Self {
+            entries: Mutex::new(LruCache::new(NonZeroUsize::new(capacity)?)),
+        })
+    }
+}
+
+#[async_trait]
+impl CacheBackend for LruBackend {
+    async fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.entries.lock().unwrap().get(key).cloned())
+    }
+
+    async fn set(&self, key: &[u8], value: Vec<u8>) -> Result<(), Error> {
+        self.entries.lock().unwrap().put(key.to_vec(), value);
+        Ok(())
+    }
+}
+
+impl<C> RedisBackend<C> {
+    pub(crate) fn new(conn: C, ttl_ms: u64) -> Self {
+        Self { conn, ttl_ms }
+    }
+}
+
+#[async_trait]
+impl<C> CacheBackend for RedisBackend<C>
+where
+    C: ConnectionLike + Clone + Send + Sync,
+{
+    async fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let mut conn = self.conn.clone();
+        redis::cmd("GET")
+            .arg(key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(format!("Redis GET failed: {e}")))
+    }
+
+    async fn set(&self, key: &[u8], value: Vec<u8>) -> Result<(), Error> {
+        let mut conn = self.conn.clone();
+        redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("PX")
+            .arg(self.ttl_ms)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::Internal(format!("Redis SET failed: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::Registry;
+    use redis::{Arg, Cmd, Pipeline, RedisFuture, Value};
+    use std::collections::HashMap;
+
+    /// A stand-in for a Redis connection, that supports just enough of the protocol for
+    /// `RedisBackend`, against an in-memory map.
+    #[derive(Clone, Default)]
+    struct LocalRedis {
+        entries: Arc<Mutex<HashMap<Vec<u8>, Vec<u8>>>>,
+    }
+
+    impl ConnectionLike for LocalRedis {
+        fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+            let args: Vec<Vec<u8>> = cmd
+                .args_iter()
+                .filter_map(|arg| match arg {
+                    Arg::Simple(bytes) => Some(bytes.to_vec()),
+                    Arg::Cursor => None,
+                })
+                .collect();
+
+            let mut entries = self.entries.lock().unwrap();
+            let response = match args.as_slice() {
+                [cmd, key] if cmd == b"GET" => match entries.get(key) {
+                    Some(value) => Value::Data(value.clone()),
+                    None => Value::Nil,
+                },
+
+                [cmd, key, value, px, _ttl] if cmd == b"SET" && px == b"PX" => {
+                    entries.insert(key.clone(), value.clone());
+                    Value::Okay
+                }
+
+                _ => panic!("Unsupported command: {args:?}"),
+            };
+
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn req_packed_commands<'a>(
+            &'a mut self,
+            _cmd: &'a Pipeline,
+            _offset: usize,
+            _count: usize,
+        ) -> RedisFuture<'a, Vec<Value>> {
+            unimplemented!("Pipelines are not supported")
+        }
+
+        fn get_db(&self) -> i64 {
+            0
+        }
+    }
+
+    fn metrics() -> Metrics {
+        Metrics::new(&Registry::new())
+    }
+
+    async fn test_cache_backend(cache: Cache) {
+        use CacheKind as K;
+        cache.set_pruned_watermark(0);
+
+        // Nothing cached yet.
+        assert_eq!(
+            cache.get::<String>(K::ObjectAtVersion, b"a", None).await,
+            None
+        );
+
+        cache
+            .set(K::ObjectAtVersion, b"a", 10, &"foo".to_string())
+            .await;
+
+        // The entry is visible from its checkpoint onwards.
+        let hit = cache.get::<String>(K::ObjectAtVersion, b"a", None).await;
+        assert_eq!(hit.as_deref(), Some("foo"));
+
+        let hit = cache
+            .get::<String>(K::ObjectAtVersion, b"a", Some(10))
+            .await;
+        assert_eq!(hit.as_deref(), Some("foo"));
+
+        let miss = cache.get::<String>(K::ObjectAtVersion, b"a", Some(9)).await;
+        assert_eq!(miss, None);
+
+        // Kinds of data do not share keys.
+        let miss = cache
+            .get::<String>(K::TransactionByDigest, b"a", None)
+            .await;
+        assert_eq!(miss, None);
+    }
+
+    #[tokio::test]
+    async fn test_lru_cache() {
+        let backend = LruBackend::new(10).unwrap();
+        test_cache_backend(Cache::new(backend, metrics())).await;
+    }
+
+    #[tokio::test]
+    async fn test_lru_cache_eviction() {
+        let cache = Cache::new(LruBackend::new(1).unwrap(), metrics());
+        cache.set_pruned_watermark(0);
+        cache.set(CacheKind::ObjectAtVersion, b"a", 0, &1u64).await;
+        cache.set(CacheKind::ObjectAtVersion, b"b", 0, &2u64).await;
+
+        let a = cache
+            .get::<u64>(CacheKind::ObjectAtVersion, b"a", None)
+            .await;
+        let b = cache
+            .get::<u64>(CacheKind::ObjectAtVersion, b"b", None)
+            .await;
+        assert_eq!((a, b), (None, Some(2)));
+    }
+
+    #[tokio::test]
+    async fn test_pruned_entries_are_not_served() {
+        use CacheKind as K;
+        let cache = Cache::new(LruBackend::new(10).unwrap(), metrics());
+
+        cache.set(K::ObjectAtVersion, b"a", 10, &1u64).await;
+        cache.set(K::CheckpointBySeqNum, b"a", 10, &2u64).await;
+        cache.set_range(K::EventPage, b"a", 5..=20, &3u64).await;
+
+        // Until the pruner watermark is known, prunable data is never served.
+        assert_eq!(cache.get::<u64>(K::ObjectAtVersion, b"a", None).await, None);
+        assert_eq!(cache.get::<u64>(K::EventPage, b"a", None).await, None);
+        assert_eq!(
+            cache.get::<u64>(K::CheckpointBySeqNum, b"a", None).await,
+            Some(2)
+        );
+
+        cache.set_pruned_watermark(5);
+        assert_eq!(
+            cache.get::<u64>(K::ObjectAtVersion, b"a", None).await,
+            Some(1)
+        );
+        assert_eq!(cache.get::<u64>(K::EventPage, b"a", None).await, Some(3));
+
+        // Entries are served from the checkpoint they were read at, even if they depend on data
+        // from earlier checkpoints.
+        assert_eq!(cache.get::<u64>(K::EventPage, b"a", Some(19)).await, None);
+        assert_eq!(
+            cache.get::<u64>(K::EventPage, b"a", Some(20)).await,
+            Some(3)
+        );
+
+        // Once the pruner moves past the data an entry depends on, it is no longer served.
+        cache.set_pruned_watermark(6);
+        assert_eq!(
+            cache.get::<u64>(K::ObjectAtVersion, b"a", None).await,
+            Some(1)
+        );
+        assert_eq!(cache.get::<u64>(K::EventPage, b"a", None).await, None);
+
+        cache.set_pruned_watermark(11);
+        assert_eq!(cache.get::<u64>(K::ObjectAtVersion, b"a", None).await, None);
+        assert_eq!(
+            cache.get::<u64>(K::CheckpointBySeqNum, b"a", None).await,
+            Some(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache() {
+        let backend = RedisBackend::new(LocalRedis::default(), 1000);
+        test_cache_backend(Cache::new(backend, metrics())).await;
+    }
+}
@@ -1,9 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::RangeInclusive;
 use std::time::Instant;
 
-use super::QueryExecutor;
+use super::{
+    cache::{Cache, CacheKind},
+    QueryExecutor,
+};
 use crate::{config::Limits, error::Error, metrics::Metrics};
 use async_trait::async_trait;
 use diesel::{
@@ -12,6 +16,7 @@ use diesel::{
     query_dsl::LoadQuery,
     QueryResult, RunQueryDsl,
 };
+use serde::{de::DeserializeOwned, Serialize};
 use sui_indexer::indexer_reader::IndexerReader;
 
 use tracing::error;
@@ -21,6 +26,8 @@ pub(crate) struct PgExecutor {
     pub inner: IndexerReader,
     pub limits: Limits,
     pub metrics: Metrics,
+    /// Cache for immutable data, consulted before querying the database, if caching is enabled.
+    pub cache: Option<Cache>,
 }
 
 pub(crate) struct PgConnection<'c> {
@@ -34,6 +41,58 @@ impl PgExecutor {
             inner,
             limits,
             metrics,
+            cache: None,
+        }
+    }
+
+    pub(crate) fn with_cache(self, cache: Option<Cache>) -> Self {
+        Self { cache, ..self }
+    }
+
+    /// Look up `key` in the cache, if caching is enabled (see [`Cache::get`]).
+    pub(crate) async fn cache_get<T: DeserializeOwned>(
+        &self,
+        kind: CacheKind,
+        key: &[u8],
+        checkpoint_viewed_at: Option<u64>,
+    ) -> Option<T> {
+        self.cache
+            .as_ref()?
+            .get(kind, key, checkpoint_viewed_at)
+            .await
+    }
+
+    /// Cache `value` under `key`, if caching is enabled (see [`Cache::set`]).
+    pub(crate) async fn cache_set<T: Serialize>(
+        &self,
+        kind: CacheKind,
+        key: &[u8],
+        checkpoint: u64,
+        value: &T,
+    ) {
+        if let Some(cache) = &self.cache {
+            cache.set(kind, key, checkpoint, value).await;
+        }
+    }
+
+    /// Cache `value` under `key`, if caching is enabled (see [`Cache::set_range`]).
+    pub(crate) async fn cache_set_range<T: Serialize>(
+        &self,
+        kind: CacheKind,
+        key: &[u8],
+        checkpoints: RangeInclusive<u64>,
+        value: &T,
+    ) {
+        if let Some(cache) = &self.cache {
+            cache.set_range(kind, key, checkpoints, value).await;
+        }
+    }
+
+    /// Tell the cache, if caching is enabled, that data from before `checkpoint` has been pruned
+    /// (see [`Cache::set_pruned_watermark`]).
+    pub(crate) fn set_cache_pruned_watermark(&self, checkpoint: u64) {
+        if let Some(cache) = &self.cache {
+            cache.set_pruned_watermark(checkpoint);
         }
     }
 }
@@ -187,7 +246,7 @@ mod tests {
     use diesel::QueryDsl;
     use sui_framework::BuiltInFramework;
     use sui_indexer::{
-        db::{get_pg_pool_connection, new_pg_connection_pool, reset_database},
+        db::{get_pool_connection, new_connection_pool, reset_database},
         models::objects::StoredObject,
         schema::objects,
         types::IndexedObject,
@@ -195,8 +254,8 @@ mod tests {
 
     #[test]
     fn test_query_cost() {
-        let pool = new_pg_connection_pool(DEFAULT_SERVER_DB_URL, Some(5)).unwrap();
-        let mut conn = get_pg_pool_connection(&pool).unwrap();
+        let pool = new_connection_pool(DEFAULT_SERVER_DB_URL, Some(5)).unwrap();
+        let mut conn = get_pool_connection(&pool).unwrap();
         reset_database(&mut conn, /* drop_all */ true).unwrap();
 
         let objects: Vec<StoredObject> = BuiltInFramework::iter_system_packages()
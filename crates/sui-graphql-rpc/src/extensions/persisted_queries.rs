@@ -0,0 +1,288 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::PersistedQueriesConfig;
+use crate::error::{code, graphql_error};
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{from_value, Request, ServerResult};
+use fastcrypto::hash::{HashFunction, Sha256};
+use lru::LruCache;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+/// The only version of the Automatic Persisted Queries protocol.
+const PERSISTED_QUERY_VERSION: u32 = 1;
+
+/// Extension factory for resolving persisted queries: Requests can refer to a query by the SHA-256
+/// hash of its text (in the `persistedQuery` request extension), instead of sending its text.
+///
+/// Queries are either registered by the operator in the service config, or persisted
+/// automatically when a client sends a query's text along with its hash. In allow-list mode, only
+/// registered queries are run.
+pub(crate) struct PersistedQueries {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Registered queries, by hash.
+    registered: HashMap<String, String>,
+    /// Automatically persisted queries, by hash, or `None` if they are not supported.
+    automatic: Option<Mutex<LruCache<String, String>>>,
+    allow_list_only: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQuery {
+    version: u32,
+    sha256_hash: String,
+}
+
+impl PersistedQueries {
+    pub(crate) fn new(config: &PersistedQueriesConfig) -> Self {
+        let registered = config
+            .registered
+            .values()
+            .map(|query| (query_hash(query), query.clone()))
+            .collect();
+
+        let automatic = (config.automatic && !config.allow_list_only)
+            .then(|| NonZeroUsize::new(config.max_automatic))
+            .flatten()
+            .map(|capacity| Mutex::new(LruCache::new(capacity)));
+
+        Self {
+            inner: Arc::new(Inner {
+                registered,
+                automatic,
+                allow_list_only: config.allow_list_only,
+            }),
+        }
+    }
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueries {
+            inner: self.inner.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueries {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let persisted = match request.extensions.remove("persistedQuery") {
+            None => None,
+            Some(value) => {
+                let persisted: PersistedQuery = from_value(value).map_err(|_| {
+                    graphql_error(
+                        code::BAD_REQUEST,
+                        "Invalid \"persistedQuery\" request extension",
+                    )
+                })?;
+
+                if persisted.version != PERSISTED_QUERY_VERSION {
+                    return Err(graphql_error(
+                        code::BAD_REQUEST,
+                        format!(
+                            "Unsupported persisted query version {}. Only version \
+                             {PERSISTED_QUERY_VERSION} is supported",
+                            persisted.version,
+                        ),
+                    ));
+                }
+
+                Some(persisted)
+            }
+        };
+
+        if request.query.is_empty() {
+            if let Some(persisted) = persisted {
+                let Some(query) = self.inner.get(&persisted.sha256_hash) else {
+                    // Clients following the protocol recognise this message, and retry the
+                    // request with the query's text.
+                    return Err(graphql_error(
+                        code::PERSISTED_QUERY_NOT_FOUND,
+                        "PersistedQueryNotFound",
+                    ));
+                };
+
+                request.query = query;
+            }
+        } else if persisted.is_some() || self.inner.allow_list_only {
+            let hash = query_hash(&request.query);
+
+            if let Some(persisted) = &persisted {
+                if persisted.sha256_hash != hash {
+                    return Err(graphql_error(
+                        code::BAD_REQUEST,
+                        "Persisted query hash does not match the query",
+                    ));
+                }
+            }
+
+            if self.inner.allow_list_only && !self.inner.registered.contains_key(&hash) {
+                return Err(graphql_error(
+                    code::FORBIDDEN,
+                    "Only registered queries can be run on this service",
+                ));
+            }
+
+            if let Some(automatic) = &self.inner.automatic {
+                if !self.inner.registered.contains_key(&hash) {
+                    automatic.lock().unwrap().put(hash, request.query.clone());
+                }
+            }
+        }
+
+        next.run(ctx, request).await
+    }
+}
+
+impl Inner {
+    fn get(&self, hash: &str) -> Option<String> {
+        if let Some(query) = self.registered.get(hash) {
+            return Some(query.clone());
+        }
+
+        self.automatic.as_ref()?.lock().unwrap().get(hash).cloned()
+    }
+}
+
+/// The hex-encoded SHA-256 hash of `query`, which is how persisted queries are referred to.
+fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()).digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, Value};
+    use std::collections::BTreeMap;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn one(&self) -> u32 {
+            1
+        }
+
+        async fn two(&self) -> u32 {
+            2
+        }
+    }
+
+    fn schema(config: PersistedQueriesConfig) -> Schema<Query, EmptyMutation, EmptySubscription> {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(PersistedQueries::new(&config))
+            .finish()
+    }
+
+    fn request(query: &str, hash: Option<&str>) -> Request {
+        let mut request = Request::new(query);
+        if let Some(hash) = hash {
+            request.extensions.insert(
+                "persistedQuery".to_string(),
+                async_graphql::value!({
+                    "version": 1,
+                    "sha256Hash": hash,
+                }),
+            );
+        }
+        request
+    }
+
+    fn error_code(response: &async_graphql::Response) -> Option<String> {
+        let ext = response.errors.first()?.extensions.as_ref()?;
+        match ext.get("code")? {
+            Value::String(code) => Some(code.clone()),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_automatic_persisted_query() {
+        let schema = schema(PersistedQueriesConfig {
+            automatic: true,
+            ..Default::default()
+        });
+
+        let query = "{ one }";
+        let hash = query_hash(query);
+
+        // The query has not been persisted yet.
+        let response = schema.execute(request("", Some(&hash))).await;
+        assert_eq!(
+            error_code(&response).as_deref(),
+            Some(code::PERSISTED_QUERY_NOT_FOUND)
+        );
+
+        // Persist it, and then run it by hash alone.
+        let response = schema.execute(request(query, Some(&hash))).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+
+        let response = schema.execute(request("", Some(&hash))).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+        assert_eq!(response.data, async_graphql::value!({ "one": 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_hash() {
+        let schema = schema(PersistedQueriesConfig {
+            automatic: true,
+            ..Default::default()
+        });
+
+        let response = schema
+            .execute(request("{ one }", Some(&query_hash("{ two }"))))
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some(code::BAD_REQUEST));
+    }
+
+    #[tokio::test]
+    async fn test_allow_list() {
+        let schema = schema(PersistedQueriesConfig {
+            automatic: true,
+            allow_list_only: true,
+            registered: BTreeMap::from([("one".to_string(), "{ one }".to_string())]),
+            ..Default::default()
+        });
+
+        // Registered queries can be run by hash, or by text.
+        let response = schema
+            .execute(request("", Some(&query_hash("{ one }"))))
+            .await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+
+        let response = schema.execute(request("{ one }", None)).await;
+        assert!(response.is_ok(), "{:?}", response.errors);
+
+        // Other queries are rejected, and cannot be persisted automatically.
+        let response = schema.execute(request("{ two }", None)).await;
+        assert_eq!(error_code(&response).as_deref(), Some(code::FORBIDDEN));
+
+        let response = schema
+            .execute(request("{ two }", Some(&query_hash("{ two }"))))
+            .await;
+        assert_eq!(error_code(&response).as_deref(), Some(code::FORBIDDEN));
+
+        let response = schema
+            .execute(request("", Some(&query_hash("{ two }"))))
+            .await;
+        assert_eq!(
+            error_code(&response).as_deref(),
+            Some(code::PERSISTED_QUERY_NOT_FOUND)
+        );
+    }
+}
@@ -127,7 +127,9 @@ impl Extension for QueryLimitsChecker {
         let cfg = ctx
             .data::<ServiceConfig>()
             .expect("No service config provided in schema data");
-        if query.len() > cfg.limits.max_query_payload_size as usize {
+        // Requests made with an API key may be subject to different limits.
+        let limits = ctx.data_opt::<Limits>().unwrap_or(&cfg.limits);
+        if query.len() > limits.max_query_payload_size as usize {
             metrics
                 .request_metrics
                 .query_payload_too_large_size
@@ -144,7 +146,7 @@ impl Extension for QueryLimitsChecker {
                 code::BAD_USER_INPUT,
                 format!(
                     "Query payload is too large. The maximum allowed is {} bytes",
-                    cfg.limits.max_query_payload_size
+                    limits.max_query_payload_size
                 ),
             ));
         }
@@ -180,7 +182,7 @@ impl Extension for QueryLimitsChecker {
 
             running_costs.depth = 0;
             self.analyze_selection_set(
-                &cfg.limits,
+                limits,
                 &doc.fragments,
                 sel_set,
                 &mut running_costs,
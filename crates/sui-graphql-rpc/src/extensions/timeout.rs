@@ -14,7 +14,10 @@ use tokio::time::timeout;
 use tracing::error;
 use uuid::Uuid;
 
-use crate::{config::ServiceConfig, error::code};
+use crate::{
+    config::{Limits, ServiceConfig},
+    error::code,
+};
 
 /// Extension factory for creating new `Timeout` instances, per query.
 pub(crate) struct Timeout;
@@ -55,7 +58,8 @@ impl Extension for TimeoutExt {
         let cfg = ctx
             .data::<ServiceConfig>()
             .expect("No service config provided in schema data");
-        let request_timeout = Duration::from_millis(cfg.limits.request_timeout_ms);
+        let limits = ctx.data_opt::<Limits>().unwrap_or(&cfg.limits);
+        let request_timeout = Duration::from_millis(limits.request_timeout_ms);
         timeout(request_timeout, next.run(ctx, operation_name))
             .await
             .unwrap_or_else(|_| {
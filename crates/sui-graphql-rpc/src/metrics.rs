@@ -51,6 +51,8 @@ pub(crate) struct DBMetrics {
     pub db_fetches: IntCounterVec,
     /// The fetch latency grouped by result (success or error)
     pub db_fetch_latency: HistogramVec,
+    /// The number of cache lookups grouped by kind of data and result (hit or miss)
+    pub cache_lookups: IntCounterVec,
     // TODO make this work, blocked by pg.rs (unclear if to use log function or smth else)
     pub _db_query_cost: Histogram,
     // TODO determine if we want this metric, and implement it
@@ -104,6 +106,15 @@ impl Metrics {
             .observe(time.as_secs_f64());
     }
 
+    /// Updates the cache related metrics (hits and misses, by the kind of data looked up)
+    pub(crate) fn observe_cache_lookup(&self, kind: &str, hit: bool) {
+        let label = if hit { "hit" } else { "miss" };
+        self.db_metrics
+            .cache_lookups
+            .with_label_values(&[kind, label])
+            .inc();
+    }
+
     /// The total time needed for handling the query
     pub(crate) fn query_latency(&self, time: Duration) {
         self.request_metrics
@@ -162,6 +173,13 @@ impl DBMetrics {
                 registry,
             )
             .unwrap(),
+            cache_lookups: register_int_counter_vec_with_registry!(
+                "cache_lookups",
+                "The number of cache lookups grouped by kind of data and result (hit or miss)",
+                &["kind", "type"],
+                registry
+            )
+            .unwrap(),
             _db_query_cost: register_histogram_with_registry!(
                 "db_query_cost",
                 "Cost of a DB query",
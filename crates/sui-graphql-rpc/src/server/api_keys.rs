@@ -0,0 +1,255 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{ApiKeysConfig, Limits, QuotaStoreConfig};
+use crate::data::Db;
+use crate::error::{code, graphql_error, Error};
+use async_graphql::ServerError;
+use diesel::sql_types::{BigInt, Text};
+use diesel::{sql_query, QueryableByName, RunQueryDsl};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_indexer::db::{get_pool_connection, new_connection_pool, ConnectionPool};
+use tracing::error;
+
+/// Number of connections used to track quotas, when they are tracked in Postgres.
+const QUOTA_STORE_POOL_SIZE: u32 = 2;
+
+/// Limit profiles assigned to API keys, and the request quotas that are enforced for them.
+#[derive(Default)]
+pub(crate) struct ApiKeys {
+    /// The profile for each API key.
+    keys: HashMap<String, Arc<Profile>>,
+    require_key: bool,
+    quota_window_ms: u64,
+    quotas: QuotaStore,
+}
+
+/// The limits that apply to a request, and the DB executor that enforces its DB query cost limit.
+/// Both are added to the request's context data, where they take precedence over their
+/// service-wide counterparts.
+pub(crate) struct Profile {
+    pub limits: Limits,
+    pub db: Db,
+    max_requests: Option<u64>,
+}
+
+/// Where the number of requests made with each API key in the current quota window is counted.
+enum QuotaStore {
+    /// API key -> (window, requests).
+    Memory(Mutex<HashMap<String, (u64, u64)>>),
+    Postgres(ConnectionPool),
+}
+
+#[derive(QueryableByName)]
+struct Requests {
+    #[diesel(sql_type = BigInt)]
+    requests: i64,
+}
+
+impl ApiKeys {
+    pub(crate) fn new(
+        config: &ApiKeysConfig,
+        limits: &Limits,
+        db: &Db,
+        db_url: &str,
+    ) -> Result<Self, Error> {
+        let profiles: BTreeMap<_, _> = config
+            .profiles
+            .iter()
+            .map(|(name, profile)| {
+                let limits = profile.limits(limits);
+                let db = Db {
+                    limits,
+                    ..db.clone()
+                };
+                let profile = Profile {
+                    limits,
+                    db,
+                    max_requests: profile.max_requests,
+                };
+
+                (name.as_str(), Arc::new(profile))
+            })
+            .collect();
+
+        let mut keys = HashMap::new();
+        for (key, name) in &config.keys {
+            let Some(profile) = profiles.get(name.as_str()) else {
+                return Err(Error::Internal(format!(
+                    "API key assigned to unknown limit profile '{name}'"
+                )));
+            };
+
+            keys.insert(key.clone(), profile.clone());
+        }
+
+        if config.quota_window_ms == 0 {
+            return Err(Error::Internal(
+                "API key quota window must be non-zero".to_string(),
+            ));
+        }
+
+        let quotas = match config.quota_store {
+            QuotaStoreConfig::Memory => QuotaStore::default(),
+            QuotaStoreConfig::Postgres => QuotaStore::postgres(db_url)?,
+        };
+
+        Ok(Self {
+            keys,
+            require_key: config.require_key,
+            quota_window_ms: config.quota_window_ms,
+            quotas,
+        })
+    }
+
+    /// Decide whether a request carrying `api_key` (if any) can be served, counting it against its
+    /// key's quota. Returns the profile the request should be served under, or `None` if it should
+    /// be served under the service-wide limits.
+    pub(crate) async fn admit(
+        &self,
+        api_key: Option<&str>,
+    ) -> Result<Option<Arc<Profile>>, ServerError> {
+        let Some(api_key) = api_key else {
+            return if self.require_key {
+                Err(graphql_error(
+                    code::FORBIDDEN,
+                    "An API key is required to access this service",
+                ))
+            } else {
+                Ok(None)
+            };
+        };
+
+        let Some(profile) = self.keys.get(api_key) else {
+            return Err(graphql_error(code::FORBIDDEN, "Unrecognized API key"));
+        };
+
+        let Some(max_requests) = profile.max_requests else {
+            return Ok(Some(profile.clone()));
+        };
+
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let window = now_ms / self.quota_window_ms;
+
+        let requests = self.quotas.record(api_key, window).await.map_err(|e| {
+            error!("Failed to record API key request: {e}");
+            graphql_error(code::INTERNAL_SERVER_ERROR, "Failed to check API key quota")
+        })?;
+
+        if requests > max_requests {
+            return Err(graphql_error(
+                code::QUOTA_EXCEEDED,
+                format!(
+                    "Request quota exceeded. The maximum allowed is {max_requests} requests every \
+                     {}ms",
+                    self.quota_window_ms,
+                ),
+            ));
+        }
+
+        Ok(Some(profile.clone()))
+    }
+}
+
+impl Default for QuotaStore {
+    fn default() -> Self {
+        QuotaStore::Memory(Mutex::new(HashMap::new()))
+    }
+}
+
+impl QuotaStore {
+    /// Count requests in the `graphql_api_key_quotas` table, which is created by the indexer's
+    /// migrations.
+    fn postgres(db_url: &str) -> Result<Self, Error> {
+        let pool = new_connection_pool(db_url, Some(QUOTA_STORE_POOL_SIZE))
+            .map_err(|e| Error::Internal(format!("Failed to create quota store: {e}")))?;
+
+        Ok(QuotaStore::Postgres(pool))
+    }
+
+    /// Count a request made with `api_key` during `window`, returning the number of requests made
+    /// with that key during the window so far, including this one.
+    async fn record(&self, api_key: &str, window: u64) -> Result<u64, Error> {
+        match self {
+            QuotaStore::Memory(counts) => {
+                let mut counts = counts.lock().unwrap();
+                let (count_window, requests) =
+                    counts.entry(api_key.to_string()).or_insert((window, 0));
+
+                if *count_window != window {
+                    *count_window = window;
+                    *requests = 0;
+                }
+
+                *requests += 1;
+                Ok(*requests)
+            }
+
+            QuotaStore::Postgres(pool) => {
+                let pool = pool.clone();
+                let api_key = api_key.to_string();
+                tokio::task::spawn_blocking(move || {
+                    let mut conn =
+                        get_pool_connection(&pool).map_err(|e| Error::Internal(e.to_string()))?;
+
+                    let Requests { requests } = sql_query(
+                        "INSERT INTO graphql_api_key_quotas (api_key, quota_window, requests)
+                         VALUES ($1, $2, 1)
+                         ON CONFLICT (api_key) DO UPDATE SET
+                             requests = CASE
+                                 WHEN graphql_api_key_quotas.quota_window = EXCLUDED.quota_window
+                                 THEN graphql_api_key_quotas.requests + 1
+                                 ELSE 1
+                             END,
+                             quota_window = EXCLUDED.quota_window
+                         RETURNING requests",
+                    )
+                    .bind::<Text, _>(api_key)
+                    .bind::<BigInt, _>(window as i64)
+                    .get_result(&mut conn)
+                    .map_err(|e| Error::Internal(e.to_string()))?;
+
+                    Ok(requests as u64)
+                })
+                .await
+                .map_err(|e| Error::Internal(e.to_string()))?
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_quota_store() {
+        let store = QuotaStore::default();
+
+        assert_eq!(store.record("a", 0).await.unwrap(), 1);
+        assert_eq!(store.record("a", 0).await.unwrap(), 2);
+        assert_eq!(store.record("b", 0).await.unwrap(), 1);
+
+        // Counts reset when the window moves on.
+        assert_eq!(store.record("a", 1).await.unwrap(), 1);
+        assert_eq!(store.record("b", 0).await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_admit_without_keys() {
+        let keys = ApiKeys::default();
+        assert!(keys.admit(None).await.unwrap().is_none());
+        assert!(keys.admit(Some("unknown")).await.is_err());
+
+        let keys = ApiKeys {
+            require_key: true,
+            ..Default::default()
+        };
+        assert!(keys.admit(None).await.is_err());
+    }
+}
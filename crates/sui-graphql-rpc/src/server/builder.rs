@@ -1,11 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use super::api_keys::ApiKeys;
 use super::watermark_task::{Watermark, WatermarkLock, WatermarkTask};
 use crate::config::{
     ConnectionConfig, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
     RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
 };
+use crate::data::cache::Cache;
 use crate::data::package_resolver::DbPackageStore;
 use crate::data::Db;
 use crate::metrics::Metrics;
@@ -20,6 +22,7 @@ use crate::{
     extensions::{
         feature_gate::FeatureGate,
         logger::Logger,
+        persisted_queries::PersistedQueries,
         query_limits_checker::{QueryLimitsChecker, ShowUsage},
         timeout::Timeout,
     },
@@ -30,7 +33,7 @@ use async_graphql::dataloader::DataLoader;
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
 use async_graphql::EmptySubscription;
-use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
+use async_graphql::{extensions::ExtensionFactory, Response, Schema, SchemaBuilder};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::extract::FromRef;
 use axum::extract::{connect_info::IntoMakeServiceWithConnectInfo, ConnectInfo, State};
@@ -47,8 +50,9 @@ use mysten_metrics::spawn_monitored_task;
 use mysten_network::callback::{CallbackLayer, MakeCallbackHandler, ResponseHandler};
 use std::convert::Infallible;
 use std::net::TcpStream;
+use std::sync::Arc;
 use std::{any::Any, net::SocketAddr, time::Instant};
-use sui_graphql_rpc_headers::{LIMITS_HEADER, VERSION_HEADER};
+use sui_graphql_rpc_headers::{API_KEY_HEADER, LIMITS_HEADER, VERSION_HEADER};
 use sui_package_resolver::{PackageStoreWithLruCache, Resolver};
 use sui_sdk::SuiClientBuilder;
 use tokio::join;
@@ -107,6 +111,7 @@ pub(crate) struct ServerBuilder {
     schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
+    api_keys: Option<ApiKeys>,
 }
 
 #[derive(Clone)]
@@ -155,6 +160,7 @@ impl ServerBuilder {
             schema: schema_builder(),
             router: None,
             db_reader: None,
+            api_keys: None,
         }
     }
 
@@ -188,12 +194,14 @@ impl ServerBuilder {
         Schema<Query, Mutation, EmptySubscription>,
         Db,
         Router,
+        ApiKeys,
     ) {
         let address = self.address();
         let ServerBuilder {
             schema,
             db_reader,
             router,
+            api_keys,
             ..
         } = self;
         (
@@ -201,6 +209,7 @@ impl ServerBuilder {
             schema.finish(),
             db_reader.expect("DB reader not initialized"),
             router.expect("Router not initialized"),
+            api_keys.unwrap_or_default(),
         )
     }
 
@@ -272,6 +281,7 @@ impl ServerBuilder {
                 hyper::header::CONTENT_TYPE,
                 VERSION_HEADER.clone(),
                 LIMITS_HEADER.clone(),
+                API_KEY_HEADER.clone(),
             ]);
         Ok(cors)
     }
@@ -279,7 +289,7 @@ impl ServerBuilder {
     /// Consumes the `ServerBuilder` to create a `Server` that can be run.
     pub fn build(self) -> Result<Server, Error> {
         let state = self.state.clone();
-        let (address, schema, db_reader, router) = self.build_components();
+        let (address, schema, db_reader, router, api_keys) = self.build_components();
 
         // Initialize the watermark background task struct.
         let watermark_task = WatermarkTask::new(
@@ -292,6 +302,7 @@ impl ServerBuilder {
         let app = router
             .layer(axum::extract::Extension(schema))
             .layer(axum::extract::Extension(watermark_task.lock()))
+            .layer(axum::extract::Extension(Arc::new(api_keys)))
             .layer(Self::cors()?);
 
         Ok(Server {
@@ -360,12 +371,21 @@ impl ServerBuilder {
         )
         .map_err(|e| Error::Internal(format!("Failed to create pg connection pool: {}", e)))?;
 
+        // CACHE
+        let cache = Cache::from_config(&config.service.cache, metrics.clone()).await?;
+
         // DB
-        let db = Db::new(reader.clone(), config.service.limits, metrics.clone());
+        let db = Db::new(reader.clone(), config.service.limits, metrics.clone()).with_cache(cache);
         let pg_conn_pool = PgManager::new(reader.clone());
         let package_store = DbPackageStore::new(db.clone());
         let package_cache = PackageStoreWithLruCache::new(package_store);
         builder.db_reader = Some(db.clone());
+        builder.api_keys = Some(ApiKeys::new(
+            &config.service.api_keys,
+            &config.service.limits,
+            &db,
+            &config.connection.db_url,
+        )?);
 
         // SDK for talking to fullnode. Used for executing transactions only
         // TODO: fail fast if no url, once we enable mutations fully
@@ -398,6 +418,13 @@ impl ServerBuilder {
             .context_data(metrics.clone())
             .context_data(config.clone());
 
+        let persisted_queries = &config.service.persisted_queries;
+        if persisted_queries.automatic
+            || persisted_queries.allow_list_only
+            || !persisted_queries.registered.is_empty()
+        {
+            builder = builder.extension(PersistedQueries::new(persisted_queries));
+        }
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
         }
@@ -437,15 +464,35 @@ pub fn export_schema() -> String {
 }
 
 /// Entry point for graphql requests. Each request is stamped with a unique ID, a `ShowUsage` flag
-/// if set in the request headers, and the watermark as set by the background task.
+/// if set in the request headers, and the watermark as set by the background task. Requests made
+/// with an API key are counted against its quota, and served under the limits of its profile.
 async fn graphql_handler(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     schema: axum::Extension<SuiGraphQLSchema>,
     axum::Extension(watermark_lock): axum::Extension<WatermarkLock>,
+    axum::Extension(api_keys): axum::Extension<Arc<ApiKeys>>,
     headers: HeaderMap,
     req: GraphQLRequest,
 ) -> (axum::http::Extensions, GraphQLResponse) {
+    let mut extensions = axum::http::Extensions::new();
+
+    let api_key = headers
+        .get(&API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok());
+    let profile = match api_keys.admit(api_key).await {
+        Ok(profile) => profile,
+        Err(error) => {
+            let errors = vec![error];
+            extensions.insert(GraphqlErrors(std::sync::Arc::new(errors.clone())));
+            return (extensions, Response::from_errors(errors).into());
+        }
+    };
+
     let mut req = req.into_inner();
+    if let Some(profile) = profile {
+        req.data.insert(profile.limits);
+        req.data.insert(profile.db.clone());
+    }
     req.data.insert(Uuid::new_v4());
     if headers.contains_key(ShowUsage::name()) {
         req.data.insert(ShowUsage)
@@ -460,7 +507,6 @@ async fn graphql_handler(
 
     // If there are errors, insert them as an extention so that the Metrics callback handler can
     // pull it out later.
-    let mut extensions = axum::http::Extensions::new();
     if result.is_err() {
         extensions.insert(GraphqlErrors(std::sync::Arc::new(result.errors.clone())));
     };
@@ -3,6 +3,7 @@
 
 pub mod graphiql_server;
 
+pub(crate) mod api_keys;
 pub mod builder;
 pub mod version;
 pub(crate) mod watermark_task;
@@ -4,6 +4,7 @@
 use crate::data::{Db, DbConnection, QueryExecutor};
 use crate::error::Error;
 use crate::metrics::Metrics;
+use crate::types::checkpoint::Checkpoint;
 use async_graphql::ServerError;
 use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
 use std::mem;
@@ -87,6 +88,15 @@ impl WatermarkTask {
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
+
+                    // Keep the cache from serving data that the indexer has pruned since.
+                    match Watermark::query_pruned(&self.db).await {
+                        Ok(pruned) => self.db.set_cache_pruned_watermark(pruned),
+                        Err(e) => {
+                            error!("{}", e);
+                            self.metrics.inc_errors(&[ServerError::new(e.to_string(), None)]);
+                        }
+                    }
                 }
             }
         }
@@ -132,4 +142,11 @@ impl Watermark {
             epoch: epoch as u64,
         }))
     }
+
+    /// The first checkpoint that no table has been pruned past.
+    pub(crate) async fn query_pruned(db: &Db) -> Result<u64, Error> {
+        db.execute(|conn| Checkpoint::pruned_watermark(conn))
+            .await
+            .map_err(|e| Error::Internal(format!("Failed to fetch pruner watermark: {e}")))
+    }
 }
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
+    address_balance_change::{self, AddressBalanceChange},
     balance::{self, Balance},
     coin::Coin,
     cursor::Page,
@@ -15,6 +16,7 @@ use super::{
     type_filter::ExactTypeFilter,
 };
 use async_graphql::{connection::Connection, *};
+use sui_types::gas_coin::GAS;
 
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
 pub(crate) struct Address {
@@ -119,6 +121,31 @@ impl Address {
         OwnerImpl::from(self).default_suins_name(ctx, format).await
     }
 
+    /// The changes to this address's balance of coins with marker type `type`, in the order of the
+    /// transactions that made them, along with the balance after each change. If type is not
+    /// supplied, it defaults to `0x2::sui::SUI`.
+    async fn balance_changes(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<address_balance_change::Cursor>,
+        last: Option<u64>,
+        before: Option<address_balance_change::Cursor>,
+        type_: Option<ExactTypeFilter>,
+    ) -> Result<Connection<String, AddressBalanceChange>> {
+        let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
+        let coin = type_.map_or_else(GAS::type_tag, |t| t.0);
+        AddressBalanceChange::paginate(
+            ctx.data_unchecked(),
+            page,
+            self.address,
+            coin,
+            self.checkpoint_viewed_at,
+        )
+        .await
+        .extend()
+    }
+
     /// The SuinsRegistration NFTs owned by this address. These grant the owner the capability to
     /// manage the associated domain.
     pub(crate) async fn suins_registrations(
@@ -0,0 +1,193 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::connection::{Connection, CursorType, Edge};
+use async_graphql::*;
+use diesel::{ExpressionMethods, QueryDsl};
+use serde::{Deserialize, Serialize};
+use sui_indexer::models::balance_changes::StoredBalanceChange;
+use sui_indexer::schema::balance_changes;
+use sui_types::parse_sui_type_tag;
+use sui_types::TypeTag;
+
+use super::checkpoint::{Checkpoint, CheckpointId};
+use super::cursor::{self, Page, Paginated, Target};
+use super::{
+    big_int::BigInt, date_time::DateTime, digest::Digest, move_type::MoveType,
+    sui_address::SuiAddress, transaction_block::TransactionBlock,
+};
+use crate::consistency::Checkpointed;
+use crate::data::{self, Db, QueryExecutor};
+use crate::error::Error;
+
+/// A change to an address's balance of a coin type, made by one transaction.
+#[derive(Clone, Debug)]
+pub(crate) struct AddressBalanceChange {
+    pub stored: StoredBalanceChange,
+    /// The checkpoint sequence number this was viewed at.
+    pub checkpoint_viewed_at: u64,
+}
+
+/// Contents of an AddressBalanceChange's cursor.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct AddressBalanceChangeKey {
+    /// Transaction Sequence Number
+    tx: u64,
+
+    /// The checkpoint sequence number this was viewed at.
+    #[serde(rename = "c")]
+    checkpoint_viewed_at: u64,
+}
+
+pub(crate) type Cursor = cursor::JsonCursor<AddressBalanceChangeKey>;
+type Query<ST, GB> = data::Query<ST, balance_changes::table, GB>;
+
+/// A change to an address's balance of a coin type, made by one transaction, along with the
+/// address's balance of that coin type after the transaction.
+#[Object]
+impl AddressBalanceChange {
+    /// The transaction that changed the balance.
+    async fn transaction_block(&self, ctx: &Context<'_>) -> Result<Option<TransactionBlock>> {
+        let digest = Digest::try_from(self.stored.transaction_digest.as_slice()).extend()?;
+        TransactionBlock::query(
+            ctx.data_unchecked(),
+            digest,
+            Some(self.checkpoint_viewed_at),
+        )
+        .await
+        .extend()
+    }
+
+    /// The checkpoint that the transaction was finalized in.
+    async fn checkpoint(&self, ctx: &Context<'_>) -> Result<Option<Checkpoint>> {
+        Checkpoint::query(
+            ctx,
+            CheckpointId::by_seq_num(self.stored.checkpoint_sequence_number as u64),
+            Some(self.checkpoint_viewed_at),
+        )
+        .await
+        .extend()
+    }
+
+    /// The timestamp of the checkpoint that the transaction was finalized in.
+    async fn timestamp(&self) -> Result<Option<DateTime>> {
+        Ok(Some(DateTime::from_ms(self.stored.timestamp_ms)?))
+    }
+
+    /// The inner type of the coin whose balance has changed (e.g. `0x2::sui::SUI`).
+    async fn coin_type(&self) -> Result<MoveType> {
+        let type_ = parse_sui_type_tag(&self.stored.coin_type)
+            .map_err(|e| Error::Internal(format!("Failed to parse coin type: {e}")))
+            .extend()?;
+        Ok(MoveType::new(type_))
+    }
+
+    /// The signed balance change.
+    async fn amount(&self) -> BigInt {
+        BigInt::from(self.stored.amount.0)
+    }
+
+    /// The address's total balance of the coin type after the change.
+    async fn balance(&self) -> BigInt {
+        BigInt::from(self.stored.balance.0)
+    }
+}
+
+impl AddressBalanceChange {
+    /// Query the database for a `page` of the changes to `address`'s balance of `coin_type`, in
+    /// the order of the transactions that made them.
+    ///
+    /// The `checkpoint_viewed_at` parameter is an Option<u64> representing the
+    /// checkpoint_sequence_number at which this page was queried for, or `None` if the data was
+    /// requested at the latest checkpoint. Each entity returned in the connection will inherit this
+    /// checkpoint, so that when viewing that entity's state, it will be from the reference of this
+    /// checkpoint_viewed_at parameter.
+    ///
+    /// If the `Page<Cursor>` is set, then this function will defer to the `checkpoint_viewed_at` in
+    /// the cursor if they are consistent.
+    pub(crate) async fn paginate(
+        db: &Db,
+        page: Page<Cursor>,
+        address: SuiAddress,
+        coin_type: TypeTag,
+        checkpoint_viewed_at: Option<u64>,
+    ) -> Result<Connection<String, AddressBalanceChange>, Error> {
+        let cursor_viewed_at = page.validate_cursor_consistency()?;
+        let checkpoint_viewed_at: Option<u64> = cursor_viewed_at.or(checkpoint_viewed_at);
+        let coin_type = coin_type.to_canonical_string(/* with_prefix */ true);
+
+        let ((prev, next, results), checkpoint_viewed_at) = db
+            .execute_repeatable(move |conn| {
+                let checkpoint_viewed_at = match checkpoint_viewed_at {
+                    Some(value) => Ok(value),
+                    None => Checkpoint::available_range(conn).map(|(_, rhs)| rhs),
+                }?;
+
+                let result = page.paginate_query::<StoredBalanceChange, _, _, _>(
+                    conn,
+                    checkpoint_viewed_at,
+                    move || {
+                        use balance_changes::dsl;
+                        dsl::balance_changes
+                            .into_boxed()
+                            .filter(dsl::owner.eq(address.into_vec()))
+                            .filter(dsl::coin_type.eq(coin_type.clone()))
+                            .filter(dsl::checkpoint_sequence_number.le(checkpoint_viewed_at as i64))
+                    },
+                )?;
+
+                Ok::<_, diesel::result::Error>((result, checkpoint_viewed_at))
+            })
+            .await?;
+
+        let mut conn = Connection::new(prev, next);
+        for stored in results {
+            let cursor = stored.cursor(checkpoint_viewed_at).encode_cursor();
+            conn.edges.push(Edge::new(
+                cursor,
+                AddressBalanceChange {
+                    stored,
+                    checkpoint_viewed_at,
+                },
+            ));
+        }
+
+        Ok(conn)
+    }
+}
+
+impl Paginated<Cursor> for StoredBalanceChange {
+    type Source = balance_changes::table;
+
+    fn filter_ge<ST, GB>(cursor: &Cursor, query: Query<ST, GB>) -> Query<ST, GB> {
+        query.filter(balance_changes::dsl::tx_sequence_number.ge(cursor.tx as i64))
+    }
+
+    fn filter_le<ST, GB>(cursor: &Cursor, query: Query<ST, GB>) -> Query<ST, GB> {
+        query.filter(balance_changes::dsl::tx_sequence_number.le(cursor.tx as i64))
+    }
+
+    fn order<ST, GB>(asc: bool, query: Query<ST, GB>) -> Query<ST, GB> {
+        use balance_changes::dsl;
+        if asc {
+            query.order(dsl::tx_sequence_number.asc())
+        } else {
+            query.order(dsl::tx_sequence_number.desc())
+        }
+    }
+}
+
+impl Target<Cursor> for StoredBalanceChange {
+    fn cursor(&self, checkpoint_viewed_at: u64) -> Cursor {
+        Cursor::new(AddressBalanceChangeKey {
+            tx: self.tx_sequence_number as u64,
+            checkpoint_viewed_at,
+        })
+    }
+}
+
+impl Checkpointed for Cursor {
+    fn checkpoint_viewed_at(&self) -> u64 {
+        self.checkpoint_viewed_at
+    }
+}
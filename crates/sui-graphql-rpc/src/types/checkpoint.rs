@@ -14,7 +14,7 @@ use super::{
 };
 use crate::consistency::Checkpointed;
 use crate::{
-    data::{self, Conn, Db, DbConnection, QueryExecutor},
+    data::{self, cache::CacheKind, Conn, Db, DbConnection, QueryExecutor},
     error::Error,
 };
 use async_graphql::{
@@ -22,12 +22,12 @@ use async_graphql::{
     dataloader::{DataLoader, Loader},
     *,
 };
-use diesel::{CombineDsl, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel::{dsl::max, CombineDsl, ExpressionMethods, OptionalExtension, QueryDsl};
 use fastcrypto::encoding::{Base58, Encoding};
 use serde::{Deserialize, Serialize};
 use sui_indexer::{
     models::checkpoints::StoredCheckpoint,
-    schema::{checkpoints, objects_snapshot},
+    schema::{checkpoints, objects_snapshot, pruner_watermarks},
 };
 use sui_types::messages_checkpoint::CheckpointDigest;
 
@@ -404,7 +404,7 @@ impl Checkpoint {
             lhs.union(rhs)
         })?;
 
-        Ok(match checkpoint_range.as_slice() {
+        let (lhs, rhs) = match checkpoint_range.as_slice() {
             [] => (0, 0),
             [single_value] => (0, *single_value as u64),
             values => {
@@ -412,7 +412,24 @@ impl Checkpoint {
                 let max_value = *values.iter().max().unwrap();
                 (min_value as u64, max_value as u64)
             }
-        })
+        };
+
+        // Tables that the indexer prunes may be missing data for checkpoints before their pruner
+        // watermark.
+        Ok((lhs.max(Self::pruned_watermark(conn)?), rhs))
+    }
+
+    /// Queries the database for the first checkpoint that no table has been pruned past, or 0 if
+    /// nothing has been pruned. This method takes a connection, so that it can be used in an
+    /// execute_repeatable transaction.
+    pub(crate) fn pruned_watermark(conn: &mut Conn) -> Result<u64, diesel::result::Error> {
+        use pruner_watermarks::dsl as watermarks;
+
+        let pruned: Option<i64> = conn.result(move || {
+            watermarks::pruner_watermarks.select(max(watermarks::checkpoint_sequence_number))
+        })?;
+
+        Ok(pruned.unwrap_or_default() as u64)
     }
 }
 
@@ -460,32 +477,54 @@ impl Loader<SeqNumKey> for Db {
     async fn load(&self, keys: &[SeqNumKey]) -> Result<HashMap<SeqNumKey, Checkpoint>, Error> {
         use checkpoints::dsl;
 
-        let checkpoint_ids: BTreeSet<_> = keys
-            .iter()
-            .filter_map(|key| {
-                if let Some(viewed_at) = key.checkpoint_viewed_at {
-                    // Filter out keys querying for checkpoints after their own consistency cursor.
-                    (viewed_at >= key.sequence_number).then_some(key.sequence_number as i64)
-                } else {
-                    Some(key.sequence_number as i64)
-                }
-            })
-            .collect();
+        let mut checkpoint_id_to_stored = BTreeMap::new();
+        let mut checkpoint_ids = BTreeSet::new();
+        for key in keys {
+            // Filter out keys querying for checkpoints after their own consistency cursor.
+            if matches!(key.checkpoint_viewed_at, Some(viewed_at) if viewed_at < key.sequence_number)
+                || checkpoint_id_to_stored.contains_key(&key.sequence_number)
+            {
+                continue;
+            }
 
-        let checkpoints: Vec<StoredCheckpoint> = self
-            .execute(move |conn| {
-                conn.results(move || {
-                    dsl::checkpoints
-                        .filter(dsl::sequence_number.eq_any(checkpoint_ids.iter().cloned()))
-                })
-            })
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to fetch checkpoints: {e}")))?;
+            let cached: Option<StoredCheckpoint> = self
+                .cache_get(
+                    CacheKind::CheckpointBySeqNum,
+                    &key.sequence_number.to_be_bytes(),
+                    key.checkpoint_viewed_at,
+                )
+                .await;
+
+            if let Some(stored) = cached {
+                checkpoint_id_to_stored.insert(key.sequence_number, stored);
+            } else {
+                checkpoint_ids.insert(key.sequence_number as i64);
+            }
+        }
 
-        let checkpoint_id_to_stored: BTreeMap<_, _> = checkpoints
-            .into_iter()
-            .map(|stored| (stored.sequence_number as u64, stored))
-            .collect();
+        if !checkpoint_ids.is_empty() {
+            let checkpoints: Vec<StoredCheckpoint> = self
+                .execute(move |conn| {
+                    conn.results(move || {
+                        dsl::checkpoints
+                            .filter(dsl::sequence_number.eq_any(checkpoint_ids.iter().cloned()))
+                    })
+                })
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to fetch checkpoints: {e}")))?;
+
+            for stored in checkpoints {
+                let seq_num = stored.sequence_number as u64;
+                self.cache_set(
+                    CacheKind::CheckpointBySeqNum,
+                    &seq_num.to_be_bytes(),
+                    seq_num,
+                    &stored,
+                )
+                .await;
+                checkpoint_id_to_stored.insert(seq_num, stored);
+            }
+        }
 
         Ok(keys
             .iter()
@@ -515,21 +554,51 @@ impl Loader<DigestKey> for Db {
     async fn load(&self, keys: &[DigestKey]) -> Result<HashMap<DigestKey, Checkpoint>, Error> {
         use checkpoints::dsl;
 
-        let digests: BTreeSet<_> = keys.iter().map(|key| key.digest.to_vec()).collect();
+        let mut checkpoint_id_to_stored = BTreeMap::new();
+        let mut digests = BTreeSet::new();
+        for key in keys {
+            let digest = key.digest.to_vec();
+            if checkpoint_id_to_stored.contains_key(&digest) {
+                continue;
+            }
 
-        let checkpoints: Vec<StoredCheckpoint> = self
-            .execute(move |conn| {
-                conn.results(move || {
-                    dsl::checkpoints.filter(dsl::checkpoint_digest.eq_any(digests.iter().cloned()))
-                })
-            })
-            .await
-            .map_err(|e| Error::Internal(format!("Failed to fetch checkpoints: {e}")))?;
+            let cached: Option<StoredCheckpoint> = self
+                .cache_get(
+                    CacheKind::CheckpointByDigest,
+                    &digest,
+                    key.checkpoint_viewed_at,
+                )
+                .await;
+
+            if let Some(stored) = cached {
+                checkpoint_id_to_stored.insert(digest, stored);
+            } else {
+                digests.insert(digest);
+            }
+        }
 
-        let checkpoint_id_to_stored: BTreeMap<_, _> = checkpoints
-            .into_iter()
-            .map(|stored| (stored.checkpoint_digest.clone(), stored))
-            .collect();
+        if !digests.is_empty() {
+            let checkpoints: Vec<StoredCheckpoint> = self
+                .execute(move |conn| {
+                    conn.results(move || {
+                        dsl::checkpoints
+                            .filter(dsl::checkpoint_digest.eq_any(digests.iter().cloned()))
+                    })
+                })
+                .await
+                .map_err(|e| Error::Internal(format!("Failed to fetch checkpoints: {e}")))?;
+
+            for stored in checkpoints {
+                self.cache_set(
+                    CacheKind::CheckpointByDigest,
+                    &stored.checkpoint_digest,
+                    stored.sequence_number as u64,
+                    &stored,
+                )
+                .await;
+                checkpoint_id_to_stored.insert(stored.checkpoint_digest.clone(), stored);
+            }
+        }
 
         Ok(keys
             .iter()
@@ -555,3 +624,50 @@ impl Loader<DigestKey> for Db {
             .collect())
     }
 }
+
+#[cfg(all(test, feature = "pg_integration"))]
+mod tests {
+    use super::*;
+    use crate::config::{Limits, DEFAULT_SERVER_DB_URL};
+    use crate::metrics::Metrics;
+    use diesel::connection::SimpleConnection;
+    use sui_indexer::{
+        db::{get_pool_connection, new_connection_pool, reset_database},
+        indexer_reader::IndexerReader,
+    };
+
+    #[tokio::test]
+    async fn test_available_range_after_pruning() {
+        let pool = new_connection_pool(DEFAULT_SERVER_DB_URL, Some(5)).unwrap();
+        let mut conn = get_pool_connection(&pool).unwrap();
+        reset_database(&mut conn, /* drop_all */ true).unwrap();
+
+        conn.batch_execute(
+            "INSERT INTO checkpoints (
+                 sequence_number, checkpoint_digest, epoch, network_total_transactions,
+                 end_of_epoch, tx_digests, timestamp_ms, total_gas_cost, computation_cost,
+                 storage_cost, storage_rebate, non_refundable_storage_fee, checkpoint_commitments,
+                 validator_signature
+             )
+             SELECT g, int8send(g), 0, g, false, '{}', 0, 0, 0, 0, 0, 0, '\\x', '\\x'
+             FROM generate_series(0, 20) g",
+        )
+        .unwrap();
+
+        let reader = IndexerReader::new(DEFAULT_SERVER_DB_URL).unwrap();
+        let metrics = Metrics::new(&prometheus::Registry::new());
+        let db = Db::new(reader, Limits::default(), metrics);
+        let available_range = || db.execute(|conn| Checkpoint::available_range(conn));
+
+        // Nothing has been pruned, so every checkpoint is available.
+        assert_eq!(available_range().await.unwrap(), (0, 20));
+
+        // The range starts at the latest checkpoint that any table has been pruned up to.
+        conn.batch_execute(
+            "INSERT INTO pruner_watermarks (table_name, epoch, checkpoint_sequence_number)
+             VALUES ('events', 1, 5), ('transactions', 2, 10)",
+        )
+        .unwrap();
+        assert_eq!(available_range().await.unwrap(), (10, 20));
+    }
+}
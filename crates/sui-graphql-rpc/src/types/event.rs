@@ -12,7 +12,7 @@ use super::{
     move_value::MoveValue, sui_address::SuiAddress,
 };
 use crate::consistency::Checkpointed;
-use crate::data::{self, QueryExecutor};
+use crate::data::{self, cache::CacheKind, QueryExecutor};
 use crate::{data::Db, error::Error};
 use async_graphql::connection::{Connection, CursorType, Edge};
 use async_graphql::*;
@@ -161,14 +161,70 @@ impl Event {
         let cursor_viewed_at = page.validate_cursor_consistency()?;
         let checkpoint_viewed_at: Option<u64> = cursor_viewed_at.or(checkpoint_viewed_at);
 
-        let ((prev, next, results), checkpoint_viewed_at) = db
+        // A cached page can only be served if we know the checkpoint it is being viewed at, because
+        // otherwise that has to be fetched from the database anyway.
+        let cache_key = match checkpoint_viewed_at {
+            Some(checkpoint_viewed_at) if db.cache.is_some() => {
+                page_cache_key(&page, &filter, checkpoint_viewed_at)
+            }
+            _ => None,
+        };
+
+        let cached: Option<(bool, bool, Vec<StoredEvent>)> = match &cache_key {
+            Some(key) => {
+                db.cache_get(CacheKind::EventPage, key, checkpoint_viewed_at)
+                    .await
+            }
+            None => None,
+        };
+
+        let ((prev, next, results), checkpoint_viewed_at) = match (cached, checkpoint_viewed_at) {
+            (Some(cached), Some(checkpoint_viewed_at)) => (cached, checkpoint_viewed_at),
+            _ => Self::query_page(db, page, filter, checkpoint_viewed_at, cache_key).await?,
+        };
+
+        let mut conn = Connection::new(prev, next);
+
+        // Defer to the provided checkpoint_viewed_at, but if it is not provided, use the
+        // current available range. This sets a consistent upper bound for the nested queries.
+        for stored in results {
+            let cursor = stored.cursor(checkpoint_viewed_at).encode_cursor();
+            conn.edges.push(Edge::new(
+                cursor,
+                Event::try_from_stored_event(stored, checkpoint_viewed_at)?,
+            ));
+        }
+
+        Ok(conn)
+    }
+
+    /// Query the database for a `page` of events (see [`Event::paginate`]), returning whether
+    /// there are previous and next pages, the events in the page, and the checkpoint they were
+    /// viewed at. If a `cache_key` is provided, the page is cached under it.
+    async fn query_page(
+        db: &Db,
+        page: Page<Cursor>,
+        filter: EventFilter,
+        checkpoint_viewed_at: Option<u64>,
+        cache_key: Option<Vec<u8>>,
+    ) -> Result<((bool, bool, Vec<StoredEvent>), u64), Error> {
+        let caching = cache_key.is_some();
+        let (result, pruned_watermark, checkpoint_viewed_at) = db
             .execute_repeatable(move |conn| {
                 let checkpoint_viewed_at = match checkpoint_viewed_at {
                     Some(value) => Ok(value),
                     None => Checkpoint::available_range(conn).map(|(_, rhs)| rhs),
                 }?;
 
-                let result = page.paginate_query::<StoredEvent, _, _, _>(
+                // The page depends on every event that has not been pruned, so a cached copy is
+                // only valid until the pruner next moves.
+                let pruned_watermark = if caching {
+                    Checkpoint::pruned_watermark(conn)?
+                } else {
+                    0
+                };
+
+                let (prev, next, results) = page.paginate_query::<StoredEvent, _, _, _>(
                     conn,
                     checkpoint_viewed_at,
                     move || {
@@ -224,23 +280,25 @@ impl Event {
                     },
                 )?;
 
-                Ok::<_, diesel::result::Error>((result, checkpoint_viewed_at))
+                Ok::<_, diesel::result::Error>((
+                    (prev, next, results.collect::<Vec<_>>()),
+                    pruned_watermark,
+                    checkpoint_viewed_at,
+                ))
             })
             .await?;
 
-        let mut conn = Connection::new(prev, next);
-
-        // Defer to the provided checkpoint_viewed_at, but if it is not provided, use the
-        // current available range. This sets a consistent upper bound for the nested queries.
-        for stored in results {
-            let cursor = stored.cursor(checkpoint_viewed_at).encode_cursor();
-            conn.edges.push(Edge::new(
-                cursor,
-                Event::try_from_stored_event(stored, checkpoint_viewed_at)?,
-            ));
+        if let Some(key) = cache_key {
+            db.cache_set_range(
+                CacheKind::EventPage,
+                &key,
+                pruned_watermark..=checkpoint_viewed_at,
+                &result,
+            )
+            .await;
         }
 
-        Ok(conn)
+        Ok((result, checkpoint_viewed_at))
     }
 
     pub(crate) fn try_from_stored_transaction(
@@ -315,6 +373,27 @@ impl Event {
     }
 }
 
+/// The key that the events in `page`, matching `filter`, and viewed at `checkpoint_viewed_at` are
+/// cached under, or `None` if it could not be serialized.
+fn page_cache_key(
+    page: &Page<Cursor>,
+    filter: &EventFilter,
+    checkpoint_viewed_at: u64,
+) -> Option<Vec<u8>> {
+    bcs::to_bytes(&(
+        checkpoint_viewed_at,
+        page.after().map(|c| c.encode_cursor()),
+        page.before().map(|c| c.encode_cursor()),
+        page.limit() as u64,
+        page.is_from_front(),
+        filter.sender,
+        filter.transaction_digest.map(|d| d.to_vec()),
+        filter.emitting_module.as_ref().map(|m| m.to_string()),
+        filter.event_type.as_ref().map(|t| t.to_string()),
+    ))
+    .ok()
+}
+
 impl Paginated<Cursor> for StoredEvent {
     type Source = events::table;
 
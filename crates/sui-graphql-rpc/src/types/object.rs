@@ -3,6 +3,7 @@
 
 use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::str::FromStr;
 
 use super::balance::{self, Balance};
 use super::big_int::BigInt;
@@ -22,17 +23,19 @@ use super::transaction_block;
 use super::transaction_block::TransactionBlockFilter;
 use super::type_filter::{ExactTypeFilter, TypeFilter};
 use super::{owner::Owner, sui_address::SuiAddress, transaction_block::TransactionBlock};
-use crate::consistency::{build_objects_query, consistent_range, Checkpointed, View};
+use crate::consistency::{
+    build_objects_query, build_unpaginated_objects_query, consistent_range, Checkpointed, View,
+};
 use crate::data::package_resolver::PackageResolver;
-use crate::data::{self, Db, DbConnection, QueryExecutor};
+use crate::data::{self, cache::CacheKind, Db, DbConnection, QueryExecutor};
 use crate::error::Error;
 use crate::raw_query::RawQuery;
 use crate::types::base64::Base64;
 use crate::types::intersect;
-use crate::{filter, or_filter};
+use crate::{filter, or_filter, query};
 use async_graphql::connection::{CursorType, Edge};
 use async_graphql::{connection::Connection, *};
-use diesel::{CombineDsl, ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel::{CombineDsl, ExpressionMethods, OptionalExtension, QueryDsl, QueryableByName};
 use move_core_types::annotated_value::{MoveStruct, MoveTypeLayout};
 use move_core_types::language_storage::StructTag;
 use serde::{Deserialize, Serialize};
@@ -101,7 +104,8 @@ pub(crate) struct ObjectRef {
 ///
 /// - Type matches the `type` filter,
 /// - AND, whose owner matches the `owner` filter,
-/// - AND, whose ID is in `objectIds` OR whose ID and version is in `objectKeys`.
+/// - AND, whose ID is in `objectIds` OR whose ID and version is in `objectKeys`,
+/// - AND, whose field values satisfy all the `fields` filters.
 #[derive(InputObject, Default, Debug, Clone, Eq, PartialEq)]
 pub(crate) struct ObjectFilter {
     /// This field is used to specify the type of objects that should be included in the query
@@ -122,6 +126,13 @@ pub(crate) struct ObjectFilter {
 
     /// Filter for live or potentially historical objects by their ID and version.
     pub object_keys: Option<Vec<ObjectKey>>,
+
+    /// Filter for objects by the values of fields in their contents.
+    ///
+    /// Only fields that the indexer is configured to project out of objects of a given type can be
+    /// filtered on, and objects without a value for a filtered field (because the field is not
+    /// projected for their type, or holds a value that cannot be projected) are excluded.
+    pub fields: Option<Vec<ObjectFieldFilter>>,
 }
 
 #[derive(InputObject, Debug, Clone, Eq, PartialEq)]
@@ -130,6 +141,51 @@ pub(crate) struct ObjectKey {
     pub version: u64,
 }
 
+/// Constrains objects by the value of one of the fields in their contents.
+#[derive(InputObject, Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ObjectFieldFilter {
+    /// Path to the field from the object's top-level struct, with the names of nested fields
+    /// separated by `.`, e.g. `price`, or `item.name`.
+    pub path: String,
+
+    /// How the field's value is compared to `value`.
+    pub op: FieldComparison,
+
+    /// The value to compare the field against. Integers are compared numerically against integer
+    /// fields. Other values are compared against the text of the field's value: booleans are
+    /// `true` or `false`, addresses and IDs are `0x`-prefixed hex, and strings are compared as is.
+    pub value: String,
+}
+
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum FieldComparison {
+    /// Equal to.
+    Eq,
+    /// Not equal to.
+    Ne,
+    /// Less than.
+    Lt,
+    /// Less than or equal to.
+    Le,
+    /// Greater than.
+    Gt,
+    /// Greater than or equal to.
+    Ge,
+}
+
+/// Orders objects by the value of one of the integer fields in their contents, breaking ties by
+/// object ID. Objects without a value for the field are excluded.
+#[derive(InputObject, Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ObjectFieldOrder {
+    /// Path to the field from the object's top-level struct, with the names of nested fields
+    /// separated by `.`. Only fields that the indexer is configured to project can be ordered by.
+    pub path: String,
+
+    /// Order objects from the largest value of the field to the smallest, rather than smallest to
+    /// largest (the default).
+    pub descending: Option<bool>,
+}
+
 /// The object's owner type: Immutable, Shared, Parent, or Address.
 #[derive(Union, Clone)]
 pub enum ObjectOwner {
@@ -201,6 +257,19 @@ pub(crate) struct HistoricalObjectCursor {
     /// The checkpoint sequence number this was viewed at.
     #[serde(rename = "c")]
     checkpoint_viewed_at: u64,
+    /// The value of the field that the page was ordered by, negated if it was ordered in
+    /// descending order, or `None` if the page was ordered by object ID.
+    #[serde(rename = "k")]
+    order_key: Option<String>,
+}
+
+/// An object, alongside the value of the field that its page is ordered by.
+#[derive(QueryableByName)]
+struct StoredOrderedObject {
+    #[diesel(embed)]
+    object: StoredHistoryObject,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    order_key: String,
 }
 
 /// Interface implemented by on-chain values that are addressable by an ID (also referred to as its
@@ -740,6 +809,17 @@ impl Object {
         checkpoint_viewed_at: Option<u64>,
         downcast: impl Fn(Object) -> Result<T, Error>,
     ) -> Result<Connection<String, T>, Error> {
+        if page
+            .after()
+            .into_iter()
+            .chain(page.before())
+            .any(|c| c.order_key.is_some())
+        {
+            return Err(Error::Client(
+                "Cursor is from a page ordered by a field".to_string(),
+            ));
+        }
+
         // If cursors are provided, defer to the `checkpoint_viewed_at` in the cursor if they are
         // consistent. Otherwise, use the value from the parameter, or set to None. This is so that
         // paginated queries are consistent with the previous query that created the cursor.
@@ -782,6 +862,66 @@ impl Object {
         Ok(conn)
     }
 
+    /// Query the database for a `page` of objects, optionally `filter`-ed, ordered by the value of
+    /// the field described by `order`. The page uses the value of that field and the object ID as
+    /// its cursor, so cursors from pages that are not ordered by a field are rejected.
+    ///
+    /// `checkpoint_viewed_at` is treated the same way as in `paginate_subtype`.
+    pub(crate) async fn paginate_ordered(
+        db: &Db,
+        page: Page<Cursor>,
+        filter: ObjectFilter,
+        order: ObjectFieldOrder,
+        checkpoint_viewed_at: Option<u64>,
+    ) -> Result<Connection<String, Object>, Error> {
+        if page
+            .after()
+            .into_iter()
+            .chain(page.before())
+            .any(|c| c.order_key.is_none())
+        {
+            return Err(Error::Client(
+                "Cursor is not from a page ordered by a field".to_string(),
+            ));
+        }
+
+        let cursor_viewed_at = page.validate_cursor_consistency()?;
+        let checkpoint_viewed_at: Option<u64> = cursor_viewed_at.or(checkpoint_viewed_at);
+
+        let response = db
+            .execute_repeatable(move |conn| {
+                let Some((lhs, rhs)) = consistent_range(conn, checkpoint_viewed_at)? else {
+                    return Ok::<_, diesel::result::Error>(None);
+                };
+
+                let result = page.paginate_raw_query::<StoredOrderedObject>(
+                    conn,
+                    rhs,
+                    ordered_objects_query(&filter, &order, lhs as i64, rhs as i64),
+                )?;
+
+                Ok(Some((result, rhs)))
+            })
+            .await?;
+
+        let Some(((prev, next, results), checkpoint_viewed_at)) = response else {
+            return Err(Error::Client(
+                "Requested data is outside the available range".to_string(),
+            ));
+        };
+
+        let mut conn = Connection::new(prev, next);
+
+        for stored in results {
+            let cursor = stored.cursor(checkpoint_viewed_at).encode_cursor();
+            let object =
+                Object::try_from_stored_history_object(stored.object, Some(checkpoint_viewed_at))?;
+            conn.edges.push(Edge::new(cursor, object));
+        }
+
+        Ok(conn)
+    }
+
     /// Query for the object at a specific version, at the checkpoint_viewed_at if given, else
     /// against the latest checkpoint.
     ///
@@ -798,6 +938,15 @@ impl Object {
         use objects_history::dsl as history;
         use objects_snapshot::dsl as snapshot;
 
+        let cache_key = [address.as_slice(), &version.to_be_bytes()].concat();
+        let cached: Option<StoredHistoryObject> = db
+            .cache_get(CacheKind::ObjectAtVersion, &cache_key, checkpoint_viewed_at)
+            .await;
+
+        if let Some(stored) = cached {
+            return Self::try_from_stored_history_object(stored, checkpoint_viewed_at).map(Some);
+        }
+
         let version = version as i64;
 
         let stored_objs: Option<Vec<StoredHistoryObject>> = db
@@ -833,11 +982,19 @@ impl Object {
         };
 
         // Select the max by key after the union query, because Diesel currently does not support order_by on union
-        stored_objs
-            .into_iter()
-            .max_by_key(|o| o.object_version)
-            .map(|obj| Self::try_from_stored_history_object(obj, checkpoint_viewed_at))
-            .transpose()
+        let Some(stored) = stored_objs.into_iter().max_by_key(|o| o.object_version) else {
+            return Ok(None);
+        };
+
+        db.cache_set(
+            CacheKind::ObjectAtVersion,
+            &cache_key,
+            stored.checkpoint_sequence_number as u64,
+            &stored,
+        )
+        .await;
+
+        Self::try_from_stored_history_object(stored, checkpoint_viewed_at).map(Some)
     }
 
     /// Query for the latest version of an object bounded by the provided `parent_version`.
@@ -1125,6 +1282,11 @@ impl ObjectFilter {
             owner: intersect!(owner, intersect::by_eq)?,
             object_ids,
             object_keys,
+            // Field filters are already a conjunction, so both sets of them must hold.
+            fields: intersect!(fields, |mut f, g| {
+                f.extend(g);
+                Some(f)
+            })?,
         })
     }
 
@@ -1207,6 +1369,10 @@ impl ObjectFilter {
             );
         }
 
+        for field in self.fields.iter().flatten() {
+            query = field.apply(query);
+        }
+
         if let Some(type_) = &self.type_ {
             return type_.apply_raw(query, "object_type");
         }
@@ -1219,11 +1385,68 @@ impl ObjectFilter {
     }
 }
 
+impl ObjectFieldFilter {
+    /// Applies the filter to the input `RawQuery`, by constraining the object's ID and version to
+    /// those of object versions whose projected field satisfies the filter.
+    fn apply(&self, query: RawQuery) -> RawQuery {
+        let op = self.op.as_sql();
+
+        // Integers are compared numerically, so that they are ordered by magnitude rather than
+        // lexicographically. They are inlined, because binds are always bound as text, and only
+        // consist of digits.
+        if !self.value.is_empty() && self.value.bytes().all(|b| b.is_ascii_digit()) {
+            return filter!(
+                query,
+                format!(
+                    "(object_id, object_version) IN (\
+                        SELECT object_id, object_version FROM object_fields \
+                        WHERE field_name = {{}} AND is_numeric AND numeric_value {op} {}\
+                    )",
+                    self.value,
+                ),
+                self.path
+            );
+        }
+
+        // Addresses and IDs are projected in their canonical form.
+        let value = match SuiAddress::from_str(&self.value) {
+            Ok(address) if self.value.starts_with("0x") => address.to_string(),
+            _ => self.value.clone(),
+        };
+
+        filter!(
+            query,
+            format!(
+                "(object_id, object_version) IN (\
+                    SELECT object_id, object_version FROM object_fields \
+                    WHERE field_name = {{}} AND field_value {op} {{}}\
+                )"
+            ),
+            self.path,
+            value
+        )
+    }
+}
+
+impl FieldComparison {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            FieldComparison::Eq => "=",
+            FieldComparison::Ne => "<>",
+            FieldComparison::Lt => "<",
+            FieldComparison::Le => "<=",
+            FieldComparison::Gt => ">",
+            FieldComparison::Ge => ">=",
+        }
+    }
+}
+
 impl HistoricalObjectCursor {
     pub(crate) fn new(object_id: Vec<u8>, checkpoint_viewed_at: u64) -> Self {
         Self {
             object_id,
             checkpoint_viewed_at,
+            order_key: None,
         }
     }
 }
@@ -1303,6 +1526,52 @@ impl Target<Cursor> for StoredHistoryObject {
     }
 }
 
+impl RawPaginated<Cursor> for StoredOrderedObject {
+    fn filter_ge(cursor: &Cursor, query: RawQuery) -> RawQuery {
+        filter!(
+            query,
+            format!(
+                "(candidates.order_value, candidates.object_id) >= ({{}}::numeric, '\\x{}'::bytea)",
+                hex::encode(cursor.object_id.clone())
+            ),
+            cursor.order_key.as_deref().unwrap_or_default()
+        )
+    }
+
+    fn filter_le(cursor: &Cursor, query: RawQuery) -> RawQuery {
+        filter!(
+            query,
+            format!(
+                "(candidates.order_value, candidates.object_id) <= ({{}}::numeric, '\\x{}'::bytea)",
+                hex::encode(cursor.object_id.clone())
+            ),
+            cursor.order_key.as_deref().unwrap_or_default()
+        )
+    }
+
+    fn order(asc: bool, query: RawQuery) -> RawQuery {
+        if asc {
+            query
+                .order_by("candidates.order_value ASC")
+                .order_by("candidates.object_id ASC")
+        } else {
+            query
+                .order_by("candidates.order_value DESC")
+                .order_by("candidates.object_id DESC")
+        }
+    }
+}
+
+impl Target<Cursor> for StoredOrderedObject {
+    fn cursor(&self, checkpoint_viewed_at: u64) -> Cursor {
+        Cursor::new(HistoricalObjectCursor {
+            object_id: self.object.object_id.clone(),
+            checkpoint_viewed_at,
+            order_key: Some(self.order_key.clone()),
+        })
+    }
+}
+
 impl From<&ObjectKind> for ObjectStatus {
     fn from(kind: &ObjectKind) -> Self {
         match kind {
@@ -1386,6 +1655,70 @@ where
     )
 }
 
+/// Constructs a raw query to fetch objects from the database, alongside the value of the field
+/// they are ordered by (`order_value`), and its text representation (`order_key`). The field's
+/// value is negated when ordering in descending order, so that pages are always in ascending order
+/// of `order_value`.
+///
+/// Unlike `objects_query`, the inner queries cannot be bounded by the page, because it is not
+/// ordered by object ID. Instead, they are restricted to objects that have the field.
+fn ordered_objects_query(
+    filter: &ObjectFilter,
+    order: &ObjectFieldOrder,
+    lhs: i64,
+    rhs: i64,
+) -> RawQuery {
+    // Even without other filters, the latest version of each object must be checked for the
+    // field, so that stale values are not returned for objects that have since lost it.
+    let view = if filter.object_keys.is_some() {
+        View::Historical
+    } else {
+        View::Consistent
+    };
+
+    let objects = build_unpaginated_objects_query(
+        view,
+        lhs,
+        rhs,
+        move |query| {
+            filter!(
+                filter.apply(query),
+                "(object_id, object_version) IN (\
+                    SELECT object_id, object_version FROM object_fields \
+                    WHERE field_name = {} AND is_numeric\
+                )",
+                order.path
+            )
+        },
+        move |newer| newer,
+    );
+
+    let sign = if order.descending.unwrap_or(false) {
+        "-"
+    } else {
+        ""
+    };
+
+    let fields = filter!(
+        query!(format!(
+            "SELECT object_id, object_version, {sign}numeric_value AS order_value FROM object_fields"
+        )),
+        "field_name = {} AND is_numeric",
+        order.path
+    );
+
+    let query = query!(
+        r#"SELECT candidates.*, fields.order_value, fields.order_value::text AS order_key
+            FROM ({}) candidates
+            INNER JOIN ({}) fields
+            ON (candidates.object_id = fields.object_id AND candidates.object_version = fields.object_version)"#,
+        objects,
+        fields
+    );
+
+    query!("SELECT * FROM ({}) candidates", query)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1506,4 +1839,73 @@ mod tests {
         // No overlap between these two.
         assert_eq!(f2.clone().intersect(f3.clone()), None);
     }
+
+    #[test]
+    fn test_field_filter_intersection() {
+        let price = ObjectFieldFilter {
+            path: "price".to_string(),
+            op: FieldComparison::Lt,
+            value: "100".to_string(),
+        };
+
+        let seller = ObjectFieldFilter {
+            path: "seller".to_string(),
+            op: FieldComparison::Eq,
+            value: "0x1".to_string(),
+        };
+
+        let f0 = ObjectFilter {
+            fields: Some(vec![price.clone()]),
+            ..Default::default()
+        };
+
+        let f1 = ObjectFilter {
+            fields: Some(vec![seller.clone()]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            f0.clone().intersect(f1),
+            Some(ObjectFilter {
+                fields: Some(vec![price, seller]),
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(f0.clone().intersect(ObjectFilter::default()), Some(f0));
+    }
+
+    #[test]
+    fn test_field_filter_query() {
+        let filter = ObjectFilter {
+            fields: Some(vec![
+                ObjectFieldFilter {
+                    path: "price".to_string(),
+                    op: FieldComparison::Lt,
+                    value: "100".to_string(),
+                },
+                ObjectFieldFilter {
+                    path: "seller".to_string(),
+                    op: FieldComparison::Eq,
+                    value: "0x1".to_string(),
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let (sql, binds) = filter
+            .apply(query!("SELECT * FROM objects_snapshot"))
+            .finish();
+
+        assert!(sql.contains("is_numeric AND numeric_value < 100"));
+        assert!(sql.contains("field_value = {}"));
+        assert_eq!(
+            binds,
+            vec![
+                "price".to_string(),
+                "seller".to_string(),
+                format!("0x{}", "0".repeat(63) + "1"),
+            ]
+        );
+    }
 }
@@ -26,7 +26,7 @@ use super::{
     epoch::Epoch,
     event::{self, Event, EventFilter},
     move_type::MoveType,
-    object::{self, Object, ObjectFilter, ObjectLookupKey},
+    object::{self, Object, ObjectFieldOrder, ObjectFilter, ObjectLookupKey},
     owner::Owner,
     protocol_config::ProtocolConfigs,
     sui_address::SuiAddress,
@@ -378,6 +378,10 @@ impl Query {
     }
 
     /// The objects that exist in the network.
+    ///
+    /// Objects are ordered by their IDs, unless `orderBy` is provided, in which case they are
+    /// ordered by the value of one of their fields. Cursors from pages with one ordering cannot be
+    /// used to paginate a query with the other.
     async fn objects(
         &self,
         ctx: &Context<'_>,
@@ -386,17 +390,25 @@ impl Query {
         last: Option<u64>,
         before: Option<object::Cursor>,
         filter: Option<ObjectFilter>,
+        order_by: Option<ObjectFieldOrder>,
     ) -> Result<Connection<String, Object>> {
         let Watermark { checkpoint, .. } = *ctx.data()?;
 
         let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
-        Object::paginate(
-            ctx.data_unchecked(),
-            page,
-            filter.unwrap_or_default(),
-            Some(checkpoint),
-        )
-        .await
+        let filter = filter.unwrap_or_default();
+        match order_by {
+            Some(order) => {
+                Object::paginate_ordered(
+                    ctx.data_unchecked(),
+                    page,
+                    filter,
+                    order,
+                    Some(checkpoint),
+                )
+                .await
+            }
+            None => Object::paginate(ctx.data_unchecked(), page, filter, Some(checkpoint)).await,
+        }
         .extend()
     }
 
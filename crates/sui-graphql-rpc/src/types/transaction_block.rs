@@ -28,7 +28,7 @@ use sui_types::{
 
 use crate::{
     consistency::Checkpointed,
-    data::{self, Db, DbConnection, QueryExecutor},
+    data::{self, cache::CacheKind, Db, DbConnection, QueryExecutor},
     error::Error,
     types::intersect,
 };
@@ -246,6 +246,26 @@ impl TransactionBlock {
     ) -> Result<Option<Self>, Error> {
         use transactions::dsl;
 
+        // A cached transaction can only be served if we know the checkpoint it is being viewed at,
+        // because otherwise that has to be fetched from the database anyway.
+        if let Some(checkpoint_viewed_at) = checkpoint_viewed_at {
+            let cached: Option<StoredTransaction> = db
+                .cache_get(
+                    CacheKind::TransactionByDigest,
+                    digest.as_slice(),
+                    Some(checkpoint_viewed_at),
+                )
+                .await;
+
+            if let Some(stored) = cached {
+                let inner = TransactionBlockInner::try_from(stored)?;
+                return Ok(Some(TransactionBlock {
+                    inner,
+                    checkpoint_viewed_at,
+                }));
+            }
+        }
+
         let (stored, checkpoint_viewed_at): (Option<StoredTransaction>, u64) = db
             .execute_repeatable(move |conn| {
                 let checkpoint_viewed_at = match checkpoint_viewed_at {
@@ -268,6 +288,14 @@ impl TransactionBlock {
             return Ok(None);
         };
 
+        db.cache_set(
+            CacheKind::TransactionByDigest,
+            digest.as_slice(),
+            stored.checkpoint_sequence_number as u64,
+            &stored,
+        )
+        .await;
+
         let inner = TransactionBlockInner::try_from(stored)?;
         Ok(Some(TransactionBlock {
             inner,
@@ -15,6 +15,7 @@ mod tests {
     use sui_graphql_rpc::client::ClientError;
     use sui_graphql_rpc::config::ConnectionConfig;
     use sui_graphql_rpc::test_infra::cluster::DEFAULT_INTERNAL_DATA_SOURCE_PORT;
+    use sui_types::base_types::SuiAddress;
     use sui_types::digests::ChainIdentifier;
     use sui_types::gas_coin::GAS;
     use sui_types::transaction::CallArg;
@@ -837,6 +838,98 @@ mod tests {
             .is_null());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_address_balance_changes() {
+        let rng = StdRng::from_seed([12; 32]);
+        let mut sim = Simulacrum::new_with_rng(rng);
+
+        // Transfer SUI to the same recipient three times, in separate checkpoints.
+        let recipient = SuiAddress::random_for_testing_only();
+        let mut amounts = vec![];
+        for _ in 0..3 {
+            let (transaction, amount) = sim.transfer_txn(recipient);
+            let (_, err) = sim.execute_transaction(transaction).unwrap();
+            assert!(err.is_none());
+            sim.create_checkpoint();
+            amounts.push(amount);
+        }
+
+        let connection_config = ConnectionConfig::ci_integration_test_cfg();
+        let cluster = sui_graphql_rpc::test_infra::cluster::serve_executor(
+            connection_config,
+            DEFAULT_INTERNAL_DATA_SOURCE_PORT,
+            Arc::new(sim),
+            None,
+        )
+        .await;
+        cluster
+            .wait_for_checkpoint_catchup(3, Duration::from_secs(10))
+            .await;
+
+        let page = |args: String| {
+            let query = format!(
+                r#"{{
+                    address(address: "{recipient}") {{
+                        balanceChanges({args}) {{
+                            pageInfo {{ hasNextPage hasPreviousPage endCursor startCursor }}
+                            nodes {{ amount balance checkpoint {{ sequenceNumber }} }}
+                        }}
+                    }}
+                }}"#
+            );
+            let client = &cluster.graphql_client;
+            async move {
+                let res = client
+                    .execute_to_graphql(query, true, vec![], vec![])
+                    .await
+                    .unwrap();
+                assert!(res.errors().is_empty(), "{:?}", res.errors());
+                let data = res.response_body().data.clone().into_json().unwrap();
+                data["address"]["balanceChanges"].clone()
+            }
+        };
+
+        let changes = |page: &serde_json::Value| -> Vec<(u64, u64, u64)> {
+            page["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|node| {
+                    (
+                        node["amount"].as_str().unwrap().parse().unwrap(),
+                        node["balance"].as_str().unwrap().parse().unwrap(),
+                        node["checkpoint"]["sequenceNumber"].as_u64().unwrap(),
+                    )
+                })
+                .collect()
+        };
+
+        let (a0, a1, a2) = (amounts[0], amounts[1], amounts[2]);
+
+        // Paginating forwards, from the first change.
+        let first = page("first: 2".to_string()).await;
+        assert_eq!(changes(&first), vec![(a0, a0, 1), (a1, a0 + a1, 2)]);
+        assert_eq!(first["pageInfo"]["hasNextPage"], json!(true));
+        assert_eq!(first["pageInfo"]["hasPreviousPage"], json!(false));
+
+        let end = first["pageInfo"]["endCursor"].as_str().unwrap();
+        let next = page(format!("first: 2, after: \"{end}\"")).await;
+        assert_eq!(changes(&next), vec![(a2, a0 + a1 + a2, 3)]);
+        assert_eq!(next["pageInfo"]["hasNextPage"], json!(false));
+        assert_eq!(next["pageInfo"]["hasPreviousPage"], json!(true));
+
+        // Paginating backwards, from the last change.
+        let last = page("last: 1".to_string()).await;
+        assert_eq!(changes(&last), vec![(a2, a0 + a1 + a2, 3)]);
+        assert_eq!(last["pageInfo"]["hasPreviousPage"], json!(true));
+
+        let start = last["pageInfo"]["startCursor"].as_str().unwrap();
+        let prev = page(format!("last: 2, before: \"{start}\"")).await;
+        assert_eq!(changes(&prev), vec![(a0, a0, 1), (a1, a0 + a1, 2)]);
+        assert_eq!(prev["pageInfo"]["hasPreviousPage"], json!(false));
+    }
+
     use sui_graphql_rpc::server::builder::tests::*;
 
     #[tokio::test]
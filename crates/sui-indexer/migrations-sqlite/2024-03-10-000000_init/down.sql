@@ -0,0 +1,16 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS tx_senders;
+DROP TABLE IF EXISTS tx_recipients;
+DROP TABLE IF EXISTS tx_input_objects;
+DROP TABLE IF EXISTS tx_changed_objects;
+DROP TABLE IF EXISTS tx_calls;
+DROP TABLE IF EXISTS transactions;
+DROP TABLE IF EXISTS packages;
+DROP TABLE IF EXISTS object_fields;
+DROP TABLE IF EXISTS objects_snapshot;
+DROP TABLE IF EXISTS objects_history;
+DROP TABLE IF EXISTS objects;
+DROP TABLE IF EXISTS events;
+DROP TABLE IF EXISTS epochs;
+DROP TABLE IF EXISTS display;
+DROP TABLE IF EXISTS checkpoints;
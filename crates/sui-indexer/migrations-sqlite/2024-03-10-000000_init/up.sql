@@ -0,0 +1,220 @@
+-- The SQLite counterpart of the tables created by the Postgres migrations in `migrations/`, for
+-- single-machine deployments. SQLite has no array types, so array columns are stored as BCS
+-- serialized `Vec<Option<Vec<u8>>>` blobs instead, and tables are not partitioned.
+
+CREATE TABLE checkpoints
+(
+    sequence_number                     INTEGER      PRIMARY KEY,
+    checkpoint_digest                   BLOB         NOT NULL,
+    epoch                               INTEGER      NOT NULL,
+    network_total_transactions          INTEGER      NOT NULL,
+    previous_checkpoint_digest          BLOB,
+    end_of_epoch                        BOOLEAN      NOT NULL,
+    -- BCS serialized array of TransactionDigest bytes
+    tx_digests                          BLOB         NOT NULL,
+    timestamp_ms                        INTEGER      NOT NULL,
+    total_gas_cost                      INTEGER      NOT NULL,
+    computation_cost                    INTEGER      NOT NULL,
+    storage_cost                        INTEGER      NOT NULL,
+    storage_rebate                      INTEGER      NOT NULL,
+    non_refundable_storage_fee          INTEGER      NOT NULL,
+    checkpoint_commitments              BLOB         NOT NULL,
+    validator_signature                 BLOB         NOT NULL,
+    end_of_epoch_data                   BLOB
+);
+CREATE INDEX checkpoints_epoch ON checkpoints (epoch, sequence_number);
+CREATE INDEX checkpoints_digest ON checkpoints (checkpoint_digest);
+
+CREATE TABLE display
+(
+    object_type     TEXT        PRIMARY KEY,
+    id              BLOB        NOT NULL,
+    version         SMALLINT    NOT NULL,
+    bcs             BLOB        NOT NULL
+);
+
+CREATE TABLE epochs
+(
+    epoch                           INTEGER     PRIMARY KEY,
+    first_checkpoint_id             INTEGER     NOT NULL,
+    epoch_start_timestamp           INTEGER     NOT NULL,
+    reference_gas_price             INTEGER     NOT NULL,
+    protocol_version                INTEGER     NOT NULL,
+    total_stake                     INTEGER     NOT NULL,
+    storage_fund_balance            INTEGER     NOT NULL,
+    system_state                    BLOB        NOT NULL,
+    epoch_total_transactions        INTEGER,
+    last_checkpoint_id              INTEGER,
+    epoch_end_timestamp             INTEGER,
+    storage_fund_reinvestment       INTEGER,
+    storage_charge                  INTEGER,
+    storage_rebate                  INTEGER,
+    stake_subsidy_amount            INTEGER,
+    total_gas_fees                  INTEGER,
+    total_stake_rewards_distributed INTEGER,
+    leftover_storage_fund_inflow    INTEGER,
+    epoch_commitments               BLOB
+);
+
+CREATE TABLE events
+(
+    tx_sequence_number          INTEGER      NOT NULL,
+    event_sequence_number       INTEGER      NOT NULL,
+    transaction_digest          BLOB         NOT NULL,
+    checkpoint_sequence_number  INTEGER      NOT NULL,
+    -- BCS serialized array of SuiAddress bytes
+    senders                     BLOB         NOT NULL,
+    package                     BLOB         NOT NULL,
+    module                      TEXT         NOT NULL,
+    event_type                  TEXT         NOT NULL,
+    timestamp_ms                INTEGER      NOT NULL,
+    bcs                         BLOB         NOT NULL,
+    PRIMARY KEY(tx_sequence_number, event_sequence_number)
+);
+CREATE INDEX events_package ON events (package, tx_sequence_number, event_sequence_number);
+CREATE INDEX events_package_module ON events (package, module, tx_sequence_number, event_sequence_number);
+CREATE INDEX events_event_type ON events (event_type, tx_sequence_number, event_sequence_number);
+CREATE INDEX events_checkpoint_sequence_number ON events (checkpoint_sequence_number);
+
+CREATE TABLE objects (
+    object_id                   BLOB          PRIMARY KEY,
+    object_version              INTEGER       NOT NULL,
+    object_digest               BLOB          NOT NULL,
+    checkpoint_sequence_number  INTEGER       NOT NULL,
+    owner_type                  SMALLINT      NOT NULL,
+    owner_id                    BLOB,
+    object_type                 TEXT,
+    serialized_object           BLOB          NOT NULL,
+    coin_type                   TEXT,
+    coin_balance                INTEGER,
+    df_kind                     SMALLINT,
+    df_name                     BLOB,
+    df_object_type              TEXT,
+    df_object_id                BLOB
+);
+CREATE INDEX objects_owner ON objects (owner_type, owner_id) WHERE owner_type BETWEEN 1 AND 2 AND owner_id IS NOT NULL;
+CREATE INDEX objects_coin ON objects (owner_id, coin_type) WHERE coin_type IS NOT NULL AND owner_type = 1;
+CREATE INDEX objects_checkpoint_sequence_number ON objects (checkpoint_sequence_number);
+CREATE INDEX objects_type ON objects (object_type);
+
+CREATE TABLE objects_history (
+    object_id                   BLOB          NOT NULL,
+    object_version              INTEGER       NOT NULL,
+    object_status               SMALLINT      NOT NULL,
+    object_digest               BLOB,
+    checkpoint_sequence_number  INTEGER       NOT NULL,
+    owner_type                  SMALLINT,
+    owner_id                    BLOB,
+    object_type                 TEXT,
+    serialized_object           BLOB,
+    coin_type                   TEXT,
+    coin_balance                INTEGER,
+    df_kind                     SMALLINT,
+    df_name                     BLOB,
+    df_object_type              TEXT,
+    df_object_id                BLOB,
+    PRIMARY KEY (checkpoint_sequence_number, object_id, object_version)
+);
+CREATE INDEX objects_history_owner ON objects_history (checkpoint_sequence_number, owner_type, owner_id) WHERE owner_type BETWEEN 1 AND 2 AND owner_id IS NOT NULL;
+CREATE INDEX objects_history_coin ON objects_history (checkpoint_sequence_number, owner_id, coin_type) WHERE coin_type IS NOT NULL AND owner_type = 1;
+CREATE INDEX objects_history_type ON objects_history (checkpoint_sequence_number, object_type);
+
+CREATE TABLE objects_snapshot (
+    object_id                   BLOB          PRIMARY KEY,
+    object_version              INTEGER       NOT NULL,
+    object_status               SMALLINT      NOT NULL,
+    object_digest               BLOB,
+    checkpoint_sequence_number  INTEGER       NOT NULL,
+    owner_type                  SMALLINT,
+    owner_id                    BLOB,
+    object_type                 TEXT,
+    serialized_object           BLOB,
+    coin_type                   TEXT,
+    coin_balance                INTEGER,
+    df_kind                     SMALLINT,
+    df_name                     BLOB,
+    df_object_type              TEXT,
+    df_object_id                BLOB
+);
+CREATE INDEX objects_snapshot_checkpoint_sequence_number ON objects_snapshot (checkpoint_sequence_number);
+CREATE INDEX objects_snapshot_owner ON objects_snapshot (owner_type, owner_id, object_id) WHERE owner_type BETWEEN 1 AND 2 AND owner_id IS NOT NULL;
+CREATE INDEX objects_snapshot_coin ON objects_snapshot (owner_id, coin_type, object_id) WHERE coin_type IS NOT NULL AND owner_type = 1;
+CREATE INDEX objects_snapshot_type ON objects_snapshot (object_type, object_id);
+
+CREATE TABLE object_fields (
+    object_id                   BLOB          NOT NULL,
+    object_version              INTEGER       NOT NULL,
+    checkpoint_sequence_number  INTEGER       NOT NULL,
+    field_name                  TEXT          NOT NULL,
+    field_value                 TEXT          NOT NULL,
+    is_numeric                  BOOLEAN       NOT NULL,
+    -- Integers beyond 64 bits are approximated, as SQLite has no arbitrary precision numeric type.
+    numeric_value               NUMERIC       GENERATED ALWAYS AS (CASE WHEN is_numeric THEN CAST(field_value AS NUMERIC) END) STORED,
+    PRIMARY KEY (object_id, object_version, field_name)
+);
+CREATE INDEX object_fields_numeric ON object_fields (field_name, numeric_value) WHERE is_numeric;
+CREATE INDEX object_fields_value ON object_fields (field_name, field_value);
+CREATE INDEX object_fields_checkpoint_sequence_number ON object_fields (checkpoint_sequence_number);
+
+CREATE TABLE packages
+(
+    package_id                   BLOB           PRIMARY KEY,
+    move_package                 BLOB           NOT NULL
+);
+
+CREATE TABLE transactions (
+    tx_sequence_number          INTEGER      NOT NULL,
+    transaction_digest          BLOB         NOT NULL,
+    raw_transaction             BLOB         NOT NULL,
+    raw_effects                 BLOB         NOT NULL,
+    checkpoint_sequence_number  INTEGER      NOT NULL,
+    timestamp_ms                INTEGER      NOT NULL,
+    -- BCS serialized arrays of bcs serialized IndexedObjectChange, BalanceChange and StoredEvent
+    -- bytes, respectively
+    object_changes              BLOB         NOT NULL,
+    balance_changes             BLOB         NOT NULL,
+    events                      BLOB         NOT NULL,
+    transaction_kind            SMALLINT     NOT NULL,
+    success_command_count       SMALLINT     NOT NULL,
+    PRIMARY KEY (tx_sequence_number, checkpoint_sequence_number)
+);
+CREATE INDEX transactions_transaction_digest ON transactions (transaction_digest);
+CREATE INDEX transactions_checkpoint_sequence_number ON transactions (checkpoint_sequence_number);
+CREATE INDEX transactions_transaction_kind ON transactions (transaction_kind) WHERE transaction_kind = 0;
+
+CREATE TABLE tx_calls (
+    tx_sequence_number          INTEGER      NOT NULL,
+    package                     BLOB         NOT NULL,
+    module                      TEXT         NOT NULL,
+    func                        TEXT         NOT NULL,
+    PRIMARY KEY(package, tx_sequence_number)
+);
+CREATE INDEX tx_calls_module ON tx_calls (package, module, tx_sequence_number);
+CREATE INDEX tx_calls_func ON tx_calls (package, module, func, tx_sequence_number);
+CREATE INDEX tx_calls_tx_sequence_number ON tx_calls (tx_sequence_number);
+
+CREATE TABLE tx_changed_objects (
+    tx_sequence_number          INTEGER      NOT NULL,
+    object_id                   BLOB         NOT NULL,
+    PRIMARY KEY(object_id, tx_sequence_number)
+);
+
+CREATE TABLE tx_input_objects (
+    tx_sequence_number          INTEGER      NOT NULL,
+    object_id                   BLOB         NOT NULL,
+    PRIMARY KEY(object_id, tx_sequence_number)
+);
+
+CREATE TABLE tx_recipients (
+    tx_sequence_number          INTEGER      NOT NULL,
+    recipient                   BLOB         NOT NULL,
+    PRIMARY KEY(recipient, tx_sequence_number)
+);
+CREATE INDEX tx_recipients_tx_sequence_number_index ON tx_recipients (tx_sequence_number ASC);
+
+CREATE TABLE tx_senders (
+    tx_sequence_number          INTEGER      NOT NULL,
+    sender                      BLOB         NOT NULL,
+    PRIMARY KEY(sender, tx_sequence_number)
+);
+CREATE INDEX tx_senders_tx_sequence_number_index ON tx_senders (tx_sequence_number ASC);
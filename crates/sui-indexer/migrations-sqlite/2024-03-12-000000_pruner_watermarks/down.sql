@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS tx_changed_objects_tx_sequence_number_index;
+DROP INDEX IF EXISTS tx_input_objects_tx_sequence_number_index;
+DROP TABLE IF EXISTS pruner_watermarks;
@@ -0,0 +1,11 @@
+-- The earliest epoch that is still retained in each table pruned by the indexer's retention
+-- policies, and that epoch's first checkpoint. Rows before it may already have been deleted.
+CREATE TABLE pruner_watermarks (
+    table_name                  TEXT          PRIMARY KEY,
+    epoch                       INTEGER       NOT NULL,
+    checkpoint_sequence_number  INTEGER       NOT NULL
+);
+
+-- The pruner deletes from these tables by transaction sequence number.
+CREATE INDEX tx_input_objects_tx_sequence_number_index ON tx_input_objects (tx_sequence_number ASC);
+CREATE INDEX tx_changed_objects_tx_sequence_number_index ON tx_changed_objects (tx_sequence_number ASC);
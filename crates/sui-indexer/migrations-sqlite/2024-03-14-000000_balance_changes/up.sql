@@ -0,0 +1,17 @@
+-- Changes to the balance of each coin type owned by an address, one row per transaction that
+-- changed it, along with the balance after that transaction.
+CREATE TABLE balance_changes (
+    owner                       BLOB          NOT NULL,
+    -- Coin type in its canonical form, e.g. `0x0000..0002::sui::SUI`.
+    coin_type                   TEXT          NOT NULL,
+    tx_sequence_number          INTEGER       NOT NULL,
+    transaction_digest          BLOB          NOT NULL,
+    checkpoint_sequence_number  INTEGER       NOT NULL,
+    timestamp_ms                INTEGER       NOT NULL,
+    -- Signed change in balance, negative when the balance decreased. Stored as a decimal string,
+    -- because it may not fit in an INTEGER.
+    amount                      TEXT          NOT NULL,
+    -- Balance after the transaction, also as a decimal string.
+    balance                     TEXT          NOT NULL,
+    PRIMARY KEY (owner, coin_type, tx_sequence_number)
+);
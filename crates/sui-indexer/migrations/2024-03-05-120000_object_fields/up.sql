@@ -0,0 +1,18 @@
+-- Values of fields projected from versions of objects, according to the indexer's field
+-- projections config. Rows are keyed by object version, so that objects can be filtered by field
+-- values consistently with `objects_snapshot` and `objects_history`.
+CREATE TABLE object_fields (
+    object_id                   bytea         NOT NULL,
+    object_version              bigint        NOT NULL,
+    checkpoint_sequence_number  bigint        NOT NULL,
+    -- Path to the field from the object's top-level struct, e.g. `price` or `item.name`.
+    field_name                  text          NOT NULL,
+    -- Integers in decimal, addresses and IDs in 0x-prefixed hex, and strings and booleans as-is.
+    field_value                 text          NOT NULL,
+    is_numeric                  boolean       NOT NULL,
+    numeric_value               numeric       GENERATED ALWAYS AS (CASE WHEN is_numeric THEN field_value::numeric END) STORED,
+    CONSTRAINT object_fields_pk PRIMARY KEY (object_id, object_version, field_name)
+);
+CREATE INDEX object_fields_numeric ON object_fields (field_name, numeric_value) WHERE is_numeric;
+CREATE INDEX object_fields_value ON object_fields (field_name, field_value);
+CREATE INDEX object_fields_checkpoint_sequence_number ON object_fields (checkpoint_sequence_number);
@@ -0,0 +1,7 @@
+-- Requests made with each GraphQL API key during its current quota window, shared by all GraphQL
+-- service instances that count quotas in the database.
+CREATE TABLE graphql_api_key_quotas (
+    api_key       text          PRIMARY KEY,
+    quota_window  bigint        NOT NULL,
+    requests      bigint        NOT NULL
+);
@@ -0,0 +1,16 @@
+-- Changes to the balance of each coin type owned by an address, one row per transaction that
+-- changed it, along with the balance after that transaction.
+CREATE TABLE balance_changes (
+    owner                       bytea         NOT NULL,
+    -- Coin type in its canonical form, e.g. `0x0000..0002::sui::SUI`.
+    coin_type                   text          NOT NULL,
+    tx_sequence_number          bigint        NOT NULL,
+    transaction_digest          bytea         NOT NULL,
+    checkpoint_sequence_number  bigint        NOT NULL,
+    timestamp_ms                bigint        NOT NULL,
+    -- Signed change in balance, negative when the balance decreased.
+    amount                      numeric       NOT NULL,
+    -- Balance after the transaction.
+    balance                     numeric       NOT NULL,
+    PRIMARY KEY (owner, coin_type, tx_sequence_number)
+);
@@ -3,12 +3,17 @@
 
 use crate::indexer_reader::IndexerReader;
 use jsonrpsee::{core::RpcResult, RpcModule};
+use sui_json_rpc::coin_api::parse_to_type_tag;
 use sui_json_rpc::SuiRpcModule;
-use sui_json_rpc_api::{validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS};
+use sui_json_rpc_api::{
+    validate_limit, ExtendedApiServer, QUERY_MAX_RESULT_LIMIT, QUERY_MAX_RESULT_LIMIT_CHECKPOINTS,
+};
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, Page, QueryObjectsPage, SuiObjectResponseQuery,
+    AddressBalanceChange, AddressBalanceChangePage, CheckpointedObjectID, EpochInfo, EpochPage,
+    Page, QueryObjectsPage, SuiObjectResponseQuery,
 };
 use sui_open_rpc::Module;
+use sui_types::base_types::SuiAddress;
 use sui_types::sui_serde::BigInt;
 
 pub(crate) struct ExtendedApi {
@@ -78,6 +83,44 @@ impl ExtendedApiServer for ExtendedApi {
             .await?;
         Ok(latest_checkpoint.network_total_transactions.into())
     }
+
+    async fn get_balance_changes(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<AddressBalanceChangePage> {
+        let limit = validate_limit(limit, *QUERY_MAX_RESULT_LIMIT)?;
+        // Normalize coin type tag and default to Gas
+        let coin_type =
+            parse_to_type_tag(coin_type)?.to_canonical_string(/* with_prefix */ true);
+        let mut changes = self
+            .inner
+            .spawn_blocking(move |this| {
+                this.get_balance_changes(
+                    owner,
+                    coin_type,
+                    cursor.map(|x| *x),
+                    limit + 1,
+                    descending_order.unwrap_or(false),
+                )
+            })
+            .await?;
+
+        let has_next_page = changes.len() > limit;
+        changes.truncate(limit);
+        let next_cursor = changes.last().map(|c| c.tx_sequence_number as u64);
+        Ok(Page {
+            data: changes
+                .into_iter()
+                .map(AddressBalanceChange::try_from)
+                .collect::<Result<_, _>>()?,
+            next_cursor: next_cursor.map(|seq| seq.into()),
+            has_next_page,
+        })
+    }
 }
 
 impl SuiRpcModule for ExtendedApi {
@@ -289,7 +289,12 @@ impl IndexerApiServer for IndexerApi {
         ))
     }
 
-    fn subscribe_event(&self, _sink: SubscriptionSink, _filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        _sink: SubscriptionSink,
+        _filter: EventFilter,
+        _cursor: Option<EventID>,
+    ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
 
@@ -297,6 +302,7 @@ impl IndexerApiServer for IndexerApi {
         &self,
         _sink: SubscriptionSink,
         _filter: TransactionFilter,
+        _cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
         Err(SubscriptionEmptyError)
     }
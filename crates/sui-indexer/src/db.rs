@@ -5,29 +5,40 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use diesel::migration::MigrationSource;
-use diesel::{r2d2::ConnectionManager, PgConnection, RunQueryDsl};
+use diesel::{r2d2::ConnectionManager, RunQueryDsl};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use fastcrypto::encoding::{Encoding, Hex};
 use tracing::info;
 
 use crate::errors::IndexerError;
 
-pub type PgConnectionPool = diesel::r2d2::Pool<ConnectionManager<PgConnection>>;
-pub type PgPoolConnection = diesel::r2d2::PooledConnection<ConnectionManager<PgConnection>>;
+#[cfg(feature = "postgres-feature")]
+pub type Db = diesel::pg::Pg;
+#[cfg(not(feature = "postgres-feature"))]
+pub type Db = diesel::sqlite::Sqlite;
+
+#[cfg(feature = "postgres-feature")]
+pub type DbConnection = diesel::PgConnection;
+#[cfg(not(feature = "postgres-feature"))]
+pub type DbConnection = diesel::SqliteConnection;
+
+pub type ConnectionPool = diesel::r2d2::Pool<ConnectionManager<DbConnection>>;
+pub type PoolConnection = diesel::r2d2::PooledConnection<ConnectionManager<DbConnection>>;
 
 #[derive(Debug, Clone, Copy)]
-pub struct PgConnectionPoolConfig {
+pub struct ConnectionPoolConfig {
     pub pool_size: u32,
     pub connection_timeout: Duration,
     pub statement_timeout: Duration,
 }
 
-impl PgConnectionPoolConfig {
+impl ConnectionPoolConfig {
     const DEFAULT_POOL_SIZE: u32 = 100;
     const DEFAULT_CONNECTION_TIMEOUT: u64 = 3600;
     const DEFAULT_STATEMENT_TIMEOUT: u64 = 3600;
 
-    fn connection_config(&self) -> PgConnectionConfig {
-        PgConnectionConfig {
+    fn connection_config(&self) -> ConnectionConfig {
+        ConnectionConfig {
             statement_timeout: self.statement_timeout,
             read_only: false,
         }
@@ -46,7 +57,7 @@ impl PgConnectionPoolConfig {
     }
 }
 
-impl Default for PgConnectionPoolConfig {
+impl Default for ConnectionPoolConfig {
     fn default() -> Self {
         let db_pool_size = std::env::var("DB_POOL_SIZE")
             .ok()
@@ -70,13 +81,14 @@ impl Default for PgConnectionPoolConfig {
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct PgConnectionConfig {
+pub struct ConnectionConfig {
     pub statement_timeout: Duration,
     pub read_only: bool,
 }
 
-impl diesel::r2d2::CustomizeConnection<PgConnection, diesel::r2d2::Error> for PgConnectionConfig {
-    fn on_acquire(&self, conn: &mut PgConnection) -> std::result::Result<(), diesel::r2d2::Error> {
+#[cfg(feature = "postgres-feature")]
+impl diesel::r2d2::CustomizeConnection<DbConnection, diesel::r2d2::Error> for ConnectionConfig {
+    fn on_acquire(&self, conn: &mut DbConnection) -> std::result::Result<(), diesel::r2d2::Error> {
         use diesel::sql_query;
 
         sql_query(format!(
@@ -96,20 +108,43 @@ impl diesel::r2d2::CustomizeConnection<PgConnection, diesel::r2d2::Error> for Pg
     }
 }
 
-pub fn new_pg_connection_pool(
+/// SQLite has no statement timeout, so the timeout is instead used for how long a connection
+/// waits on the database lock held by another connection before giving up.
+#[cfg(not(feature = "postgres-feature"))]
+impl diesel::r2d2::CustomizeConnection<DbConnection, diesel::r2d2::Error> for ConnectionConfig {
+    fn on_acquire(&self, conn: &mut DbConnection) -> std::result::Result<(), diesel::r2d2::Error> {
+        use diesel::connection::SimpleConnection;
+
+        // WAL lets readers proceed while the indexer is writing.
+        conn.batch_execute(&format!(
+            "PRAGMA busy_timeout = {}; PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;",
+            self.statement_timeout.as_millis(),
+        ))
+        .map_err(diesel::r2d2::Error::QueryError)?;
+
+        if self.read_only {
+            conn.batch_execute("PRAGMA query_only = ON;")
+                .map_err(diesel::r2d2::Error::QueryError)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn new_connection_pool(
     db_url: &str,
     pool_size: Option<u32>,
-) -> Result<PgConnectionPool, IndexerError> {
-    let pool_config = PgConnectionPoolConfig::default();
-    new_pg_connection_pool_with_config(db_url, pool_size, pool_config)
+) -> Result<ConnectionPool, IndexerError> {
+    let pool_config = ConnectionPoolConfig::default();
+    new_connection_pool_with_config(db_url, pool_size, pool_config)
 }
 
-pub fn new_pg_connection_pool_with_config(
+pub fn new_connection_pool_with_config(
     db_url: &str,
     pool_size: Option<u32>,
-    pool_config: PgConnectionPoolConfig,
-) -> Result<PgConnectionPool, IndexerError> {
-    let manager = ConnectionManager::<PgConnection>::new(db_url);
+    pool_config: ConnectionPoolConfig,
+) -> Result<ConnectionPool, IndexerError> {
+    let manager = ConnectionManager::<DbConnection>::new(db_url);
 
     let pool_size = pool_size.unwrap_or(pool_config.pool_size);
     diesel::r2d2::Pool::builder()
@@ -118,30 +153,33 @@ pub fn new_pg_connection_pool_with_config(
         .connection_customizer(Box::new(pool_config.connection_config()))
         .build(manager)
         .map_err(|e| {
-            IndexerError::PgConnectionPoolInitError(format!(
+            IndexerError::ConnectionPoolInitError(format!(
                 "Failed to initialize connection pool with error: {:?}",
                 e
             ))
         })
 }
 
-pub fn get_pg_pool_connection(pool: &PgConnectionPool) -> Result<PgPoolConnection, IndexerError> {
+pub fn get_pool_connection(pool: &ConnectionPool) -> Result<PoolConnection, IndexerError> {
     pool.get().map_err(|e| {
-        IndexerError::PgPoolConnectionError(format!(
-            "Failed to get connection from PG connection pool with error: {:?}",
+        IndexerError::PoolConnectionError(format!(
+            "Failed to get connection from DB connection pool with error: {:?}",
             e
         ))
     })
 }
 
+#[cfg(feature = "postgres-feature")]
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+#[cfg(not(feature = "postgres-feature"))]
+const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations-sqlite");
 
 /// Resets the database by reverting all migrations and reapplying them.
 ///
 /// If `drop_all` is set to `true`, the function will drop all tables in the database before
 /// resetting the migrations. This option is destructive and will result in the loss of all
 /// data in the tables. Use with caution, especially in production environments.
-pub fn reset_database(conn: &mut PgPoolConnection, drop_all: bool) -> Result<(), anyhow::Error> {
+pub fn reset_database(conn: &mut PoolConnection, drop_all: bool) -> Result<(), anyhow::Error> {
     info!("Resetting database ...");
     if drop_all {
         drop_all_tables(conn)
@@ -156,7 +194,16 @@ pub fn reset_database(conn: &mut PgPoolConnection, drop_all: bool) -> Result<(),
     Ok(())
 }
 
-fn drop_all_tables(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
+/// Applies any migrations that have not yet been run against the database, leaving existing data
+/// in place.
+pub fn run_pending_migrations(conn: &mut PoolConnection) -> Result<(), anyhow::Error> {
+    conn.run_pending_migrations(MIGRATIONS)
+        .map_err(|e| anyhow!("Failed to run migrations {e}"))?;
+    Ok(())
+}
+
+#[cfg(feature = "postgres-feature")]
+fn drop_all_tables(conn: &mut DbConnection) -> Result<(), diesel::result::Error> {
     info!("Dropping all tables in the database");
     let table_names: Vec<String> = diesel::dsl::sql::<diesel::sql_types::Text>(
         "
@@ -183,3 +230,41 @@ fn drop_all_tables(conn: &mut PgConnection) -> Result<(), diesel::result::Error>
     info!("Dropped all tables in the database");
     Ok(())
 }
+
+#[cfg(not(feature = "postgres-feature"))]
+fn drop_all_tables(conn: &mut DbConnection) -> Result<(), diesel::result::Error> {
+    info!("Dropping all tables in the database");
+    let table_names: Vec<String> = diesel::dsl::sql::<diesel::sql_types::Text>(
+        "
+        SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+    ",
+    )
+    .load(conn)?;
+
+    for table_name in table_names {
+        let drop_table_query = format!("DROP TABLE IF EXISTS {}", table_name);
+        diesel::sql_query(drop_table_query).execute(conn)?;
+    }
+
+    // Recreate the __diesel_schema_migrations table
+    diesel::sql_query(
+        "
+        CREATE TABLE __diesel_schema_migrations (
+            version VARCHAR(50) PRIMARY KEY,
+            run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+    ",
+    )
+    .execute(conn)?;
+    info!("Dropped all tables in the database");
+    Ok(())
+}
+
+/// Renders `bytes` as a binary literal that can be spliced into raw SQL for the enabled backend.
+pub fn bytea_literal(bytes: &[u8]) -> String {
+    if cfg!(not(feature = "postgres-feature")) {
+        format!("X'{}'", Hex::encode(bytes))
+    } else {
+        format!("'\\x{}'::bytea", Hex::encode(bytes))
+    }
+}
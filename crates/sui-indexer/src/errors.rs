@@ -52,23 +52,23 @@ pub enum IndexerError {
     #[error("Indexer failed to find object mutations, which should never happen.")]
     ObjectMutationNotAvailable,
 
-    #[error("Indexer failed to build PG connection pool with error: `{0}`")]
-    PgConnectionPoolInitError(String),
+    #[error("Indexer failed to build DB connection pool with error: `{0}`")]
+    ConnectionPoolInitError(String),
 
-    #[error("Indexer failed to get a pool connection from PG connection pool with error: `{0}`")]
-    PgPoolConnectionError(String),
+    #[error("Indexer failed to get a pool connection from DB connection pool with error: `{0}`")]
+    PoolConnectionError(String),
 
-    #[error("Indexer failed to read PostgresDB with error: `{0}`")]
-    PostgresReadError(String),
+    #[error("Indexer failed to read DB with error: `{0}`")]
+    DbReadError(String),
 
-    #[error("Indexer failed to reset PostgresDB with error: `{0}`")]
-    PostgresResetError(String),
+    #[error("Indexer failed to reset DB with error: `{0}`")]
+    DbResetError(String),
 
-    #[error("Indexer failed to commit changes to PostgresDB with error: `{0}`")]
-    PostgresWriteError(String),
+    #[error("Indexer failed to commit changes to DB with error: `{0}`")]
+    DbWriteError(String),
 
     #[error(transparent)]
-    PostgresError(#[from] diesel::result::Error),
+    DbError(#[from] diesel::result::Error),
 
     #[error("Indexer failed to initialize fullnode Http client with error: `{0}`")]
     HttpClientInitError(String),
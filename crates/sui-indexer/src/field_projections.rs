@@ -0,0 +1,339 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Field projections extract the values of selected fields from Move objects of selected types, so
+//! that objects can be filtered and ordered by those values, without deserializing them. The
+//! types and fields to project are declared in a YAML file, e.g.:
+//!
+//! ```yaml
+//! projections:
+//!   - type: "0xabc::market::Listing"
+//!     fields: ["price", "seller", "item.name"]
+//! ```
+//!
+//! A type without type parameters matches all instantiations of that type, while a type with type
+//! parameters only matches that exact instantiation. Fields are named by their path from the
+//! object's top-level struct, with nested fields separated by `.`.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::{MoveStruct, MoveStructLayout, MoveValue};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use sui_types::object::Object;
+use sui_types::parse_sui_struct_tag;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::errors::IndexerError;
+use crate::types::{IndexedObjectField, IndexerResult};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FieldProjectionsConfig {
+    #[serde(default)]
+    pub projections: Vec<FieldProjectionConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FieldProjectionConfig {
+    /// The type of objects to project fields from.
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Paths to the fields to project.
+    pub fields: Vec<String>,
+}
+
+/// The fields to project from objects, by their types.
+#[derive(Clone, Debug, Default)]
+pub struct FieldProjections {
+    /// (address, module, name) -> projections for types with that name.
+    by_type: HashMap<(AccountAddress, Identifier, Identifier), Vec<Projection>>,
+}
+
+#[derive(Clone, Debug)]
+struct Projection {
+    /// The exact type to match, or `None` if all instantiations of the type match.
+    exact: Option<StructTag>,
+    fields: Vec<String>,
+}
+
+impl FieldProjections {
+    pub fn new(config: &FieldProjectionsConfig) -> IndexerResult<Self> {
+        let mut by_type: HashMap<_, Vec<_>> = HashMap::new();
+
+        for FieldProjectionConfig { type_, fields } in &config.projections {
+            let tag = parse_sui_struct_tag(type_).map_err(|e| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Invalid type in field projection '{type_}': {e}"
+                ))
+            })?;
+
+            if let Some(field) = fields.iter().find(|f| f.split('.').any(str::is_empty)) {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "Invalid field path in field projection for '{type_}': '{field}'"
+                )));
+            }
+
+            by_type
+                .entry((tag.address, tag.module.clone(), tag.name.clone()))
+                .or_default()
+                .push(Projection {
+                    exact: (!tag.type_params.is_empty()).then_some(tag),
+                    fields: fields.clone(),
+                });
+        }
+
+        Ok(Self { by_type })
+    }
+
+    /// Load field projections from the YAML file at `path`.
+    pub fn load(path: &Path) -> IndexerResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Failed to read field projections from {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        let config: FieldProjectionsConfig = serde_yaml::from_str(&contents).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Failed to parse field projections from {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        Self::new(&config)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_type.is_empty()
+    }
+
+    /// Whether any fields are projected from objects of type `tag`.
+    pub fn matches(&self, tag: &StructTag) -> bool {
+        self.projections(tag).next().is_some()
+    }
+
+    /// Project fields out of `object`, whose contents have the layout `layout`. Fields that do not
+    /// exist, or whose values are not primitives, strings, or IDs are skipped.
+    pub fn project(
+        &self,
+        checkpoint_sequence_number: u64,
+        object: &Object,
+        layout: &MoveStructLayout,
+    ) -> IndexerResult<Vec<IndexedObjectField>> {
+        let Some(move_object) = object.data.try_as_move() else {
+            return Ok(vec![]);
+        };
+
+        let tag: StructTag = move_object.type_().clone().into();
+        let mut projections = self.projections(&tag).peekable();
+        if projections.peek().is_none() {
+            return Ok(vec![]);
+        }
+
+        let move_struct = move_object.to_move_struct(layout)?;
+        let mut fields = vec![];
+        for field_name in projections.flat_map(|p| p.fields.iter()) {
+            let Some((field_value, is_numeric)) =
+                field(&move_struct, field_name).and_then(field_value)
+            else {
+                continue;
+            };
+
+            fields.push(IndexedObjectField {
+                object_id: object.id(),
+                object_version: object.version().value(),
+                checkpoint_sequence_number,
+                field_name: field_name.clone(),
+                field_value,
+                is_numeric,
+            });
+        }
+
+        Ok(fields)
+    }
+
+    fn projections<'p>(&'p self, tag: &'p StructTag) -> impl Iterator<Item = &'p Projection> {
+        let key = (tag.address, tag.module.clone(), tag.name.clone());
+        self.by_type
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .filter(move |p| p.exact.as_ref().map_or(true, |exact| exact == tag))
+    }
+}
+
+/// Find the value at `path` (field names separated by `.`) in `move_struct`.
+fn field<'s>(move_struct: &'s MoveStruct, path: &str) -> Option<&'s MoveValue> {
+    let mut fields = &move_struct.fields;
+    let mut value = None;
+
+    for name in path.split('.') {
+        if let Some(MoveValue::Struct(s)) = value {
+            fields = &s.fields;
+        } else if value.is_some() {
+            return None;
+        }
+
+        value = Some(&fields.iter().find(|(n, _)| n.as_str() == name)?.1);
+    }
+
+    value
+}
+
+/// The text representation of `value`, and whether it is numeric, for values that can be projected:
+/// integers (as decimals), booleans, addresses, IDs and UIDs (as `0x`-prefixed hex), strings, and
+/// options of these.
+fn field_value(value: &MoveValue) -> Option<(String, bool)> {
+    use MoveValue as V;
+    Some(match value {
+        V::U8(n) => (n.to_string(), true),
+        V::U16(n) => (n.to_string(), true),
+        V::U32(n) => (n.to_string(), true),
+        V::U64(n) => (n.to_string(), true),
+        V::U128(n) => (n.to_string(), true),
+        V::U256(n) => (n.to_string(), true),
+        V::Bool(b) => (b.to_string(), false),
+        V::Address(a) | V::Signer(a) => (a.to_canonical_string(/* with_prefix */ true), false),
+        V::Vector(_) => return None,
+
+        V::Struct(s) => {
+            let tag = &s.type_;
+            match (tag.address, tag.module.as_str(), tag.name.as_str()) {
+                (MOVE_STDLIB_ADDRESS, "string" | "ascii", "String") => {
+                    let [(_, V::Vector(bytes))] = s.fields.as_slice() else {
+                        return None;
+                    };
+
+                    let bytes = bytes
+                        .iter()
+                        .map(|b| match b {
+                            V::U8(b) => Some(*b),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+
+                    (String::from_utf8(bytes).ok()?, false)
+                }
+
+                (MOVE_STDLIB_ADDRESS, "option", "Option") => {
+                    let [(_, V::Vector(elems))] = s.fields.as_slice() else {
+                        return None;
+                    };
+
+                    let [elem] = elems.as_slice() else {
+                        return None;
+                    };
+
+                    field_value(elem)?
+                }
+
+                (SUI_FRAMEWORK_ADDRESS, "object", "ID" | "UID") if s.fields.len() == 1 => {
+                    field_value(&s.fields[0].1)?
+                }
+
+                _ => return None,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::ident_str;
+    use move_core_types::u256::U256;
+
+    fn tag(s: &str) -> StructTag {
+        parse_sui_struct_tag(s).unwrap()
+    }
+
+    fn string(s: &str) -> MoveValue {
+        MoveValue::Struct(MoveStruct {
+            type_: tag("0x1::string::String"),
+            fields: vec![(
+                ident_str!("bytes").to_owned(),
+                MoveValue::Vector(s.bytes().map(MoveValue::U8).collect()),
+            )],
+        })
+    }
+
+    #[test]
+    fn test_matches() {
+        let projections = FieldProjections::new(&FieldProjectionsConfig {
+            projections: vec![
+                FieldProjectionConfig {
+                    type_: "0xabc::market::Listing".to_string(),
+                    fields: vec!["price".to_string()],
+                },
+                FieldProjectionConfig {
+                    type_: "0xabc::market::Bid<0x2::sui::SUI>".to_string(),
+                    fields: vec!["amount".to_string()],
+                },
+            ],
+        })
+        .unwrap();
+
+        assert!(projections.matches(&tag("0xabc::market::Listing<0x2::sui::SUI>")));
+        assert!(projections.matches(&tag("0xabc::market::Listing<0xabc::nft::Nft>")));
+        assert!(projections.matches(&tag("0xabc::market::Bid<0x2::sui::SUI>")));
+        assert!(!projections.matches(&tag("0xabc::market::Bid<0xabc::nft::Nft>")));
+        assert!(!projections.matches(&tag("0xabc::market::Offer")));
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        for (type_, field) in [
+            ("not a type", "price"),
+            ("0xabc::market::Listing", "item..price"),
+        ] {
+            let config = FieldProjectionsConfig {
+                projections: vec![FieldProjectionConfig {
+                    type_: type_.to_string(),
+                    fields: vec![field.to_string()],
+                }],
+            };
+
+            assert!(FieldProjections::new(&config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_field_values() {
+        let item = MoveValue::Struct(MoveStruct {
+            type_: tag("0xabc::nft::Nft"),
+            fields: vec![(ident_str!("name").to_owned(), string("Sword"))],
+        });
+
+        let listing = MoveStruct {
+            type_: tag("0xabc::market::Listing<0xabc::nft::Nft>"),
+            fields: vec![
+                (ident_str!("price").to_owned(), MoveValue::U64(100)),
+                (
+                    ident_str!("reserve").to_owned(),
+                    MoveValue::U256(U256::from(7u8)),
+                ),
+                (ident_str!("active").to_owned(), MoveValue::Bool(true)),
+                (ident_str!("item").to_owned(), item),
+                (
+                    ident_str!("tags").to_owned(),
+                    MoveValue::Vector(vec![MoveValue::U8(1)]),
+                ),
+            ],
+        };
+
+        let value = |path| field(&listing, path).and_then(field_value);
+        assert_eq!(value("price"), Some(("100".to_string(), true)));
+        assert_eq!(value("reserve"), Some(("7".to_string(), true)));
+        assert_eq!(value("active"), Some(("true".to_string(), false)));
+        assert_eq!(value("item.name"), Some(("Sword".to_string(), false)));
+        assert_eq!(value("tags"), None);
+        assert_eq!(value("price.value"), None);
+        assert_eq!(value("missing"), None);
+    }
+}
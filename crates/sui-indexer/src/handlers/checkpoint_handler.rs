@@ -38,15 +38,17 @@ use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary
 use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 
 use crate::errors::IndexerError;
+use crate::field_projections::FieldProjections;
 use crate::framework::interface::Handler;
 use crate::metrics::IndexerMetrics;
 
-use crate::db::PgConnectionPool;
+use crate::db::ConnectionPool;
 use crate::store::package_resolver::{IndexerStorePackageResolver, InterimPackageResolver};
 use crate::store::{IndexerStore, PgIndexerStore};
 use crate::types::{
-    IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo, IndexedEvent, IndexedObject,
-    IndexedPackage, IndexedTransaction, IndexerResult, TransactionKind, TxIndex,
+    IndexedBalanceChange, IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo, IndexedEvent,
+    IndexedObject, IndexedObjectField, IndexedPackage, IndexedTransaction, IndexerResult,
+    TransactionKind, TxIndex,
 };
 
 use super::tx_processor::EpochEndIndexingObjectStore;
@@ -61,6 +63,7 @@ pub async fn new_handlers<S>(
     state: S,
     client: Client,
     metrics: IndexerMetrics,
+    field_projections: FieldProjections,
 ) -> Result<CheckpointHandler<S>, IndexerError>
 where
     S: IndexerStore + Clone + Sync + Send + 'static,
@@ -95,6 +98,7 @@ where
         metrics,
         indexed_checkpoint_sender,
         package_buffer: IndexingPackageBuffer::start(package_tx),
+        field_projections: Arc::new(field_projections),
     };
 
     Ok(checkpoint_handler)
@@ -107,6 +111,8 @@ pub struct CheckpointHandler<S> {
     // buffers for packages that are being indexed but not committed to DB,
     // they will be periodically GCed to avoid OOM.
     package_buffer: Arc<Mutex<IndexingPackageBuffer>>,
+    // fields to project from objects, to filter and order objects by.
+    field_projections: Arc<FieldProjections>,
 }
 
 #[async_trait]
@@ -173,6 +179,7 @@ where
                 metrics_clone.clone(),
                 packages,
                 package_resolver.clone(),
+                self.field_projections.clone(),
             )));
         }
         let checkpoint_data_to_commit = futures::future::join_all(tasks)
@@ -299,12 +306,34 @@ where
         }))
     }
 
+    /// Indexes a single checkpoint on its own, resolving packages from the store only. Used to
+    /// re-index history, where every package the checkpoint depends on is already committed.
+    pub(crate) async fn index_checkpoint(
+        state: S,
+        data: CheckpointData,
+        metrics: IndexerMetrics,
+        package_resolver: Arc<Resolver<impl PackageStore>>,
+        field_projections: Arc<FieldProjections>,
+    ) -> Result<CheckpointDataToCommit, IndexerError> {
+        let packages = Self::index_packages(std::slice::from_ref(&data), &metrics);
+        Self::index_one_checkpoint(
+            Arc::new(state),
+            data,
+            Arc::new(metrics),
+            packages,
+            package_resolver,
+            field_projections,
+        )
+        .await
+    }
+
     async fn index_one_checkpoint(
         state: Arc<S>,
         data: CheckpointData,
         metrics: Arc<IndexerMetrics>,
         packages: Vec<IndexedPackage>,
         package_resolver: Arc<Resolver<impl PackageStore>>,
+        field_projections: Arc<FieldProjections>,
     ) -> Result<CheckpointDataToCommit, IndexerError> {
         let checkpoint_seq = data.checkpoint_summary.sequence_number;
         info!(checkpoint_seq, "Indexing checkpoint data blob");
@@ -317,6 +346,8 @@ where
             Self::index_objects(data.clone(), &metrics, package_resolver.clone()).await?;
         let object_history_changes: TransactionObjectChangesToCommit =
             Self::index_objects_history(data.clone(), package_resolver.clone()).await?;
+        let object_fields =
+            Self::index_object_fields(&data, &field_projections, package_resolver.clone()).await?;
 
         let (checkpoint, db_transactions, db_events, db_indices, db_displays) = {
             let CheckpointData {
@@ -346,6 +377,10 @@ where
                 db_displays,
             )
         };
+        let balance_changes = db_transactions
+            .iter()
+            .flat_map(IndexedBalanceChange::from_transaction)
+            .collect();
         info!(checkpoint_seq, "Indexed one checkpoint.");
         metrics
             .index_lag_ms
@@ -359,6 +394,8 @@ where
             display_updates: db_displays,
             object_changes,
             object_history_changes,
+            object_fields,
+            balance_changes,
             packages,
             epoch,
         })
@@ -671,6 +708,51 @@ where
         })
     }
 
+    // project fields out of every version of objects written in the checkpoint whose types are
+    // configured in `field_projections`.
+    async fn index_object_fields(
+        data: &CheckpointData,
+        field_projections: &FieldProjections,
+        package_resolver: Arc<Resolver<impl PackageStore>>,
+    ) -> Result<Vec<IndexedObjectField>, IndexerError> {
+        if field_projections.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let checkpoint_seq = data.checkpoint_summary.sequence_number;
+        let projected_objects: Vec<Object> = data
+            .output_objects()
+            .into_iter()
+            .filter(|o| {
+                o.data
+                    .try_as_move()
+                    .is_some_and(|m| field_projections.matches(&m.type_().clone().into()))
+            })
+            .cloned()
+            .collect();
+
+        if projected_objects.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let move_struct_layout_map =
+            get_move_struct_layout_map(&projected_objects, package_resolver).await?;
+        let mut fields = vec![];
+        for o in &projected_objects {
+            // safe to unwrap, only move objects were selected above.
+            let struct_tag: StructTag = o.data.try_as_move().unwrap().type_().clone().into();
+            let layout = move_struct_layout_map.get(&struct_tag).ok_or_else(|| {
+                IndexerError::ResolveMoveStructError(format!(
+                    "Cannot find struct layout in map for {:?}.",
+                    struct_tag
+                ))
+            })?;
+            fields.extend(field_projections.project(checkpoint_seq, o, layout)?);
+        }
+
+        Ok(fields)
+    }
+
     fn index_packages(
         checkpoint_data: &[CheckpointData],
         metrics: &IndexerMetrics,
@@ -722,7 +804,7 @@ where
             .collect()
     }
 
-    fn pg_blocking_cp(&self) -> Result<PgConnectionPool, IndexerError> {
+    fn pg_blocking_cp(&self) -> Result<ConnectionPool, IndexerError> {
         let state_as_any = self.state.as_any();
         if let Some(pg_state) = state_as_any.downcast_ref::<PgIndexerStore>() {
             return Ok(pg_state.blocking_cp());
@@ -121,6 +121,8 @@ async fn commit_checkpoints<S>(
     let mut display_updates_batch = BTreeMap::new();
     let mut object_changes_batch = vec![];
     let mut object_history_changes_batch = vec![];
+    let mut object_fields_batch = vec![];
+    let mut balance_changes_batch = vec![];
     let mut packages_batch = vec![];
 
     for indexed_checkpoint in indexed_checkpoint_batch {
@@ -132,6 +134,8 @@ async fn commit_checkpoints<S>(
             display_updates,
             object_changes,
             object_history_changes,
+            object_fields,
+            balance_changes,
             packages,
             epoch: _,
         } = indexed_checkpoint;
@@ -142,6 +146,8 @@ async fn commit_checkpoints<S>(
         display_updates_batch.extend(display_updates.into_iter());
         object_changes_batch.push(object_changes);
         object_history_changes_batch.push(object_history_changes);
+        object_fields_batch.push(object_fields);
+        balance_changes_batch.push(balance_changes);
         packages_batch.push(packages);
     }
 
@@ -153,11 +159,32 @@ async fn commit_checkpoints<S>(
     let tx_indices_batch = tx_indices_batch.into_iter().flatten().collect::<Vec<_>>();
     let events_batch = events_batch.into_iter().flatten().collect::<Vec<_>>();
     let packages_batch = packages_batch.into_iter().flatten().collect::<Vec<_>>();
+    let object_fields_batch = object_fields_batch
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let balance_changes_batch = balance_changes_batch
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
     let checkpoint_num = checkpoint_batch.len();
     let tx_count = tx_batch.len();
 
     {
         let _step_1_guard = metrics.checkpoint_db_commit_latency_step_1.start_timer();
+        // Balance changes are persisted before objects, because an owner's first balance change
+        // is relative to the coins they own as of the end of the previous batch.
+        state
+            .persist_balance_changes(balance_changes_batch)
+            .await
+            .tap_err(|e| {
+                error!(
+                    "Failed to persist balance changes with error: {}",
+                    e.to_string()
+                );
+            })
+            .expect("Persisting data into DB should not fail.");
+
         let mut persist_tasks = vec![
             state.persist_transactions(tx_batch),
             state.persist_tx_indices(tx_indices_batch),
@@ -166,6 +193,7 @@ async fn commit_checkpoints<S>(
             state.persist_packages(packages_batch),
             state.persist_objects(object_changes_batch.clone()),
             state.persist_object_history(object_history_changes_batch.clone()),
+            state.persist_object_fields(object_fields_batch),
         ];
         if object_snapshot_backfill_mode {
             persist_tasks.push(state.backfill_objects_snapshot(object_changes_batch));
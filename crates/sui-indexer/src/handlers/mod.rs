@@ -6,14 +6,16 @@ use std::collections::BTreeMap;
 use crate::{
     models::display::StoredDisplay,
     types::{
-        IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo, IndexedEvent, IndexedObject,
-        IndexedPackage, IndexedTransaction, TxIndex,
+        IndexedBalanceChange, IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo,
+        IndexedEvent, IndexedObject, IndexedObjectField, IndexedPackage, IndexedTransaction,
+        TxIndex,
     },
 };
 
 pub mod checkpoint_handler;
 pub mod committer;
 pub mod objects_snapshot_processor;
+pub mod pruner;
 pub mod tx_processor;
 
 #[derive(Debug)]
@@ -25,6 +27,8 @@ pub struct CheckpointDataToCommit {
     pub display_updates: BTreeMap<String, StoredDisplay>,
     pub object_changes: TransactionObjectChangesToCommit,
     pub object_history_changes: TransactionObjectChangesToCommit,
+    pub object_fields: Vec<IndexedObjectField>,
+    pub balance_changes: Vec<IndexedBalanceChange>,
     pub packages: Vec<IndexedPackage>,
     pub epoch: Option<EpochToCommit>,
 }
@@ -0,0 +1,80 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::retention::RetentionPolicies;
+use crate::store::IndexerStore;
+use crate::types::IndexerResult;
+
+const PRUNER_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Prunes the epochs that have fallen out of each table's retention policy, oldest first, in the
+/// background of the indexer writer.
+pub struct Pruner<S> {
+    pub store: S,
+    pub policies: RetentionPolicies,
+}
+
+impl<S> Pruner<S>
+where
+    S: IndexerStore + Clone + Sync + Send + 'static,
+{
+    pub fn new(store: S, policies: RetentionPolicies) -> Pruner<S> {
+        Self { store, policies }
+    }
+
+    // Each table's pruner watermark is the first epoch that has not been pruned from it. It is
+    // only moved forward after an epoch has been fully pruned, so that a pruner interrupted
+    // half-way through an epoch picks it up again on restart. Tables read together with
+    // `objects_snapshot` are additionally held back to the latest snapshot checkpoint, because
+    // consistent reads between the snapshot and the latest checkpoint replay history on top of
+    // the snapshot.
+    pub async fn start(&self) -> IndexerResult<()> {
+        info!("Starting pruner...");
+        loop {
+            // Failures are retried on the next poll, rather than stopping the pruner for good.
+            if let Err(e) = self.prune().await {
+                error!("Failed to prune epochs: {e}");
+            }
+            tokio::time::sleep(PRUNER_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Prunes every epoch that has fallen out of retention and can be pruned now.
+    async fn prune(&self) -> IndexerResult<()> {
+        let Some(current_epoch) = self.store.get_latest_epoch_id().await? else {
+            return Ok(());
+        };
+        let latest_snapshot_cp = self
+            .store
+            .get_latest_object_snapshot_checkpoint_sequence_number()
+            .await?
+            .unwrap_or_default();
+
+        for (table, first_epoch_to_keep) in self.policies.first_epochs_to_keep(current_epoch) {
+            let mut epoch = self
+                .store
+                .get_pruner_watermark(table)
+                .await?
+                .unwrap_or_default();
+
+            while epoch < first_epoch_to_keep {
+                let Some(range) = self.store.get_epoch_range(epoch).await? else {
+                    warn!(epoch, "Epoch to prune from {} has not ended", table.name());
+                    break;
+                };
+                if table.is_read_with_snapshot() && range.checkpoints.end > latest_snapshot_cp {
+                    break;
+                }
+
+                self.store.prune_epoch(table, epoch, range).await?;
+                epoch += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -11,11 +11,14 @@ use mysten_metrics::spawn_monitored_task;
 
 use crate::build_json_rpc_server;
 use crate::errors::IndexerError;
+use crate::field_projections::FieldProjections;
 use crate::framework::fetcher::CheckpointFetcher;
 use crate::handlers::checkpoint_handler::new_handlers;
 use crate::handlers::objects_snapshot_processor::{ObjectsSnapshotProcessor, SnapshotLagConfig};
+use crate::handlers::pruner::Pruner;
 use crate::indexer_reader::IndexerReader;
 use crate::metrics::IndexerMetrics;
+use crate::retention::RetentionPolicies;
 use crate::store::IndexerStore;
 use crate::IndexerConfig;
 
@@ -44,6 +47,15 @@ impl Indexer {
             env!("CARGO_PKG_VERSION")
         );
 
+        let field_projections = match &config.field_projections_path {
+            Some(path) => FieldProjections::load(path)?,
+            None => FieldProjections::default(),
+        };
+        let retention_policies = match &config.retention_config_path {
+            Some(path) => RetentionPolicies::load(path)?,
+            None => RetentionPolicies::default(),
+        };
+
         // None will be returned when checkpoints table is empty.
         let last_seq_from_db = store
             .get_latest_checkpoint_sequence_number()
@@ -80,7 +92,18 @@ impl Indexer {
         );
         spawn_monitored_task!(objects_snapshot_processor.start());
 
-        let checkpoint_handler = new_handlers(store, rest_client.clone(), metrics.clone()).await?;
+        if !retention_policies.is_empty() {
+            let pruner = Pruner::new(store.clone(), retention_policies);
+            spawn_monitored_task!(pruner.start());
+        }
+
+        let checkpoint_handler = new_handlers(
+            store,
+            rest_client.clone(),
+            metrics.clone(),
+            field_projections,
+        )
+        .await?;
         crate::framework::runner::run(
             mysten_metrics::metered_channel::ReceiverStream::new(
                 downloaded_checkpoint_data_receiver,
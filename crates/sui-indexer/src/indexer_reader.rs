@@ -8,13 +8,11 @@ use cached::proc_macro::cached;
 use cached::SizedCache;
 use diesel::{
     dsl::sql, r2d2::ConnectionManager, sql_types::Bool, ExpressionMethods, OptionalExtension,
-    PgConnection, QueryDsl, RunQueryDsl, TextExpressionMethods,
+    QueryDsl, RunQueryDsl, TextExpressionMethods,
 };
 use itertools::{any, Itertools};
 use tap::TapFallible;
 
-use fastcrypto::encoding::Encoding;
-use fastcrypto::encoding::Hex;
 use move_core_types::annotated_value::MoveStructLayout;
 use move_core_types::language_storage::StructTag;
 use sui_json_rpc_types::DisplayFieldsResponse;
@@ -43,9 +41,13 @@ use sui_types::{
 use sui_types::{coin::CoinMetadata, event::EventID};
 
 use crate::{
-    db::{PgConnectionConfig, PgConnectionPool, PgConnectionPoolConfig, PgPoolConnection},
+    db::{
+        bytea_literal, ConnectionConfig, ConnectionPool, ConnectionPoolConfig, DbConnection,
+        PoolConnection,
+    },
     errors::IndexerError,
     models::{
+        balance_changes::StoredBalanceChange,
         checkpoints::StoredCheckpoint,
         display::StoredDisplay,
         epoch::StoredEpochInfo,
@@ -54,7 +56,10 @@ use crate::{
         transactions::{tx_events_to_sui_tx_events, StoredTransaction},
         tx_indices::TxSequenceNumber,
     },
-    schema::{checkpoints, display, epochs, events, objects, objects_snapshot, transactions},
+    schema::{
+        balance_changes, checkpoints, display, epochs, events, objects, objects_snapshot,
+        transactions,
+    },
     store::package_resolver::IndexerStorePackageResolver,
     types::{IndexerResult, OwnerType},
 };
@@ -65,24 +70,24 @@ pub const EVENT_SEQUENCE_NUMBER_STR: &str = "event_sequence_number";
 
 #[derive(Clone)]
 pub struct IndexerReader {
-    pool: PgConnectionPool,
+    pool: ConnectionPool,
     package_resolver: Arc<Resolver<PackageStoreWithLruCache<IndexerStorePackageResolver>>>,
 }
 
 // Impl for common initialization and utilities
 impl IndexerReader {
     pub fn new<T: Into<String>>(db_url: T) -> Result<Self> {
-        let config = PgConnectionPoolConfig::default();
+        let config = ConnectionPoolConfig::default();
         Self::new_with_config(db_url, config)
     }
 
     pub fn new_with_config<T: Into<String>>(
         db_url: T,
-        config: PgConnectionPoolConfig,
+        config: ConnectionPoolConfig,
     ) -> Result<Self> {
-        let manager = ConnectionManager::<PgConnection>::new(db_url);
+        let manager = ConnectionManager::<DbConnection>::new(db_url);
 
-        let connection_config = PgConnectionConfig {
+        let connection_config = ConnectionConfig {
             statement_timeout: config.statement_timeout,
             read_only: true,
         };
@@ -104,10 +109,10 @@ impl IndexerReader {
         })
     }
 
-    fn get_connection(&self) -> Result<PgPoolConnection, IndexerError> {
+    fn get_connection(&self) -> Result<PoolConnection, IndexerError> {
         self.pool.get().map_err(|e| {
-            IndexerError::PgPoolConnectionError(format!(
-                "Failed to get connection from PG connection pool with error: {:?}",
+            IndexerError::PoolConnectionError(format!(
+                "Failed to get connection from DB connection pool with error: {:?}",
                 e
             ))
         })
@@ -115,33 +120,39 @@ impl IndexerReader {
 
     pub fn run_query<T, E, F>(&self, query: F) -> Result<T, IndexerError>
     where
-        F: FnOnce(&mut PgConnection) -> Result<T, E>,
+        F: FnOnce(&mut DbConnection) -> Result<T, E>,
         E: From<diesel::result::Error> + std::error::Error,
     {
         blocking_call_is_ok_or_panic();
 
         let mut connection = self.get_connection()?;
-        connection
-            .build_transaction()
-            .read_only()
-            .run(query)
-            .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+        let conn: &mut DbConnection = &mut connection;
+        #[cfg(feature = "postgres-feature")]
+        let result = conn.build_transaction().read_only().run(query);
+        #[cfg(not(feature = "postgres-feature"))]
+        let result = diesel::Connection::transaction(conn, query);
+        result.map_err(|e| IndexerError::DbReadError(e.to_string()))
     }
 
     pub fn run_query_repeatable<T, E, F>(&self, query: F) -> Result<T, IndexerError>
     where
-        F: FnOnce(&mut PgConnection) -> Result<T, E>,
+        F: FnOnce(&mut DbConnection) -> Result<T, E>,
         E: From<diesel::result::Error> + std::error::Error,
     {
         blocking_call_is_ok_or_panic();
 
         let mut connection = self.get_connection()?;
-        connection
+        let conn: &mut DbConnection = &mut connection;
+        #[cfg(feature = "postgres-feature")]
+        let result = conn
             .build_transaction()
             .read_only()
             .repeatable_read()
-            .run(query)
-            .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+            .run(query);
+        // SQLite transactions are always serializable.
+        #[cfg(not(feature = "postgres-feature"))]
+        let result = diesel::Connection::transaction(conn, query);
+        result.map_err(|e| IndexerError::DbReadError(e.to_string()))
     }
 
     pub async fn spawn_blocking<F, R, E>(&self, f: F) -> Result<R, E>
@@ -164,7 +175,7 @@ impl IndexerReader {
 
     pub async fn run_query_async<T, E, F>(&self, query: F) -> Result<T, IndexerError>
     where
-        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        F: FnOnce(&mut DbConnection) -> Result<T, E> + Send + 'static,
         E: From<diesel::result::Error> + std::error::Error + Send + 'static,
         T: Send + 'static,
     {
@@ -173,7 +184,7 @@ impl IndexerReader {
 
     pub async fn run_query_repeatable_async<T, E, F>(&self, query: F) -> Result<T, IndexerError>
     where
-        F: FnOnce(&mut PgConnection) -> Result<T, E> + Send + 'static,
+        F: FnOnce(&mut DbConnection) -> Result<T, E> + Send + 'static,
         E: From<diesel::result::Error> + std::error::Error + Send + 'static,
         T: Send + 'static,
     {
@@ -287,7 +298,7 @@ impl IndexerReader {
             .fetch(package_id.into())
             .await
             .map_err(|e| {
-                IndexerError::PostgresReadError(format!(
+                IndexerError::DbReadError(format!(
                     "Fail to fetch package from package store with error {:?}",
                     e
                 ))
@@ -381,6 +392,40 @@ impl IndexerReader {
             .map_err(Into::into)
     }
 
+    /// Changes to `owner`'s balance of `coin_type`, paginated by the sequence numbers of the
+    /// transactions that made them.
+    pub fn get_balance_changes(
+        &self,
+        owner: SuiAddress,
+        coin_type: String,
+        cursor: Option<u64>,
+        limit: usize,
+        descending_order: bool,
+    ) -> Result<Vec<StoredBalanceChange>, IndexerError> {
+        self.run_query(|conn| {
+            let mut boxed_query = balance_changes::table
+                .filter(balance_changes::owner.eq(owner.to_vec()))
+                .filter(balance_changes::coin_type.eq(coin_type))
+                .into_boxed();
+            if let Some(cursor) = cursor {
+                if descending_order {
+                    boxed_query =
+                        boxed_query.filter(balance_changes::tx_sequence_number.lt(cursor as i64));
+                } else {
+                    boxed_query =
+                        boxed_query.filter(balance_changes::tx_sequence_number.gt(cursor as i64));
+                }
+            }
+            if descending_order {
+                boxed_query = boxed_query.order_by(balance_changes::tx_sequence_number.desc());
+            } else {
+                boxed_query = boxed_query.order_by(balance_changes::tx_sequence_number.asc());
+            }
+
+            boxed_query.limit(limit as i64).load(conn)
+        })
+    }
+
     pub fn get_latest_sui_system_state(&self) -> Result<SuiSystemStateSummary, IndexerError> {
         let system_state: SuiSystemStateSummary =
             sui_types::sui_system_state::get_sui_system_state(self)?
@@ -671,7 +716,7 @@ impl IndexerReader {
                 query = query.filter(objects::dsl::object_id.gt(object_cursor.to_vec()));
             }
 
-            query.load::<StoredObject>(conn).map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+            query.load::<StoredObject>(conn).map_err(|e| IndexerError::DbReadError(e.to_string()))
         })
     }
 
@@ -819,64 +864,55 @@ impl IndexerReader {
                 module,
                 function,
             }) => {
-                let package = Hex::encode(package.to_vec());
+                let package = bytea_literal(&package.to_vec());
                 match (module, function) {
                     (Some(module), Some(function)) => (
                         "tx_calls".into(),
                         format!(
-                            "package = '\\x{}'::bytea AND module = '{}' AND func = '{}'",
+                            "package = {} AND module = '{}' AND func = '{}'",
                             package, module, function
                         ),
                     ),
                     (Some(module), None) => (
                         "tx_calls".into(),
-                        format!(
-                            "package = '\\x{}'::bytea AND module = '{}'",
-                            package, module
-                        ),
+                        format!("package = {} AND module = '{}'", package, module),
                     ),
                     (None, Some(_)) => {
                         return Err(IndexerError::InvalidArgumentError(
                             "Function cannot be present wihtout Module.".into(),
                         ));
                     }
-                    (None, None) => (
-                        "tx_calls".into(),
-                        format!("package = '\\x{}'::bytea", package),
-                    ),
+                    (None, None) => ("tx_calls".into(), format!("package = {}", package)),
                 }
             }
             Some(TransactionFilter::InputObject(object_id)) => {
-                let object_id = Hex::encode(object_id.to_vec());
+                let object_id = bytea_literal(&object_id.to_vec());
                 (
                     "tx_input_objects".into(),
-                    format!("object_id = '\\x{}'::bytea", object_id),
+                    format!("object_id = {}", object_id),
                 )
             }
             Some(TransactionFilter::ChangedObject(object_id)) => {
-                let object_id = Hex::encode(object_id.to_vec());
+                let object_id = bytea_literal(&object_id.to_vec());
                 (
                     "tx_changed_objects".into(),
-                    format!("object_id = '\\x{}'::bytea", object_id),
+                    format!("object_id = {}", object_id),
                 )
             }
             Some(TransactionFilter::FromAddress(from_address)) => {
-                let from_address = Hex::encode(from_address.to_vec());
-                (
-                    "tx_senders".into(),
-                    format!("sender = '\\x{}'::bytea", from_address),
-                )
+                let from_address = bytea_literal(&from_address.to_vec());
+                ("tx_senders".into(), format!("sender = {}", from_address))
             }
             Some(TransactionFilter::ToAddress(to_address)) => {
-                let to_address = Hex::encode(to_address.to_vec());
+                let to_address = bytea_literal(&to_address.to_vec());
                 (
                     "tx_recipients".into(),
-                    format!("recipient = '\\x{}'::bytea", to_address),
+                    format!("recipient = {}", to_address),
                 )
             }
             Some(TransactionFilter::FromAndToAddress { from, to }) => {
-                let from_address = Hex::encode(from.to_vec());
-                let to_address = Hex::encode(to.to_vec());
+                let from_address = bytea_literal(&from.to_vec());
+                let to_address = bytea_literal(&to.to_vec());
                 // Need to remove ambiguities for tx_sequence_number column
                 let cursor_clause = if let Some(cursor_tx_seq) = cursor_tx_seq {
                     if is_descending {
@@ -898,8 +934,8 @@ impl IndexerReader {
                     FROM tx_senders \
                     JOIN tx_recipients \
                     ON tx_senders.{TX_SEQUENCE_NUMBER_STR} = tx_recipients.{TX_SEQUENCE_NUMBER_STR} \
-                    WHERE tx_senders.sender = '\\x{}'::BYTEA \
-                    AND tx_recipients.recipient = '\\x{}'::BYTEA \
+                    WHERE tx_senders.sender = {} \
+                    AND tx_recipients.recipient = {} \
                     {} \
                     ORDER BY {TX_SEQUENCE_NUMBER_STR} {} \
                     LIMIT {}) AS inner_query
@@ -913,22 +949,24 @@ impl IndexerReader {
                 (inner_query, "1 = 1".into())
             }
             Some(TransactionFilter::FromOrToAddress { addr }) => {
-                let address = Hex::encode(addr.to_vec());
+                let address = bytea_literal(&addr.to_vec());
+                // Each side of the UNION is wrapped in its own derived table, as SQLite does not
+                // accept parenthesized compound select members.
                 let inner_query = format!(
                     "( \
-                        ( \
+                        SELECT {TX_SEQUENCE_NUMBER_STR} FROM ( \
                             SELECT {TX_SEQUENCE_NUMBER_STR} FROM tx_senders \
-                            WHERE sender = '\\x{}'::BYTEA {} \
+                            WHERE sender = {} {} \
                             ORDER BY {TX_SEQUENCE_NUMBER_STR} {} \
                             LIMIT {} \
-                        ) \
+                        ) AS senders \
                         UNION \
-                        ( \
+                        SELECT {TX_SEQUENCE_NUMBER_STR} FROM ( \
                             SELECT {TX_SEQUENCE_NUMBER_STR} FROM tx_recipients \
-                            WHERE recipient = '\\x{}'::BYTEA {} \
+                            WHERE recipient = {} {} \
                             ORDER BY {TX_SEQUENCE_NUMBER_STR} {} \
                             LIMIT {} \
-                        ) \
+                        ) AS recipients \
                     ) AS combined",
                     address,
                     cursor_clause,
@@ -1080,12 +1118,12 @@ impl IndexerReader {
             FROM EVENTS e \
             JOIN TRANSACTIONS t \
             ON t.tx_sequence_number = e.tx_sequence_number \
-            AND t.transaction_digest = '\\x{}'::bytea \
+            AND t.transaction_digest = {} \
             WHERE {cursor} \
             ORDER BY e.{EVENT_SEQUENCE_NUMBER_STR} {order_clause} \
             LIMIT {limit}
             ",
-            Hex::encode(tx_digest.into_inner()),
+            bytea_literal(&tx_digest.into_inner()),
         ))
     }
 
@@ -1141,17 +1179,17 @@ impl IndexerReader {
                 format!("e.{TX_SEQUENCE_NUMBER_STR} ASC, e.{EVENT_SEQUENCE_NUMBER_STR} ASC")
             };
             format!(
-                "( \
+                "
                     SELECT *
                     FROM tx_senders s
                     JOIN events e
                     ON e.tx_sequence_number = s.tx_sequence_number
-                    AND s.sender = '\\x{}'::bytea
+                    AND s.sender = {}
                     WHERE {} \
                     ORDER BY {} \
                     LIMIT {}
-                )",
-                Hex::encode(sender.to_vec()),
+                ",
+                bytea_literal(&sender.to_vec()),
                 cursor_clause,
                 order_clause,
                 limit,
@@ -1161,12 +1199,12 @@ impl IndexerReader {
         } else {
             let main_where_clause = match filter {
                 EventFilter::Package(package_id) => {
-                    format!("package = '\\x{}'::bytea", package_id.to_hex())
+                    format!("package = {}", bytea_literal(&package_id.to_vec()))
                 }
                 EventFilter::MoveModule { package, module } => {
                     format!(
-                        "package = '\\x{}'::bytea AND module = '{}'",
-                        package.to_hex(),
+                        "package = {} AND module = '{}'",
+                        bytea_literal(&package.to_vec()),
                         module,
                     )
                 }
@@ -1493,13 +1531,13 @@ impl IndexerReader {
             CAST(SUM(coin_balance) AS BIGINT) AS coin_balance \
             FROM objects \
             WHERE owner_type = {} \
-            AND owner_id = '\\x{}'::BYTEA \
+            AND owner_id = {} \
             AND coin_type {} \
             GROUP BY coin_type \
             ORDER BY coin_type ASC
         ",
             OwnerType::Address as i16,
-            Hex::encode(owner.to_vec()),
+            bytea_literal(&owner.to_vec()),
             coin_type_filter,
         );
 
@@ -5,7 +5,7 @@
 use std::net::SocketAddr;
 
 use anyhow::{anyhow, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder};
 use metrics::IndexerMetrics;
 use prometheus::Registry;
@@ -22,18 +22,29 @@ use crate::apis::{
     CoinReadApi, ExtendedApi, GovernanceReadApi, IndexerApi, MoveUtilsApi, ReadApi,
     TransactionBuilderApi, WriteApi,
 };
+use crate::backfill::BackfillConfig;
 use crate::indexer_reader::IndexerReader;
 use errors::IndexerError;
 
+// Enabling both backend features is allowed, so that the crate builds with `--all-features`: the
+// indexer uses Postgres whenever `postgres-feature` is enabled, and SQLite only when
+// `sqlite-feature` is enabled on its own.
+#[cfg(not(any(feature = "postgres-feature", feature = "sqlite-feature")))]
+compile_error!("one of the features `postgres-feature` or `sqlite-feature` must be enabled");
+
 pub mod apis;
+pub mod backfill;
 pub mod db;
 pub mod errors;
+pub mod field_projections;
 pub mod framework;
 pub mod handlers;
 pub mod indexer;
 pub mod indexer_reader;
 pub mod metrics;
 pub mod models;
+pub mod retention;
+#[cfg_attr(not(feature = "postgres-feature"), path = "schema_sqlite.rs")]
 pub mod schema;
 pub mod store;
 pub mod test_utils;
@@ -78,6 +89,20 @@ pub struct IndexerConfig {
     pub rpc_server_worker: bool,
     #[clap(long)]
     pub data_ingestion_path: Option<PathBuf>,
+    /// YAML file declaring the fields to project from objects, see `field_projections`.
+    #[clap(long)]
+    pub field_projections_path: Option<PathBuf>,
+    /// YAML file declaring how many epochs of each table to keep, see `retention`.
+    #[clap(long)]
+    pub retention_config_path: Option<PathBuf>,
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum Command {
+    /// Re-index a range of checkpoints into a subset of tables, see `backfill`.
+    Backfill(BackfillConfig),
 }
 
 impl IndexerConfig {
@@ -128,6 +153,9 @@ impl Default for IndexerConfig {
             fullnode_sync_worker: true,
             rpc_server_worker: true,
             data_ingestion_path: None,
+            field_projections_path: None,
+            retention_config_path: None,
+            command: None,
         }
     }
 }
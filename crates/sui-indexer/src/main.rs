@@ -4,13 +4,16 @@
 use clap::Parser;
 use tracing::{error, info};
 
-use sui_indexer::db::{get_pg_pool_connection, new_pg_connection_pool, reset_database};
+use sui_indexer::backfill::run_backfill;
+use sui_indexer::db::{
+    get_pool_connection, new_connection_pool, reset_database, run_pending_migrations,
+};
 use sui_indexer::errors::IndexerError;
 use sui_indexer::indexer::Indexer;
 use sui_indexer::metrics::start_prometheus_server;
 use sui_indexer::metrics::IndexerMetrics;
 use sui_indexer::store::PgIndexerStore;
-use sui_indexer::IndexerConfig;
+use sui_indexer::{Command, IndexerConfig};
 
 #[tokio::main]
 async fn main() -> Result<(), IndexerError> {
@@ -23,22 +26,19 @@ async fn main() -> Result<(), IndexerError> {
     info!("Parsed indexer config: {:#?}", indexer_config);
 
     let db_url = indexer_config.get_db_url().map_err(|e| {
-        IndexerError::PgPoolConnectionError(format!(
-            "Failed parsing database url with error {:?}",
-            e
-        ))
+        IndexerError::PoolConnectionError(format!("Failed parsing database url with error {:?}", e))
     })?;
-    let blocking_cp = new_pg_connection_pool(&db_url, None).map_err(|e| {
+    let blocking_cp = new_connection_pool(&db_url, None).map_err(|e| {
         error!(
-            "Failed creating Postgres connection pool with error {:?}",
+            "Failed creating database connection pool with error {:?}",
             e
         );
         e
     })?;
     if indexer_config.reset_db {
-        let mut conn = get_pg_pool_connection(&blocking_cp).map_err(|e| {
+        let mut conn = get_pool_connection(&blocking_cp).map_err(|e| {
             error!(
-                "Failed getting Postgres connection from connection pool with error {:?}",
+                "Failed getting database connection from connection pool with error {:?}",
                 e
             );
             e
@@ -49,7 +49,19 @@ async fn main() -> Result<(), IndexerError> {
                 db_url, e
             );
             error!("{}", db_err_msg);
-            IndexerError::PostgresResetError(db_err_msg)
+            IndexerError::DbResetError(db_err_msg)
+        })?;
+    } else if cfg!(not(feature = "postgres-feature")) {
+        // A SQLite database is owned by the indexer, so create or upgrade its schema on startup
+        // rather than requiring it to be set up out of band.
+        let mut conn = get_pool_connection(&blocking_cp)?;
+        run_pending_migrations(&mut conn).map_err(|e| {
+            let db_err_msg = format!(
+                "Failed running migrations on database with url: {:?} and error: {:?}",
+                db_url, e
+            );
+            error!("{}", db_err_msg);
+            IndexerError::DbResetError(db_err_msg)
         })?;
     }
 
@@ -86,6 +98,11 @@ async fn main() -> Result<(), IndexerError> {
         }
     });
 
+    if let Some(Command::Backfill(backfill_config)) = &indexer_config.command {
+        let store = PgIndexerStore::new(blocking_cp, indexer_metrics.clone());
+        return run_backfill(&indexer_config, backfill_config, store, indexer_metrics).await;
+    }
+
     if indexer_config.fullnode_sync_worker {
         let store = PgIndexerStore::new(blocking_cp, indexer_metrics.clone());
         return Indexer::start_writer(&indexer_config, store, indexer_metrics).await;
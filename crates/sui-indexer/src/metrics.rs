@@ -128,12 +128,15 @@ pub struct IndexerMetrics {
     pub checkpoint_db_commit_latency_events: Histogram,
     pub checkpoint_db_commit_latency_events_chunks: Histogram,
     pub checkpoint_db_commit_latency_packages: Histogram,
+    pub checkpoint_db_commit_latency_object_fields: Histogram,
+    pub checkpoint_db_commit_latency_balance_changes: Histogram,
     pub checkpoint_db_commit_latency_tx_indices: Histogram,
     pub checkpoint_db_commit_latency_tx_indices_chunks: Histogram,
     pub checkpoint_db_commit_latency_checkpoints: Histogram,
     pub checkpoint_db_commit_latency_epoch: Histogram,
     pub advance_epoch_latency: Histogram,
     pub update_object_snapshot_latency: Histogram,
+    pub prune_epoch_latency: Histogram,
     pub tokio_blocking_task_wait_latency: Histogram,
     // average latency of committing 1000 transactions.
     // 1000 is not necessarily the batch size, it's to roughly map average tx commit latency to [0.1, 1] seconds,
@@ -460,6 +463,20 @@ impl IndexerMetrics {
                 registry,
             )
             .unwrap(),
+            checkpoint_db_commit_latency_object_fields: register_histogram_with_registry!(
+                "checkpoint_db_commit_latency_object_fields",
+                "Time spent commiting projected object fields",
+                DB_COMMIT_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            checkpoint_db_commit_latency_balance_changes: register_histogram_with_registry!(
+                "checkpoint_db_commit_latency_balance_changes",
+                "Time spent commiting balance changes",
+                DB_COMMIT_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             checkpoint_db_commit_latency_tx_indices: register_histogram_with_registry!(
                 "checkpoint_db_commit_latency_tx_indices",
                 "Time spent commiting tx indices",
@@ -500,6 +517,12 @@ impl IndexerMetrics {
                 LATENCY_SEC_BUCKETS.to_vec(),
                 registry,
             ).unwrap(),
+            prune_epoch_latency: register_histogram_with_registry!(
+                "prune_epoch_latency",
+                "Time spent in pruning an epoch from a table",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
             tokio_blocking_task_wait_latency: register_histogram_with_registry!(
                 "tokio_blocking_task_wait_latency",
                 "Time spent to wait for tokio blocking task pool",
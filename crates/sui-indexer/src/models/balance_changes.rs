@@ -0,0 +1,68 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use sui_json_rpc_types::AddressBalanceChange;
+use sui_types::digests::TransactionDigest;
+
+use crate::errors::IndexerError;
+use crate::models::numeric::I128;
+use crate::schema::balance_changes;
+use crate::types::IndexedBalanceChange;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = balance_changes)]
+pub struct StoredBalanceChange {
+    pub owner: Vec<u8>,
+    pub coin_type: String,
+    pub tx_sequence_number: i64,
+    pub transaction_digest: Vec<u8>,
+    pub checkpoint_sequence_number: i64,
+    pub timestamp_ms: i64,
+    pub amount: I128,
+    pub balance: I128,
+}
+
+impl StoredBalanceChange {
+    /// `balance` is the owner's balance of the coin type after the change.
+    pub fn new(change: IndexedBalanceChange, balance: i128) -> Self {
+        Self {
+            owner: change.owner.to_vec(),
+            coin_type: change.coin_type,
+            tx_sequence_number: change.tx_sequence_number as i64,
+            transaction_digest: change.transaction_digest.into_inner().to_vec(),
+            checkpoint_sequence_number: change.checkpoint_sequence_number as i64,
+            timestamp_ms: change.timestamp_ms as i64,
+            amount: I128(change.amount),
+            balance: I128(balance),
+        }
+    }
+}
+
+impl TryFrom<StoredBalanceChange> for AddressBalanceChange {
+    type Error = IndexerError;
+
+    fn try_from(value: StoredBalanceChange) -> Result<Self, Self::Error> {
+        let transaction_digest = TransactionDigest::try_from(value.transaction_digest.as_slice())
+            .map_err(|e| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Failed to decode transaction digest of balance change: {e}"
+            ))
+        })?;
+
+        let balance = u128::try_from(value.balance.0).map_err(|_| {
+            IndexerError::PersistentStorageDataCorruptionError(format!(
+                "Balance after change is negative: {}",
+                value.balance.0
+            ))
+        })?;
+
+        Ok(AddressBalanceChange {
+            transaction_digest,
+            checkpoint: value.checkpoint_sequence_number as u64,
+            timestamp_ms: value.timestamp_ms as u64,
+            amount: value.amount.0,
+            balance,
+        })
+    }
+}
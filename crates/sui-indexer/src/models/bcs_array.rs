@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! SQLite has no array types, so the `bytea[]` columns of the Postgres schema are stored as BCS
+//! serialized blobs instead. These impls let the models keep using `Vec<Option<Vec<u8>>>` for
+//! those columns regardless of the backend.
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::expression::AsExpression;
+use diesel::internal::derives::as_expression::Bound;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::Binary;
+use diesel::sqlite::Sqlite;
+
+use crate::schema::sql_types::BcsArray;
+
+impl ToSql<BcsArray, Sqlite> for Vec<Option<Vec<u8>>> {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(bcs::to_bytes(self)?);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<BcsArray, Sqlite> for Vec<Option<Vec<u8>>> {
+    fn from_sql(bytes: <Sqlite as Backend>::RawValue<'_>) -> deserialize::Result<Self> {
+        let bytes = <Vec<u8> as FromSql<Binary, Sqlite>>::from_sql(bytes)?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+}
+
+impl AsExpression<BcsArray> for Vec<Option<Vec<u8>>> {
+    type Expression = Bound<BcsArray, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
+
+impl<'a> AsExpression<BcsArray> for &'a Vec<Option<Vec<u8>>> {
+    type Expression = Bound<BcsArray, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bound::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::prelude::*;
+    use diesel::sql_query;
+
+    use super::*;
+
+    #[derive(QueryableByName)]
+    struct Row {
+        #[diesel(sql_type = BcsArray)]
+        value: Vec<Option<Vec<u8>>>,
+    }
+
+    #[test]
+    fn bcs_array_round_trip() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        let value = vec![Some(vec![1u8, 2, 3]), None, Some(vec![])];
+        let row: Row = sql_query("SELECT ? AS value")
+            .bind::<BcsArray, _>(&value)
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(row.value, value);
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use sui_json_rpc_types::Checkpoint as RpcCheckpoint;
 use sui_types::base_types::TransactionDigest;
@@ -12,7 +13,7 @@ use crate::errors::IndexerError;
 use crate::schema::checkpoints;
 use crate::types::IndexedCheckpoint;
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = checkpoints)]
 pub struct StoredCheckpoint {
     pub sequence_number: i64,
@@ -8,6 +8,7 @@ use diesel::prelude::*;
 
 use move_core_types::annotated_value::MoveTypeLayout;
 use move_core_types::identifier::Identifier;
+use serde::{Deserialize, Serialize};
 use sui_json_rpc_types::{SuiEvent, SuiMoveStruct};
 use sui_package_resolver::{PackageStore, Resolver};
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -20,7 +21,7 @@ use crate::errors::IndexerError;
 use crate::schema::events;
 use crate::types::IndexedEvent;
 
-#[derive(Queryable, QueryableByName, Insertable, Debug, Clone)]
+#[derive(Queryable, QueryableByName, Insertable, Debug, Clone, Serialize, Deserialize)]
 #[diesel(table_name = events)]
 pub struct StoredEvent {
     #[diesel(sql_type = diesel::sql_types::BigInt)]
@@ -35,7 +36,14 @@ pub struct StoredEvent {
     #[diesel(sql_type = diesel::sql_types::BigInt)]
     pub checkpoint_sequence_number: i64,
 
-    #[diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Nullable<diesel::pg::sql_types::Bytea>>)]
+    #[cfg_attr(
+        feature = "postgres-feature",
+        diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Nullable<diesel::pg::sql_types::Bytea>>)
+    )]
+    #[cfg_attr(
+        not(feature = "postgres-feature"),
+        diesel(sql_type = crate::schema::sql_types::BcsArray)
+    )]
     pub senders: Vec<Option<Vec<u8>>>,
 
     #[diesel(sql_type = diesel::sql_types::Bytea)]
@@ -1,11 +1,16 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod balance_changes;
+#[cfg(not(feature = "postgres-feature"))]
+mod bcs_array;
 pub mod checkpoints;
 pub mod display;
 pub mod epoch;
 pub mod events;
+pub mod numeric;
 pub mod objects;
 pub mod packages;
+pub mod pruner_watermarks;
 pub mod transactions;
 pub mod tx_indices;
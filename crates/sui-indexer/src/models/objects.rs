@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use diesel::prelude::*;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 use move_core_types::annotated_value::MoveTypeLayout;
 use sui_json_rpc::coin_api::parse_to_struct_tag;
@@ -18,8 +19,8 @@ use sui_types::object::Object;
 use sui_types::object::ObjectRead;
 
 use crate::errors::IndexerError;
-use crate::schema::{objects, objects_history, objects_snapshot};
-use crate::types::{IndexedDeletedObject, IndexedObject, ObjectStatus};
+use crate::schema::{object_fields, objects, objects_history, objects_snapshot};
+use crate::types::{IndexedDeletedObject, IndexedObject, IndexedObjectField, ObjectStatus};
 
 #[derive(Queryable)]
 pub struct DynamicFieldColumn {
@@ -127,7 +128,9 @@ impl From<StoredDeletedObject> for StoredObjectSnapshot {
     }
 }
 
-#[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
+#[derive(
+    Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName, Serialize, Deserialize,
+)]
 #[diesel(table_name = objects_history, primary_key(object_id, object_version, checkpoint_sequence_number))]
 pub struct StoredHistoryObject {
     pub object_id: Vec<u8>,
@@ -187,6 +190,30 @@ impl From<IndexedDeletedObject> for StoredDeletedObject {
     }
 }
 
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = object_fields)]
+pub struct StoredObjectField {
+    pub object_id: Vec<u8>,
+    pub object_version: i64,
+    pub checkpoint_sequence_number: i64,
+    pub field_name: String,
+    pub field_value: String,
+    pub is_numeric: bool,
+}
+
+impl From<IndexedObjectField> for StoredObjectField {
+    fn from(f: IndexedObjectField) -> Self {
+        Self {
+            object_id: f.object_id.to_vec(),
+            object_version: f.object_version as i64,
+            checkpoint_sequence_number: f.checkpoint_sequence_number as i64,
+            field_name: f.field_name,
+            field_value: f.field_value,
+            is_numeric: f.is_numeric,
+        }
+    }
+}
+
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
 #[diesel(table_name = objects_history, primary_key(object_id, object_version, checkpoint_sequence_number))]
 pub struct StoredDeletedHistoryObject {
@@ -255,7 +282,7 @@ impl StoredObject {
         let oref = self.get_object_ref()?;
         let object: sui_types::object::Object = self.try_into()?;
         let Some(move_object) = object.data.try_as_move().cloned() else {
-            return Err(IndexerError::PostgresReadError(format!(
+            return Err(IndexerError::DbReadError(format!(
                 "Object {:?} is not a Move object",
                 oref,
             )));
@@ -361,13 +388,13 @@ impl StoredObject {
         let oref = self.get_object_ref()?;
         let object: sui_types::object::Object = self.clone().try_into()?;
         let Some(move_object) = object.data.try_as_move().cloned() else {
-            return Err(IndexerError::PostgresReadError(format!(
+            return Err(IndexerError::DbReadError(format!(
                 "Object {:?} is not a Move object",
                 oref,
             )));
         };
         if !move_object.type_().is_dynamic_field() {
-            return Err(IndexerError::PostgresReadError(format!(
+            return Err(IndexerError::DbReadError(format!(
                 "Object {:?} is not a dynamic field",
                 oref,
             )));
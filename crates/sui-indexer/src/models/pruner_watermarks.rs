@@ -0,0 +1,16 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+
+use crate::schema::pruner_watermarks;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = pruner_watermarks)]
+pub struct StoredPrunerWatermark {
+    pub table_name: String,
+    /// The earliest epoch that has not been pruned from the table.
+    pub epoch: i64,
+    /// The first checkpoint of `epoch`.
+    pub checkpoint_sequence_number: i64,
+}
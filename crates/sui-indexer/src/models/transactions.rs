@@ -4,6 +4,7 @@
 use std::sync::Arc;
 
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use move_core_types::annotated_value::MoveTypeLayout;
 use move_core_types::language_storage::TypeTag;
@@ -24,7 +25,7 @@ use crate::types::IndexedObjectChange;
 use crate::types::IndexedTransaction;
 use crate::types::IndexerResult;
 
-#[derive(Clone, Debug, Queryable, Insertable, QueryableByName)]
+#[derive(Clone, Debug, Queryable, Insertable, QueryableByName, Serialize, Deserialize)]
 #[diesel(table_name = transactions)]
 pub struct StoredTransaction {
     pub tx_sequence_number: i64,
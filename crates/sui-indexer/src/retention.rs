@@ -0,0 +1,221 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retention policies bound how much history the indexer keeps in its history and lookup tables,
+//! by the number of epochs (including the current one) to keep for each table. Tables without a
+//! policy are kept forever. The policies are declared in a YAML file, e.g.:
+//!
+//! ```yaml
+//! epochs-to-keep:
+//!   objects_history: 2
+//!   events: 30
+//! ```
+//!
+//! Epochs that fall out of a table's retention are pruned in the background, see
+//! `handlers::pruner`.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::IndexerError;
+use crate::types::IndexerResult;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// Number of epochs to keep, by table.
+    #[serde(default)]
+    pub epochs_to_keep: BTreeMap<PrunableTable, u64>,
+}
+
+/// The tables that rows can be pruned from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrunableTable {
+    Transactions,
+    Events,
+    ObjectsHistory,
+    ObjectFields,
+    TxSenders,
+    TxRecipients,
+    TxInputObjects,
+    TxChangedObjects,
+    TxCalls,
+}
+
+/// The column that rows are pruned by.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PruneKey {
+    TxSequenceNumber,
+    CheckpointSequenceNumber,
+}
+
+/// The checkpoints and transactions in an epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochRange {
+    pub checkpoints: Range<u64>,
+    pub transactions: Range<u64>,
+}
+
+/// How many epochs to keep of each table that has a retention policy.
+#[derive(Clone, Debug, Default)]
+pub struct RetentionPolicies {
+    epochs_to_keep: BTreeMap<PrunableTable, u64>,
+}
+
+impl PrunableTable {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrunableTable::Transactions => "transactions",
+            PrunableTable::Events => "events",
+            PrunableTable::ObjectsHistory => "objects_history",
+            PrunableTable::ObjectFields => "object_fields",
+            PrunableTable::TxSenders => "tx_senders",
+            PrunableTable::TxRecipients => "tx_recipients",
+            PrunableTable::TxInputObjects => "tx_input_objects",
+            PrunableTable::TxChangedObjects => "tx_changed_objects",
+            PrunableTable::TxCalls => "tx_calls",
+        }
+    }
+
+    pub fn prune_key(&self) -> PruneKey {
+        match self {
+            PrunableTable::ObjectsHistory | PrunableTable::ObjectFields => {
+                PruneKey::CheckpointSequenceNumber
+            }
+            _ => PruneKey::TxSequenceNumber,
+        }
+    }
+
+    /// Whether the table is partitioned by epoch, in which case it is pruned by dropping whole
+    /// partitions rather than by deleting rows.
+    pub fn is_partitioned(&self) -> bool {
+        cfg!(feature = "postgres-feature")
+            && matches!(
+                self,
+                PrunableTable::Transactions | PrunableTable::ObjectsHistory
+            )
+    }
+
+    /// Whether the table is read together with `objects_snapshot` for consistent queries, in which
+    /// case checkpoints after the latest snapshot must not be pruned.
+    pub fn is_read_with_snapshot(&self) -> bool {
+        matches!(
+            self,
+            PrunableTable::ObjectsHistory | PrunableTable::ObjectFields
+        )
+    }
+}
+
+impl PruneKey {
+    pub fn column(&self) -> &'static str {
+        match self {
+            PruneKey::TxSequenceNumber => "tx_sequence_number",
+            PruneKey::CheckpointSequenceNumber => "checkpoint_sequence_number",
+        }
+    }
+}
+
+impl EpochRange {
+    /// The range of values of `key` in the epoch.
+    pub fn by_key(&self, key: PruneKey) -> Range<u64> {
+        match key {
+            PruneKey::TxSequenceNumber => self.transactions.clone(),
+            PruneKey::CheckpointSequenceNumber => self.checkpoints.clone(),
+        }
+    }
+}
+
+impl RetentionPolicies {
+    pub fn new(config: &RetentionConfig) -> IndexerResult<Self> {
+        if let Some((table, _)) = config.epochs_to_keep.iter().find(|(_, keep)| **keep == 0) {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Retention policy for '{}' must keep at least one epoch",
+                table.name()
+            )));
+        }
+
+        Ok(Self {
+            epochs_to_keep: config.epochs_to_keep.clone(),
+        })
+    }
+
+    /// Load retention policies from the YAML file at `path`.
+    pub fn load(path: &Path) -> IndexerResult<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Failed to read retention policies from {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        let config: RetentionConfig = serde_yaml::from_str(&contents).map_err(|e| {
+            IndexerError::InvalidArgumentError(format!(
+                "Failed to parse retention policies from {}: {e}",
+                path.display()
+            ))
+        })?;
+
+        Self::new(&config)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.epochs_to_keep.is_empty()
+    }
+
+    /// The first epoch of each table with a policy that should be kept, given the current epoch.
+    pub fn first_epochs_to_keep(
+        &self,
+        current_epoch: u64,
+    ) -> impl Iterator<Item = (PrunableTable, u64)> + '_ {
+        self.epochs_to_keep
+            .iter()
+            .map(move |(table, keep)| (*table, (current_epoch + 1).saturating_sub(*keep)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_epochs_to_keep() {
+        let config: RetentionConfig = serde_yaml::from_str(
+            r#"
+            epochs-to-keep:
+              objects_history: 2
+              events: 30
+            "#,
+        )
+        .unwrap();
+        let policies = RetentionPolicies::new(&config).unwrap();
+
+        assert_eq!(
+            policies.first_epochs_to_keep(10).collect::<Vec<_>>(),
+            vec![
+                (PrunableTable::Events, 0),
+                (PrunableTable::ObjectsHistory, 9)
+            ],
+        );
+        assert_eq!(
+            policies.first_epochs_to_keep(40).collect::<Vec<_>>(),
+            vec![
+                (PrunableTable::Events, 11),
+                (PrunableTable::ObjectsHistory, 39)
+            ],
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config: RetentionConfig =
+            serde_yaml::from_str("epochs-to-keep: { events: 0 }").unwrap();
+        assert!(RetentionPolicies::new(&config).is_err());
+
+        assert!(serde_yaml::from_str::<RetentionConfig>("epochs-to-keep: { objects: 1 }").is_err());
+        assert!(serde_yaml::from_str::<RetentionConfig>("epochs_to_keep: { events: 1 }").is_err());
+    }
+}
@@ -2,6 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    balance_changes (owner, coin_type, tx_sequence_number) {
+        owner -> Bytea,
+        coin_type -> Text,
+        tx_sequence_number -> Int8,
+        transaction_digest -> Bytea,
+        checkpoint_sequence_number -> Int8,
+        timestamp_ms -> Int8,
+        amount -> Numeric,
+        balance -> Numeric,
+    }
+}
+
 diesel::table! {
     checkpoints (sequence_number) {
         sequence_number -> Int8,
@@ -71,6 +84,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    graphql_api_key_quotas (api_key) {
+        api_key -> Text,
+        quota_window -> Int8,
+        requests -> Int8,
+    }
+}
+
+diesel::table! {
+    object_fields (object_id, object_version, field_name) {
+        object_id -> Bytea,
+        object_version -> Int8,
+        checkpoint_sequence_number -> Int8,
+        field_name -> Text,
+        field_value -> Text,
+        is_numeric -> Bool,
+        numeric_value -> Nullable<Numeric>,
+    }
+}
+
 diesel::table! {
     objects (object_id) {
         object_id -> Bytea,
@@ -157,6 +190,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    pruner_watermarks (table_name) {
+        table_name -> Text,
+        epoch -> Int8,
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
 diesel::table! {
     transactions (tx_sequence_number, checkpoint_sequence_number) {
         tx_sequence_number -> Int8,
@@ -227,15 +268,19 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    balance_changes,
     checkpoints,
     display,
     epochs,
     events,
+    graphql_api_key_quotas,
+    object_fields,
     objects,
     objects_history,
     objects_history_partition_0,
     objects_snapshot,
     packages,
+    pruner_watermarks,
     transactions,
     transactions_partition_0,
     tx_calls,
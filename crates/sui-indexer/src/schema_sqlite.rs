@@ -0,0 +1,263 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+// SQLite counterpart of `schema.rs`, used when `sqlite-feature` is enabled without
+// `postgres-feature`. Array columns are stored as BCS serialized blobs and tables are not
+// partitioned.
+
+pub mod sql_types {
+    /// A `Vec<Option<Vec<u8>>>` stored as a BCS serialized blob, standing in for Postgres'
+    /// `bytea[]` columns.
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(sqlite_type(name = "Binary"))]
+    pub struct BcsArray;
+}
+
+diesel::table! {
+    balance_changes (owner, coin_type, tx_sequence_number) {
+        owner -> Binary,
+        coin_type -> Text,
+        tx_sequence_number -> BigInt,
+        transaction_digest -> Binary,
+        checkpoint_sequence_number -> BigInt,
+        timestamp_ms -> BigInt,
+        amount -> Text,
+        balance -> Text,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BcsArray;
+
+    checkpoints (sequence_number) {
+        sequence_number -> BigInt,
+        checkpoint_digest -> Binary,
+        epoch -> BigInt,
+        network_total_transactions -> BigInt,
+        previous_checkpoint_digest -> Nullable<Binary>,
+        end_of_epoch -> Bool,
+        tx_digests -> BcsArray,
+        timestamp_ms -> BigInt,
+        total_gas_cost -> BigInt,
+        computation_cost -> BigInt,
+        storage_cost -> BigInt,
+        storage_rebate -> BigInt,
+        non_refundable_storage_fee -> BigInt,
+        checkpoint_commitments -> Binary,
+        validator_signature -> Binary,
+        end_of_epoch_data -> Nullable<Binary>,
+    }
+}
+
+diesel::table! {
+    display (object_type) {
+        object_type -> Text,
+        id -> Binary,
+        version -> SmallInt,
+        bcs -> Binary,
+    }
+}
+
+diesel::table! {
+    epochs (epoch) {
+        epoch -> BigInt,
+        first_checkpoint_id -> BigInt,
+        epoch_start_timestamp -> BigInt,
+        reference_gas_price -> BigInt,
+        protocol_version -> BigInt,
+        total_stake -> BigInt,
+        storage_fund_balance -> BigInt,
+        system_state -> Binary,
+        epoch_total_transactions -> Nullable<BigInt>,
+        last_checkpoint_id -> Nullable<BigInt>,
+        epoch_end_timestamp -> Nullable<BigInt>,
+        storage_fund_reinvestment -> Nullable<BigInt>,
+        storage_charge -> Nullable<BigInt>,
+        storage_rebate -> Nullable<BigInt>,
+        stake_subsidy_amount -> Nullable<BigInt>,
+        total_gas_fees -> Nullable<BigInt>,
+        total_stake_rewards_distributed -> Nullable<BigInt>,
+        leftover_storage_fund_inflow -> Nullable<BigInt>,
+        epoch_commitments -> Nullable<Binary>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BcsArray;
+
+    events (tx_sequence_number, event_sequence_number) {
+        tx_sequence_number -> BigInt,
+        event_sequence_number -> BigInt,
+        transaction_digest -> Binary,
+        checkpoint_sequence_number -> BigInt,
+        senders -> BcsArray,
+        package -> Binary,
+        module -> Text,
+        event_type -> Text,
+        timestamp_ms -> BigInt,
+        bcs -> Binary,
+    }
+}
+
+diesel::table! {
+    object_fields (object_id, object_version, field_name) {
+        object_id -> Binary,
+        object_version -> BigInt,
+        checkpoint_sequence_number -> BigInt,
+        field_name -> Text,
+        field_value -> Text,
+        is_numeric -> Bool,
+        numeric_value -> Nullable<Double>,
+    }
+}
+
+diesel::table! {
+    objects (object_id) {
+        object_id -> Binary,
+        object_version -> BigInt,
+        object_digest -> Binary,
+        checkpoint_sequence_number -> BigInt,
+        owner_type -> SmallInt,
+        owner_id -> Nullable<Binary>,
+        object_type -> Nullable<Text>,
+        serialized_object -> Binary,
+        coin_type -> Nullable<Text>,
+        coin_balance -> Nullable<BigInt>,
+        df_kind -> Nullable<SmallInt>,
+        df_name -> Nullable<Binary>,
+        df_object_type -> Nullable<Text>,
+        df_object_id -> Nullable<Binary>,
+    }
+}
+
+diesel::table! {
+    objects_history (checkpoint_sequence_number, object_id, object_version) {
+        object_id -> Binary,
+        object_version -> BigInt,
+        object_status -> SmallInt,
+        object_digest -> Nullable<Binary>,
+        checkpoint_sequence_number -> BigInt,
+        owner_type -> Nullable<SmallInt>,
+        owner_id -> Nullable<Binary>,
+        object_type -> Nullable<Text>,
+        serialized_object -> Nullable<Binary>,
+        coin_type -> Nullable<Text>,
+        coin_balance -> Nullable<BigInt>,
+        df_kind -> Nullable<SmallInt>,
+        df_name -> Nullable<Binary>,
+        df_object_type -> Nullable<Text>,
+        df_object_id -> Nullable<Binary>,
+    }
+}
+
+diesel::table! {
+    objects_snapshot (object_id) {
+        object_id -> Binary,
+        object_version -> BigInt,
+        object_status -> SmallInt,
+        object_digest -> Nullable<Binary>,
+        checkpoint_sequence_number -> BigInt,
+        owner_type -> Nullable<SmallInt>,
+        owner_id -> Nullable<Binary>,
+        object_type -> Nullable<Text>,
+        serialized_object -> Nullable<Binary>,
+        coin_type -> Nullable<Text>,
+        coin_balance -> Nullable<BigInt>,
+        df_kind -> Nullable<SmallInt>,
+        df_name -> Nullable<Binary>,
+        df_object_type -> Nullable<Text>,
+        df_object_id -> Nullable<Binary>,
+    }
+}
+
+diesel::table! {
+    packages (package_id) {
+        package_id -> Binary,
+        move_package -> Binary,
+    }
+}
+
+diesel::table! {
+    pruner_watermarks (table_name) {
+        table_name -> Text,
+        epoch -> BigInt,
+        checkpoint_sequence_number -> BigInt,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::BcsArray;
+
+    transactions (tx_sequence_number, checkpoint_sequence_number) {
+        tx_sequence_number -> BigInt,
+        transaction_digest -> Binary,
+        raw_transaction -> Binary,
+        raw_effects -> Binary,
+        checkpoint_sequence_number -> BigInt,
+        timestamp_ms -> BigInt,
+        object_changes -> BcsArray,
+        balance_changes -> BcsArray,
+        events -> BcsArray,
+        transaction_kind -> SmallInt,
+        success_command_count -> SmallInt,
+    }
+}
+
+diesel::table! {
+    tx_calls (package, tx_sequence_number) {
+        tx_sequence_number -> BigInt,
+        package -> Binary,
+        module -> Text,
+        func -> Text,
+    }
+}
+
+diesel::table! {
+    tx_changed_objects (object_id, tx_sequence_number) {
+        tx_sequence_number -> BigInt,
+        object_id -> Binary,
+    }
+}
+
+diesel::table! {
+    tx_input_objects (object_id, tx_sequence_number) {
+        tx_sequence_number -> BigInt,
+        object_id -> Binary,
+    }
+}
+
+diesel::table! {
+    tx_recipients (recipient, tx_sequence_number) {
+        tx_sequence_number -> BigInt,
+        recipient -> Binary,
+    }
+}
+
+diesel::table! {
+    tx_senders (sender, tx_sequence_number) {
+        tx_sequence_number -> BigInt,
+        sender -> Binary,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(
+    balance_changes,
+    checkpoints,
+    display,
+    epochs,
+    events,
+    object_fields,
+    objects,
+    objects_history,
+    objects_snapshot,
+    packages,
+    pruner_watermarks,
+    transactions,
+    tx_calls,
+    tx_changed_objects,
+    tx_input_objects,
+    tx_recipients,
+    tx_senders,
+);
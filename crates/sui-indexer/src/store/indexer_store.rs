@@ -10,7 +10,11 @@ use crate::errors::IndexerError;
 use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
 use crate::models::display::StoredDisplay;
 use crate::models::objects::{StoredDeletedObject, StoredObject};
-use crate::types::{IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex};
+use crate::retention::{EpochRange, PrunableTable};
+use crate::types::{
+    IndexedBalanceChange, IndexedCheckpoint, IndexedEvent, IndexedObjectField, IndexedPackage,
+    IndexedTransaction, TxIndex,
+};
 
 #[allow(clippy::large_enum_variant)]
 pub enum ObjectChangeToCommit {
@@ -66,6 +70,18 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
 
     async fn persist_packages(&self, packages: Vec<IndexedPackage>) -> Result<(), IndexerError>;
 
+    async fn persist_object_fields(
+        &self,
+        fields: Vec<IndexedObjectField>,
+    ) -> Result<(), IndexerError>;
+
+    /// Persists balance changes along with the balances after them. Balances are running sums, so
+    /// batches of changes must be persisted in order.
+    async fn persist_balance_changes(
+        &self,
+        changes: Vec<IndexedBalanceChange>,
+    ) -> Result<(), IndexerError>;
+
     async fn persist_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
 
     async fn advance_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError>;
@@ -75,5 +91,22 @@ pub trait IndexerStore: Any + Clone + Sync + Send + 'static {
         epoch: u64,
     ) -> Result<u64, IndexerError>;
 
+    async fn get_latest_epoch_id(&self) -> Result<Option<u64>, IndexerError>;
+
+    /// The checkpoints and transactions in `epoch`, or `None` if the epoch has not ended yet.
+    async fn get_epoch_range(&self, epoch: u64) -> Result<Option<EpochRange>, IndexerError>;
+
+    /// The earliest epoch that has not been pruned from `table`, if it was ever pruned.
+    async fn get_pruner_watermark(&self, table: PrunableTable)
+        -> Result<Option<u64>, IndexerError>;
+
+    /// Removes the rows of `epoch` from `table`, and moves the table's pruner watermark past it.
+    async fn prune_epoch(
+        &self,
+        table: PrunableTable,
+        epoch: u64,
+        range: EpochRange,
+    ) -> Result<(), IndexerError>;
+
     fn as_any(&self) -> &dyn Any;
 }
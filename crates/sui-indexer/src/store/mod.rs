@@ -7,17 +7,19 @@ pub use pg_indexer_store::PgIndexerStore;
 pub mod indexer_store;
 pub mod package_resolver;
 mod pg_indexer_store;
+#[cfg(feature = "postgres-feature")]
 mod pg_partition_manager;
 
 pub(crate) mod diesel_macro {
     macro_rules! read_only_blocking {
         ($pool:expr, $query:expr) => {{
-            let mut pg_pool_conn = crate::db::get_pg_pool_connection($pool)?;
-            pg_pool_conn
-                .build_transaction()
-                .read_only()
-                .run($query)
-                .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+            let mut pg_pool_conn = crate::db::get_pool_connection($pool)?;
+            let conn: &mut crate::db::DbConnection = &mut pg_pool_conn;
+            #[cfg(feature = "postgres-feature")]
+            let result = conn.build_transaction().read_only().run($query);
+            #[cfg(not(feature = "postgres-feature"))]
+            let result = diesel::Connection::transaction(conn, $query);
+            result.map_err(|e| IndexerError::DbReadError(e.to_string()))
         }};
     }
 
@@ -27,23 +29,26 @@ pub(crate) mod diesel_macro {
             backoff.max_elapsed_time = Some($max_elapsed);
 
             let result = match backoff::retry(backoff, || {
-                let mut pg_pool_conn = crate::db::get_pg_pool_connection($pool).map_err(|e| {
+                let mut pg_pool_conn = crate::db::get_pool_connection($pool).map_err(|e| {
                     backoff::Error::Transient {
-                        err: IndexerError::PostgresWriteError(e.to_string()),
+                        err: IndexerError::DbWriteError(e.to_string()),
                         retry_after: None,
                     }
                 })?;
-                pg_pool_conn
-                    .build_transaction()
-                    .read_write()
-                    .run($query)
-                    .map_err(|e| {
-                        tracing::error!("Error with persisting data into DB: {:?}, retrying...", e);
-                        backoff::Error::Transient {
-                            err: IndexerError::PostgresWriteError(e.to_string()),
-                            retry_after: None,
-                        }
-                    })
+                let conn: &mut crate::db::DbConnection = &mut pg_pool_conn;
+                // SQLite only allows one writer at a time, so take the write lock upfront rather
+                // than failing when upgrading from a read lock.
+                #[cfg(feature = "postgres-feature")]
+                let result = conn.build_transaction().read_write().run($query);
+                #[cfg(not(feature = "postgres-feature"))]
+                let result = conn.immediate_transaction($query);
+                result.map_err(|e| {
+                    tracing::error!("Error with persisting data into DB: {:?}, retrying...", e);
+                    backoff::Error::Transient {
+                        err: IndexerError::DbWriteError(e.to_string()),
+                        retry_after: None,
+                    }
+                })
             }) {
                 Ok(v) => Ok(v),
                 Err(backoff::Error::Transient { err, .. }) => Err(err),
@@ -13,7 +13,7 @@ use sui_package_resolver::{error::Error as PackageResolverError, Package, Packag
 use sui_types::base_types::{ObjectID, SequenceNumber};
 use sui_types::object::Object;
 
-use crate::db::PgConnectionPool;
+use crate::db::ConnectionPool;
 use crate::errors::IndexerError;
 use crate::handlers::tx_processor::IndexingPackageBuffer;
 use crate::metrics::IndexerMetrics;
@@ -24,11 +24,11 @@ use crate::types::IndexedPackage;
 /// A package resolver that reads packages from the database.
 #[derive(Clone)]
 pub struct IndexerStorePackageResolver {
-    cp: PgConnectionPool,
+    cp: ConnectionPool,
 }
 
 impl IndexerStorePackageResolver {
-    pub fn new(cp: PgConnectionPool) -> Self {
+    pub fn new(cp: ConnectionPool) -> Self {
         Self { cp }
     }
 }
@@ -69,7 +69,7 @@ impl IndexerStorePackageResolver {
             query.get_result::<i64>(conn).optional()
         })?
         else {
-            return Err(IndexerError::PostgresReadError(format!(
+            return Err(IndexerError::DbReadError(format!(
                 "Package version not found in DB: {:?}",
                 id
             )));
@@ -86,14 +86,14 @@ impl IndexerStorePackageResolver {
             query.get_result::<Vec<u8>>(conn).optional()
         })?
         else {
-            return Err(IndexerError::PostgresReadError(format!(
+            return Err(IndexerError::DbReadError(format!(
                 "Package not found in DB: {:?}",
                 id
             )));
         };
         let object = bcs::from_bytes::<Object>(&bcs)?;
         Package::read(&object).map_err(|e| {
-            IndexerError::PostgresReadError(format!("Failed parsing object to package: {:?}", e))
+            IndexerError::DbReadError(format!("Failed parsing object to package: {:?}", e))
         })
     }
 
@@ -21,28 +21,37 @@ use tracing::info;
 
 use sui_types::base_types::ObjectID;
 
-use crate::db::PgConnectionPool;
+use crate::db::ConnectionPool;
 use crate::errors::{Context, IndexerError};
 use crate::handlers::EpochToCommit;
 use crate::handlers::TransactionObjectChangesToCommit;
 use crate::metrics::IndexerMetrics;
+use crate::models::balance_changes::StoredBalanceChange;
 use crate::models::checkpoints::StoredCheckpoint;
 use crate::models::display::StoredDisplay;
 use crate::models::epoch::StoredEpochInfo;
 use crate::models::events::StoredEvent;
+use crate::models::numeric::I128;
 use crate::models::objects::{
     StoredDeletedHistoryObject, StoredDeletedObject, StoredHistoryObject, StoredObject,
-    StoredObjectSnapshot,
+    StoredObjectField, StoredObjectSnapshot,
 };
 use crate::models::packages::StoredPackage;
+use crate::models::pruner_watermarks::StoredPrunerWatermark;
 use crate::models::transactions::StoredTransaction;
+use crate::retention::{EpochRange, PrunableTable, PruneKey};
 use crate::schema::{
-    checkpoints, display, epochs, events, objects, objects_history, objects_snapshot, packages,
-    transactions, tx_calls, tx_changed_objects, tx_input_objects, tx_recipients, tx_senders,
+    balance_changes, checkpoints, display, epochs, events, object_fields, objects, objects_history,
+    objects_snapshot, packages, pruner_watermarks, transactions, tx_calls, tx_changed_objects,
+    tx_input_objects, tx_recipients, tx_senders,
 };
 use crate::store::diesel_macro::{read_only_blocking, transactional_blocking_with_retry};
-use crate::types::{IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex};
+use crate::types::{
+    IndexedBalanceChange, IndexedCheckpoint, IndexedEvent, IndexedObjectField, IndexedPackage,
+    IndexedTransaction, OwnerType, TxIndex,
+};
 
+#[cfg(feature = "postgres-feature")]
 use super::pg_partition_manager::{EpochPartitionData, PgPartitionManager};
 use super::IndexerStore;
 use super::ObjectChangeToCommit;
@@ -71,6 +80,10 @@ const PG_COMMIT_PARALLEL_CHUNK_SIZE: usize = 100;
 // optimistic locking.
 const PG_COMMIT_OBJECTS_PARALLEL_CHUNK_SIZE: usize = 500;
 const PG_DB_COMMIT_SLEEP_DURATION: Duration = Duration::from_secs(3600);
+// The amount of transactions, or checkpoints for tables pruned by checkpoint, to delete in one
+// DB transaction when pruning a table that is not partitioned.
+const PRUNE_TRANSACTIONS_BATCH_SIZE: u64 = 10_000;
+const PRUNE_CHECKPOINTS_BATCH_SIZE: u64 = 100;
 
 // with rn = 1, we only select the latest version of each object,
 // so that we don't have to update the same object multiple times.
@@ -103,15 +116,16 @@ SET object_version = EXCLUDED.object_version,
 
 #[derive(Clone)]
 pub struct PgIndexerStore {
-    blocking_cp: PgConnectionPool,
+    blocking_cp: ConnectionPool,
     metrics: IndexerMetrics,
     parallel_chunk_size: usize,
     parallel_objects_chunk_size: usize,
+    #[cfg(feature = "postgres-feature")]
     partition_manager: PgPartitionManager,
 }
 
 impl PgIndexerStore {
-    pub fn new(blocking_cp: PgConnectionPool, metrics: IndexerMetrics) -> Self {
+    pub fn new(blocking_cp: ConnectionPool, metrics: IndexerMetrics) -> Self {
         let parallel_chunk_size = std::env::var("PG_COMMIT_PARALLEL_CHUNK_SIZE")
             .unwrap_or_else(|_e| PG_COMMIT_PARALLEL_CHUNK_SIZE.to_string())
             .parse::<usize>()
@@ -120,6 +134,7 @@ impl PgIndexerStore {
             .unwrap_or_else(|_e| PG_COMMIT_OBJECTS_PARALLEL_CHUNK_SIZE.to_string())
             .parse::<usize>()
             .unwrap();
+        #[cfg(feature = "postgres-feature")]
         let partition_manager = PgPartitionManager::new(blocking_cp.clone())
             .expect("Failed to initialize partition manager");
 
@@ -128,11 +143,12 @@ impl PgIndexerStore {
             metrics,
             parallel_chunk_size,
             parallel_objects_chunk_size,
+            #[cfg(feature = "postgres-feature")]
             partition_manager,
         }
     }
 
-    pub fn blocking_cp(&self) -> PgConnectionPool {
+    pub fn blocking_cp(&self) -> ConnectionPool {
         self.blocking_cp.clone()
     }
 
@@ -599,6 +615,120 @@ impl PgIndexerStore {
         })
     }
 
+    fn persist_object_fields(&self, fields: Vec<IndexedObjectField>) -> Result<(), IndexerError> {
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_object_fields
+            .start_timer();
+        let fields = fields
+            .into_iter()
+            .map(StoredObjectField::from)
+            .collect::<Vec<_>>();
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                for fields_chunk in fields.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(object_fields::table)
+                        .values(fields_chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write object fields to PostgresDB")?;
+                }
+                Ok::<(), IndexerError>(())
+            },
+            PG_DB_COMMIT_SLEEP_DURATION
+        )
+        .tap_ok(|_| {
+            let elapsed = guard.stop_and_record();
+            info!(elapsed, "Persisted {} object fields", fields.len());
+        })
+        .tap_err(|e| {
+            tracing::error!("Failed to persist object fields with error: {}", e);
+        })
+    }
+
+    fn persist_balance_changes(
+        &self,
+        changes: Vec<IndexedBalanceChange>,
+    ) -> Result<(), IndexerError> {
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_balance_changes
+            .start_timer();
+        let len = changes.len();
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                // Each change adds to the latest balance before it, either from earlier in this
+                // batch, or already in the DB. The first change to an owner's balance of a coin
+                // type that the indexer sees starts from the coins it owns in the live object set,
+                // which is only written once this batch's balance changes are committed.
+                let mut balances: HashMap<(Vec<u8>, String), i128> = HashMap::new();
+                let mut stored = Vec::with_capacity(changes.len());
+                for change in &changes {
+                    let balance =
+                        match balances.entry((change.owner.to_vec(), change.coin_type.clone())) {
+                            Entry::Occupied(entry) => entry.into_mut(),
+                            Entry::Vacant(entry) => {
+                                let (owner, coin_type) = entry.key();
+                                let previous = balance_changes::table
+                                    .filter(balance_changes::owner.eq(owner))
+                                    .filter(balance_changes::coin_type.eq(coin_type))
+                                    .filter(
+                                        balance_changes::tx_sequence_number
+                                            .lt(change.tx_sequence_number as i64),
+                                    )
+                                    .order(balance_changes::tx_sequence_number.desc())
+                                    .select(balance_changes::balance)
+                                    .first::<I128>(conn)
+                                    .optional()
+                                    .map_err(IndexerError::from)
+                                    .context("Failed to read balance from PostgresDB")?;
+
+                                let previous = match previous {
+                                    Some(I128(balance)) => balance,
+                                    None => objects::table
+                                        .filter(objects::owner_type.eq(OwnerType::Address as i16))
+                                        .filter(objects::owner_id.eq(owner))
+                                        .filter(objects::coin_type.eq(coin_type))
+                                        .select(objects::coin_balance)
+                                        .load::<Option<i64>>(conn)
+                                        .map_err(IndexerError::from)
+                                        .context("Failed to read coin balances from PostgresDB")?
+                                        .into_iter()
+                                        .map(|b| b.unwrap_or(0) as u64 as i128)
+                                        .sum(),
+                                };
+
+                                entry.insert(previous)
+                            }
+                        };
+                    *balance += change.amount;
+                    stored.push(StoredBalanceChange::new(change.clone(), *balance));
+                }
+
+                for chunk in stored.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(balance_changes::table)
+                        .values(chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .map_err(IndexerError::from)
+                        .context("Failed to write balance changes to PostgresDB")?;
+                }
+                Ok::<(), IndexerError>(())
+            },
+            PG_DB_COMMIT_SLEEP_DURATION
+        )
+        .tap_ok(|_| {
+            let elapsed = guard.stop_and_record();
+            info!(elapsed, "Persisted {} balance changes", len);
+        })
+        .tap_err(|e| {
+            tracing::error!("Failed to persist balance changes with error: {}", e);
+        })
+    }
+
     async fn persist_tx_indices_chunk(&self, indices: Vec<TxIndex>) -> Result<(), IndexerError> {
         let guard = self
             .metrics
@@ -773,7 +903,7 @@ impl PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all tx indices in a chunk: {:?}",
                     e
                 ))
@@ -843,6 +973,7 @@ impl PgIndexerStore {
         })
     }
 
+    #[cfg(feature = "postgres-feature")]
     fn advance_epoch(&self, epoch_to_commit: EpochToCommit) -> Result<(), IndexerError> {
         let last_epoch_id = epoch_to_commit.last_epoch.as_ref().map(|e| e.epoch);
         // partition_0 has been created, so no need to advance it.
@@ -882,6 +1013,12 @@ impl PgIndexerStore {
         Ok(())
     }
 
+    /// SQLite tables are not partitioned by epoch, so there is nothing to advance.
+    #[cfg(not(feature = "postgres-feature"))]
+    fn advance_epoch(&self, _epoch_to_commit: EpochToCommit) -> Result<(), IndexerError> {
+        Ok(())
+    }
+
     fn get_network_total_transactions_by_end_of_epoch(
         &self,
         epoch: u64,
@@ -897,6 +1034,142 @@ impl PgIndexerStore {
         .map(|v| v as u64)
     }
 
+    fn get_latest_epoch_id(&self) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            epochs::table
+                .select(max(epochs::epoch))
+                .first::<Option<i64>>(conn)
+                .map(|v| v.map(|v| v as u64))
+        })
+        .context("Failed reading latest epoch id from PostgresDB")
+    }
+
+    fn get_epoch_range(&self, epoch: u64) -> Result<Option<EpochRange>, IndexerError> {
+        // The epoch has ended once the next epoch has started.
+        let first_checkpoints: Vec<i64> = read_only_blocking!(&self.blocking_cp, |conn| {
+            epochs::table
+                .filter(epochs::epoch.between(epoch as i64, epoch as i64 + 1))
+                .order(epochs::epoch.asc())
+                .select(epochs::first_checkpoint_id)
+                .load::<i64>(conn)
+        })
+        .context("Failed reading epoch checkpoints from PostgresDB")?;
+        let &[start_cp, end_cp] = first_checkpoints.as_slice() else {
+            return Ok(None);
+        };
+
+        let start_tx = if epoch == 0 {
+            0
+        } else {
+            self.get_network_total_transactions_by_end_of_epoch(epoch - 1)?
+        };
+        let end_tx = self.get_network_total_transactions_by_end_of_epoch(epoch)?;
+
+        Ok(Some(EpochRange {
+            checkpoints: start_cp as u64..end_cp as u64,
+            transactions: start_tx..end_tx,
+        }))
+    }
+
+    fn get_pruner_watermark(&self, table: PrunableTable) -> Result<Option<u64>, IndexerError> {
+        read_only_blocking!(&self.blocking_cp, |conn| {
+            pruner_watermarks::table
+                .filter(pruner_watermarks::table_name.eq(table.name()))
+                .select(pruner_watermarks::epoch)
+                .first::<i64>(conn)
+                .optional()
+                .map(|v| v.map(|v| v as u64))
+        })
+        .context("Failed reading pruner watermark from PostgresDB")
+    }
+
+    fn prune_epoch(
+        &self,
+        table: PrunableTable,
+        epoch: u64,
+        range: EpochRange,
+    ) -> Result<(), IndexerError> {
+        if table.is_partitioned() {
+            self.drop_epoch_partition(table, epoch)?;
+        } else {
+            self.delete_epoch_rows(table, &range)?;
+        }
+
+        let watermark = StoredPrunerWatermark {
+            table_name: table.name().to_string(),
+            epoch: epoch as i64 + 1,
+            checkpoint_sequence_number: range.checkpoints.end as i64,
+        };
+        transactional_blocking_with_retry!(
+            &self.blocking_cp,
+            |conn| {
+                diesel::insert_into(pruner_watermarks::table)
+                    .values(&watermark)
+                    .on_conflict(pruner_watermarks::table_name)
+                    .do_update()
+                    .set((
+                        pruner_watermarks::epoch.eq(excluded(pruner_watermarks::epoch)),
+                        pruner_watermarks::checkpoint_sequence_number
+                            .eq(excluded(pruner_watermarks::checkpoint_sequence_number)),
+                    ))
+                    .execute(conn)
+                    .map_err(IndexerError::from)
+                    .context("Failed to write pruner watermark to PostgresDB")?;
+                Ok::<(), IndexerError>(())
+            },
+            PG_DB_COMMIT_SLEEP_DURATION
+        )
+    }
+
+    #[cfg(feature = "postgres-feature")]
+    fn drop_epoch_partition(&self, table: PrunableTable, epoch: u64) -> Result<(), IndexerError> {
+        self.partition_manager
+            .drop_table_epoch_partition(table.name().to_string(), epoch)
+    }
+
+    /// SQLite tables are not partitioned by epoch, so they are always pruned by deleting rows.
+    #[cfg(not(feature = "postgres-feature"))]
+    fn drop_epoch_partition(&self, table: PrunableTable, _epoch: u64) -> Result<(), IndexerError> {
+        Err(IndexerError::InvalidArgumentError(format!(
+            "Table {} is not partitioned",
+            table.name()
+        )))
+    }
+
+    /// Deletes the rows of an epoch from a table in batches, each in its own DB transaction, so
+    /// that pruning does not hold locks on the table for long.
+    fn delete_epoch_rows(
+        &self,
+        table: PrunableTable,
+        range: &EpochRange,
+    ) -> Result<(), IndexerError> {
+        let key = table.prune_key();
+        let batch_size = match key {
+            PruneKey::TxSequenceNumber => PRUNE_TRANSACTIONS_BATCH_SIZE,
+            PruneKey::CheckpointSequenceNumber => PRUNE_CHECKPOINTS_BATCH_SIZE,
+        };
+
+        let range = range.by_key(key);
+        let mut lo = range.start;
+        while lo < range.end {
+            let hi = (lo + batch_size).min(range.end);
+            let query = format!(
+                "DELETE FROM {table} WHERE {column} >= {lo} AND {column} < {hi}",
+                table = table.name(),
+                column = key.column(),
+            );
+            transactional_blocking_with_retry!(
+                &self.blocking_cp,
+                |conn| diesel::sql_query(query.clone()).execute(conn),
+                PG_DB_COMMIT_SLEEP_DURATION
+            )
+            .context(&format!("Failed to prune rows from {}", table.name()))?;
+            lo = hi;
+        }
+
+        Ok(())
+    }
+
     async fn execute_in_blocking_worker<F, R>(&self, f: F) -> Result<R, IndexerError>
     where
         F: FnOnce(Self) -> Result<R, IndexerError> + Send + 'static,
@@ -1007,7 +1280,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all object mutation chunks: {:?}",
                     e
                 ))
@@ -1030,7 +1303,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all object deletion chunks: {:?}",
                     e
                 ))
@@ -1080,7 +1353,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all objects snapshot chunks: {:?}",
                     e
                 ))
@@ -1132,7 +1405,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all objects history chunks: {:?}",
                     e
                 ))
@@ -1160,10 +1433,7 @@ impl IndexerStore for PgIndexerStore {
         self.spawn_blocking_task(move |this| this.update_objects_snapshot(start_cp, end_cp))
             .await
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to update objects snapshot: {:?}",
-                    e
-                ))
+                IndexerError::DbWriteError(format!("Failed to update objects snapshot: {:?}", e))
             })??;
         let elapsed = guard.stop_and_record();
         info!(
@@ -1208,7 +1478,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all transactions chunks: {:?}",
                     e
                 ))
@@ -1244,10 +1514,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
-                    "Failed to persist all events chunks: {:?}",
-                    e
-                ))
+                IndexerError::DbWriteError(format!("Failed to persist all events chunks: {:?}", e))
             })?;
         let elapsed = guard.stop_and_record();
         info!(elapsed, "Persisted {} events", len);
@@ -1274,6 +1541,28 @@ impl IndexerStore for PgIndexerStore {
             .await
     }
 
+    async fn persist_object_fields(
+        &self,
+        fields: Vec<IndexedObjectField>,
+    ) -> Result<(), IndexerError> {
+        if fields.is_empty() {
+            return Ok(());
+        }
+        self.execute_in_blocking_worker(move |this| this.persist_object_fields(fields))
+            .await
+    }
+
+    async fn persist_balance_changes(
+        &self,
+        changes: Vec<IndexedBalanceChange>,
+    ) -> Result<(), IndexerError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        self.execute_in_blocking_worker(move |this| this.persist_balance_changes(changes))
+            .await
+    }
+
     async fn persist_tx_indices(&self, indices: Vec<TxIndex>) -> Result<(), IndexerError> {
         if indices.is_empty() {
             return Ok(());
@@ -1304,7 +1593,7 @@ impl IndexerStore for PgIndexerStore {
             .into_iter()
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| {
-                IndexerError::PostgresWriteError(format!(
+                IndexerError::DbWriteError(format!(
                     "Failed to persist all tx_indices chunks: {:?}",
                     e
                 ))
@@ -1334,6 +1623,38 @@ impl IndexerStore for PgIndexerStore {
         .await
     }
 
+    async fn get_latest_epoch_id(&self) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(|this| this.get_latest_epoch_id())
+            .await
+    }
+
+    async fn get_epoch_range(&self, epoch: u64) -> Result<Option<EpochRange>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.get_epoch_range(epoch))
+            .await
+    }
+
+    async fn get_pruner_watermark(
+        &self,
+        table: PrunableTable,
+    ) -> Result<Option<u64>, IndexerError> {
+        self.execute_in_blocking_worker(move |this| this.get_pruner_watermark(table))
+            .await
+    }
+
+    async fn prune_epoch(
+        &self,
+        table: PrunableTable,
+        epoch: u64,
+        range: EpochRange,
+    ) -> Result<(), IndexerError> {
+        let guard = self.metrics.prune_epoch_latency.start_timer();
+        self.execute_in_blocking_worker(move |this| this.prune_epoch(table, epoch, range))
+            .await?;
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, epoch, "Pruned epoch from {}", table.name());
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
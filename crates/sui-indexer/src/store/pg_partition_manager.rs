@@ -7,7 +7,7 @@ use std::collections::BTreeMap;
 use std::time::Duration;
 use tracing::{error, info};
 
-use crate::db::PgConnectionPool;
+use crate::db::ConnectionPool;
 use crate::handlers::EpochToCommit;
 use crate::models::epoch::StoredEpochInfo;
 use crate::store::diesel_macro::{read_only_blocking, transactional_blocking_with_retry};
@@ -27,7 +27,7 @@ GROUP BY table_name;
 
 #[derive(Clone)]
 pub struct PgPartitionManager {
-    cp: PgConnectionPool,
+    cp: ConnectionPool,
 }
 
 #[derive(Clone, Debug)]
@@ -54,7 +54,7 @@ impl EpochPartitionData {
 }
 
 impl PgPartitionManager {
-    pub fn new(cp: PgConnectionPool) -> Result<Self, IndexerError> {
+    pub fn new(cp: ConnectionPool) -> Result<Self, IndexerError> {
         let manager = Self { cp };
         let tables = manager.get_table_partitions()?;
         info!(
@@ -125,4 +125,36 @@ impl PgPartitionManager {
         }
         Ok(())
     }
+
+    /// Detaches and drops the partition of `table` holding `epoch`. Only partitions of epochs
+    /// before the current one should be dropped, as the current partition receives new rows.
+    pub fn drop_table_epoch_partition(
+        &self,
+        table: String,
+        epoch: u64,
+    ) -> Result<(), IndexerError> {
+        let partitions = self.get_table_partitions()?;
+        if partitions
+            .get(&table)
+            .map_or(true, |last_partition| *last_partition <= epoch)
+        {
+            return Err(IndexerError::InvalidArgumentError(format!(
+                "Cannot drop partition {epoch} of table {table}, which is not before its last \
+                 partition."
+            )));
+        }
+
+        transactional_blocking_with_retry!(
+            &self.cp,
+            |conn| {
+                RunQueryDsl::execute(
+                    diesel::sql_query(format!("DROP TABLE IF EXISTS {table}_partition_{epoch}")),
+                    conn,
+                )
+            },
+            Duration::from_secs(10)
+        )?;
+        info!("Dropped epoch partition {} for table {}", epoch, table);
+        Ok(())
+    }
 }
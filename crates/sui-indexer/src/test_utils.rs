@@ -11,7 +11,7 @@ use std::time::Duration;
 use sui_json_rpc_types::SuiTransactionBlockResponse;
 use tracing::info;
 
-use crate::db::{new_pg_connection_pool_with_config, reset_database, PgConnectionPoolConfig};
+use crate::db::{new_connection_pool_with_config, reset_database, ConnectionPoolConfig};
 use crate::errors::IndexerError;
 use crate::handlers::objects_snapshot_processor::SnapshotLagConfig;
 use crate::indexer::Indexer;
@@ -76,7 +76,7 @@ pub async fn start_test_indexer_impl(
         .unwrap();
 
     // Set connection timeout for tests to 1 second
-    let mut pool_config = PgConnectionPoolConfig::default();
+    let mut pool_config = ConnectionPoolConfig::default();
     pool_config.set_connection_timeout(Duration::from_secs(1));
 
     // Default writer mode
@@ -104,7 +104,7 @@ pub async fn start_test_indexer_impl(
 
         // Open in default mode
         let blocking_pool =
-            new_pg_connection_pool_with_config(&default_db_url, Some(5), pool_config).unwrap();
+            new_connection_pool_with_config(&default_db_url, Some(5), pool_config).unwrap();
         let mut default_conn = blocking_pool.get().unwrap();
 
         // Delete the old db if it exists
@@ -119,8 +119,7 @@ pub async fn start_test_indexer_impl(
         parsed_url = replace_db_name(&parsed_url, &new_database).0;
     }
 
-    let blocking_pool =
-        new_pg_connection_pool_with_config(&parsed_url, Some(5), pool_config).unwrap();
+    let blocking_pool = new_connection_pool_with_config(&parsed_url, Some(5), pool_config).unwrap();
     let store = PgIndexerStore::new(blocking_pool.clone(), indexer_metrics.clone());
 
     let handle = match reader_writer_config {
@@ -173,11 +172,11 @@ pub async fn force_delete_database(db_url: String) {
     // Hence switch to the default `postgres` database to drop the active database.
     let (default_db_url, db_name) = replace_db_name(&db_url, "postgres");
     // Set connection timeout for tests to 1 second
-    let mut pool_config = PgConnectionPoolConfig::default();
+    let mut pool_config = ConnectionPoolConfig::default();
     pool_config.set_connection_timeout(Duration::from_secs(1));
 
     let blocking_pool =
-        new_pg_connection_pool_with_config(&default_db_url, Some(5), pool_config).unwrap();
+        new_connection_pool_with_config(&default_db_url, Some(5), pool_config).unwrap();
     blocking_pool
         .get()
         .unwrap()
@@ -311,6 +311,17 @@ impl IndexedObject {
     }
 }
 
+/// The value of a field projected from a version of an object, see `field_projections`.
+#[derive(Clone, Debug)]
+pub struct IndexedObjectField {
+    pub object_id: ObjectID,
+    pub object_version: u64,
+    pub checkpoint_sequence_number: u64,
+    pub field_name: String,
+    pub field_value: String,
+    pub is_numeric: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct IndexedDeletedObject {
     pub object_id: ObjectID,
@@ -318,6 +329,41 @@ pub struct IndexedDeletedObject {
     pub checkpoint_sequence_number: u64,
 }
 
+/// A change to the balance of coins of one type owned by an address, made by a transaction.
+#[derive(Clone, Debug)]
+pub struct IndexedBalanceChange {
+    pub owner: SuiAddress,
+    pub coin_type: String,
+    pub tx_sequence_number: u64,
+    pub transaction_digest: TransactionDigest,
+    pub checkpoint_sequence_number: u64,
+    pub timestamp_ms: u64,
+    pub amount: i128,
+}
+
+impl IndexedBalanceChange {
+    /// The balance changes of `transaction` to coins owned by addresses. Changes to coins owned by
+    /// objects are not tracked.
+    pub fn from_transaction(transaction: &IndexedTransaction) -> Vec<Self> {
+        transaction
+            .balance_change
+            .iter()
+            .filter_map(|change| match change.owner {
+                Owner::AddressOwner(owner) => Some(Self {
+                    owner,
+                    coin_type: change.coin_type.to_canonical_string(/* with_prefix */ true),
+                    tx_sequence_number: transaction.tx_sequence_number,
+                    transaction_digest: transaction.tx_digest,
+                    checkpoint_sequence_number: transaction.checkpoint_sequence_number,
+                    timestamp_ms: transaction.timestamp_ms,
+                    amount: change.amount,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug)]
 pub struct IndexedPackage {
     pub package_id: ObjectID,
@@ -0,0 +1,113 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// SQLite needs no external database, so these tests run whenever the indexer is built against it.
+#[cfg(not(feature = "postgres-feature"))]
+mod sqlite_ingestion_tests {
+    use diesel::ExpressionMethods;
+    use diesel::{QueryDsl, RunQueryDsl};
+    use simulacrum::Simulacrum;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sui_indexer::db::get_pool_connection;
+    use sui_indexer::errors::IndexerError;
+    use sui_indexer::models::transactions::StoredTransaction;
+    use sui_indexer::schema::transactions;
+    use sui_indexer::store::{indexer_store::IndexerStore, PgIndexerStore};
+    use sui_indexer::test_utils::{start_test_indexer, ReaderWriterConfig};
+    use sui_types::base_types::SuiAddress;
+    use sui_types::effects::TransactionEffectsAPI;
+    use sui_types::storage::ReadStore;
+    use tempfile::TempDir;
+
+    const DEFAULT_SERVER_PORT: u16 = 3100;
+
+    /// Set up a test indexer writing to a fresh SQLite database in `dir`, fetching from a REST
+    /// endpoint served by the given Simulacrum.
+    async fn set_up(sim: Arc<Simulacrum>, dir: &TempDir) -> PgIndexerStore {
+        let server_url: SocketAddr = format!("127.0.0.1:{}", DEFAULT_SERVER_PORT)
+            .parse()
+            .unwrap();
+
+        tokio::spawn(async move {
+            let chain_id = (*sim
+                .get_checkpoint_by_sequence_number(0)
+                .unwrap()
+                .unwrap()
+                .digest())
+            .into();
+
+            sui_rest_api::RestService::new_without_version(sim, chain_id)
+                .start_service(server_url, Some("/rest".to_owned()))
+                .await;
+        });
+
+        let db_url = dir.path().join("indexer.sqlite");
+        let (store, _) = start_test_indexer(
+            Some(db_url.to_string_lossy().into_owned()),
+            format!("http://{}", server_url),
+            ReaderWriterConfig::writer_mode(None),
+        )
+        .await;
+        store
+    }
+
+    /// Wait for the indexer to catch up to the given checkpoint sequence number.
+    async fn wait_for_checkpoint(store: &PgIndexerStore, checkpoint_sequence_number: u64) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while {
+                let cp_opt = store.get_latest_checkpoint_sequence_number().await.unwrap();
+                cp_opt.is_none() || (cp_opt.unwrap() < checkpoint_sequence_number)
+            } {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        })
+        .await
+        .expect("Timeout waiting for indexer to catchup to checkpoint");
+    }
+
+    #[tokio::test]
+    pub async fn test_transaction_table() -> Result<(), IndexerError> {
+        let mut sim = Simulacrum::new();
+
+        // Execute a simple transaction.
+        let transfer_recipient = SuiAddress::random_for_testing_only();
+        let (transaction, _) = sim.transfer_txn(transfer_recipient);
+        let (effects, err) = sim.execute_transaction(transaction.clone()).unwrap();
+        assert!(err.is_none());
+
+        // Create a checkpoint which should include the transaction we executed.
+        let checkpoint = sim.create_checkpoint();
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = set_up(Arc::new(sim), &dir).await;
+
+        // Wait for the indexer to catch up to the checkpoint.
+        wait_for_checkpoint(&store, 1).await;
+
+        let digest = effects.transaction_digest();
+
+        // Read the transaction from the database directly, including the array columns that
+        // SQLite stores as BCS blobs.
+        let mut conn = get_pool_connection(&store.blocking_cp())?;
+        let db_txn: StoredTransaction = transactions::table
+            .filter(transactions::transaction_digest.eq(digest.inner().to_vec()))
+            .first::<StoredTransaction>(&mut conn)?;
+
+        // Check that the transaction was stored correctly.
+        assert_eq!(db_txn.tx_sequence_number, 1);
+        assert_eq!(db_txn.transaction_digest, digest.inner().to_vec());
+        assert_eq!(
+            db_txn.raw_transaction,
+            bcs::to_bytes(&transaction.data()).unwrap()
+        );
+        assert_eq!(db_txn.raw_effects, bcs::to_bytes(&effects).unwrap());
+        assert_eq!(db_txn.timestamp_ms, checkpoint.timestamp_ms as i64);
+        assert_eq!(db_txn.checkpoint_sequence_number, 1);
+        assert_eq!(db_txn.transaction_kind, 1);
+        assert_eq!(db_txn.success_command_count, 2); // split coin + transfer
+        assert_eq!(db_txn.balance_changes.len(), 2);
+        Ok(())
+    }
+}
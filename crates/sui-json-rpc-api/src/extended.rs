@@ -5,9 +5,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    CheckpointedObjectID, EpochInfo, EpochPage, QueryObjectsPage, SuiObjectResponseQuery,
+    AddressBalanceChangePage, CheckpointedObjectID, EpochInfo, EpochPage, QueryObjectsPage,
+    SuiObjectResponseQuery,
 };
 use sui_open_rpc_macros::open_rpc;
+use sui_types::base_types::SuiAddress;
 use sui_types::sui_serde::BigInt;
 
 #[open_rpc(namespace = "suix", tag = "Extended API")]
@@ -43,4 +45,21 @@ pub trait ExtendedApi {
 
     #[method(name = "getTotalTransactions")]
     async fn get_total_transactions(&self) -> RpcResult<BigInt<u64>>;
+
+    /// Return the changes to an address' balance of a coin type, in the order of the transactions
+    /// that made them, along with the balance after each change.
+    #[method(name = "getBalanceChanges")]
+    async fn get_balance_changes(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// optional paging cursor
+        cursor: Option<BigInt<u64>>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// flag to return results in descending order
+        descending_order: Option<bool>,
+    ) -> RpcResult<AddressBalanceChangePage>;
 }
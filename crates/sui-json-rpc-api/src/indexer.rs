@@ -71,11 +71,19 @@ pub trait IndexerApi {
         &self,
         /// The filter criteria of the event stream. See [Event filter](https://docs.sui.io/build/event_api#event-filters) documentation for examples.
         filter: EventFilter,
+        /// An optional event id to resume the stream from. If provided, the events after it that match the filter are replayed from history before switching to live events. The filter must then be supported by `queryEvents`.
+        cursor: Option<EventID>,
     );
 
     /// Subscribe to a stream of Sui transaction effects
     #[subscription(name = "subscribeTransaction", item = SuiTransactionBlockEffects)]
-    fn subscribe_transaction(&self, filter: TransactionFilter);
+    fn subscribe_transaction(
+        &self,
+        /// The filter criteria of the transaction stream.
+        filter: TransactionFilter,
+        /// An optional transaction digest to resume the stream from. If provided, the transactions after it that match the filter are replayed from history before switching to live transactions. The filter must then be supported by `queryTransactionBlocks`.
+        cursor: Option<TransactionDigest>,
+    );
 
     /// Return the list of dynamic field objects owned by an object.
     #[method(name = "getDynamicFields")]
@@ -10,8 +10,11 @@ use tokio::time::timeout;
 
 use sui_core::test_utils::wait_for_tx;
 use sui_json_rpc_types::{
-    SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, TransactionFilter,
+    EventFilter, SuiEvent, SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI,
+    TransactionFilter,
 };
+use sui_types::event::EventID;
+use sui_types::parse_sui_struct_tag;
 use test_cluster::TestClusterBuilder;
 
 #[tokio::test]
@@ -46,3 +49,105 @@ async fn test_subscribe_transaction() -> Result<(), anyhow::Error> {
     assert_eq!(&digest, effects.transaction_digest());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_subscribe_transaction_from_cursor() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+
+    let address = &cluster.get_address_0();
+    let wallet = cluster.wallet;
+
+    let ws_client = cluster.fullnode_handle.ws_client().await;
+
+    let package_id = publish_nfts_package(&wallet).await.0;
+    let (_, _, cursor) = create_devnet_nft(&wallet, package_id).await;
+    let (_, _, replayed) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(replayed, cluster.fullnode_handle.sui_node.state()).await;
+
+    let mut sub: Subscription<SuiTransactionBlockEffects> = ws_client
+        .subscribe(
+            "suix_subscribeTransaction",
+            rpc_params![TransactionFilter::FromAddress(*address), cursor],
+            "suix_unsubscribeTransaction",
+        )
+        .await
+        .unwrap();
+
+    // Transactions after the cursor are replayed from history...
+    let effects = match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(tx))) => tx,
+        _ => panic!("Failed to get replayed tx"),
+    };
+    assert_eq!(&replayed, effects.transaction_digest());
+
+    // ...before switching to live transactions.
+    let (_, _, live) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(live, cluster.fullnode_handle.sui_node.state()).await;
+
+    let effects = match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(tx))) => tx,
+        _ => panic!("Failed to get live tx"),
+    };
+    assert_eq!(&live, effects.transaction_digest());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribe_event_from_cursor() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await;
+
+    let wallet = cluster.wallet;
+    let ws_client = cluster.fullnode_handle.ws_client().await;
+
+    let package_id = publish_nfts_package(&wallet).await.0;
+    let event_type = parse_sui_struct_tag(&format!("{package_id}::devnet_nft::MintNFTEvent"))?;
+
+    let (_, _, cursor) = create_devnet_nft(&wallet, package_id).await;
+    let (_, _, replayed) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(replayed, cluster.fullnode_handle.sui_node.state()).await;
+
+    let cursor = EventID {
+        tx_digest: cursor,
+        event_seq: 0,
+    };
+    let mut sub: Subscription<SuiEvent> = ws_client
+        .subscribe(
+            "suix_subscribeEvent",
+            rpc_params![EventFilter::MoveEventType(event_type.clone()), cursor],
+            "suix_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+
+    // Events after the cursor are replayed from history...
+    let event = match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(event))) => event,
+        _ => panic!("Failed to get replayed event"),
+    };
+    assert_eq!(replayed, event.id.tx_digest);
+    assert_eq!(event_type, event.type_);
+
+    // ...before switching to live events.
+    let (_, _, live) = create_devnet_nft(&wallet, package_id).await;
+    wait_for_tx(live, cluster.fullnode_handle.sui_node.state()).await;
+
+    let event = match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(event))) => event,
+        _ => panic!("Failed to get live event"),
+    };
+    assert_eq!(live, event.id.tx_digest);
+
+    // Filters that can't be replayed can't be resumed from a cursor.
+    let rejected: Result<Subscription<SuiEvent>, _> = ws_client
+        .subscribe(
+            "suix_subscribeEvent",
+            rpc_params![EventFilter::Package(package_id), cursor],
+            "suix_unsubscribeEvent",
+        )
+        .await;
+    assert!(rejected.is_err());
+    Ok(())
+}
@@ -305,6 +305,25 @@ impl EventFilter {
         })
     }
 
+    /// Whether the events matching this filter can be replayed from the event indexes, which
+    /// is required to query them, or to resume a subscription to them from a cursor.
+    pub fn is_replayable(&self) -> bool {
+        match self {
+            EventFilter::All(filters) => filters.is_empty(),
+            EventFilter::Sender(_)
+            | EventFilter::Transaction(_)
+            | EventFilter::MoveModule { .. }
+            | EventFilter::MoveEventType(_)
+            | EventFilter::MoveEventModule { .. }
+            | EventFilter::TimeRange { .. } => true,
+            EventFilter::Package(_)
+            | EventFilter::MoveEventField { .. }
+            | EventFilter::Any(_)
+            | EventFilter::And(_, _)
+            | EventFilter::Or(_, _) => false,
+        }
+    }
+
     pub fn and(self, other_filter: EventFilter) -> Self {
         Self::All(vec![self, other_filter])
     }
@@ -14,6 +14,7 @@ use serde_with::DisplayFromStr;
 use sui_types::base_types::AuthorityName;
 use sui_types::base_types::{EpochId, ObjectID};
 use sui_types::committee::Committee;
+use sui_types::digests::TransactionDigest;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::sui_serde::BigInt;
 use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
@@ -21,6 +22,7 @@ use sui_types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
 use crate::Page;
 
 pub type EpochPage = Page<EpochInfo, BigInt<u64>>;
+pub type AddressBalanceChangePage = Page<AddressBalanceChange, BigInt<u64>>;
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -116,3 +118,25 @@ pub struct MoveFunctionName {
     #[serde_as(as = "DisplayFromStr")]
     pub function: Identifier,
 }
+
+/// A change to an address' balance of one coin type, made by a transaction.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalanceChange {
+    pub transaction_digest: TransactionDigest,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub checkpoint: CheckpointSequenceNumber,
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub timestamp_ms: u64,
+    /// The amount the balance changed by, negative when the balance decreased.
+    #[schemars(with = "String")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: i128,
+    /// The balance after the transaction.
+    #[schemars(with = "BigInt<u128>")]
+    #[serde_as(as = "BigInt<u128>")]
+    pub balance: u128,
+}
@@ -248,6 +248,14 @@ impl From<Error> for RpcError {
                             ErrorObject::owned(TRANSIENT_ERROR_CODE, err.to_string(), None::<()>);
                         RpcError::Call(CallError::Custom(error_object))
                     }
+                    QuorumDriverError::TransactionRejected(rejection) => {
+                        let error_object = ErrorObject::owned(
+                            TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
+                            format!("Transaction is rejected by validation hook {rejection}"),
+                            Some(rejection),
+                        );
+                        RpcError::Call(CallError::Custom(error_object))
+                    }
                 }
             }
             _ => RpcError::Call(CallError::Failed(e.into())),
@@ -314,6 +322,7 @@ mod tests {
     use sui_types::crypto::AuthorityPublicKeyBytes;
     use sui_types::digests::ObjectDigest;
     use sui_types::digests::TransactionDigest;
+    use sui_types::error::TransactionRejection;
 
     fn test_object_ref() -> ObjectRef {
         (
@@ -506,5 +515,28 @@ mod tests {
             let expected_message = expect!["Transaction is not processed because 10 of validators by stake are overloaded with certificates pending execution."];
             expected_message.assert_eq(error_object.message());
         }
+
+        #[test]
+        fn test_transaction_rejected() {
+            let quorum_driver_error =
+                QuorumDriverError::TransactionRejected(TransactionRejection {
+                    hook: "min-gas-price-for-packages".to_string(),
+                    code: "gas-price-too-low".to_string(),
+                    message: "Gas price is too low".to_string(),
+                });
+
+            let rpc_error: RpcError = Error::QuorumDriverError(quorum_driver_error).into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_code = expect!["-32002"];
+            expected_code.assert_eq(&error_object.code().to_string());
+            let expected_message = expect!["Transaction is rejected by validation hook min-gas-price-for-packages (gas-price-too-low): Gas price is too low"];
+            expected_message.assert_eq(error_object.message());
+            let expected_data = expect![[
+                r#"{"hook":"min-gas-price-for-packages","code":"gas-price-too-low","message":"Gas price is too low"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
     }
 }
@@ -1,9 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use async_trait::async_trait;
-use futures::{future, Stream};
+use futures::{future, Future, SinkExt, Stream, StreamExt};
 use jsonrpsee::{
     core::{error::SubscriptionClosed, RpcResult},
     types::SubscriptionResult,
@@ -13,17 +13,21 @@ use move_bytecode_utils::layout::TypeLayoutBuilder;
 use move_core_types::language_storage::TypeTag;
 use mysten_metrics::spawn_monitored_task;
 use serde::Serialize;
+use std::collections::HashSet;
+use std::hash::Hash;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
+use sui_core::subscription_handler::EVENT_DISPATCH_BUFFER_SIZE;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_api::{
     cap_page_limit, validate_limit, IndexerApiOpenRpc, IndexerApiServer, JsonRpcMetrics,
     ReadApiServer, QUERY_MAX_RESULT_LIMIT,
 };
 use sui_json_rpc_types::{
-    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiObjectDataOptions,
-    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
+    DynamicFieldPage, EventFilter, EventPage, ObjectsPage, Page, SuiEvent, SuiObjectDataOptions,
+    SuiObjectResponse, SuiObjectResponseQuery, SuiTransactionBlockEffects,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse, SuiTransactionBlockResponseQuery,
+    TransactionBlocksPage, TransactionFilter,
 };
 use sui_open_rpc::Module;
 use sui_storage::key_value_store::TransactionKeyValueStore;
@@ -31,7 +35,7 @@ use sui_types::{
     base_types::{ObjectID, SuiAddress},
     digests::TransactionDigest,
     dynamic_field::{DynamicFieldName, Field},
-    error::SuiObjectResponseError,
+    error::{SuiError, SuiObjectResponseError},
     event::EventID,
 };
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
@@ -70,6 +74,89 @@ pub fn spawn_subscription<S, T>(
         };
     });
 }
+
+/// Streams the items after `cursor`, replaying them from history with `page` before switching to
+/// the live items from `subscribe`.
+///
+/// History is first caught up on without a live subscription, which would otherwise overflow while
+/// paging through a long backlog. Once a page comes back empty, the live subscription is opened,
+/// and history is paged through once more to cover the items committed in between; the duplicates
+/// of these items in the live stream are skipped. Live streams are dropped when their subscriber
+/// falls behind, in which case the stream goes back to replaying history after the last item sent.
+pub fn resume_subscription<T, K, L, LS, P, PF>(
+    cursor: K,
+    subscribe: L,
+    page: P,
+    key: fn(&T) -> K,
+) -> impl Stream<Item = T>
+where
+    T: Send + 'static,
+    K: Clone + Eq + Hash + Send + 'static,
+    L: Fn() -> LS + Send + 'static,
+    LS: Stream<Item = T> + Unpin + Send,
+    P: Fn(K) -> PF + Send + 'static,
+    PF: Future<Output = StateReadResult<Vec<T>>> + Send,
+{
+    let (mut sender, receiver) = futures::channel::mpsc::channel(EVENT_DISPATCH_BUFFER_SIZE);
+    spawn_monitored_task!(async move {
+        let mut cursor = cursor;
+        loop {
+            let mut subscription = None;
+            let mut replayed = HashSet::new();
+            let mut live = loop {
+                let items = match page(cursor.clone()).await {
+                    Ok(items) => items,
+                    Err(e) => {
+                        warn!("Failed to replay subscription history: {e}");
+                        return;
+                    }
+                };
+                if items.is_empty() {
+                    if let Some(live) = subscription.take() {
+                        break live;
+                    }
+                    subscription = Some(subscribe());
+                    continue;
+                }
+                for item in items {
+                    cursor = key(&item);
+                    if subscription.is_some() {
+                        replayed.insert(cursor.clone());
+                    }
+                    if sender.send(item).await.is_err() {
+                        return;
+                    }
+                }
+            };
+
+            while let Some(item) = live.next().await {
+                let item_key = key(&item);
+                if replayed.remove(&item_key) {
+                    continue;
+                }
+                cursor = item_key;
+                if sender.send(item).await.is_err() {
+                    return;
+                }
+            }
+            debug!("Live subscription dropped, resuming from history.");
+        }
+    });
+    receiver
+}
+
+/// Whether the transactions matching `filter` can be replayed from the transaction indexes.
+fn is_replayable_transaction_filter(filter: &TransactionFilter) -> bool {
+    matches!(
+        filter,
+        TransactionFilter::MoveFunction { .. }
+            | TransactionFilter::InputObject(_)
+            | TransactionFilter::ChangedObject(_)
+            | TransactionFilter::FromAddress(_)
+            | TransactionFilter::ToAddress(_)
+    )
+}
+
 const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
 
 pub struct IndexerApi<R> {
@@ -290,15 +377,45 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
     }
 
     #[instrument(skip(self))]
-    fn subscribe_event(&self, sink: SubscriptionSink, filter: EventFilter) -> SubscriptionResult {
+    fn subscribe_event(
+        &self,
+        sink: SubscriptionSink,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SubscriptionResult {
+        if cursor.is_some() && !filter.is_replayable() {
+            return Err(anyhow!(
+                "Subscriptions with this event filter cannot be resumed from a cursor"
+            )
+            .into());
+        }
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
-            sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_events(filter),
-            Some(permit),
+        let handler = self.state.get_subscription_handler();
+        let Some(cursor) = cursor else {
+            spawn_subscription(sink, handler.subscribe_events(filter), Some(permit));
+            return Ok(());
+        };
+
+        let state = self.state.clone();
+        let kv_store = self.transaction_kv_store.clone();
+        let live_filter = filter.clone();
+        let events = resume_subscription(
+            cursor,
+            move || handler.subscribe_events(live_filter.clone()),
+            move |cursor| {
+                let state = state.clone();
+                let kv_store = kv_store.clone();
+                let filter = filter.clone();
+                async move {
+                    let limit = cap_page_limit(None);
+                    state
+                        .query_events(&kv_store, filter, Some(cursor), limit, false)
+                        .await
+                }
+            },
+            |event: &SuiEvent| event.id,
         );
+        spawn_subscription(sink, events, Some(permit));
         Ok(())
     }
 
@@ -306,15 +423,51 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
         &self,
         sink: SubscriptionSink,
         filter: TransactionFilter,
+        cursor: Option<TransactionDigest>,
     ) -> SubscriptionResult {
+        if cursor.is_some() && !is_replayable_transaction_filter(&filter) {
+            return Err(anyhow!(
+                "Subscriptions with this transaction filter cannot be resumed from a cursor"
+            )
+            .into());
+        }
         let permit = self.acquire_subscribe_permit()?;
-        spawn_subscription(
-            sink,
-            self.state
-                .get_subscription_handler()
-                .subscribe_transactions(filter),
-            Some(permit),
+        let handler = self.state.get_subscription_handler();
+        let Some(cursor) = cursor else {
+            spawn_subscription(sink, handler.subscribe_transactions(filter), Some(permit));
+            return Ok(());
+        };
+
+        let state = self.state.clone();
+        let kv_store = self.transaction_kv_store.clone();
+        let live_filter = filter.clone();
+        let transactions = resume_subscription(
+            cursor,
+            move || handler.subscribe_transactions(live_filter.clone()),
+            move |cursor| {
+                let state = state.clone();
+                let kv_store = kv_store.clone();
+                let filter = filter.clone();
+                async move {
+                    let limit = Some(cap_page_limit(None));
+                    let digests = state
+                        .get_transactions(&kv_store, Some(filter), Some(cursor), limit, false)
+                        .await?;
+                    let effects = kv_store.multi_get_fx_by_tx_digest(&digests).await?;
+                    digests
+                        .into_iter()
+                        .zip(effects)
+                        .map(|(digest, effects)| -> StateReadResult<_> {
+                            let effects =
+                                effects.ok_or(SuiError::TransactionNotFound { digest })?;
+                            Ok(SuiTransactionBlockEffects::try_from(effects)?)
+                        })
+                        .collect::<StateReadResult<Vec<_>>>()
+                }
+            },
+            |effects: &SuiTransactionBlockEffects| *effects.transaction_digest(),
         );
+        spawn_subscription(sink, transactions, Some(permit));
         Ok(())
     }
 
@@ -0,0 +1,797 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, fmt::Write, fs, path::PathBuf, sync::Arc};
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::file_format::Visibility;
+use move_binary_format::normalized::{Function, Module, Struct, Type};
+use move_cli::base;
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_package::BuildConfig as MoveBuildConfig;
+use sui_move_build::{BuildConfig, CompiledPackage};
+use sui_package_resolver::{error::Error as ResolverError, Package, PackageStore};
+use sui_rest_api::Client;
+use sui_types::{
+    base_types::{ObjectID, SequenceNumber},
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS,
+};
+
+use crate::build::resolve_lock_file_path;
+
+/// Generate Rust bindings for a Move package: a BCS-compatible Rust struct for every struct in the
+/// package and its dependencies, and a function adding a Move call to a programmable transaction
+/// for every public or entry function in the package.
+#[derive(Parser)]
+#[group(id = "sui-move-gen-rust")]
+pub struct GenRust {
+    /// File to write the bindings to. The bindings are printed to stdout if this is not set.
+    #[clap(long, short)]
+    pub output: Option<PathBuf>,
+    /// Generate bindings for the package published on-chain at this ID, instead of the local
+    /// package. The package and its dependencies are fetched from the active environment.
+    #[clap(long = "package-id")]
+    pub package_id: Option<ObjectID>,
+}
+
+impl GenRust {
+    pub fn execute(
+        &self,
+        path: Option<PathBuf>,
+        build_config: MoveBuildConfig,
+    ) -> anyhow::Result<()> {
+        if self.package_id.is_some() {
+            bail!("Generating bindings for an on-chain package requires a connection to a network");
+        }
+        let rerooted_path = base::reroot_path(path)?;
+        let build_config = resolve_lock_file_path(build_config, Some(rerooted_path.clone()))?;
+        let pkg = BuildConfig {
+            config: build_config,
+            run_bytecode_verifier: true,
+            print_diags_to_stderr: true,
+        }
+        .build(rerooted_path)?;
+
+        self.write(&generate_bindings(&pkg)?)
+    }
+
+    /// Generate bindings for the on-chain package `--package-id`, fetching packages from the REST
+    /// API of the full node at `rest_url`.
+    pub async fn execute_on_chain(&self, rest_url: &str) -> anyhow::Result<()> {
+        let Some(package_id) = self.package_id else {
+            bail!("Missing the ID of the on-chain package to generate bindings for");
+        };
+        let store = RestPackageStore(Client::new(rest_url));
+        self.write(&generate_on_chain_bindings(&store, package_id.into()).await?)
+    }
+
+    fn write(&self, bindings: &str) -> anyhow::Result<()> {
+        match &self.output {
+            Some(output) => fs::write(output, bindings)
+                .with_context(|| format!("Writing bindings to {}", output.display()))?,
+            None => print!("{bindings}"),
+        }
+        Ok(())
+    }
+}
+
+/// Fetches packages through a full node's REST API.
+struct RestPackageStore(Client);
+
+#[async_trait]
+impl PackageStore for RestPackageStore {
+    async fn version(&self, id: AccountAddress) -> sui_package_resolver::Result<SequenceNumber> {
+        Ok(self.get(id).await?.version())
+    }
+
+    async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+        Ok(Arc::new(Package::read(&self.get(id).await?)?))
+    }
+}
+
+impl RestPackageStore {
+    async fn get(
+        &self,
+        id: AccountAddress,
+    ) -> sui_package_resolver::Result<sui_types::object::Object> {
+        self.0
+            .get_object(ObjectID::from(id))
+            .await
+            .map_err(|e| ResolverError::Store {
+                store: "REST API",
+                source: Box::new(e),
+            })
+    }
+}
+
+/// A module to generate bindings for, along with the name of the package it belongs to.
+struct BoundModule {
+    package: String,
+    module: Module,
+    /// Whether to generate bindings for the functions of the module, and not just its structs.
+    functions: bool,
+}
+
+/// Generate the source of a Rust module containing bindings for the root package of `pkg` and the
+/// structs in its dependencies. Each Move package becomes a Rust module named after the package,
+/// containing a Rust module for every Move module.
+pub fn generate_bindings(pkg: &CompiledPackage) -> anyhow::Result<String> {
+    let root_package = pkg.package.compiled_package_info.package_name.to_string();
+    let mut modules = vec![];
+    for unit in &pkg.package.root_compiled_units {
+        modules.push(BoundModule {
+            package: root_package.clone(),
+            module: Module::new(&unit.unit.module),
+            functions: true,
+        });
+    }
+    for (package, unit) in &pkg.package.deps_compiled_units {
+        modules.push(BoundModule {
+            package: package.to_string(),
+            module: Module::new(&unit.unit.module),
+            functions: false,
+        });
+    }
+    render(&root_package, &modules)
+}
+
+/// Generate bindings for the package stored on-chain at `package_id`, and the structs in its
+/// dependencies, loading packages from `store`. Packages have no name on-chain, so the Rust module
+/// of a package is named after its ID, except for the system packages.
+pub async fn generate_on_chain_bindings<S: PackageStore>(
+    store: &S,
+    package_id: AccountAddress,
+) -> anyhow::Result<String> {
+    let root = store.fetch(package_id).await?;
+    let root_package = on_chain_package_name(root.runtime_id());
+    let mut modules = vec![];
+    for module in root.modules().values() {
+        modules.push(BoundModule {
+            package: root_package.clone(),
+            module: Module::new(module.bytecode()),
+            functions: true,
+        });
+    }
+    for (runtime_id, storage_id) in root.linkage() {
+        let dep = store.fetch(*storage_id).await?;
+        for module in dep.modules().values() {
+            modules.push(BoundModule {
+                package: on_chain_package_name(*runtime_id),
+                module: Module::new(module.bytecode()),
+                functions: false,
+            });
+        }
+    }
+    render(&root_package, &modules)
+}
+
+fn on_chain_package_name(runtime_id: AccountAddress) -> String {
+    match runtime_id {
+        MOVE_STDLIB_ADDRESS => "MoveStdlib".to_string(),
+        SUI_FRAMEWORK_ADDRESS => "Sui".to_string(),
+        SUI_SYSTEM_ADDRESS => "SuiSystem".to_string(),
+        id => format!("package_{}", id.short_str_lossless()),
+    }
+}
+
+fn render(root_package: &str, modules: &[BoundModule]) -> anyhow::Result<String> {
+    let generator = Generator::new(modules);
+    let mut out = String::new();
+    writeln!(
+        out,
+        "// Rust bindings for the Move package `{root_package}` and its dependencies, generated by \
+         `sui move gen-rust`.\n\
+         // Do not edit by hand: re-run the command whenever the package changes instead.\n\
+         //\n\
+         // The bindings depend on the `anyhow`, `move-core-types`, `serde` and `sui-types` crates."
+    )?;
+
+    let mut packages: BTreeMap<String, Vec<&BoundModule>> = BTreeMap::new();
+    for module in modules {
+        if module.functions || !module.module.structs.is_empty() {
+            packages
+                .entry(rust_module_name(&module.package))
+                .or_default()
+                .push(module);
+        }
+    }
+
+    for (package, modules) in packages {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "#[allow(non_camel_case_types, non_snake_case, clippy::all, unused)]"
+        )?;
+        writeln!(out, "pub mod {} {{", ident(&package))?;
+        for (i, module) in modules.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            generator.module(&mut out, module)?;
+        }
+        writeln!(out, "}}")?;
+    }
+
+    Ok(out)
+}
+
+struct Generator {
+    /// Rust paths (relative to the root of the bindings) of the Move modules, by module ID.
+    paths: BTreeMap<(AccountAddress, Identifier), String>,
+    /// Whether each type parameter of a struct is omitted from its Rust type, by struct.
+    omitted: BTreeMap<(AccountAddress, Identifier, Identifier), Vec<bool>>,
+}
+
+impl Generator {
+    fn new(modules: &[BoundModule]) -> Self {
+        let mut paths = BTreeMap::new();
+        let mut omitted = BTreeMap::new();
+        for BoundModule {
+            package, module, ..
+        } in modules
+        {
+            paths.insert(
+                (module.address, module.name.clone()),
+                format!(
+                    "{}::{}",
+                    ident(&rust_module_name(package)),
+                    ident(module.name.as_str())
+                ),
+            );
+            for (name, struct_) in &module.structs {
+                omitted.insert(
+                    (module.address, module.name.clone(), name.clone()),
+                    struct_
+                        .type_parameters
+                        .iter()
+                        .map(|p| p.is_phantom)
+                        .collect(),
+                );
+            }
+        }
+
+        // Rust does not allow unused type parameters, so also omit type parameters that are not
+        // declared phantom, but only appear in the fields of a struct in omitted positions (if at
+        // all). Omitting a type parameter can make others unused in turn, so repeat until no more
+        // type parameters are omitted.
+        let mut generator = Self { paths, omitted };
+        loop {
+            let mut changed = false;
+            for BoundModule { module, .. } in modules {
+                for (name, struct_) in &module.structs {
+                    let key = (module.address, module.name.clone(), name.clone());
+                    for idx in 0..struct_.type_parameters.len() {
+                        if !generator.omitted[&key][idx]
+                            && !struct_
+                                .fields
+                                .iter()
+                                .any(|f| generator.uses_type_parameter(&f.type_, idx as u16))
+                        {
+                            generator.omitted.get_mut(&key).unwrap()[idx] = true;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+            if !changed {
+                return generator;
+            }
+        }
+    }
+
+    /// Whether the Rust type for `type_` mentions type parameter `idx`.
+    fn uses_type_parameter(&self, type_: &Type, idx: u16) -> bool {
+        match type_ {
+            Type::TypeParameter(i) => *i == idx,
+            Type::Vector(inner) | Type::Reference(inner) | Type::MutableReference(inner) => {
+                self.uses_type_parameter(inner, idx)
+            }
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                let key = (*address, module.clone(), name.clone());
+                let omitted = self
+                    .omitted
+                    .get(&key)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                type_arguments.iter().enumerate().any(|(i, arg)| {
+                    !omitted.get(i).copied().unwrap_or(false) && self.uses_type_parameter(arg, idx)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    fn module(&self, out: &mut String, bound: &BoundModule) -> anyhow::Result<()> {
+        let module = &bound.module;
+        writeln!(out, "    pub mod {} {{", ident(module.name.as_str()))?;
+        writeln!(
+            out,
+            "        pub const ADDRESS: &str = \"{}\";",
+            module.address.to_hex_literal()
+        )?;
+        writeln!(out, "        pub const MODULE: &str = \"{}\";", module.name)?;
+
+        for (name, struct_) in &module.structs {
+            writeln!(out)?;
+            self.struct_(out, module, name, struct_)?;
+        }
+
+        if bound.functions {
+            for (name, function) in &module.functions {
+                if function.visibility == Visibility::Public || function.is_entry {
+                    writeln!(out)?;
+                    self.function(out, module, name, function)?;
+                }
+            }
+        }
+
+        writeln!(out, "    }}")?;
+        Ok(())
+    }
+
+    fn struct_(
+        &self,
+        out: &mut String,
+        module: &Module,
+        name: &Identifier,
+        struct_: &Struct,
+    ) -> anyhow::Result<()> {
+        // Phantom type parameters do not affect the layout of the struct, so they are omitted from
+        // the Rust type.
+        let omitted = &self.omitted[&(module.address, module.name.clone(), name.clone())];
+        let generics: Vec<_> = omitted
+            .iter()
+            .enumerate()
+            .filter(|(_, omitted)| !**omitted)
+            .map(|(i, _)| type_parameter(i as u16))
+            .collect();
+        let generics = if generics.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", generics.join(", "))
+        };
+
+        writeln!(
+            out,
+            "        /// `{}::{}::{name}`",
+            module.address.to_hex_literal(),
+            module.name
+        )?;
+        writeln!(
+            out,
+            "        #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]"
+        )?;
+        writeln!(
+            out,
+            "        pub struct {}{generics} {{",
+            ident(name.as_str())
+        )?;
+        for field in &struct_.fields {
+            writeln!(
+                out,
+                "            pub {}: {},",
+                ident(field.name.as_str()),
+                self.rust_type(module, &field.type_)?
+            )?;
+        }
+        writeln!(out, "        }}")?;
+
+        let type_args: Vec<_> = (0..struct_.type_parameters.len())
+            .map(|i| type_argument(i as u16))
+            .collect();
+        writeln!(out)?;
+        writeln!(
+            out,
+            "        impl{generics} {}{generics} {{",
+            ident(name.as_str())
+        )?;
+        writeln!(
+            out,
+            "            /// The Move type of this struct, instantiated with the given type arguments."
+        )?;
+        writeln!(
+            out,
+            "            pub fn struct_tag({}) -> ::move_core_types::language_storage::StructTag {{",
+            type_args
+                .iter()
+                .map(|a| format!("{a}: ::move_core_types::language_storage::TypeTag"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(
+            out,
+            "                ::move_core_types::language_storage::StructTag {{\n\
+             \x20                   address: ::move_core_types::account_address::AccountAddress::from_hex_literal(ADDRESS).unwrap(),\n\
+             \x20                   module: ::move_core_types::identifier::Identifier::new(MODULE).unwrap(),\n\
+             \x20                   name: ::move_core_types::identifier::Identifier::new(\"{name}\").unwrap(),\n\
+             \x20                   type_params: vec![{}],\n\
+             \x20               }}",
+            type_args.join(", ")
+        )?;
+        writeln!(out, "            }}")?;
+        writeln!(out, "        }}")?;
+        Ok(())
+    }
+
+    fn function(
+        &self,
+        out: &mut String,
+        module: &Module,
+        name: &Identifier,
+        function: &Function,
+    ) -> anyhow::Result<()> {
+        let mut parameters = function.parameters.as_slice();
+        // The transaction context is supplied by the runtime, rather than by the caller.
+        if let Some((last, rest)) = parameters.split_last() {
+            if is_tx_context(last) {
+                parameters = rest;
+            }
+        }
+
+        writeln!(
+            out,
+            "        /// Add a call to `{}::{}::{name}` to `builder`, returning its result.",
+            module.address.to_hex_literal(),
+            module.name
+        )?;
+        writeln!(out, "        pub fn {}(", ident(name.as_str()))?;
+        writeln!(
+            out,
+            "            builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,"
+        )?;
+        writeln!(
+            out,
+            "            package: ::sui_types::base_types::ObjectID,"
+        )?;
+        for i in 0..function.type_parameters.len() {
+            writeln!(
+                out,
+                "            {}: ::move_core_types::language_storage::TypeTag,",
+                type_argument(i as u16)
+            )?;
+        }
+        let mut arguments = vec![];
+        for (i, param) in parameters.iter().enumerate() {
+            let arg = format!("arg{i}");
+            // Pure values are passed by value and serialized into the transaction, while objects,
+            // references and values of generic types are passed as arguments to the transaction.
+            if is_pure(param) {
+                writeln!(
+                    out,
+                    "            {arg}: {},",
+                    self.rust_type(module, param)?
+                )?;
+                arguments.push(format!("builder.pure({arg})?"));
+            } else {
+                writeln!(
+                    out,
+                    "            {arg}: ::sui_types::transaction::Argument,"
+                )?;
+                arguments.push(arg);
+            }
+        }
+        writeln!(
+            out,
+            "        ) -> ::anyhow::Result<::sui_types::transaction::Argument> {{"
+        )?;
+        writeln!(
+            out,
+            "            let arguments = vec![{}];",
+            arguments.join(", ")
+        )?;
+        writeln!(
+            out,
+            "            Ok(builder.programmable_move_call(\n\
+             \x20               package,\n\
+             \x20               ::move_core_types::identifier::Identifier::new(MODULE)?,\n\
+             \x20               ::move_core_types::identifier::Identifier::new(\"{name}\")?,\n\
+             \x20               vec![{}],\n\
+             \x20               arguments,\n\
+             \x20           ))",
+            (0..function.type_parameters.len())
+                .map(|i| type_argument(i as u16))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(out, "        }}")?;
+        Ok(())
+    }
+
+    /// The Rust type for values of Move type `type_`, referred to from within the bindings for
+    /// `module`.
+    fn rust_type(&self, module: &Module, type_: &Type) -> anyhow::Result<String> {
+        Ok(match type_ {
+            Type::Bool => "bool".to_string(),
+            Type::U8 => "u8".to_string(),
+            Type::U16 => "u16".to_string(),
+            Type::U32 => "u32".to_string(),
+            Type::U64 => "u64".to_string(),
+            Type::U128 => "u128".to_string(),
+            Type::U256 => "::move_core_types::u256::U256".to_string(),
+            Type::Address => "::sui_types::base_types::SuiAddress".to_string(),
+            Type::Vector(inner) => format!("::std::vec::Vec<{}>", self.rust_type(module, inner)?),
+            Type::TypeParameter(idx) => type_parameter(*idx),
+            Type::Struct {
+                address,
+                module: struct_module,
+                name,
+                type_arguments,
+            } => {
+                let key = (*address, struct_module.clone(), name.clone());
+                let Some(omitted) = self.omitted.get(&key) else {
+                    bail!("Could not find the definition of struct {address}::{struct_module}::{name}");
+                };
+                let path = if (*address, struct_module) == (module.address, &module.name) {
+                    ident(name.as_str())
+                } else {
+                    format!(
+                        "super::super::{}::{}",
+                        self.paths[&(*address, struct_module.clone())],
+                        ident(name.as_str())
+                    )
+                };
+                let mut args = vec![];
+                for (arg, omitted) in type_arguments.iter().zip(omitted) {
+                    if !omitted {
+                        args.push(self.rust_type(module, arg)?);
+                    }
+                }
+                if args.is_empty() {
+                    path
+                } else {
+                    format!("{path}<{}>", args.join(", "))
+                }
+            }
+            Type::Signer | Type::Reference(_) | Type::MutableReference(_) => {
+                bail!("Type {type_} cannot be represented in Rust")
+            }
+        })
+    }
+}
+
+/// Whether values of `type_` can be passed to a Move call as a pure (non-object) argument.
+fn is_pure(type_: &Type) -> bool {
+    match type_ {
+        Type::Bool
+        | Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::U128
+        | Type::U256
+        | Type::Address => true,
+        Type::Vector(inner) => is_pure(inner),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => match (address, module.as_str(), name.as_str()) {
+            (a, "string", "String") | (a, "ascii", "String") if *a == MOVE_STDLIB_ADDRESS => true,
+            (a, "option", "Option") if *a == MOVE_STDLIB_ADDRESS => {
+                type_arguments.iter().all(is_pure)
+            }
+            (a, "object", "ID") if *a == SUI_FRAMEWORK_ADDRESS => true,
+            _ => false,
+        },
+        Type::Signer | Type::TypeParameter(_) | Type::Reference(_) | Type::MutableReference(_) => {
+            false
+        }
+    }
+}
+
+fn is_tx_context(type_: &Type) -> bool {
+    let (Type::Reference(inner) | Type::MutableReference(inner)) = type_ else {
+        return false;
+    };
+    matches!(
+        &**inner,
+        Type::Struct { address, module, name, .. }
+            if *address == SUI_FRAMEWORK_ADDRESS
+                && module.as_str() == "tx_context"
+                && name.as_str() == "TxContext"
+    )
+}
+
+fn type_parameter(idx: u16) -> String {
+    format!("T{idx}")
+}
+
+fn type_argument(idx: u16) -> String {
+    format!("t{idx}")
+}
+
+/// The name of the Rust module for a Move package, e.g. `move_stdlib` for `MoveStdlib`.
+fn rust_module_name(package: &str) -> String {
+    let mut name = String::new();
+    let mut prev_lower = false;
+    for c in package.chars() {
+        if c.is_ascii_uppercase() && prev_lower {
+            name.push('_');
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        name.push(if c == '-' {
+            '_'
+        } else {
+            c.to_ascii_lowercase()
+        });
+    }
+    name
+}
+
+/// `name` as a Rust identifier, escaping Rust keywords that are valid Move identifiers.
+fn ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+        "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro",
+        "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+        "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+        "where", "while", "yield", "abstract", "become",
+    ];
+    match name {
+        // These cannot be used as raw identifiers.
+        "self" | "Self" | "super" | "crate" => format!("{name}_"),
+        _ if KEYWORDS.contains(&name) => format!("r#{name}"),
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use move_core_types::language_storage::TypeTag;
+    use sui_types::{
+        base_types::TransactionDigest,
+        object::{Object, OBJECT_START_VERSION},
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{Argument, Command},
+    };
+
+    /// The bindings for `tests/data/gen_rust`, checked in so that they are compiled along with the
+    /// tests.
+    mod bindings {
+        include!("../tests/data/gen_rust/bindings.rs");
+    }
+
+    /// Serves the packages it was created with, all at their initial version.
+    struct InMemoryPackageStore(BTreeMap<AccountAddress, Arc<Package>>);
+
+    #[async_trait]
+    impl PackageStore for InMemoryPackageStore {
+        async fn version(
+            &self,
+            id: AccountAddress,
+        ) -> sui_package_resolver::Result<SequenceNumber> {
+            self.fetch(id).await?;
+            Ok(OBJECT_START_VERSION)
+        }
+
+        async fn fetch(&self, id: AccountAddress) -> sui_package_resolver::Result<Arc<Package>> {
+            self.0
+                .get(&id)
+                .cloned()
+                .ok_or_else(|| ResolverError::PackageNotFound(id))
+        }
+    }
+
+    fn compiled_package() -> CompiledPackage {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "data", "gen_rust"]);
+        BuildConfig::new_for_testing().build(path).unwrap()
+    }
+
+    fn bindings() -> String {
+        generate_bindings(&compiled_package()).unwrap()
+    }
+
+    #[test]
+    fn test_checked_in_bindings() {
+        // Run with `UPDATE_EXPECT=1` to update the checked in bindings.
+        expect_test::expect_file!["../tests/data/gen_rust/bindings.rs"].assert_eq(&bindings());
+    }
+
+    #[test]
+    fn test_compiled_bindings() {
+        use bindings::gen_rust::shapes;
+
+        let point = shapes::Point { x: 3, y: 4 };
+        let bytes = bcs::to_bytes(&point).unwrap();
+        assert_eq!(bytes, bcs::to_bytes(&(3u64, 4u64)).unwrap());
+        assert_eq!(bcs::from_bytes::<shapes::Point>(&bytes).unwrap(), point);
+
+        let tag = shapes::Pair::<u64, bool>::struct_tag(TypeTag::U64, TypeTag::Bool);
+        assert_eq!(tag.address, AccountAddress::ZERO);
+        assert_eq!(tag.module.as_str(), "shapes");
+        assert_eq!(tag.name.as_str(), "Pair");
+        assert_eq!(tag.type_params, vec![TypeTag::U64, TypeTag::Bool]);
+
+        let package = ObjectID::random();
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let p = builder.pure(point).unwrap();
+        shapes::scale(&mut builder, package, p, 2).unwrap();
+        let pt = builder.finish();
+
+        let [Command::MoveCall(call)] = pt.commands.as_slice() else {
+            panic!("Expected a single Move call, got: {:?}", pt.commands);
+        };
+        assert_eq!(call.package, package);
+        assert_eq!(call.module.as_str(), "shapes");
+        assert_eq!(call.function.as_str(), "scale");
+        assert!(call.type_arguments.is_empty());
+        assert_eq!(call.arguments, vec![Argument::Input(0), Argument::Input(1)]);
+    }
+
+    #[tokio::test]
+    async fn test_on_chain_bindings() {
+        let modules: Vec<_> = compiled_package().get_modules().cloned().collect();
+        let object =
+            Object::new_package_for_testing(&modules, TransactionDigest::genesis_marker(), [])
+                .unwrap();
+        let package = Package::read(&object).unwrap();
+        let store =
+            InMemoryPackageStore(BTreeMap::from([(package.storage_id(), Arc::new(package))]));
+
+        // The package has no name on-chain, so its bindings are named after its ID, but are
+        // otherwise the same as the bindings for the local package.
+        let on_chain = generate_on_chain_bindings(&store, AccountAddress::ZERO)
+            .await
+            .unwrap();
+        let local = bindings();
+        let (_, on_chain) = on_chain.split_once("pub mod package_0 {").unwrap();
+        let (_, local) = local.split_once("pub mod gen_rust {").unwrap();
+        assert_eq!(on_chain, local);
+    }
+
+    #[test]
+    fn test_struct_bindings() {
+        let bindings = bindings();
+        for expected in [
+            "pub mod gen_rust {",
+            "    pub mod shapes {",
+            "        pub struct Point {\n            pub x: u64,\n            pub y: u64,\n        }",
+            // Phantom type parameters are dropped, other type parameters are kept
+            "        pub struct Tagged {\n            pub value: u64,\n        }",
+            "        pub struct Pair<T0, T1> {\n            pub first: T0,\n            pub second: T1,\n        }",
+            "            pub r#type: u8,",
+            "            pub points: ::std::vec::Vec<Point>,",
+            "            pub fn struct_tag(t0: ::move_core_types::language_storage::TypeTag) -> ",
+        ] {
+            assert!(bindings.contains(expected), "Missing {expected:?} in:\n{bindings}");
+        }
+    }
+
+    #[test]
+    fn test_function_bindings() {
+        let bindings = bindings();
+        for expected in [
+            // Pure values are taken by value, objects and generic values as arguments
+            "        pub fn scale(\n\
+             \x20           builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,\n\
+             \x20           package: ::sui_types::base_types::ObjectID,\n\
+             \x20           arg0: ::sui_types::transaction::Argument,\n\
+             \x20           arg1: u64,\n\
+             \x20       ) -> ::anyhow::Result<::sui_types::transaction::Argument> {\n\
+             \x20           let arguments = vec![arg0, builder.pure(arg1)?];",
+            "        pub fn pair(\n\
+             \x20           builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,\n\
+             \x20           package: ::sui_types::base_types::ObjectID,\n\
+             \x20           t0: ::move_core_types::language_storage::TypeTag,\n\
+             \x20           t1: ::move_core_types::language_storage::TypeTag,\n",
+            "                vec![t0, t1],",
+            // Entry functions are included even if they are private
+            "        pub fn reset(",
+        ] {
+            assert!(bindings.contains(expected), "Missing {expected:?} in:\n{bindings}");
+        }
+        // Private functions are not
+        assert!(!bindings.contains("pub fn helper("), "{bindings}");
+    }
+}
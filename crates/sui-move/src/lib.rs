@@ -14,9 +14,13 @@ pub mod build;
 pub mod coverage;
 #[cfg(feature = "disassemble")]
 pub mod disassemble;
+#[cfg(feature = "gen_rust")]
+pub mod gen_rust;
 pub mod manage_package;
 pub mod migrate;
 pub mod new;
+#[cfg(feature = "prove")]
+pub mod prove;
 #[cfg(feature = "unit_test")]
 pub mod unit_test;
 
@@ -28,9 +32,13 @@ pub enum Command {
     Coverage(coverage::Coverage),
     #[cfg(feature = "disassemble")]
     Disassemble(disassemble::Disassemble),
+    #[cfg(feature = "gen_rust")]
+    GenRust(gen_rust::GenRust),
     ManagePackage(manage_package::ManagePackage),
     Migrate(migrate::Migrate),
     New(new::New),
+    #[cfg(feature = "prove")]
+    Prove(prove::Prove),
     #[cfg(feature = "unit_test")]
     Test(unit_test::Test),
 }
@@ -57,9 +65,13 @@ pub fn execute_move_command(
         Command::Coverage(c) => c.execute(package_path, build_config),
         #[cfg(feature = "disassemble")]
         Command::Disassemble(c) => c.execute(package_path, build_config),
+        #[cfg(feature = "gen_rust")]
+        Command::GenRust(c) => c.execute(package_path, build_config),
         Command::ManagePackage(c) => c.execute(package_path, build_config),
         Command::Migrate(c) => c.execute(package_path, build_config),
         Command::New(c) => c.execute(package_path),
+        #[cfg(feature = "prove")]
+        Command::Prove(c) => c.execute(package_path, build_config),
 
         #[cfg(feature = "unit_test")]
         Command::Test(c) => {
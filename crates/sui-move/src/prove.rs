@@ -0,0 +1,184 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use move_cli::base;
+use move_package::{BuildConfig, ModelConfig};
+use move_prover::boogie_backend::options::CustomNativeOptions;
+use std::{fs, path::PathBuf, time::Instant};
+
+/// Sui natives that return a mutable reference derived from one of their arguments, and so need
+/// to be treated like a mutable borrow by the prover.
+const BORROW_NATIVES: &[&str] = &["dynamic_field::borrow_child_object_mut"];
+
+/// Sui natives modeled in `sui-natives.bpl`, with the name of the instance list the model
+/// iterates over for them, and whether only object types are valid instances.
+const NATIVE_INSTANCES: &[(&str, &str, bool)] = &[
+    ("0x2::object", "object_instances", true),
+    ("0x2::transfer", "transfer_instances", true),
+    ("0x2::event", "sui_event_instances", false),
+    ("0x2::types", "sui_types_instances", false),
+    ("0x2::dynamic_field", "dynamic_field_instances", true),
+    (
+        "0x2::dynamic_field::hash_type_and_key",
+        "dynamic_field_key_instances",
+        false,
+    ),
+    ("0x2::prover", "prover_instances", false),
+];
+
+/// Run the Move Prover on the package at `path`, verifying the specifications of its modules
+/// with Boogie and Z3. Their paths are taken from the `BOOGIE_EXE` and `Z3_EXE` environment
+/// variables, or from `backend.boogie_exe` and `backend.z3_exe` in a `Prover.toml` file in the
+/// package directory. Other options are read from that file too, and can be overridden by passing
+/// prover options after `--`.
+#[derive(Parser)]
+#[group(id = "sui-move-prove")]
+pub struct Prove {
+    /// Only check modules in files whose name contains this string.
+    #[clap(name = "filter", short = 't', long = "target")]
+    pub target_filter: Option<String>,
+
+    /// Whether to also check test-only code.
+    #[clap(long = "for-test")]
+    pub for_test: bool,
+
+    /// Options passed on to the prover, e.g. `sui move prove -- --verbose debug`.
+    #[clap(last = true)]
+    pub options: Vec<String>,
+}
+
+impl Prove {
+    pub fn execute(
+        self,
+        path: Option<PathBuf>,
+        mut build_config: BuildConfig,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = base::reroot_path(path)?;
+        let Self {
+            target_filter,
+            for_test,
+            options,
+        } = self;
+
+        // The first argument is the program name.
+        let mut args = vec!["package".to_string()];
+        let prover_toml = rerooted_path.join("Prover.toml");
+        if prover_toml.exists() {
+            args.push(format!("--config={}", prover_toml.to_string_lossy()));
+        }
+        args.extend(options);
+
+        let mut options = move_prover::cli::Options::create_from_args(&args)?;
+        options
+            .prover
+            .borrow_natives
+            .extend(BORROW_NATIVES.iter().map(|n| n.to_string()));
+        options.backend.custom_natives = Some(CustomNativeOptions {
+            template_bytes: include_bytes!("sui-natives.bpl").to_vec(),
+            native_instances: NATIVE_INSTANCES
+                .iter()
+                .map(|(name, list, objects_only)| {
+                    (name.to_string(), list.to_string(), *objects_only)
+                })
+                .collect(),
+        });
+        if options.output_path == move_prover::cli::Options::default().output_path {
+            // Keep the generated Boogie file out of the package sources.
+            let build_dir = build_config
+                .install_dir
+                .clone()
+                .unwrap_or_else(|| rerooted_path.clone())
+                .join("build");
+            fs::create_dir_all(&build_dir)?;
+            options.output_path = build_dir.join("output.bpl").to_string_lossy().to_string();
+        }
+        options.setup_logging();
+
+        build_config.test_mode = for_test;
+        let now = Instant::now();
+        let model = build_config.move_model_for_package(
+            &rerooted_path,
+            ModelConfig {
+                all_files_as_targets: false,
+                target_filter,
+            },
+        )?;
+
+        let mut error_writer = StandardStream::stderr(ColorChoice::Auto);
+        move_prover::run_move_prover_with_model(&model, &mut error_writer, options, Some(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_compiler::editions::Flavor;
+
+    fn prove(package: &str, options: Vec<String>) -> anyhow::Result<()> {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.extend(["tests", "data", package]);
+        let install_dir = tempfile::tempdir().unwrap();
+
+        let prove = Prove {
+            target_filter: None,
+            for_test: false,
+            options,
+        };
+        let build_config = BuildConfig {
+            dev_mode: true,
+            install_dir: Some(install_dir.path().to_path_buf()),
+            default_flavor: Some(Flavor::Sui),
+            ..Default::default()
+        };
+        prove.execute(Some(path), build_config)
+    }
+
+    /// Whether Boogie and Z3 are available to verify specifications.
+    fn has_solver() -> bool {
+        std::env::var("BOOGIE_EXE").is_ok_and(|e| !e.is_empty())
+            && std::env::var("Z3_EXE").is_ok_and(|e| !e.is_empty())
+    }
+
+    #[test]
+    fn test_failing_spec_is_not_verified() {
+        let err = prove("prove", vec![]).unwrap_err();
+        if has_solver() {
+            assert!(err.to_string().contains("verification errors"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_generate_boogie_for_sui_package() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let output = output_dir.path().join("output.bpl");
+        let options = vec![
+            "--generate-only".to_string(),
+            format!("--output={}", output.to_string_lossy()),
+        ];
+        prove("prove_sui", options).unwrap();
+
+        let boogie = fs::read_to_string(output).unwrap();
+        for verify in [
+            "$0_counter_new$verify",
+            "$0_counter_create$verify",
+            "$0_counter_increment$verify",
+            "$0_counter_value$verify",
+        ] {
+            assert!(boogie.contains(verify), "missing `{verify}`");
+        }
+        assert!(
+            boogie.contains("procedure {:inline 1} $2_transfer_transfer_impl'$0_counter_Counter'")
+        );
+    }
+
+    #[test]
+    fn test_verify_sui_package() {
+        if !has_solver() {
+            eprintln!("skipping: set `BOOGIE_EXE` and `Z3_EXE` to run the prover");
+            return;
+        }
+        prove("prove_sui", vec![]).unwrap();
+    }
+}
@@ -0,0 +1,139 @@
+// Rust bindings for the Move package `GenRust` and its dependencies, generated by `sui move gen-rust`.
+// Do not edit by hand: re-run the command whenever the package changes instead.
+//
+// The bindings depend on the `anyhow`, `move-core-types`, `serde` and `sui-types` crates.
+
+#[allow(non_camel_case_types, non_snake_case, clippy::all, unused)]
+pub mod gen_rust {
+    pub mod shapes {
+        pub const ADDRESS: &str = "0x0";
+        pub const MODULE: &str = "shapes";
+
+        /// `0x0::shapes::Pair`
+        #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Pair<T0, T1> {
+            pub first: T0,
+            pub second: T1,
+        }
+
+        impl<T0, T1> Pair<T0, T1> {
+            /// The Move type of this struct, instantiated with the given type arguments.
+            pub fn struct_tag(t0: ::move_core_types::language_storage::TypeTag, t1: ::move_core_types::language_storage::TypeTag) -> ::move_core_types::language_storage::StructTag {
+                ::move_core_types::language_storage::StructTag {
+                    address: ::move_core_types::account_address::AccountAddress::from_hex_literal(ADDRESS).unwrap(),
+                    module: ::move_core_types::identifier::Identifier::new(MODULE).unwrap(),
+                    name: ::move_core_types::identifier::Identifier::new("Pair").unwrap(),
+                    type_params: vec![t0, t1],
+                }
+            }
+        }
+
+        /// `0x0::shapes::Point`
+        #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Point {
+            pub x: u64,
+            pub y: u64,
+        }
+
+        impl Point {
+            /// The Move type of this struct, instantiated with the given type arguments.
+            pub fn struct_tag() -> ::move_core_types::language_storage::StructTag {
+                ::move_core_types::language_storage::StructTag {
+                    address: ::move_core_types::account_address::AccountAddress::from_hex_literal(ADDRESS).unwrap(),
+                    module: ::move_core_types::identifier::Identifier::new(MODULE).unwrap(),
+                    name: ::move_core_types::identifier::Identifier::new("Point").unwrap(),
+                    type_params: vec![],
+                }
+            }
+        }
+
+        /// `0x0::shapes::Polygon`
+        #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Polygon {
+            pub r#type: u8,
+            pub points: ::std::vec::Vec<Point>,
+        }
+
+        impl Polygon {
+            /// The Move type of this struct, instantiated with the given type arguments.
+            pub fn struct_tag() -> ::move_core_types::language_storage::StructTag {
+                ::move_core_types::language_storage::StructTag {
+                    address: ::move_core_types::account_address::AccountAddress::from_hex_literal(ADDRESS).unwrap(),
+                    module: ::move_core_types::identifier::Identifier::new(MODULE).unwrap(),
+                    name: ::move_core_types::identifier::Identifier::new("Polygon").unwrap(),
+                    type_params: vec![],
+                }
+            }
+        }
+
+        /// `0x0::shapes::Tagged`
+        #[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct Tagged {
+            pub value: u64,
+        }
+
+        impl Tagged {
+            /// The Move type of this struct, instantiated with the given type arguments.
+            pub fn struct_tag(t0: ::move_core_types::language_storage::TypeTag) -> ::move_core_types::language_storage::StructTag {
+                ::move_core_types::language_storage::StructTag {
+                    address: ::move_core_types::account_address::AccountAddress::from_hex_literal(ADDRESS).unwrap(),
+                    module: ::move_core_types::identifier::Identifier::new(MODULE).unwrap(),
+                    name: ::move_core_types::identifier::Identifier::new("Tagged").unwrap(),
+                    type_params: vec![t0],
+                }
+            }
+        }
+
+        /// Add a call to `0x0::shapes::pair` to `builder`, returning its result.
+        pub fn pair(
+            builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+            package: ::sui_types::base_types::ObjectID,
+            t0: ::move_core_types::language_storage::TypeTag,
+            t1: ::move_core_types::language_storage::TypeTag,
+            arg0: ::sui_types::transaction::Argument,
+            arg1: ::sui_types::transaction::Argument,
+        ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+            let arguments = vec![arg0, arg1];
+            Ok(builder.programmable_move_call(
+                package,
+                ::move_core_types::identifier::Identifier::new(MODULE)?,
+                ::move_core_types::identifier::Identifier::new("pair")?,
+                vec![t0, t1],
+                arguments,
+            ))
+        }
+
+        /// Add a call to `0x0::shapes::reset` to `builder`, returning its result.
+        pub fn reset(
+            builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+            package: ::sui_types::base_types::ObjectID,
+            arg0: ::sui_types::transaction::Argument,
+        ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+            let arguments = vec![arg0];
+            Ok(builder.programmable_move_call(
+                package,
+                ::move_core_types::identifier::Identifier::new(MODULE)?,
+                ::move_core_types::identifier::Identifier::new("reset")?,
+                vec![],
+                arguments,
+            ))
+        }
+
+        /// Add a call to `0x0::shapes::scale` to `builder`, returning its result.
+        pub fn scale(
+            builder: &mut ::sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+            package: ::sui_types::base_types::ObjectID,
+            arg0: ::sui_types::transaction::Argument,
+            arg1: u64,
+        ) -> ::anyhow::Result<::sui_types::transaction::Argument> {
+            let arguments = vec![arg0, builder.pure(arg1)?];
+            Ok(builder.programmable_move_call(
+                package,
+                ::move_core_types::identifier::Identifier::new(MODULE)?,
+                ::move_core_types::identifier::Identifier::new("scale")?,
+                vec![],
+                arguments,
+            ))
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module gen_rust::shapes {
+    public struct Point has copy, drop, store {
+        x: u64,
+        y: u64,
+    }
+
+    public struct Tagged<phantom T> has copy, drop {
+        value: u64,
+    }
+
+    public struct Pair<T0, T1> has copy, drop {
+        first: T0,
+        second: T1,
+    }
+
+    public struct Polygon has drop {
+        type: u8,
+        points: vector<Point>,
+    }
+
+    public fun scale(p: &mut Point, factor: u64) {
+        p.x = helper(p.x, factor);
+        p.y = helper(p.y, factor);
+    }
+
+    public fun pair<T0, T1>(first: T0, second: T1): Pair<T0, T1> {
+        Pair { first, second }
+    }
+
+    entry fun reset(p: &mut Point) {
+        p.x = 0;
+        p.y = 0;
+    }
+
+    fun helper(x: u64, y: u64): u64 {
+        x * y
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module prove::counter {
+    public fun increment(x: u64): u64 {
+        x + 1
+    }
+
+    spec increment {
+        // Does not hold: the prover must not report this as verified.
+        ensures result == x;
+    }
+}
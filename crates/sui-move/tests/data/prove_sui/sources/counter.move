@@ -0,0 +1,43 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module prove_sui::counter {
+    public struct Counter has key {
+        id: UID,
+        value: u64,
+    }
+
+    public fun new(ctx: &mut TxContext): Counter {
+        Counter { id: object::new(ctx), value: 0 }
+    }
+
+    spec new {
+        ensures result.value == 0;
+    }
+
+    public fun create(ctx: &mut TxContext) {
+        transfer::transfer(new(ctx), ctx.sender())
+    }
+
+    spec create {
+        // Creating an object increments the number of IDs created by the transaction.
+        aborts_if ctx.ids_created == MAX_U64;
+    }
+
+    public fun increment(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+
+    spec increment {
+        aborts_if counter.value == MAX_U64;
+        ensures counter.value == old(counter.value) + 1;
+    }
+
+    public fun value(counter: &Counter): u64 {
+        counter.value
+    }
+
+    spec value {
+        ensures result == counter.value;
+    }
+}
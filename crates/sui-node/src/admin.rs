@@ -9,10 +9,13 @@ use axum::{
     Router,
 };
 use humantime::parse_duration;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
+use sui_config::certificate_deny_config::CertificateDenyConfig;
+use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_types::error::SuiError;
+use sui_types::traffic_control::PolicyConfig;
 use telemetry_subscribers::TracingHandle;
 use tracing::info;
 
@@ -47,6 +50,26 @@ use tracing::info;
 // Reset tracing to the TRACE_FILTER env var.
 //
 //   $ curl -X POST 'http://127.0.0.1:1337/reset-tracing'
+//
+// View the transaction deny config, in the YAML format of the node config:
+//
+//   $ curl 'http://127.0.0.1:1337/transaction-deny-config'
+//
+// Replace the transaction deny config until the node restarts (update the node config as well
+// to keep it):
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/transaction-deny-config' --data-binary @deny.yaml
+//
+// Replace the certificate deny config from the next epoch on. Certificate denials change
+// execution results, so the config only takes effect at an epoch boundary, every validator has to
+// stage the same config before it, and certificates cannot be removed from the deny list:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/certificate-deny-config' --data-binary @deny.yaml
+//
+// View or replace the traffic control policy of the validator gRPC service:
+//
+//   $ curl 'http://127.0.0.1:1337/traffic-control-policy'
+//   $ curl -X POST 'http://127.0.0.1:1337/traffic-control-policy' --data-binary @policy.yaml
 
 const LOGGING_ROUTE: &str = "/logging";
 const TRACING_ROUTE: &str = "/enable-tracing";
@@ -56,6 +79,9 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const TRANSACTION_DENY_CONFIG: &str = "/transaction-deny-config";
+const CERTIFICATE_DENY_CONFIG: &str = "/certificate-deny-config";
+const TRAFFIC_CONTROL_POLICY: &str = "/traffic-control-policy";
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -86,6 +112,18 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(FORCE_CLOSE_EPOCH, post(force_close_epoch))
         .route(TRACING_ROUTE, post(enable_tracing))
         .route(TRACING_RESET_ROUTE, post(reset_tracing))
+        .route(
+            TRANSACTION_DENY_CONFIG,
+            get(get_transaction_deny_config).post(reload_transaction_deny_config),
+        )
+        .route(
+            CERTIFICATE_DENY_CONFIG,
+            get(get_certificate_deny_config).post(reload_certificate_deny_config),
+        )
+        .route(
+            TRAFFIC_CONTROL_POLICY,
+            get(get_traffic_control_policy).post(update_traffic_control_policy),
+        )
         .with_state(Arc::new(app_state));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
@@ -288,3 +326,95 @@ async fn force_close_epoch(
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
     }
 }
+
+fn to_yaml<T: Serialize>(config: &T) -> (StatusCode, String) {
+    match serde_yaml::to_string(config) {
+        Ok(yaml) => (StatusCode::OK, yaml),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+fn from_yaml<T: DeserializeOwned>(body: &str) -> Result<T, (StatusCode, String)> {
+    serde_yaml::from_str(body).map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("can't parse config: {err}\n"),
+        )
+    })
+}
+
+async fn get_transaction_deny_config(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    to_yaml(state.node.state().transaction_deny_config().as_ref())
+}
+
+async fn reload_transaction_deny_config(
+    State(state): State<Arc<AppState>>,
+    body: String,
+) -> (StatusCode, String) {
+    let config: TransactionDenyConfig = match from_yaml(&body) {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+
+    match state.node.state().reload_transaction_deny_config(config) {
+        Ok(()) => (
+            StatusCode::OK,
+            "transaction deny config reloaded\n".to_string(),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn get_certificate_deny_config(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    to_yaml(state.node.state().certificate_deny_config().as_ref())
+}
+
+async fn reload_certificate_deny_config(
+    State(state): State<Arc<AppState>>,
+    body: String,
+) -> (StatusCode, String) {
+    let config: CertificateDenyConfig = match from_yaml(&body) {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+
+    match state.node.state().reload_certificate_deny_config(config) {
+        Ok(()) => (
+            StatusCode::OK,
+            "certificate deny config will be applied at the next epoch\n".to_string(),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+async fn get_traffic_control_policy(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    match state.node.traffic_control_policy().await {
+        Some(policy_config) => to_yaml(&policy_config),
+        None => (
+            StatusCode::NOT_FOUND,
+            "traffic control is not enabled on this node\n".to_string(),
+        ),
+    }
+}
+
+async fn update_traffic_control_policy(
+    State(state): State<Arc<AppState>>,
+    body: String,
+) -> (StatusCode, String) {
+    let policy_config: PolicyConfig = match from_yaml(&body) {
+        Ok(policy_config) => policy_config,
+        Err(response) => return response,
+    };
+
+    match state
+        .node
+        .update_traffic_control_policy(policy_config)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::OK,
+            "traffic control policy updated\n".to_string(),
+        ),
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
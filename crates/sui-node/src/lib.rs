@@ -30,6 +30,7 @@ use sui_core::epoch::randomness::RandomnessManager;
 use sui_core::execution_cache::ExecutionCacheMetrics;
 use sui_core::execution_cache::NotifyReadWrapper;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
+use sui_core::traffic_controller::TrafficController;
 use sui_json_rpc::ServerType;
 use sui_json_rpc_api::JsonRpcMetrics;
 use sui_network::randomness;
@@ -38,6 +39,7 @@ use sui_types::crypto::RandomnessRound;
 use sui_types::digests::ChainIdentifier;
 use sui_types::message_envelope::get_google_jwk_bytes;
 use sui_types::sui_system_state::SuiSystemState;
+use sui_types::traffic_control::PolicyConfig;
 use tap::tap::TapFallible;
 use tokio::runtime::Handle;
 use tokio::sync::broadcast;
@@ -117,6 +119,7 @@ use sui_storage::{
     key_value_store_metrics::KeyValueStoreMetrics,
 };
 use sui_storage::{FileCompression, IndexStore, StorageFormat};
+use sui_transaction_checks::validation_hooks::TransactionValidationHooks;
 use sui_types::base_types::{AuthorityName, EpochId};
 use sui_types::committee::Committee;
 use sui_types::crypto::KeypairTraits;
@@ -139,6 +142,7 @@ pub mod metrics;
 
 pub struct ValidatorComponents {
     validator_server_handle: JoinHandle<Result<()>>,
+    traffic_controller: Option<Arc<TrafficController>>,
     validator_overload_monitor_handle: Option<JoinHandle<()>>,
     consensus_manager: ConsensusManager,
     consensus_epoch_data_remover: EpochDataRemover,
@@ -618,6 +622,7 @@ impl SuiNode {
             config.expensive_safety_check_config.clone(),
             config.transaction_deny_config.clone(),
             config.certificate_deny_config.clone(),
+            TransactionValidationHooks::from_config(&config.transaction_validation_hooks)?,
             config.indirect_objects_threshold,
             config.state_debug_dump_config.clone(),
             config.authority_overload_config.clone(),
@@ -1111,7 +1116,7 @@ impl SuiNode {
         let sui_tx_validator_metrics =
             SuiTxValidatorMetrics::new(&registry_service.default_registry());
 
-        let validator_server_handle = Self::start_grpc_validator_service(
+        let (validator_server_handle, traffic_controller) = Self::start_grpc_validator_service(
             &config,
             state.clone(),
             consensus_adapter.clone(),
@@ -1149,6 +1154,7 @@ impl SuiNode {
             consensus_epoch_data_remover,
             accumulator,
             validator_server_handle,
+            traffic_controller,
             validator_overload_monitor_handle,
             checkpoint_metrics,
             sui_node_metrics,
@@ -1169,6 +1175,7 @@ impl SuiNode {
         consensus_epoch_data_remover: EpochDataRemover,
         accumulator: Arc<StateAccumulator>,
         validator_server_handle: JoinHandle<Result<()>>,
+        traffic_controller: Option<Arc<TrafficController>>,
         validator_overload_monitor_handle: Option<JoinHandle<()>>,
         checkpoint_metrics: Arc<CheckpointMetrics>,
         sui_node_metrics: Arc<SuiNodeMetrics>,
@@ -1226,6 +1233,7 @@ impl SuiNode {
             epoch_store.clone(),
             low_scoring_authorities,
             throughput_calculator,
+            config.commit_scheduler_config.clone(),
         );
 
         consensus_manager
@@ -1254,6 +1262,7 @@ impl SuiNode {
 
         Ok(ValidatorComponents {
             validator_server_handle,
+            traffic_controller,
             validator_overload_monitor_handle,
             consensus_manager,
             consensus_epoch_data_remover,
@@ -1343,7 +1352,10 @@ impl SuiNode {
         state: Arc<AuthorityState>,
         consensus_adapter: Arc<ConsensusAdapter>,
         prometheus_registry: &Registry,
-    ) -> Result<tokio::task::JoinHandle<Result<()>>> {
+    ) -> Result<(
+        tokio::task::JoinHandle<Result<()>>,
+        Option<Arc<TrafficController>>,
+    )> {
         let validator_service = ValidatorService::new(
             state.clone(),
             consensus_adapter,
@@ -1359,6 +1371,7 @@ impl SuiNode {
         let mut server_builder =
             ServerBuilder::from_config(&server_conf, GrpcMetrics::new(prometheus_registry));
 
+        let traffic_controller = validator_service.traffic_controller();
         server_builder = server_builder.add_service(ValidatorServer::new(validator_service));
 
         let server = server_builder
@@ -1369,13 +1382,35 @@ impl SuiNode {
         info!("Listening to traffic on {local_addr}");
         let grpc_server = spawn_monitored_task!(server.serve().map_err(Into::into));
 
-        Ok(grpc_server)
+        Ok((grpc_server, traffic_controller))
     }
 
     pub fn state(&self) -> Arc<AuthorityState> {
         self.state.clone()
     }
 
+    /// Returns the policy of the validator traffic controller, if traffic control is enabled.
+    pub async fn traffic_control_policy(&self) -> Option<PolicyConfig> {
+        self.validator_components
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|components| components.traffic_controller.as_ref())
+            .map(|traffic_controller| traffic_controller.policy_config())
+    }
+
+    /// Replaces the policy of the validator traffic controller. The update is kept across epochs,
+    /// but not across restarts, which read the policy from `NodeConfig` again. Traffic control
+    /// of the JSON-RPC server of fullnodes is not covered.
+    pub async fn update_traffic_control_policy(&self, policy_config: PolicyConfig) -> Result<()> {
+        let components = self.validator_components.lock().await;
+        let traffic_controller = components
+            .as_ref()
+            .and_then(|components| components.traffic_controller.as_ref())
+            .ok_or_else(|| anyhow!("Traffic control is not enabled on this node"))?;
+        traffic_controller.update_policy_config(policy_config)
+    }
+
     // Only used for testing because of how epoch store is loaded.
     pub fn reference_gas_price_for_testing(&self) -> Result<u64, anyhow::Error> {
         self.state.reference_gas_price_for_testing()
@@ -1540,6 +1575,7 @@ impl SuiNode {
             // in the new epoch.
             let new_validator_components = if let Some(ValidatorComponents {
                 validator_server_handle,
+                traffic_controller,
                 validator_overload_monitor_handle,
                 consensus_manager,
                 consensus_epoch_data_remover,
@@ -1584,6 +1620,7 @@ impl SuiNode {
                             consensus_epoch_data_remover,
                             self.accumulator.clone(),
                             validator_server_handle,
+                            traffic_controller,
                             validator_overload_monitor_handle,
                             checkpoint_metrics,
                             self.metrics.clone(),
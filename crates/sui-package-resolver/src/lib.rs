@@ -554,6 +554,21 @@ impl Package {
         })
     }
 
+    /// The ID this package was loaded from on-chain.
+    pub fn storage_id(&self) -> AccountAddress {
+        self.storage_id
+    }
+
+    /// The ID that other packages refer to this package by at runtime.
+    pub fn runtime_id(&self) -> AccountAddress {
+        self.runtime_id
+    }
+
+    /// The package's transitive dependencies, mapping their runtime IDs to their storage IDs.
+    pub fn linkage(&self) -> &BTreeMap<AccountAddress, AccountAddress> {
+        &self.linkage
+    }
+
     pub fn module(&self, module: &str) -> Result<&Module> {
         self.modules
             .get(module)
@@ -13,7 +13,7 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 44;
+const MAX_PROTOCOL_VERSION: u64 = 45;
 
 // Record history of protocol version allocations here:
 //
@@ -120,6 +120,7 @@ const MAX_PROTOCOL_VERSION: u64 = 44;
 //             Introduce an explicit parameter for the tick limit per package (previously this was
 //             represented by the parameter for the tick limit per module).
 // Version 44: Enable consensus fork detection on mainnet.
+// Version 45: Enable leader scoring and schedule updates in Mysticeti, in devnet only.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -411,6 +412,10 @@ struct FeatureFlags {
     // Set the upper bound allowed for max_epoch in zklogin signature.
     #[serde(skip_serializing_if = "Option::is_none")]
     zklogin_max_epoch_upper_bound_delta: Option<u64>,
+
+    // Swap low scoring leaders in the Mysticeti leader schedule, based on reputation scores.
+    #[serde(skip_serializing_if = "is_false")]
+    mysticeti_leader_scoring_and_schedule: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -429,6 +434,15 @@ pub enum ConsensusTransactionOrdering {
     None,
     /// Order transactions by gas price, highest first.
     ByGasPrice,
+    /// Interleave transactions of different senders round-robin, in the order in which the
+    /// senders first appear. Transactions of the same sender keep their consensus order.
+    BySenderRoundRobin,
+    /// Group transactions on the same shared object together, so that transactions that
+    /// conflict are scheduled next to each other. Groups keep the order of their first
+    /// transaction.
+    BySharedObject,
+    /// Shuffle transactions, with a seed derived from the commit digest and the transactions.
+    ByRandomShuffle,
 }
 
 impl ConsensusTransactionOrdering {
@@ -442,7 +456,8 @@ impl ConsensusTransactionOrdering {
 pub enum PerObjectCongestionControlMode {
     #[default]
     None, // No congestion control.
-    TotalGasBudget, // Use txn gas budget as execution cost.
+    TotalGasBudget,    // Use txn gas budget as execution cost.
+    TotalCommandCount, // Use the number of commands in the txn as execution cost.
 }
 
 impl PerObjectCongestionControlMode {
@@ -1252,6 +1267,10 @@ impl ProtocolConfig {
     pub fn consensus_choice(&self) -> ConsensusChoice {
         self.feature_flags.consensus_choice
     }
+
+    pub fn mysticeti_leader_scoring_and_schedule(&self) -> bool {
+        self.feature_flags.mysticeti_leader_scoring_and_schedule
+    }
 }
 
 #[cfg(not(msim))]
@@ -2099,6 +2118,11 @@ impl ProtocolConfig {
                         cfg.feature_flags.consensus_choice = ConsensusChoice::SwapEachEpoch;
                     }
                 }
+                45 => {
+                    if chain != Chain::Testnet && chain != Chain::Mainnet {
+                        cfg.feature_flags.mysticeti_leader_scoring_and_schedule = true;
+                    }
+                }
                 // Use this template when making changes:
                 //
                 //     // modify an existing constant.
@@ -2236,6 +2260,13 @@ impl ProtocolConfig {
         self.consensus_max_transactions_in_block_bytes = Some(val);
     }
 
+    pub fn set_consensus_transaction_ordering_for_testing(
+        &mut self,
+        val: ConsensusTransactionOrdering,
+    ) {
+        self.feature_flags.consensus_transaction_ordering = val;
+    }
+
     pub fn set_per_object_congestion_control_mode(&mut self, val: PerObjectCongestionControlMode) {
         self.feature_flags.per_object_congestion_control_mode = val;
     }
@@ -2251,6 +2282,10 @@ impl ProtocolConfig {
     pub fn set_zklogin_max_epoch_upper_bound_delta(&mut self, val: Option<u64>) {
         self.feature_flags.zklogin_max_epoch_upper_bound_delta = val
     }
+
+    pub fn set_mysticeti_leader_scoring_and_schedule_for_testing(&mut self, val: bool) {
+        self.feature_flags.mysticeti_leader_scoring_and_schedule = val
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
@@ -6,10 +6,11 @@ use futures::stream;
 use futures::StreamExt;
 use futures_core::Stream;
 use jsonrpsee::core::client::Subscription;
+use jsonrpsee::ws_client::WsClient;
 use std::collections::BTreeMap;
 use std::future;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use sui_json_rpc_types::DevInspectArgs;
 use sui_json_rpc_types::SuiData;
 
@@ -605,7 +606,7 @@ impl ReadApi {
             ));
         };
         let subscription: Subscription<SuiTransactionBlockEffects> =
-            c.subscribe_transaction(filter).await?;
+            c.subscribe_transaction(filter, None).await?;
         Ok(subscription.map(|item| Ok(item?)))
     }
 
@@ -997,6 +998,84 @@ impl CoinReadApi {
     }
 }
 
+const MIN_RESUBSCRIBE_DELAY: Duration = Duration::from_millis(100);
+const MAX_RESUBSCRIBE_DELAY: Duration = Duration::from_secs(30);
+
+/// An event subscription that resumes after the last event it returned when its connection drops,
+/// if its filter is replayable.
+struct EventSubscription {
+    api: Arc<RpcClient>,
+    filter: EventFilter,
+    /// The last event returned, or the latest event when the subscription started, if known. The
+    /// subscription can only be resumed once this is known.
+    cursor: Option<EventID>,
+    /// `None` once the subscription can no longer be resumed.
+    subscription: Option<Subscription<SuiEvent>>,
+    /// The connection the subscription was resumed on. The client's own connection is not
+    /// re-established, so resumed subscriptions each open their own.
+    ws: Option<WsClient>,
+    delay: Duration,
+}
+
+impl EventSubscription {
+    async fn next(&mut self) -> Option<SuiRpcResult<SuiEvent>> {
+        loop {
+            match self.subscription.as_mut()?.next().await {
+                Some(Ok(event)) => {
+                    self.cursor = Some(event.id);
+                    self.delay = MIN_RESUBSCRIBE_DELAY;
+                    return Some(Ok(event));
+                }
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => {}
+            }
+
+            // Events that were missed while the subscription was down can't be replayed, so the
+            // subscription ends instead of resuming with a gap.
+            if !self.filter.is_replayable() || self.cursor.is_none() {
+                self.subscription = None;
+                return None;
+            }
+
+            if let Err(e) = self.resubscribe().await {
+                self.subscription = None;
+                return Some(Err(e));
+            }
+        }
+    }
+
+    /// Reconnect and resume the subscription after the last event returned, retrying with
+    /// backoff until the server can be reached.
+    async fn resubscribe(&mut self) -> SuiRpcResult<()> {
+        let Some(config) = &self.api.ws_config else {
+            return Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            ));
+        };
+
+        loop {
+            // The delay is only reset once an event is received, so that subscriptions that keep
+            // being dropped by the server are not resumed in a tight loop.
+            tokio::time::sleep(self.delay).await;
+            self.delay = (self.delay * 2).min(MAX_RESUBSCRIBE_DELAY);
+
+            let Ok(ws) = config.connect().await else {
+                continue;
+            };
+            match ws.subscribe_event(self.filter.clone(), self.cursor).await {
+                Ok(subscription) => {
+                    self.subscription = Some(subscription);
+                    self.ws = Some(ws);
+                    return Ok(());
+                }
+                // The server rejected the subscription, which retrying will not fix.
+                Err(e @ jsonrpsee::core::Error::Call(_)) => return Err(e.into()),
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
 /// Event API provides the functionality to fetch, query, or subscribe to events on the Sui network.
 #[derive(Clone)]
 pub struct EventApi {
@@ -1010,9 +1089,13 @@ impl EventApi {
 
     /// Return a stream of events, or an error upon failure.
     ///
-    /// Subscription is only possible via WebSockets.
+    /// Subscription is only possible via WebSockets. If the connection drops, the stream
+    /// reconnects and resumes after the last event it returned, see [Self::subscribe_event_from].
     /// For a list of possible event filters, see [EventFilter].
     ///
+    /// Streams of events matching filters that are not replayable (see
+    /// [EventFilter::is_replayable]) end when the connection drops, rather than resuming.
+    ///
     /// # Examples
     ///
     /// ```rust, no_run
@@ -1041,15 +1124,71 @@ impl EventApi {
         &self,
         filter: EventFilter,
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
-        match &self.api.ws {
-            Some(c) => {
-                let subscription: Subscription<SuiEvent> = c.subscribe_event(filter).await?;
-                Ok(subscription.map(|item| Ok(item?)))
-            }
-            _ => Err(Error::Subscription(
+        self.subscribe_event_from(filter, None).await
+    }
+
+    /// Return a stream of the events after `cursor`, or of new events if `cursor` is `None`, or an
+    /// error upon failure. The events between `cursor` and the latest event are replayed before new
+    /// events are streamed, which requires the filter to be supported by [Self::query_events].
+    ///
+    /// Whenever the connection drops, the stream reconnects with backoff and resumes after the
+    /// last event it returned, so no events are missed. If `cursor` is `None`, the latest event
+    /// is fetched before subscribing, so that the stream can resume even before it has returned
+    /// any events. The stream only ends if the server rejects the resumed subscription, or if the
+    /// filter is not replayable, in which case it ends when the connection drops.
+    ///
+    /// Fullnodes without event indexes can't serve the latest event, and older fullnodes reject
+    /// subscriptions from a cursor. In either case, a stream with no `cursor` falls back to a
+    /// subscription to new events, which also ends if the connection drops before it has returned
+    /// any events.
+    pub async fn subscribe_event_from(
+        &self,
+        filter: EventFilter,
+        cursor: Option<EventID>,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEvent>>> {
+        let Some(c) = &self.api.ws else {
+            return Err(Error::Subscription(
                 "Subscription only supported by WebSocket client.".to_string(),
-            )),
-        }
+            ));
+        };
+
+        let (mut cursor, fetched) = match cursor {
+            Some(cursor) => (Some(cursor), false),
+            None if filter.is_replayable() => {
+                let latest = self
+                    .query_events(EventFilter::All(vec![]), None, Some(1), true)
+                    .await
+                    .ok()
+                    .and_then(|page| page.data.first().map(|event| event.id));
+                (latest, true)
+            }
+            None => (None, false),
+        };
+
+        let subscription: Subscription<SuiEvent> =
+            match c.subscribe_event(filter.clone(), cursor).await {
+                Ok(subscription) => subscription,
+                // Only the cursor that was fetched here can be dropped, as the caller's cursor is
+                // where they asked the stream to start.
+                Err(jsonrpsee::core::Error::Call(_)) if fetched && cursor.is_some() => {
+                    cursor = None;
+                    c.subscribe_event(filter.clone(), None).await?
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+        let state = EventSubscription {
+            api: self.api.clone(),
+            filter,
+            cursor,
+            subscription: Some(subscription),
+            ws: None,
+            delay: MIN_RESUBSCRIBE_DELAY,
+        };
+        Ok(Box::pin(stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        })))
     }
 
     /// Return a list of events for the given transaction digest, or an error upon failure.
@@ -1224,3 +1363,155 @@ impl GovernanceApi {
         Ok(*self.api.http.get_reference_gas_price().await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ServerInfo, WsConfig};
+    use jsonrpsee::core::error::SubscriptionClosed;
+    use jsonrpsee::http_client::{HeaderMap, HttpClientBuilder};
+    use jsonrpsee::server::{ServerBuilder, ServerHandle};
+    use jsonrpsee::RpcModule;
+    use std::sync::Mutex;
+    use sui_types::{parse_sui_struct_tag, Identifier};
+
+    /// Events sent by the server before it closes each subscription.
+    const EVENTS_PER_SUBSCRIPTION: u64 = 2;
+
+    fn event(event_seq: u64) -> SuiEvent {
+        SuiEvent {
+            id: EventID {
+                tx_digest: TransactionDigest::new([7; 32]),
+                event_seq,
+            },
+            package_id: ObjectID::ZERO,
+            transaction_module: Identifier::new("m").unwrap(),
+            sender: SuiAddress::ZERO,
+            type_: parse_sui_struct_tag("0x42::m::Foo").unwrap(),
+            parsed_json: serde_json::Value::Null,
+            bcs: vec![],
+            timestamp_ms: None,
+        }
+    }
+
+    /// The requests made to the server.
+    #[derive(Default)]
+    struct Requests {
+        queries: Mutex<usize>,
+        cursors: Mutex<Vec<Option<EventID>>>,
+    }
+
+    /// Serves an event stream whose latest event has sequence number 0, and whose subscriptions
+    /// are closed by the server after every `EVENTS_PER_SUBSCRIPTION` events, as if the
+    /// connection dropped.
+    async fn serve() -> (Arc<Requests>, Arc<RpcClient>, ServerHandle) {
+        let requests = Arc::new(Requests::default());
+        let mut module = RpcModule::new(requests.clone());
+        module
+            .register_method("suix_queryEvents", |_, requests| {
+                *requests.queries.lock().unwrap() += 1;
+                Ok::<_, jsonrpsee::core::Error>(EventPage {
+                    data: vec![event(0)],
+                    next_cursor: Some(event(0).id),
+                    has_next_page: false,
+                })
+            })
+            .unwrap();
+        module
+            .register_subscription(
+                "suix_subscribeEvent",
+                "suix_subscribeEvent",
+                "suix_unsubscribeEvent",
+                |params, mut sink, requests| {
+                    let mut params = params.sequence();
+                    let _: EventFilter = params.next().unwrap();
+                    let cursor: Option<EventID> = params.optional_next().unwrap();
+                    requests.cursors.lock().unwrap().push(cursor);
+
+                    let start = cursor.map_or(0, |c| c.event_seq + 1);
+                    let events = (start..start + EVENTS_PER_SUBSCRIPTION).map(event);
+                    tokio::spawn(async move {
+                        sink.pipe_from_stream(stream::iter(events)).await;
+                        sink.close(SubscriptionClosed::Success);
+                    });
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = server.start(module).unwrap();
+
+        let ws_config = WsConfig {
+            url: format!("ws://{addr}"),
+            headers: HeaderMap::new(),
+            request_timeout: Duration::from_secs(10),
+            max_concurrent_requests: 16,
+            ping_interval: None,
+        };
+        let api = RpcClient {
+            http: HttpClientBuilder::default()
+                .build(format!("http://{addr}"))
+                .unwrap(),
+            ws: Some(ws_config.connect().await.unwrap()),
+            ws_config: Some(ws_config),
+            info: ServerInfo {
+                rpc_methods: vec![],
+                subscriptions: vec![],
+                version: "test".to_string(),
+            },
+        };
+        (requests, Arc::new(api), handle)
+    }
+
+    #[tokio::test]
+    async fn test_subscription_resumes_after_drop() {
+        let (requests, api, _handle) = serve().await;
+        let filter = EventFilter::Sender(SuiAddress::ZERO);
+        let stream = EventApi::new(api).subscribe_event(filter).await.unwrap();
+
+        // Events keep coming across the dropped subscriptions, without gaps or repeats.
+        let events: Vec<_> =
+            tokio::time::timeout(Duration::from_secs(10), stream.take(5).collect())
+                .await
+                .unwrap();
+        let seqs: Vec<_> = events
+            .into_iter()
+            .map(|e| e.unwrap().id.event_seq)
+            .collect();
+        assert_eq!(seqs, vec![1, 2, 3, 4, 5]);
+
+        // The subscription started after the latest event, and each resumed subscription after
+        // the last event returned.
+        assert_eq!(*requests.queries.lock().unwrap(), 1);
+        let cursors: Vec<_> = requests
+            .cursors
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| c.unwrap().event_seq)
+            .collect();
+        assert_eq!(cursors, vec![0, 2, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_ends_after_drop_if_not_replayable() {
+        let (requests, api, _handle) = serve().await;
+        let filter = EventFilter::Package(ObjectID::ZERO);
+        let stream = EventApi::new(api).subscribe_event(filter).await.unwrap();
+
+        let events: Vec<_> = tokio::time::timeout(Duration::from_secs(10), stream.collect())
+            .await
+            .unwrap();
+        let seqs: Vec<_> = events
+            .into_iter()
+            .map(|e| e.unwrap().id.event_seq)
+            .collect();
+        assert_eq!(seqs, vec![0, 1]);
+
+        // The filter can't be resumed from a cursor, so none was fetched or sent.
+        assert_eq!(*requests.queries.lock().unwrap(), 0);
+        assert_eq!(*requests.cursors.lock().unwrap(), vec![None]);
+    }
+}
@@ -208,18 +208,15 @@ impl SuiClientBuilder {
         );
         headers.insert(CLIENT_SDK_TYPE_HEADER, HeaderValue::from_static("rust"));
 
-        let ws = if let Some(url) = self.ws_url {
-            let mut builder = WsClientBuilder::default()
-                .max_request_body_size(2 << 30)
-                .max_concurrent_requests(self.max_concurrent_requests)
-                .set_headers(headers.clone())
-                .request_timeout(self.request_timeout);
-
-            if let Some(duration) = self.ws_ping_interval {
-                builder = builder.ping_interval(duration)
-            }
-
-            Some(builder.build(url).await?)
+        let ws_config = self.ws_url.map(|url| WsConfig {
+            url,
+            headers: headers.clone(),
+            request_timeout: self.request_timeout,
+            max_concurrent_requests: self.max_concurrent_requests,
+            ping_interval: self.ws_ping_interval,
+        });
+        let ws = if let Some(config) = &ws_config {
+            Some(config.connect().await?)
         } else {
             None
         };
@@ -233,7 +230,12 @@ impl SuiClientBuilder {
 
         let info = Self::get_server_info(&http, &ws).await?;
 
-        let rpc = RpcClient { http, ws, info };
+        let rpc = RpcClient {
+            http,
+            ws,
+            ws_config,
+            info,
+        };
         let api = Arc::new(rpc);
         let read_api = Arc::new(ReadApi::new(api.clone()));
         let quorum_driver_api = QuorumDriverApi::new(api.clone());
@@ -419,9 +421,37 @@ pub struct SuiClient {
 pub(crate) struct RpcClient {
     http: HttpClient,
     ws: Option<WsClient>,
+    /// Used to open new WebSocket connections, for subscriptions to resume on after `ws` drops.
+    ws_config: Option<WsConfig>,
     info: ServerInfo,
 }
 
+/// The settings of the WebSocket client.
+pub(crate) struct WsConfig {
+    url: String,
+    headers: HeaderMap,
+    request_timeout: Duration,
+    max_concurrent_requests: usize,
+    ping_interval: Option<Duration>,
+}
+
+impl WsConfig {
+    /// Open a new WebSocket connection.
+    pub(crate) async fn connect(&self) -> SuiRpcResult<WsClient> {
+        let mut builder = WsClientBuilder::default()
+            .max_request_body_size(2 << 30)
+            .max_concurrent_requests(self.max_concurrent_requests)
+            .set_headers(self.headers.clone())
+            .request_timeout(self.request_timeout);
+
+        if let Some(duration) = self.ping_interval {
+            builder = builder.ping_interval(duration)
+        }
+
+        Ok(builder.build(&self.url).await?)
+    }
+}
+
 impl Debug for RpcClient {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -14,9 +14,16 @@ use std::collections::{BTreeMap, HashMap};
 use std::ops::Deref;
 use std::sync::Arc;
 use sui_config::node::RunWithRange;
+use sui_core::consensus_handler::{
+    SequencedConsensusTransaction, SequencedConsensusTransactionKind,
+    VerifiedSequencedConsensusTransaction,
+};
+use sui_core::post_consensus_tx_reorder::PostConsensusTxReorder;
 use sui_test_transaction_builder::PublishData;
 use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::digests::ConsensusCommitDigest;
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::messages_consensus::{ConsensusTransaction, ConsensusTransactionKind};
 use sui_types::messages_grpc::HandleTransactionResponse;
 use sui_types::mock_checkpoint_builder::ValidatorKeypairProvider;
 use sui_types::transaction::{
@@ -256,6 +263,56 @@ impl BenchmarkContext {
         results.into_iter().map(|r| r.unwrap()).collect()
     }
 
+    /// Orders the transactions of each consensus commit of `checkpoint_size` transactions, with
+    /// the consensus transaction ordering of the protocol config.
+    pub(crate) fn order_transactions(
+        &self,
+        transactions: Vec<CertifiedTransaction>,
+        checkpoint_size: usize,
+    ) -> Vec<CertifiedTransaction> {
+        let ordering = self
+            .validator
+            .get_epoch_store()
+            .protocol_config()
+            .consensus_transaction_ordering();
+        if ordering.is_none() {
+            return transactions;
+        }
+
+        info!("Ordering transactions with {:?}", ordering);
+        let name = self.validator.get_validator().name;
+        let start_time = std::time::Instant::now();
+        let mut ordered = Vec::with_capacity(transactions.len());
+        for commit in transactions.chunks(checkpoint_size) {
+            let mut sequenced: Vec<_> = commit
+                .iter()
+                .map(|tx| {
+                    VerifiedSequencedConsensusTransaction(SequencedConsensusTransaction::new_test(
+                        ConsensusTransaction::new_certificate_message(&name, tx.clone()),
+                    ))
+                })
+                .collect();
+            // Each commit gets its own digest, as consensus would give it.
+            PostConsensusTxReorder::reorder(
+                &mut sequenced,
+                ordering,
+                Some(&ConsensusCommitDigest::random()),
+            );
+            ordered.extend(sequenced.into_iter().map(|tx| match tx.0.transaction {
+                SequencedConsensusTransactionKind::External(ConsensusTransaction {
+                    kind: ConsensusTransactionKind::UserTransaction(cert),
+                    ..
+                }) => *cert,
+                _ => unreachable!("Only user transactions are ordered in benchmark"),
+            }));
+        }
+        info!(
+            "Ordering finished in {}ms",
+            start_time.elapsed().as_millis()
+        );
+        ordered
+    }
+
     pub(crate) async fn benchmark_transaction_execution(
         &self,
         transactions: Vec<CertifiedTransaction>,
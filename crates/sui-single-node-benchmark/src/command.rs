@@ -4,6 +4,7 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 use strum_macros::EnumIter;
+use sui_protocol_config::ConsensusTransactionOrdering;
 
 #[derive(Parser)]
 #[clap(
@@ -44,6 +45,13 @@ pub struct Command {
         help = "Which component to benchmark"
     )]
     pub component: Component,
+    #[arg(
+        long,
+        default_value = "none",
+        ignore_case = true,
+        help = "How transactions are ordered within each consensus commit"
+    )]
+    pub consensus_transaction_ordering: TransactionOrdering,
     #[clap(subcommand)]
     pub workload: WorkloadKind,
 }
@@ -71,6 +79,30 @@ pub enum Component {
     CheckpointExecutor,
 }
 
+/// Post-consensus ordering policy, see `ConsensusTransactionOrdering`.
+#[derive(Copy, Clone, ValueEnum)]
+pub enum TransactionOrdering {
+    None,
+    ByGasPrice,
+    BySenderRoundRobin,
+    BySharedObject,
+    ByRandomShuffle,
+}
+
+impl From<TransactionOrdering> for ConsensusTransactionOrdering {
+    fn from(ordering: TransactionOrdering) -> Self {
+        match ordering {
+            TransactionOrdering::None => ConsensusTransactionOrdering::None,
+            TransactionOrdering::ByGasPrice => ConsensusTransactionOrdering::ByGasPrice,
+            TransactionOrdering::BySenderRoundRobin => {
+                ConsensusTransactionOrdering::BySenderRoundRobin
+            }
+            TransactionOrdering::BySharedObject => ConsensusTransactionOrdering::BySharedObject,
+            TransactionOrdering::ByRandomShuffle => ConsensusTransactionOrdering::ByRandomShuffle,
+        }
+    }
+}
+
 #[derive(Subcommand, Clone)]
 pub enum WorkloadKind {
     PTB {
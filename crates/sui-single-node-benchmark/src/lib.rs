@@ -35,6 +35,7 @@ pub async fn run_benchmark(
     }
 
     let transactions = ctx.certify_transactions(transactions, skip_signing).await;
+    let transactions = ctx.order_transactions(transactions, checkpoint_size);
     ctx.validator()
         .assigned_shared_object_versions(&transactions)
         .await;
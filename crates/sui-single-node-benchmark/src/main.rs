@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
+use sui_protocol_config::{ConsensusTransactionOrdering, ProtocolConfig};
 use sui_single_node_benchmark::command::Command;
 use sui_single_node_benchmark::run_benchmark;
 use sui_single_node_benchmark::workload::Workload;
@@ -14,6 +15,11 @@ async fn main() {
         .init();
 
     let args = Command::parse();
+    let ordering: ConsensusTransactionOrdering = args.consensus_transaction_ordering.into();
+    let _config_guard = ProtocolConfig::apply_overrides_for_testing(move |_, mut config| {
+        config.set_consensus_transaction_ordering_for_testing(ordering);
+        config
+    });
     run_benchmark(
         Workload::new(args.tx_count, args.workload),
         args.component,
@@ -193,6 +193,7 @@ impl ValidatorConfigBuilder {
             name_service_reverse_registry_id: None,
             transaction_deny_config: Default::default(),
             certificate_deny_config: Default::default(),
+            transaction_validation_hooks: vec![],
             state_debug_dump_config: Default::default(),
             state_archive_write_config: StateArchiveConfig::default(),
             state_archive_read_config: vec![],
@@ -211,6 +212,7 @@ impl ValidatorConfigBuilder {
             websocket_only: false,
             policy_config: self.policy_config,
             firewall_config: self.firewall_config,
+            commit_scheduler_config: None,
         }
     }
 
@@ -462,6 +464,7 @@ impl FullnodeConfigBuilder {
             name_service_reverse_registry_id: None,
             transaction_deny_config: Default::default(),
             certificate_deny_config: Default::default(),
+            transaction_validation_hooks: vec![],
             state_debug_dump_config: Default::default(),
             state_archive_write_config: StateArchiveConfig::default(),
             state_archive_read_config: vec![],
@@ -478,6 +481,7 @@ impl FullnodeConfigBuilder {
             websocket_only: false,
             policy_config: self.policy_config,
             firewall_config: self.fw_config,
+            commit_scheduler_config: None,
         }
     }
 }
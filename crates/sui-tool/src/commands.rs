@@ -5,9 +5,10 @@ use crate::{
     check_completed_snapshot,
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
     download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
-    get_latest_available_epoch, get_object, get_transaction_block, make_clients, pkg_dump,
-    restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum, ConciseObjectOutput,
-    GroupedObjectOutput, VerboseObjectOutput,
+    export_consensus_dag, get_latest_available_epoch, get_object, get_transaction_block,
+    make_clients, pkg_dump, replay_consensus_commits, restore_from_db_checkpoint, verify_archive,
+    verify_archive_by_checksum, ConciseObjectOutput, ConsensusDagFormat, GroupedObjectOutput,
+    VerboseObjectOutput,
 };
 use anyhow::Result;
 use std::env;
@@ -190,7 +191,8 @@ pub enum ToolCommand {
     /// its own sub-directory, named for its ID on-chain, containing two metadata files
     /// (linkage.json and origins.json) as well as a file for every module it contains. Each module
     /// file is named for its module name, with a .mv suffix, and contains Move bytecode (suitable
-    /// for passing into a disassembler).
+    /// for passing into a disassembler). With --decompile, each module is also decompiled into a
+    /// file with a .move suffix.
     #[command(name = "dump-packages")]
     DumpPackages {
         /// Connection information for the Indexer's Postgres DB.
@@ -201,6 +203,11 @@ pub enum ToolCommand {
         #[clap(long, short)]
         output_dir: PathBuf,
 
+        /// Also write a decompiled Move source file (with a .move suffix) next to each module's
+        /// bytecode.
+        #[clap(long)]
+        decompile: bool,
+
         /// If false (default), log level will be overridden to "off", and output will be reduced to
         /// necessary status information.
         #[clap(short, long = "verbose")]
@@ -426,6 +433,45 @@ pub enum ToolCommand {
         )]
         sender_signed_data: String,
     },
+
+    /// Export a range of rounds of the consensus DAG, with the leader decisions and the
+    /// propagation delays of each authority. Only the current epoch can be inspected.
+    #[command(name = "consensus-dag")]
+    ConsensusDag {
+        /// Path of the authority db, used to read the committee of the current epoch.
+        #[arg(long = "db-path")]
+        db_path: PathBuf,
+        /// Path of the consensus db, with one sub-directory per epoch.
+        #[arg(long = "consensus-db-path")]
+        consensus_db_path: PathBuf,
+        #[arg(long = "start-round")]
+        start_round: u32,
+        #[arg(long = "end-round")]
+        end_round: u32,
+        #[arg(long = "format", value_enum, default_value = "dot")]
+        format: ConsensusDagFormat,
+        /// Write the export to this file instead of stdout.
+        #[arg(long = "output")]
+        output: Option<PathBuf>,
+    },
+
+    /// Replay the commit rule over the consensus blocks of the current epoch, and check that
+    /// it reproduces the commits in the consensus db.
+    #[command(name = "consensus-replay")]
+    ConsensusReplay {
+        /// Path of the authority db, used to read the committee of the current epoch.
+        #[arg(long = "db-path")]
+        db_path: PathBuf,
+        /// Path of the consensus db, with one sub-directory per epoch.
+        #[arg(long = "consensus-db-path")]
+        consensus_db_path: PathBuf,
+        /// Override the number of leaders per round of the replayed committer.
+        #[arg(long = "number-of-leaders")]
+        number_of_leaders: Option<usize>,
+        /// Override whether the replayed committer is pipelined.
+        #[arg(long = "pipeline")]
+        pipeline: Option<bool>,
+    },
 }
 
 trait OptionDebug<T> {
@@ -532,6 +578,7 @@ impl ToolCommand {
             ToolCommand::DumpPackages {
                 db_url,
                 output_dir,
+                decompile,
                 verbose,
             } => {
                 if !verbose {
@@ -540,7 +587,7 @@ impl ToolCommand {
                         .expect("Failed to update log level");
                 }
 
-                pkg_dump::dump(db_url, output_dir).await?;
+                pkg_dump::dump(db_url, output_dir, decompile).await?;
             }
             ToolCommand::DumpValidators { genesis, concise } => {
                 let genesis = Genesis::load(genesis).unwrap();
@@ -1008,6 +1055,39 @@ impl ToolCommand {
                 let result = agg.process_transaction(transaction, None).await;
                 println!("{:?}", result);
             }
+            ToolCommand::ConsensusDag {
+                db_path,
+                consensus_db_path,
+                start_round,
+                end_round,
+                format,
+                output,
+            } => {
+                let export = export_consensus_dag(
+                    &db_path,
+                    &consensus_db_path,
+                    start_round,
+                    end_round,
+                    format,
+                )?;
+                match output {
+                    Some(output) => std::fs::write(output, export)?,
+                    None => println!("{export}"),
+                }
+            }
+            ToolCommand::ConsensusReplay {
+                db_path,
+                consensus_db_path,
+                number_of_leaders,
+                pipeline,
+            } => {
+                replay_consensus_commits(
+                    &db_path,
+                    &consensus_db_path,
+                    number_of_leaders,
+                    pipeline,
+                )?;
+            }
         };
         Ok(())
     }
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
+use consensus_core::{CommitReplayer, DagInspector};
 use fastcrypto::traits::ToFromBytes;
 use futures::future::join_all;
 use futures::future::AbortHandle;
@@ -20,7 +21,7 @@ use sui_config::{genesis::Genesis, NodeConfig};
 use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClient};
 use sui_core::execution_cache::ExecutionCache;
 use sui_network::default_mysten_network_config;
-use sui_protocol_config::Chain;
+use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_sdk::SuiClientBuilder;
 use sui_storage::object_store::http::HttpDownloaderBuilder;
 use sui_storage::object_store::util::Manifest;
@@ -46,6 +47,9 @@ use sui_archival::{verify_archive_with_checksums, verify_archive_with_genesis_co
 use sui_config::node::ArchiveReaderConfig;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::epoch_start_configuration::{
+    EpochStartConfigTrait, EpochStartConfiguration,
+};
 use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::epoch::committee_store::CommitteeStore;
@@ -62,6 +66,7 @@ use sui_types::messages_grpc::{
 };
 
 use sui_types::storage::{ReadStore, SharedInMemoryStore};
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use tracing::info;
 use typed_store::rocks::MetricConf;
 
@@ -795,6 +800,102 @@ fn start_summary_sync(
     })
 }
 
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum ConsensusDagFormat {
+    Dot,
+    Json,
+}
+
+/// Reads the start configuration of the current epoch from the authority db at `db_path`, and
+/// returns it with the protocol config and the consensus db path of the epoch.
+/// `consensus_db_path` is the consensus db path, with one sub-directory per epoch.
+fn read_consensus_epoch(
+    db_path: &Path,
+    consensus_db_path: &Path,
+) -> Result<(EpochStartConfiguration, ProtocolConfig, PathBuf), anyhow::Error> {
+    let perpetual_db = AuthorityPerpetualTables::open_readonly(&db_path.join("store"));
+    let epoch_start_configuration = perpetual_db
+        .epoch_start_configuration
+        .get(&())?
+        .ok_or_else(|| anyhow!("Epoch start configuration not found in {:?}", db_path))?;
+    let system_state = epoch_start_configuration.epoch_start_state();
+    let protocol_config =
+        ProtocolConfig::get_for_version(system_state.protocol_version(), Chain::Unknown);
+    let epoch_db_path = consensus_db_path.join(system_state.epoch().to_string());
+    Ok((epoch_start_configuration, protocol_config, epoch_db_path))
+}
+
+/// Exports rounds `start_round..=end_round` of the consensus DAG of the current epoch.
+/// Both the authority db and the consensus db are opened read-only.
+pub fn export_consensus_dag(
+    db_path: &Path,
+    consensus_db_path: &Path,
+    start_round: u32,
+    end_round: u32,
+    format: ConsensusDagFormat,
+) -> Result<String, anyhow::Error> {
+    let (epoch_start_configuration, protocol_config, epoch_db_path) =
+        read_consensus_epoch(db_path, consensus_db_path)?;
+    info!(
+        "Inspecting consensus rounds {start_round}..={end_round} at {:?}",
+        epoch_db_path
+    );
+    let inspector = DagInspector::open(
+        epoch_start_configuration
+            .epoch_start_state()
+            .get_mysticeti_committee(),
+        protocol_config,
+        &epoch_db_path,
+    );
+    let report = inspector.inspect(start_round, end_round)?;
+    Ok(match format {
+        ConsensusDagFormat::Dot => report.to_dot(),
+        ConsensusDagFormat::Json => serde_json::to_string_pretty(&report)?,
+    })
+}
+
+/// Replays the commit rule over the consensus blocks of the current epoch, and reports the
+/// stored commits that the replay does not reproduce. Fails if there is any.
+pub fn replay_consensus_commits(
+    db_path: &Path,
+    consensus_db_path: &Path,
+    number_of_leaders: Option<usize>,
+    pipeline: Option<bool>,
+) -> Result<(), anyhow::Error> {
+    let (epoch_start_configuration, protocol_config, epoch_db_path) =
+        read_consensus_epoch(db_path, consensus_db_path)?;
+    let mut replayer = CommitReplayer::open(
+        epoch_start_configuration
+            .epoch_start_state()
+            .get_mysticeti_committee(),
+        protocol_config,
+        &epoch_db_path,
+    );
+    if let Some(number_of_leaders) = number_of_leaders {
+        replayer = replayer.with_number_of_leaders(number_of_leaders);
+    }
+    if let Some(pipeline) = pipeline {
+        replayer = replayer.with_pipeline(pipeline);
+    }
+
+    let result = replayer.replay()?;
+    println!(
+        "Replayed {} commits, {} commits are stored",
+        result.committed_sub_dags.len(),
+        result.num_stored_commits
+    );
+    for divergence in &result.divergences {
+        println!("Divergence: {:?}", divergence);
+    }
+    if !result.divergences.is_empty() {
+        return Err(anyhow!(
+            "{} stored commits diverge from the replay",
+            result.divergences.len()
+        ));
+    }
+    Ok(())
+}
+
 pub async fn get_latest_available_epoch(
     snapshot_store_config: &ObjectStoreConfig,
 ) -> Result<u64, anyhow::Error> {
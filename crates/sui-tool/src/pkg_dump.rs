@@ -13,13 +13,15 @@ use diesel::{
     r2d2::{ConnectionManager, Pool},
     PgConnection, RunQueryDsl,
 };
+use move_binary_format::CompiledModule;
+use move_decompiler::decompiler::Decompiler;
 use sui_indexer::{models::packages::StoredPackage, schema::packages};
 use sui_types::{base_types::SuiAddress, move_package::MovePackage};
 use tracing::info;
 
 type PgPool = Pool<ConnectionManager<PgConnection>>;
 
-pub(crate) async fn dump(db_url: String, output_dir: PathBuf) -> Result<()> {
+pub(crate) async fn dump(db_url: String, output_dir: PathBuf, decompile: bool) -> Result<()> {
     ensure_output_directory(&output_dir)?;
 
     let conn = ConnectionManager::<PgConnection>::new(db_url);
@@ -42,7 +44,7 @@ pub(crate) async fn dump(db_url: String, output_dir: PathBuf) -> Result<()> {
         }
 
         let id = SuiAddress::from_bytes(&pkg.package_id).context("Parsing package ID")?;
-        dump_package(&output_dir, id, &pkg.move_package)
+        dump_package(&output_dir, id, &pkg.move_package, decompile)
             .with_context(|| format!("Dumping package: {id}"))?;
     }
 
@@ -87,7 +89,7 @@ fn query_packages(pool: &PgPool) -> Result<Vec<StoredPackage>> {
     Ok(packages::dsl::packages.load::<StoredPackage>(&mut conn)?)
 }
 
-fn dump_package(output_dir: &Path, id: SuiAddress, pkg: &[u8]) -> Result<()> {
+fn dump_package(output_dir: &Path, id: SuiAddress, pkg: &[u8], decompile: bool) -> Result<()> {
     let package = bcs::from_bytes::<MovePackage>(pkg).context("Deserializing")?;
     let origins: BTreeMap<_, _> = package
         .type_origin_table()
@@ -115,7 +117,17 @@ fn dump_package(output_dir: &Path, id: SuiAddress, pkg: &[u8]) -> Result<()> {
     for (module_name, module_bytes) in package.serialized_module_map() {
         let module_path = package_dir.join(format!("{module_name}.mv"));
         fs::write(module_path, module_bytes)
-            .with_context(|| format!("Writing module: {module_name}"))?
+            .with_context(|| format!("Writing module: {module_name}"))?;
+
+        if decompile {
+            let module = CompiledModule::deserialize_with_defaults(module_bytes)
+                .with_context(|| format!("Deserializing module: {module_name}"))?;
+            let source = Decompiler::new(&module)
+                .decompile()
+                .with_context(|| format!("Decompiling module: {module_name}"))?;
+            fs::write(package_dir.join(format!("{module_name}.move")), source)
+                .with_context(|| format!("Writing decompiled module: {module_name}"))?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use sui_config::transaction_validation_hook_config::TransactionValidationHookConfig;
+use sui_types::{
+    base_types::ObjectID,
+    error::TransactionRejection,
+    signature::GenericSignature,
+    transaction::{Command, TransactionData, TransactionDataAPI},
+};
+
+/// A custom check on the transactions submitted to this node, run after the deny checks of
+/// `TransactionDenyConfig`.
+pub trait TransactionValidationHook: Send + Sync {
+    /// Name of the hook, reported in its rejections.
+    fn name(&self) -> &str;
+
+    fn validate(
+        &self,
+        tx_data: &TransactionData,
+        tx_signatures: &[GenericSignature],
+    ) -> Result<(), RejectionReason>;
+}
+
+/// Why a hook rejects a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectionReason {
+    /// Machine readable reason, e.g. for clients to decide whether to resubmit.
+    pub code: String,
+    pub message: String,
+}
+
+impl RejectionReason {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Builds a plugin hook from the `params` of its `TransactionValidationHookConfig::Plugin`.
+pub type TransactionValidationPluginFactory =
+    fn(&BTreeMap<String, String>) -> anyhow::Result<Arc<dyn TransactionValidationHook>>;
+
+static PLUGINS: Lazy<RwLock<HashMap<String, TransactionValidationPluginFactory>>> =
+    Lazy::new(Default::default);
+
+/// Registers a plugin under `name`, so that it can be configured as a validation hook. Plugins
+/// must be registered before the node is started.
+pub fn register_transaction_validation_plugin(
+    name: &str,
+    factory: TransactionValidationPluginFactory,
+) {
+    PLUGINS.write().insert(name.to_string(), factory);
+}
+
+/// The validation hooks of a node, run in order until one of them rejects the transaction.
+#[derive(Clone, Default)]
+pub struct TransactionValidationHooks {
+    hooks: Arc<RwLock<Vec<Arc<dyn TransactionValidationHook>>>>,
+}
+
+impl TransactionValidationHooks {
+    pub fn from_config(configs: &[TransactionValidationHookConfig]) -> anyhow::Result<Self> {
+        let hooks = Self::default();
+        for config in configs {
+            let hook: Arc<dyn TransactionValidationHook> = match config {
+                TransactionValidationHookConfig::MinGasPriceForPackages {
+                    packages,
+                    min_gas_price,
+                } => Arc::new(MinGasPriceForPackages {
+                    packages: packages.iter().copied().collect(),
+                    min_gas_price: *min_gas_price,
+                }),
+                TransactionValidationHookConfig::Plugin { name, params } => {
+                    let factory = *PLUGINS.read().get(name).ok_or_else(|| {
+                        anyhow!("Transaction validation plugin {name} is not registered")
+                    })?;
+                    factory(params)?
+                }
+            };
+            hooks.register(hook);
+        }
+        Ok(hooks)
+    }
+
+    /// Appends `hook` to the hooks, which applies to transactions submitted from now on.
+    pub fn register(&self, hook: Arc<dyn TransactionValidationHook>) {
+        self.hooks.write().push(hook);
+    }
+
+    pub fn check(
+        &self,
+        tx_data: &TransactionData,
+        tx_signatures: &[GenericSignature],
+    ) -> Result<(), TransactionRejection> {
+        for hook in self.hooks.read().iter() {
+            if let Err(reason) = hook.validate(tx_data, tx_signatures) {
+                return Err(TransactionRejection {
+                    hook: hook.name().to_string(),
+                    code: reason.code,
+                    message: reason.message,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+struct MinGasPriceForPackages {
+    packages: HashSet<ObjectID>,
+    min_gas_price: u64,
+}
+
+impl TransactionValidationHook for MinGasPriceForPackages {
+    fn name(&self) -> &str {
+        "min-gas-price-for-packages"
+    }
+
+    fn validate(
+        &self,
+        tx_data: &TransactionData,
+        _tx_signatures: &[GenericSignature],
+    ) -> Result<(), RejectionReason> {
+        if tx_data.gas_price() >= self.min_gas_price {
+            return Ok(());
+        }
+        for command in tx_data.kind().iter_commands() {
+            if let Command::MoveCall(call) = command {
+                if self.packages.contains(&call.package) {
+                    return Err(RejectionReason::new(
+                        "gas-price-too-low",
+                        format!(
+                            "Calls to package {} require a gas price of at least {}, got {}",
+                            call.package,
+                            self.min_gas_price,
+                            tx_data.gas_price()
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
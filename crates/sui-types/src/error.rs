@@ -257,6 +257,26 @@ pub enum UserInputError {
 
     #[error("Commands following a command with Random can only be TransferObjects or MergeCoins")]
     PostRandomCommandRestrictions,
+
+    #[error("Transaction is rejected by validation hook {0}")]
+    TransactionRejected(TransactionRejection),
+}
+
+/// Structured reason of a transaction rejected by a validation hook of the node it was
+/// submitted to.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, Hash)]
+pub struct TransactionRejection {
+    /// Name of the hook that rejected the transaction.
+    pub hook: String,
+    /// Machine readable reason, defined by the hook.
+    pub code: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for TransactionRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.hook, self.code, self.message)
+    }
 }
 
 #[derive(
@@ -620,6 +640,12 @@ pub enum SuiError {
 
     #[error("Too many requests")]
     TooManyRequests,
+
+    #[error("Validator cannot handle transactions on hot objects {object_ids:?} at the moment. Please retry after at least {retry_after_secs} seconds.")]
+    ValidatorOverloadedOnObjectsRetryAfter {
+        object_ids: Vec<ObjectID>,
+        retry_after_secs: u64,
+    },
 }
 
 #[repr(u64)]
@@ -730,6 +756,12 @@ impl From<UserInputError> for SuiError {
     }
 }
 
+impl From<TransactionRejection> for SuiError {
+    fn from(rejection: TransactionRejection) -> Self {
+        UserInputError::TransactionRejected(rejection).into()
+    }
+}
+
 impl From<SuiObjectResponseError> for SuiError {
     fn from(error: SuiObjectResponseError) -> Self {
         SuiError::SuiObjectResponseError { error }
@@ -775,6 +807,7 @@ impl SuiError {
             SuiError::TooOldTransactionPendingOnObject { .. } => true,
             SuiError::TooManyTransactionsPendingConsensus => true,
             SuiError::ValidatorOverloadedRetryAfter { .. } => true,
+            SuiError::ValidatorOverloadedOnObjectsRetryAfter { .. } => true,
 
             // Non retryable error
             SuiError::ExecutionError(..) => false,
@@ -820,7 +853,11 @@ impl SuiError {
     }
 
     pub fn is_retryable_overload(&self) -> bool {
-        matches!(self, SuiError::ValidatorOverloadedRetryAfter { .. })
+        matches!(
+            self,
+            SuiError::ValidatorOverloadedRetryAfter { .. }
+                | SuiError::ValidatorOverloadedOnObjectsRetryAfter { .. }
+        )
     }
 }
 
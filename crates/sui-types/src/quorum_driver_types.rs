@@ -11,7 +11,7 @@ use crate::effects::{
     CertifiedTransactionEffects, TransactionEffects, TransactionEvents,
     VerifiedCertifiedTransactionEffects,
 };
-use crate::error::SuiError;
+use crate::error::{SuiError, TransactionRejection};
 use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::transaction::{Transaction, VerifiedTransaction};
 use serde::{Deserialize, Serialize};
@@ -64,6 +64,8 @@ pub enum QuorumDriverError {
         errors: GroupedErrors,
         retry_after_secs: u64,
     },
+    #[error("Transaction is rejected by validation hook {0}.")]
+    TransactionRejected(TransactionRejection),
 }
 
 pub type GroupedErrors = Vec<(SuiError, StakeUnit, Vec<ConciseAuthorityPublicKeyBytes>)>;
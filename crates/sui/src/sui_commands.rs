@@ -6,6 +6,7 @@ use crate::console::start_console;
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
+use crate::upgrade_compatibility::CheckUpgrade;
 use crate::validator_commands::SuiValidatorCommand;
 use anyhow::{anyhow, bail};
 use clap::*;
@@ -147,7 +148,7 @@ pub enum SuiCommand {
         build_config: BuildConfig,
         /// Subcommands.
         #[clap(subcommand)]
-        cmd: sui_move::Command,
+        cmd: MoveCommand,
     },
 
     /// Tool for Fire Drill
@@ -157,6 +158,16 @@ pub enum SuiCommand {
     },
 }
 
+/// Move subcommands: those provided by `sui-move`, plus the ones that need to talk to a network.
+#[derive(Parser)]
+pub enum MoveCommand {
+    /// Check that the package can be published as an upgrade of an on-chain package, reporting
+    /// every incompatible change.
+    CheckUpgrade(CheckUpgrade),
+    #[clap(flatten)]
+    Base(sui_move::Command),
+}
+
 impl SuiCommand {
     pub async fn execute(self) -> Result<(), anyhow::Error> {
         move_package::package_hooks::register_package_hooks(Box::new(SuiPackageHooks));
@@ -318,7 +329,25 @@ impl SuiCommand {
                 package_path,
                 build_config,
                 cmd,
-            } => execute_move_command(package_path, build_config, cmd),
+            } => match cmd {
+                MoveCommand::CheckUpgrade(cmd) => {
+                    let config_path = cmd
+                        .config
+                        .clone()
+                        .unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                    prompt_if_no_config(&config_path, false).await?;
+                    let context = WalletContext::new(&config_path, None, None)?;
+                    cmd.execute(package_path, build_config, &context).await
+                }
+                MoveCommand::Base(sui_move::Command::GenRust(cmd)) if cmd.package_id.is_some() => {
+                    let config_path = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+                    prompt_if_no_config(&config_path, false).await?;
+                    let context = WalletContext::new(&config_path, None, None)?;
+                    let rpc = &context.config.get_active_env()?.rpc;
+                    cmd.execute_on_chain(&format!("{rpc}/rest")).await
+                }
+                MoveCommand::Base(cmd) => execute_move_command(package_path, build_config, cmd),
+            },
             SuiCommand::FireDrill { fire_drill } => run_fire_drill(fire_drill).await,
         }
     }
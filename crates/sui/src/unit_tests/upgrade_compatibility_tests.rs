@@ -0,0 +1,104 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use move_binary_format::normalized;
+use sui_move_build::{BuildConfig, CompiledPackage};
+use sui_protocol_config::{Chain, ProtocolVersion};
+use sui_types::move_package::{normalize_deserialized_modules, UpgradePolicy};
+
+use crate::upgrade_compatibility::{check_upgrade_compatibility, protocol_config_for_version};
+
+fn build(name: &str) -> CompiledPackage {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "data", "upgrade_compatibility", name]);
+    BuildConfig::new_for_testing().build(path).unwrap()
+}
+
+fn existing() -> BTreeMap<String, normalized::Module> {
+    normalize_deserialized_modules(build("base").get_modules())
+}
+
+fn messages(policy: UpgradePolicy) -> Vec<String> {
+    check_upgrade_compatibility(policy, &existing(), &build("upgrade"))
+        .unwrap()
+        .into_iter()
+        .map(|i| i.message)
+        .collect()
+}
+
+#[test]
+fn test_identical_package_is_compatible() {
+    let base = build("base");
+    for policy in [
+        UpgradePolicy::Compatible,
+        UpgradePolicy::Additive,
+        UpgradePolicy::DepOnly,
+    ] {
+        let incompatibilities = check_upgrade_compatibility(policy, &existing(), &base).unwrap();
+        assert!(incompatibilities.is_empty(), "{incompatibilities:?}");
+    }
+}
+
+#[test]
+fn test_compatible_reports_all_incompatibilities() {
+    assert_eq!(
+        messages(UpgradePolicy::Compatible),
+        vec![
+            "Fields of struct 'Kept' changed",
+            "Abilities of struct 'Point' changed from 'copy, drop' to 'copy, drop, store'",
+            "Struct 'Removed' was removed",
+            "Signature of public function 'add' changed",
+            "Public function 'gone' was removed",
+            "Public function 'made_private' is no longer public",
+        ]
+    );
+}
+
+#[test]
+fn test_additive_and_dep_only() {
+    let additive = vec![
+        "Struct 'Kept' changed",
+        "Struct 'Point' changed",
+        "Struct 'Removed' was removed",
+        "Function 'add' changed",
+        "Function 'gone' was removed",
+        "Function 'helper' changed",
+        "Function 'made_private' changed",
+    ];
+    assert_eq!(messages(UpgradePolicy::Additive), additive);
+
+    let mut dep_only = additive;
+    dep_only.push("Function 'added' was added");
+    assert_eq!(messages(UpgradePolicy::DepOnly), dep_only);
+}
+
+#[test]
+fn test_incompatibility_locations() {
+    let incompatibilities =
+        check_upgrade_compatibility(UpgradePolicy::Compatible, &existing(), &build("upgrade"))
+            .unwrap();
+    let location = |needle: &str| {
+        incompatibilities
+            .iter()
+            .find(|i| i.message.contains(needle))
+            .and_then(|i| i.location.clone())
+            .unwrap()
+    };
+
+    // Changed declarations point at their definition, removed ones at the enclosing module.
+    assert!(location("'Point'").contains("upgrades.move:5:"));
+    assert!(location("'made_private'").contains("upgrades.move:9:"));
+    assert!(location("'Removed'").contains("upgrades.move:4:"));
+}
+
+#[test]
+fn test_unsupported_network_protocol_version() {
+    let config = protocol_config_for_version(ProtocolVersion::MAX, Chain::Unknown).unwrap();
+    assert_eq!(config.version, ProtocolVersion::MAX);
+
+    let err = protocol_config_for_version(ProtocolVersion::MAX + 10, Chain::Unknown).unwrap_err();
+    assert!(err.to_string().contains("upgrade sui"), "{err}");
+}
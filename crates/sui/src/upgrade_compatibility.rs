@@ -0,0 +1,478 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks whether a local Move package is a valid upgrade of a package that is already published
+//! on-chain, without submitting an upgrade transaction. Unlike the check performed by the adapter
+//! during execution, which stops at the first problem, every incompatibility is collected and
+//! reported together with its location in the upgraded package's sources.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Context};
+use clap::*;
+use move_binary_format::{
+    file_format::{AbilitySet, FunctionDefinitionIndex, StructDefinitionIndex, Visibility},
+    normalized, CompiledModule,
+};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_package::{compilation::compiled_package::CompiledUnitWithSource, BuildConfig};
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiRawData};
+use sui_move_build::CompiledPackage;
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_sdk::{apis::ReadApi, wallet_context::WalletContext};
+use sui_types::{
+    base_types::ObjectID,
+    digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier},
+    execution_config_utils::to_binary_config,
+    move_package::{normalize_modules, UpgradePolicy},
+};
+
+use crate::client_commands::compile_package;
+
+/// Upgrade policies that an upgrade can be checked against, mirroring `sui::package`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[clap(rename_all = "kebab-case")]
+pub enum CheckUpgradePolicy {
+    Compatible,
+    Additive,
+    DepOnly,
+}
+
+impl From<CheckUpgradePolicy> for UpgradePolicy {
+    fn from(policy: CheckUpgradePolicy) -> Self {
+        match policy {
+            CheckUpgradePolicy::Compatible => UpgradePolicy::Compatible,
+            CheckUpgradePolicy::Additive => UpgradePolicy::Additive,
+            CheckUpgradePolicy::DepOnly => UpgradePolicy::DepOnly,
+        }
+    }
+}
+
+/// Check that the package at `--path` can be published as an upgrade of an on-chain package.
+#[derive(Parser)]
+pub struct CheckUpgrade {
+    /// ID of the on-chain package to check the upgrade against.
+    #[clap(long = "package-id")]
+    pub package_id: ObjectID,
+    /// The upgrade policy to check the package against.
+    #[clap(long, value_enum, default_value = "compatible")]
+    pub policy: CheckUpgradePolicy,
+    /// Sets the file storing the state of our user accounts (an empty one will be created if missing)
+    #[clap(long = "client.config")]
+    pub config: Option<PathBuf>,
+}
+
+impl CheckUpgrade {
+    pub async fn execute(
+        self,
+        package_path: Option<PathBuf>,
+        build_config: BuildConfig,
+        context: &WalletContext,
+    ) -> anyhow::Result<()> {
+        let package_path = match package_path {
+            Some(path) => path,
+            None => std::env::current_dir()?,
+        };
+        let client = context.get_client().await?;
+        let read_api = client.read_api();
+
+        let object = read_api
+            .get_object_with_options(self.package_id, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()
+            .map_err(|e| anyhow!("Failed to fetch package {}: {e}", self.package_id))?;
+        let Some(SuiRawData::Package(package)) = object.bcs else {
+            bail!("Object {} is not a package", self.package_id);
+        };
+
+        let protocol_config = network_protocol_config(read_api).await?;
+        let binary_config = to_binary_config(&protocol_config);
+        let existing = normalize_modules(package.module_map.values(), &binary_config)
+            .map_err(|e| anyhow!("Failed to normalize on-chain package modules: {e}"))?;
+
+        let (_, _, compiled_package, _) = compile_package(
+            read_api,
+            build_config,
+            package_path,
+            /* with_unpublished_dependencies */ false,
+            /* skip_dependency_verification */ true,
+        )
+        .await?;
+
+        let incompatibilities =
+            check_upgrade_compatibility(self.policy.into(), &existing, &compiled_package)?;
+        if incompatibilities.is_empty() {
+            println!(
+                "Package is a {} upgrade of {}",
+                UpgradePolicy::from(self.policy),
+                self.package_id
+            );
+            return Ok(());
+        }
+
+        for incompatibility in &incompatibilities {
+            eprintln!("{incompatibility}");
+        }
+        bail!(
+            "Found {} incompatible change(s) for a {} upgrade of {}",
+            incompatibilities.len(),
+            UpgradePolicy::from(self.policy),
+            self.package_id
+        )
+    }
+}
+
+/// The protocol config the network is currently running, which the upgrade will be checked
+/// against when it is published.
+async fn network_protocol_config(read_api: &ReadApi) -> anyhow::Result<ProtocolConfig> {
+    let config = read_api.get_protocol_config(None).await?;
+    let chain_id = read_api.get_chain_identifier().await?;
+    let chain = if chain_id == get_mainnet_chain_identifier().to_string() {
+        Chain::Mainnet
+    } else if chain_id == get_testnet_chain_identifier().to_string() {
+        Chain::Testnet
+    } else {
+        Chain::Unknown
+    };
+    protocol_config_for_version(config.protocol_version, chain)
+}
+
+/// The protocol config at `version`, which fails if this CLI does not support the version.
+fn protocol_config_for_version(
+    version: ProtocolVersion,
+    chain: Chain,
+) -> anyhow::Result<ProtocolConfig> {
+    ProtocolConfig::get_for_version_if_supported(version, chain).ok_or_else(|| {
+        if version > ProtocolVersion::MAX {
+            anyhow!(
+                "Network protocol version {} is newer than this CLI supports; upgrade sui",
+                version.as_u64()
+            )
+        } else {
+            anyhow!(
+                "Network protocol version {} is older than this CLI supports",
+                version.as_u64()
+            )
+        }
+    })
+}
+
+/// A single reason why a package cannot be published as an upgrade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    pub module: String,
+    pub message: String,
+    /// Location (`file:line:column`) of the offending declaration in the upgraded package, if the
+    /// declaration still exists there.
+    pub location: Option<String>,
+}
+
+impl Display for Incompatibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "{location}: module '{}': {}", self.module, self.message),
+            None => write!(f, "module '{}': {}", self.module, self.message),
+        }
+    }
+}
+
+/// Declaration an incompatibility is reported against.
+enum Declaration {
+    Module,
+    Struct(Identifier),
+    Function(Identifier),
+}
+
+/// Returns all incompatibilities between the `existing` on-chain modules and the root modules of
+/// `upgrade` under `policy`. The modules in `upgrade` are expected to be at address 0x0, and are
+/// compared as if they were published at the address of the existing modules.
+pub fn check_upgrade_compatibility(
+    policy: UpgradePolicy,
+    existing: &BTreeMap<String, normalized::Module>,
+    upgrade: &CompiledPackage,
+) -> anyhow::Result<Vec<Incompatibility>> {
+    let Some(runtime_id) = existing.values().next().map(|m| m.address) else {
+        bail!("Existing package does not contain any modules");
+    };
+
+    let units: BTreeMap<String, &CompiledUnitWithSource> = upgrade
+        .package
+        .root_modules()
+        .map(|unit| (unit.unit.name.to_string(), unit))
+        .collect();
+
+    let mut incompatibilities = vec![];
+    for (name, old_module) in existing {
+        let Some(unit) = units.get(name) else {
+            incompatibilities.push(Incompatibility {
+                module: name.clone(),
+                message: "Existing module not found in next version of package".to_string(),
+                location: None,
+            });
+            continue;
+        };
+
+        let mut module = unit.unit.module.clone();
+        substitute_self_address(&mut module, runtime_id)
+            .with_context(|| format!("Failed to check module '{name}'"))?;
+        let new_module = normalized::Module::new(&module);
+
+        let decls = match policy {
+            UpgradePolicy::Compatible => compatible_incompatibilities(old_module, &new_module),
+            UpgradePolicy::Additive => inclusion_incompatibilities(false, old_module, &new_module),
+            UpgradePolicy::DepOnly => inclusion_incompatibilities(true, old_module, &new_module),
+        };
+        incompatibilities.extend(decls.into_iter().map(|(decl, message)| Incompatibility {
+            module: name.clone(),
+            message,
+            location: declaration_location(unit, &module, &decl),
+        }));
+    }
+
+    Ok(incompatibilities)
+}
+
+/// Mirrors `move_binary_format::compatibility::Compatibility::check` with the configuration used
+/// by the adapter for `COMPATIBLE` upgrades (no new abilities, struct type parameters must not
+/// change, and friend and private entry functions are not checked).
+fn compatible_incompatibilities(
+    old: &normalized::Module,
+    new: &normalized::Module,
+) -> Vec<(Declaration, String)> {
+    let mut errors = vec![];
+    if old.address != new.address || old.name != new.name {
+        errors.push((
+            Declaration::Module,
+            format!(
+                "Module changed from '{}::{}' to '{}::{}'",
+                old.address, old.name, new.address, new.name
+            ),
+        ));
+    }
+
+    for (name, old_struct) in &old.structs {
+        let Some(new_struct) = new.structs.get(name) else {
+            errors.push((Declaration::Module, format!("Struct '{name}' was removed")));
+            continue;
+        };
+
+        if old_struct.abilities != new_struct.abilities {
+            errors.push((
+                Declaration::Struct(name.clone()),
+                format!(
+                    "Abilities of struct '{name}' changed from '{}' to '{}'",
+                    display_abilities(old_struct.abilities),
+                    display_abilities(new_struct.abilities),
+                ),
+            ));
+        }
+        if old_struct.type_parameters != new_struct.type_parameters {
+            errors.push((
+                Declaration::Struct(name.clone()),
+                format!("Type parameters of struct '{name}' changed"),
+            ));
+        }
+        if old_struct.fields != new_struct.fields {
+            errors.push((
+                Declaration::Struct(name.clone()),
+                format!("Fields of struct '{name}' changed"),
+            ));
+        }
+    }
+
+    for (name, old_func) in &old.functions {
+        if old_func.visibility != Visibility::Public {
+            continue;
+        }
+
+        let Some(new_func) = new.functions.get(name) else {
+            errors.push((
+                Declaration::Module,
+                format!("Public function '{name}' was removed"),
+            ));
+            continue;
+        };
+
+        if new_func.visibility != Visibility::Public {
+            errors.push((
+                Declaration::Function(name.clone()),
+                format!("Public function '{name}' is no longer public"),
+            ));
+        }
+
+        if old_func.parameters != new_func.parameters
+            || old_func.return_ != new_func.return_
+            || !fun_type_parameters_compatible(&old_func.type_parameters, &new_func.type_parameters)
+        {
+            errors.push((
+                Declaration::Function(name.clone()),
+                format!("Signature of public function '{name}' changed"),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Mirrors `move_binary_format::compatibility::InclusionCheck::check`, used by the adapter for
+/// `ADDITIVE` (subset) and `DEP_ONLY` (equal) upgrades.
+fn inclusion_incompatibilities(
+    equal: bool,
+    old: &normalized::Module,
+    new: &normalized::Module,
+) -> Vec<(Declaration, String)> {
+    let mut errors = vec![];
+    if old.address != new.address || old.name != new.name {
+        errors.push((
+            Declaration::Module,
+            format!(
+                "Module changed from '{}::{}' to '{}::{}'",
+                old.address, old.name, new.address, new.name
+            ),
+        ));
+    }
+    if old.file_format_version > new.file_format_version {
+        errors.push((
+            Declaration::Module,
+            format!(
+                "File format version decreased from {} to {}",
+                old.file_format_version, new.file_format_version
+            ),
+        ));
+    }
+
+    for (name, old_struct) in &old.structs {
+        match new.structs.get(name) {
+            None => errors.push((Declaration::Module, format!("Struct '{name}' was removed"))),
+            Some(new_struct) if new_struct != old_struct => errors.push((
+                Declaration::Struct(name.clone()),
+                format!("Struct '{name}' changed"),
+            )),
+            Some(_) => (),
+        }
+    }
+
+    for (name, old_func) in &old.functions {
+        match new.functions.get(name) {
+            None => errors.push((
+                Declaration::Module,
+                format!("Function '{name}' was removed"),
+            )),
+            Some(new_func) if new_func != old_func => errors.push((
+                Declaration::Function(name.clone()),
+                format!("Function '{name}' changed"),
+            )),
+            Some(_) => (),
+        }
+    }
+
+    if !equal {
+        return errors;
+    }
+
+    for name in new.structs.keys() {
+        if !old.structs.contains_key(name) {
+            errors.push((
+                Declaration::Struct(name.clone()),
+                format!("Struct '{name}' was added"),
+            ));
+        }
+    }
+    for name in new.functions.keys() {
+        if !old.functions.contains_key(name) {
+            errors.push((
+                Declaration::Function(name.clone()),
+                format!("Function '{name}' was added"),
+            ));
+        }
+    }
+    if old.friends.len() != new.friends.len() {
+        errors.push((
+            Declaration::Module,
+            "Friend declarations changed".to_string(),
+        ));
+    }
+
+    errors
+}
+
+// Function type parameter constraints may only be relaxed, see
+// `move_binary_format::compatibility`.
+fn fun_type_parameters_compatible(old: &[AbilitySet], new: &[AbilitySet]) -> bool {
+    old.len() == new.len() && old.iter().zip(new).all(|(old, new)| new.is_subset(*old))
+}
+
+fn display_abilities(abilities: AbilitySet) -> String {
+    abilities
+        .into_iter()
+        .map(|a| format!("{a:?}").to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Set the self address of `module` (which must be 0x0) to `address`, as the adapter does when
+/// publishing an upgrade.
+fn substitute_self_address(
+    module: &mut CompiledModule,
+    address: AccountAddress,
+) -> anyhow::Result<()> {
+    let self_handle = module.self_handle().clone();
+    let name = module.identifier_at(self_handle.name).to_string();
+    let Some(address_mut) = module
+        .address_identifiers
+        .get_mut(self_handle.address.0 as usize)
+    else {
+        bail!("Module '{name}' has an invalid address index");
+    };
+    if *address_mut != AccountAddress::ZERO {
+        bail!("Module '{name}' must have 0x0 as its address to be checked as an upgrade");
+    }
+    *address_mut = address;
+    Ok(())
+}
+
+/// Look up the location of `decl` in the source file `unit` was compiled from.
+fn declaration_location(
+    unit: &CompiledUnitWithSource,
+    module: &CompiledModule,
+    decl: &Declaration,
+) -> Option<String> {
+    let source_map = &unit.unit.source_map;
+    let loc = match decl {
+        Declaration::Module => source_map.definition_location,
+        Declaration::Struct(name) => {
+            let idx = module.struct_defs().iter().position(|def| {
+                let handle = module.struct_handle_at(def.struct_handle);
+                module.identifier_at(handle.name) == name.as_ident_str()
+            })?;
+            source_map
+                .get_struct_source_map(StructDefinitionIndex(idx as u16))
+                .ok()?
+                .definition_location
+        }
+        Declaration::Function(name) => {
+            let idx = module.function_defs().iter().position(|def| {
+                let handle = module.function_handle_at(def.function);
+                module.identifier_at(handle.name) == name.as_ident_str()
+            })?;
+            source_map
+                .get_function_source_map(FunctionDefinitionIndex(idx as u16))
+                .ok()?
+                .definition_location
+        }
+    };
+
+    let source = fs::read_to_string(&unit.source_path).ok()?;
+    let offset = (loc.start() as usize).min(source.len());
+    let preceding = &source[..offset];
+    let line = preceding.matches('\n').count() + 1;
+    let column = offset - preceding.rfind('\n').map_or(0, |i| i + 1) + 1;
+    Some(format!("{}:{line}:{column}", unit.source_path.display()))
+}
+
+#[cfg(test)]
+#[path = "unit_tests/upgrade_compatibility_tests.rs"]
+mod upgrade_compatibility_tests;
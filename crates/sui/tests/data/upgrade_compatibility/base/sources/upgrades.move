@@ -0,0 +1,13 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module upgrades::upgrades {
+    public struct Point has copy, drop { x: u64, y: u64 }
+    public struct Kept has drop { v: u64 }
+    public struct Removed has drop {}
+
+    public fun add(a: u64, b: u64): u64 { a + b }
+    public fun gone() {}
+    public fun made_private() {}
+    fun helper(): u64 { 0 }
+}
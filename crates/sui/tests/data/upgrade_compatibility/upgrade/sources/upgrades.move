@@ -0,0 +1,12 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module upgrades::upgrades {
+    public struct Point has copy, drop, store { x: u64, y: u64 }
+    public struct Kept has drop { v: u64, w: u64 }
+
+    public fun add(a: u64, b: u64, c: u64): u64 { a + b + c }
+    public(package) fun made_private() {}
+    fun helper(): u64 { 1 }
+    public fun added() {}
+}
@@ -12,7 +12,9 @@ use crate::{
         *,
     },
     editions::Edition,
-    expansion, hlir, interface_generator, naming, parser,
+    expansion, hlir, interface_generator,
+    linters::LintConfig,
+    naming, parser,
     parser::{comments::*, *},
     shared::{
         CompilationEnv, Flags, IndexedPhysicalPackagePath, IndexedVfsPackagePath, NamedAddressMap,
@@ -58,6 +60,8 @@ pub struct Compiler {
     /// Predefined filter for compiler warnings.
     warning_filter: Option<WarningFilters>,
     known_warning_filters: Vec<(/* Prefix */ Option<Symbol>, Vec<WarningFilter>)>,
+    /// Per-lint severities, resolved against the known warning filters.
+    lint_config: LintConfig,
     package_configs: BTreeMap<Symbol, PackageConfig>,
     default_config: Option<PackageConfig>,
     /// Root path of the virtual file system.
@@ -189,6 +193,7 @@ impl Compiler {
             visitors: vec![],
             warning_filter: None,
             known_warning_filters: vec![],
+            lint_config: LintConfig::new(),
             package_configs,
             default_config: None,
             vfs_root,
@@ -284,6 +289,14 @@ impl Compiler {
         self
     }
 
+    /// Sets the severity of individual lints. Lints are referred to by their known filter name,
+    /// so they must be added through `add_custom_known_filters`.
+    pub fn set_lint_config(mut self, config: LintConfig) -> Self {
+        assert!(self.lint_config.is_empty());
+        self.lint_config = config;
+        self
+    }
+
     /// Sets the PackageConfig for files without a specified package
     pub fn set_default_config(mut self, config: PackageConfig) -> Self {
         assert!(self.default_config.is_none());
@@ -308,6 +321,7 @@ impl Compiler {
             visitors,
             warning_filter,
             known_warning_filters,
+            lint_config,
             package_configs,
             default_config,
             vfs_root,
@@ -366,6 +380,7 @@ impl Compiler {
         for (prefix, filters) in known_warning_filters {
             compilation_env.add_custom_known_filters(prefix, filters)?;
         }
+        compilation_env.apply_lint_config(&lint_config)?;
 
         let (mut source_text, pprog, comments) =
             parse_program(&mut compilation_env, maps, targets, deps)?;
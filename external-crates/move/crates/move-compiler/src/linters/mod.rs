@@ -1,6 +1,8 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
 use move_symbol_pool::Symbol;
 
 use crate::{command_line::compiler::Visitor, diagnostics::codes::WarningFilter};
@@ -15,6 +17,20 @@ pub enum LintLevel {
     All,
 }
 
+/// The level at which an individual lint is reported, e.g. as configured in a package manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintSeverity {
+    // Do not report the lint
+    Allow,
+    // Report the lint as a warning, enabling it if it is not run by default
+    Warn,
+    // Report the lint as an error, enabling it if it is not run by default
+    Error,
+}
+
+/// Severity for individual lints, keyed by the lint's filter name, e.g. `share_owned`
+pub type LintConfig = BTreeMap<Symbol, LintSeverity>;
+
 pub const ALLOW_ATTR_CATEGORY: &str = "lint";
 pub const LINT_WARNING_PREFIX: &str = "Lint ";
 
@@ -30,3 +46,45 @@ pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
         }
     }
 }
+
+impl LintSeverity {
+    pub const ALLOW: &'static str = "allow";
+    pub const WARN: &'static str = "warn";
+    pub const ERROR: &'static str = "error";
+
+    /// Returns true if a lint configured at this severity should be run
+    pub fn is_enabled(self) -> bool {
+        match self {
+            LintSeverity::Allow => false,
+            LintSeverity::Warn | LintSeverity::Error => true,
+        }
+    }
+}
+
+impl FromStr for LintSeverity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            Self::ALLOW => LintSeverity::Allow,
+            Self::WARN => LintSeverity::Warn,
+            Self::ERROR => LintSeverity::Error,
+            _ => anyhow::bail!(
+                "Unknown lint severity '{s}'. Expected one of: '{}', '{}', '{}'",
+                Self::ALLOW,
+                Self::WARN,
+                Self::ERROR
+            ),
+        })
+    }
+}
+
+impl fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintSeverity::Allow => write!(f, "{}", Self::ALLOW),
+            LintSeverity::Warn => write!(f, "{}", Self::WARN),
+            LintSeverity::Error => write!(f, "{}", Self::ERROR),
+        }
+    }
+}
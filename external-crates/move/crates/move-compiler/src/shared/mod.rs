@@ -14,6 +14,7 @@ use crate::{
         FeatureGate, Flavor,
     },
     expansion::ast as E,
+    linters::{LintConfig, LintSeverity, ALLOW_ATTR_CATEGORY},
     naming::ast as N,
     sui_mode,
     typing::visitor::{TypingVisitor, TypingVisitorObj},
@@ -230,6 +231,8 @@ pub struct CompilationEnv {
     known_filters: BTreeMap<FilterPrefix, BTreeMap<FilterName, BTreeSet<WarningFilter>>>,
     /// Maps a diagnostics ID to a known filter name.
     known_filter_names: BTreeMap<DiagnosticsID, (FilterPrefix, FilterName)>,
+    /// Maps a lint's diagnostics ID to the severity it was configured at, if any.
+    lint_severities: BTreeMap<DiagnosticsID, LintSeverity>,
     prim_definers:
         BTreeMap<crate::naming::ast::BuiltinTypeName_, crate::expansion::ast::ModuleIdent>,
     // TODO(tzakian): Remove the global counter and use this counter instead
@@ -349,6 +352,7 @@ impl CompilationEnv {
             default_config: default_config.unwrap_or_default(),
             known_filters,
             known_filter_names,
+            lint_severities: BTreeMap::new(),
             prim_definers: BTreeMap::new(),
             mapped_files: MappedFiles::empty(),
         }
@@ -394,7 +398,9 @@ impl CompilationEnv {
                     );
                     diag.add_note(help)
                 }
-                if self.flags.warnings_are_errors() {
+                if self.flags.warnings_are_errors()
+                    || self.lint_severities.get(&diag.info().id()) == Some(&LintSeverity::Error)
+                {
                     diag = diag.set_severity(Severity::NonblockingError)
                 }
             }
@@ -539,6 +545,58 @@ impl CompilationEnv {
         Ok(())
     }
 
+    /// Applies per-lint severities. Must be called after all known lint filters have been added,
+    /// as lints are resolved by their filter name, e.g. `share_owned` for `allow(lint(share_owned))`.
+    /// Allowed lints are filtered for the entire compilation, while lints configured as errors are
+    /// reported as non-blocking errors.
+    pub fn apply_lint_config(&mut self, config: &LintConfig) -> anyhow::Result<()> {
+        let lint_prefix: FilterPrefix = Some(ALLOW_ATTR_CATEGORY.into());
+        let mut allowed = WarningFilters::new_for_source();
+        let mut any_allowed = false;
+        for (name, severity) in config {
+            let filters = self.filter_from_str(lint_prefix, *name);
+            if filters.is_empty() {
+                anyhow::bail!(
+                    "Unknown lint '{name}'. Known lints: {}",
+                    self.known_filters
+                        .get(&lint_prefix)
+                        .map(|filters| filters
+                            .keys()
+                            .map(|n| format!("'{n}'"))
+                            .collect::<Vec<_>>()
+                            .join(", "))
+                        .unwrap_or_default()
+                );
+            }
+            match severity {
+                LintSeverity::Allow => {
+                    any_allowed = true;
+                    filters.into_iter().for_each(|filter| allowed.add(filter));
+                }
+                LintSeverity::Warn | LintSeverity::Error => {
+                    // the 'all' filter is not a code filter, so severities set through it apply to
+                    // every known lint code
+                    let ids: Vec<DiagnosticsID> = self
+                        .known_filter_names
+                        .iter()
+                        .filter(|(_, (attr, filter_name))| {
+                            *attr == lint_prefix
+                                && (filter_name == name || name.as_str() == FILTER_ALL)
+                        })
+                        .map(|(id, _)| *id)
+                        .collect();
+                    for id in ids {
+                        self.lint_severities.insert(id, *severity);
+                    }
+                }
+            }
+        }
+        if any_allowed {
+            self.add_warning_filter_scope(allowed);
+        }
+        Ok(())
+    }
+
     pub fn flags(&self) -> &Flags {
         &self.flags
     }
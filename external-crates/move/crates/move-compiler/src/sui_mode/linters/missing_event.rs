@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags entry functions that take a mutable reference to an object (a struct with
+//! the key ability), and thus potentially change its state, but never call sui::event::emit.
+//! Without events, off-chain services cannot easily observe these state changes. Only direct calls
+//! in the function body are considered, so events emitted by called functions are not taken into
+//! account.
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    parser::ast::{Ability_, FunctionName},
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    sui_mode::SUI_ADDR_NAME,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    LinterDiagCategory, EVENT_EMIT_FUN, EVENT_MOD_NAME, LINTER_DEFAULT_DIAG_CODE,
+    LINT_WARNING_PREFIX, SUI_PKG_NAME,
+};
+
+const MISSING_EVENT_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::MissingEvent as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "state-changing entry function without event emission",
+);
+
+pub struct MissingEventVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+    /// Set if the function being visited emits an event
+    emits_event: bool,
+}
+
+impl TypingVisitorConstructor for MissingEventVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context {
+            env,
+            emits_event: false,
+        }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // skips if true
+        mdef.attributes.is_test_or_test_only() || ident.value.address.is(SUI_ADDR_NAME)
+    }
+
+    fn visit_function_custom(
+        &mut self,
+        _module: ModuleIdent,
+        fname: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        if fdef.attributes.is_test_or_test_only() || fdef.entry.is_none() {
+            return true;
+        }
+        if !fdef
+            .signature
+            .parameters
+            .iter()
+            .any(|(_, _, t)| is_mut_object_ref(t))
+        {
+            return true;
+        }
+        let T::FunctionBody_::Defined(seq) = &mut fdef.body.value else {
+            return true;
+        };
+        self.emits_event = false;
+        self.visit_seq(seq);
+        if !self.emits_event {
+            report_missing_event(self.env, fname);
+        }
+        true
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        if let E::ModuleCall(call) = &exp.exp.value {
+            if call.module.value.is(SUI_PKG_NAME, EVENT_MOD_NAME)
+                && call.name.0.value.as_str() == EVENT_EMIT_FUN
+            {
+                self.emits_event = true;
+            }
+        }
+        // no need to look further once an event has been found
+        self.emits_event
+    }
+}
+
+fn is_mut_object_ref(sp!(_, t): &N::Type) -> bool {
+    let N::Type_::Ref(true, inner) = t else {
+        return false;
+    };
+    let N::Type_::Apply(Some(abilities), _, _) = &inner.value else {
+        return false;
+    };
+    abilities.has_ability_(Ability_::Key)
+}
+
+fn report_missing_event(env: &mut CompilationEnv, fname: FunctionName) {
+    let msg = format!(
+        "Entry function '{fname}' takes an object by mutable reference but does not emit an event \
+        with '{SUI_PKG_NAME}::{EVENT_MOD_NAME}::{EVENT_EMIT_FUN}'"
+    );
+    let mut d = diag!(MISSING_EVENT_DIAG, (fname.loc(), msg));
+    d.add_note("Events allow off-chain services to track changes to the object");
+    env.add_diag(d);
+}
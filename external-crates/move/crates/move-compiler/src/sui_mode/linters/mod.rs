@@ -7,8 +7,9 @@ use crate::{
     diagnostics::codes::WarningFilter,
     expansion::ast as E,
     hlir::ast::{BaseType_, SingleType, SingleType_},
-    linters::{LintLevel, ALLOW_ATTR_CATEGORY, LINT_WARNING_PREFIX},
+    linters::{LintConfig, LintLevel, ALLOW_ATTR_CATEGORY, LINT_WARNING_PREFIX},
     naming::ast as N,
+    shared::FILTER_ALL,
     typing::visitor::TypingVisitor,
 };
 use move_ir_types::location::Loc;
@@ -18,9 +19,13 @@ pub mod coin_field;
 pub mod collection_equality;
 pub mod custom_state_change;
 pub mod freeze_wrapped;
+pub mod missing_event;
 pub mod public_random;
+pub mod public_transfer_cap;
 pub mod self_transfer;
 pub mod share_owned;
+pub mod unbounded_vector;
+pub mod unused_tx_context;
 
 pub const SUI_PKG_NAME: &str = "sui";
 
@@ -61,6 +66,17 @@ pub const VEC_MAP_STRUCT_NAME: &str = "VecMap";
 pub const VEC_SET_MOD_NAME: &str = "vec_set";
 pub const VEC_SET_STRUCT_NAME: &str = "VecSet";
 
+pub const TX_CONTEXT_MOD_NAME: &str = "tx_context";
+pub const TX_CONTEXT_STRUCT_NAME: &str = "TxContext";
+
+pub const EVENT_MOD_NAME: &str = "event";
+pub const EVENT_EMIT_FUN: &str = "emit";
+
+pub const STD_PKG_NAME: &str = "std";
+pub const VECTOR_MOD_NAME: &str = "vector";
+pub const VECTOR_PUSH_BACK_FUN: &str = "push_back";
+pub const VECTOR_APPEND_FUN: &str = "append";
+
 pub const SHARE_OWNED_FILTER_NAME: &str = "share_owned";
 pub const SELF_TRANSFER_FILTER_NAME: &str = "self_transfer";
 pub const CUSTOM_STATE_CHANGE_FILTER_NAME: &str = "custom_state_change";
@@ -68,6 +84,10 @@ pub const COIN_FIELD_FILTER_NAME: &str = "coin_field";
 pub const FREEZE_WRAPPED_FILTER_NAME: &str = "freeze_wrapped";
 pub const COLLECTION_EQUALITY_FILTER_NAME: &str = "collection_equality";
 pub const PUBLIC_RANDOM_FILTER_NAME: &str = "public_random";
+pub const UNUSED_TX_CONTEXT_FILTER_NAME: &str = "unused_tx_context";
+pub const UNBOUNDED_VECTOR_FILTER_NAME: &str = "unbounded_vector";
+pub const MISSING_EVENT_FILTER_NAME: &str = "missing_event";
+pub const PUBLIC_TRANSFER_CAP_FILTER_NAME: &str = "public_transfer_cap";
 
 pub const RANDOM_MOD_NAME: &str = "random";
 pub const RANDOM_STRUCT_NAME: &str = "Random";
@@ -83,6 +103,10 @@ pub enum LinterDiagCategory {
     FreezeWrapped,
     CollectionEquality,
     PublicRandom,
+    UnusedTxContext,
+    UnboundedVector,
+    MissingEvent,
+    PublicTransferCap,
 }
 
 /// A default code for each linter category (as long as only one code per category is used, no other
@@ -134,27 +158,100 @@ pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
             LINTER_DEFAULT_DIAG_CODE,
             Some(PUBLIC_RANDOM_FILTER_NAME),
         ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::UnusedTxContext as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(UNUSED_TX_CONTEXT_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::UnboundedVector as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(UNBOUNDED_VECTOR_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::MissingEvent as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(MISSING_EVENT_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::PublicTransferCap as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(PUBLIC_TRANSFER_CAP_FILTER_NAME),
+        ),
     ];
     (Some(ALLOW_ATTR_CATEGORY.into()), filters)
 }
 
 pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
+    linter_visitors_with_config(level, &LintConfig::new())
+}
+
+/// Returns the linters to run for the given level. Linters that are not run by default are also
+/// returned if they are enabled by name (or through `all`) in the lint configuration.
+pub fn linter_visitors_with_config(level: LintLevel, config: &LintConfig) -> Vec<Visitor> {
+    let is_enabled = |name: &str| {
+        [name, FILTER_ALL].iter().any(|n| {
+            config
+                .get(&Symbol::from(*n))
+                .is_some_and(|severity| severity.is_enabled())
+        })
+    };
     match level {
         LintLevel::None => vec![],
-        LintLevel::Default | LintLevel::All => {
-            vec![
-                share_owned::ShareOwnedVerifier.visitor(),
-                self_transfer::SelfTransferVerifier.visitor(),
-                custom_state_change::CustomStateChangeVerifier.visitor(),
-                coin_field::CoinFieldVisitor.visitor(),
-                freeze_wrapped::FreezeWrappedVisitor.visitor(),
-                collection_equality::CollectionEqualityVisitor.visitor(),
-                public_random::PublicRandomVisitor.visitor(),
-            ]
-        }
+        LintLevel::Default => default_linters()
+            .into_iter()
+            .chain(
+                extra_linters()
+                    .into_iter()
+                    .filter(|(name, _)| is_enabled(name))
+                    .map(|(_, visitor)| visitor),
+            )
+            .collect(),
+        LintLevel::All => default_linters()
+            .into_iter()
+            .chain(extra_linters().into_iter().map(|(_, visitor)| visitor))
+            .collect(),
     }
 }
 
+fn default_linters() -> Vec<Visitor> {
+    vec![
+        share_owned::ShareOwnedVerifier.visitor(),
+        self_transfer::SelfTransferVerifier.visitor(),
+        custom_state_change::CustomStateChangeVerifier.visitor(),
+        coin_field::CoinFieldVisitor.visitor(),
+        freeze_wrapped::FreezeWrappedVisitor.visitor(),
+        collection_equality::CollectionEqualityVisitor.visitor(),
+        public_random::PublicRandomVisitor.visitor(),
+    ]
+}
+
+/// Linters that are only run with `LintLevel::All` or when enabled by name
+fn extra_linters() -> Vec<(&'static str, Visitor)> {
+    vec![
+        (
+            UNUSED_TX_CONTEXT_FILTER_NAME,
+            unused_tx_context::UnusedTxContextVisitor.visitor(),
+        ),
+        (
+            UNBOUNDED_VECTOR_FILTER_NAME,
+            unbounded_vector::UnboundedVectorVisitor.visitor(),
+        ),
+        (
+            MISSING_EVENT_FILTER_NAME,
+            missing_event::MissingEventVisitor.visitor(),
+        ),
+        (
+            PUBLIC_TRANSFER_CAP_FILTER_NAME,
+            public_transfer_cap::PublicTransferCapVisitor.visitor(),
+        ),
+    ]
+}
+
 pub fn base_type(t: &N::Type) -> Option<&N::Type> {
     use N::Type_ as T;
     match &t.value {
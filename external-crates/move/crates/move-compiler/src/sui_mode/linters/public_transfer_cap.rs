@@ -0,0 +1,103 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags calls to sui::transfer::public_transfer on capabilities, i.e. objects whose
+//! type name ends with `Cap` by convention. A capability that can be transferred with
+//! public_transfer must have the store ability, which means that whoever holds it can pass it on
+//! (or wrap it) freely, which is rarely intended for privileged objects.
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    sui_mode::SUI_ADDR_NAME,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX, PUBLIC_TRANSFER_FUN,
+    SUI_PKG_NAME, TRANSFER_FUN, TRANSFER_MOD_NAME,
+};
+
+const PUBLIC_TRANSFER_CAP_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::PublicTransferCap as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "public transfer of a capability",
+);
+
+/// Suffix used by convention for capability type names, e.g. `AdminCap` or `TreasuryCap`
+const CAPABILITY_SUFFIX: &str = "Cap";
+
+pub struct PublicTransferCapVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for PublicTransferCapVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // skips if true
+        mdef.attributes.is_test_or_test_only() || ident.value.address.is(SUI_ADDR_NAME)
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        let E::ModuleCall(call) = &exp.exp.value else {
+            return false;
+        };
+        if !call.module.value.is(SUI_PKG_NAME, TRANSFER_MOD_NAME)
+            || call.name.0.value.as_str() != PUBLIC_TRANSFER_FUN
+        {
+            return false;
+        }
+        let Some(sp!(_, N::Type_::Apply(_, sp!(_, tname), _))) = call.type_arguments.first() else {
+            return false;
+        };
+        let N::TypeName_::ModuleType(_, sname) = tname else {
+            return false;
+        };
+        if sname.value().as_str().ends_with(CAPABILITY_SUFFIX) {
+            let msg = format!(
+                "Capability of type '{tname}' is transferred with \
+                '{SUI_PKG_NAME}::{TRANSFER_MOD_NAME}::{PUBLIC_TRANSFER_FUN}'"
+            );
+            let mut d = diag!(PUBLIC_TRANSFER_CAP_DIAG, (exp.exp.loc, msg));
+            d.add_note(format!(
+                "Capabilities with the 'store' ability can be freely transferred by their owner. \
+                Consider removing 'store' and using '{SUI_PKG_NAME}::{TRANSFER_MOD_NAME}::\
+                {TRANSFER_FUN}' instead"
+            ));
+            self.env.add_diag(d);
+        }
+        false
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags growing a vector stored in a field of an object (a struct with the key
+//! ability) via std::vector::push_back or std::vector::append. If the object is shared, anyone can
+//! grow the vector without bound, eventually making the object too large to be used in a
+//! transaction. Dynamic fields or table-like collections (e.g. sui::table::Table) are better
+//! suited for unbounded data.
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    parser::ast::Ability_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    sui_mode::SUI_ADDR_NAME,
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    base_type, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX, STD_PKG_NAME,
+    VECTOR_APPEND_FUN, VECTOR_MOD_NAME, VECTOR_PUSH_BACK_FUN,
+};
+
+const UNBOUNDED_VECTOR_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::UnboundedVector as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "possibly unbounded vector growth in an object",
+);
+
+const VECTOR_GROWTH_FUNCTIONS: &[(&str, &str, &str)] = &[
+    (STD_PKG_NAME, VECTOR_MOD_NAME, VECTOR_PUSH_BACK_FUN),
+    (STD_PKG_NAME, VECTOR_MOD_NAME, VECTOR_APPEND_FUN),
+];
+
+pub struct UnboundedVectorVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for UnboundedVectorVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // skips if true
+        mdef.attributes.is_test_or_test_only() || ident.value.address.is(SUI_ADDR_NAME)
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        let E::ModuleCall(call) = &exp.exp.value else {
+            return false;
+        };
+        let Some((addr, module, fun)) =
+            VECTOR_GROWTH_FUNCTIONS.iter().find(|(addr, module, fun)| {
+                call.module.value.is(*addr, *module) && call.name.0.value.as_str() == *fun
+            })
+        else {
+            return false;
+        };
+        let Some(first_arg) = first_argument(&call.arguments) else {
+            return false;
+        };
+        let E::Borrow(true, obj, field) = &first_arg.exp.value else {
+            return false;
+        };
+        let Some(sp!(_, N::Type_::Apply(abilities, sp!(_, tname), _))) = base_type(&obj.ty) else {
+            return false;
+        };
+        let is_object = abilities
+            .as_ref()
+            .is_some_and(|abilities| abilities.has_ability_(Ability_::Key));
+        if is_object {
+            let msg = format!(
+                "Calling '{addr}::{module}::{fun}' grows the vector in field '{field}' of object \
+                type '{tname}' without a bound",
+            );
+            let mut d = diag!(UNBOUNDED_VECTOR_DIAG, (exp.exp.loc, msg));
+            d.add_note(
+                "If the object is shared, anyone can grow the vector until the object is too \
+                large to be used in a transaction",
+            );
+            d.add_note("Consider bounding the length of the vector or using dynamic fields");
+            self.env.add_diag(d);
+        }
+        false
+    }
+}
+
+fn first_argument(args: &T::Exp) -> Option<&T::Exp> {
+    use T::UnannotatedExp_ as E;
+    match &args.exp.value {
+        E::ExpList(items) => match items.first()? {
+            T::ExpListItem::Single(e, _) => Some(e),
+            T::ExpListItem::Splat(_, _, _) => None,
+        },
+        _ => Some(args),
+    }
+}
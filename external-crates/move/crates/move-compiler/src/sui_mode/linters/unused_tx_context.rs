@@ -0,0 +1,134 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags function parameters of type sui::tx_context::TxContext (or references to
+//! it) that are never used in the function body. Unlike the unused variable warning, parameters
+//! prefixed with an underscore are flagged as well, since an unused context needlessly requires
+//! callers to provide one.
+
+use std::collections::BTreeMap;
+
+use move_ir_types::location::Loc;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    naming::ast as N,
+    parser::ast::FunctionName,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    sui_mode::{INIT_FUNCTION_NAME, SUI_ADDR_NAME},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    base_type, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX, SUI_PKG_NAME,
+    TX_CONTEXT_MOD_NAME, TX_CONTEXT_STRUCT_NAME,
+};
+
+const UNUSED_TX_CONTEXT_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::UnusedTxContext as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "unused 'TxContext' parameter",
+);
+
+pub struct UnusedTxContextVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+    /// TxContext parameters of the function being visited that have not been used (yet)
+    unused_params: BTreeMap<N::Var_, Loc>,
+}
+
+impl TypingVisitorConstructor for UnusedTxContextVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context {
+            env,
+            unused_params: BTreeMap::new(),
+        }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+
+    fn visit_module_custom(&mut self, ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // skips if true
+        mdef.attributes.is_test_or_test_only() || ident.value.address.is(SUI_ADDR_NAME)
+    }
+
+    fn visit_function_custom(
+        &mut self,
+        _module: ModuleIdent,
+        fname: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        // `init` must take a TxContext, regardless of whether it is used
+        if fdef.attributes.is_test_or_test_only() || fname.0.value == INIT_FUNCTION_NAME {
+            return true;
+        }
+        self.unused_params = fdef
+            .signature
+            .parameters
+            .iter()
+            .filter(|(_, _, t)| is_tx_context(t))
+            .map(|(_, v, _)| (v.value, v.loc))
+            .collect();
+        if self.unused_params.is_empty() {
+            return true;
+        }
+        let T::FunctionBody_::Defined(seq) = &mut fdef.body.value else {
+            return true;
+        };
+        self.visit_seq(seq);
+        for (var, loc) in std::mem::take(&mut self.unused_params) {
+            let msg = format!(
+                "Parameter '{}' of type '{}::{}::{}' is never used in function '{fname}'",
+                var.name, SUI_PKG_NAME, TX_CONTEXT_MOD_NAME, TX_CONTEXT_STRUCT_NAME
+            );
+            let mut d = diag!(UNUSED_TX_CONTEXT_DIAG, (loc, msg));
+            d.add_note(
+                "Consider removing the parameter, so callers are not required to provide it",
+            );
+            self.env.add_diag(d);
+        }
+        true
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        match &exp.exp.value {
+            E::Move { var, .. } | E::Copy { var, .. } | E::Use(var) | E::BorrowLocal(_, var) => {
+                self.unused_params.remove(&var.value);
+            }
+            _ => (),
+        }
+        false
+    }
+}
+
+fn is_tx_context(t: &N::Type) -> bool {
+    base_type(t).is_some_and(|bt| {
+        bt.value
+            .is(SUI_PKG_NAME, TX_CONTEXT_MOD_NAME, TX_CONTEXT_STRUCT_NAME)
+    })
+}
@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use sui::event;
+    use sui::object::UID;
+
+    struct Counter has key {
+        id: UID,
+        value: u64,
+    }
+
+    struct Incremented has copy, drop {
+        value: u64,
+    }
+
+    entry fun increment(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+
+    entry fun increment_with_event(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+        event::emit(Incremented { value: counter.value });
+    }
+
+    entry fun read_only(counter: &Counter): u64 {
+        counter.value
+    }
+
+    public fun not_entry(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+
+    #[allow(lint(missing_event))]
+    entry fun increment_suppressed(counter: &mut Counter) {
+        counter.value = counter.value + 1;
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
+
+module sui::event {
+    public fun emit<T: copy + drop>(_event: T) {
+        abort 0
+    }
+}
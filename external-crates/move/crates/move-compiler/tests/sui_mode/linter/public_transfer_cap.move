@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use sui::object::UID;
+    use sui::transfer;
+
+    struct AdminCap has key, store {
+        id: UID,
+    }
+
+    struct Item has key, store {
+        id: UID,
+    }
+
+    public fun give_cap(cap: AdminCap, recipient: address) {
+        transfer::public_transfer(cap, recipient)
+    }
+
+    public fun give_item(item: Item, recipient: address) {
+        transfer::public_transfer(item, recipient)
+    }
+
+    #[allow(lint(public_transfer_cap))]
+    public fun give_cap_suppressed(cap: AdminCap, recipient: address) {
+        transfer::public_transfer(cap, recipient)
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
+
+module sui::transfer {
+    public fun public_transfer<T: key + store>(_: T, _: address) {
+        abort 0
+    }
+}
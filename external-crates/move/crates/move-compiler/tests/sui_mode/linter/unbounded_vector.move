@@ -0,0 +1,39 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use sui::object::UID;
+    use std::vector;
+
+    struct Registry has key {
+        id: UID,
+        entries: vector<address>,
+    }
+
+    struct Local has drop {
+        entries: vector<address>,
+    }
+
+    public fun register(registry: &mut Registry, entry: address) {
+        vector::push_back(&mut registry.entries, entry);
+    }
+
+    public fun register_all(registry: &mut Registry, entries: vector<address>) {
+        vector::append(&mut registry.entries, entries);
+    }
+
+    public fun local_ok(local: &mut Local, entry: address) {
+        vector::push_back(&mut local.entries, entry);
+    }
+
+    #[allow(lint(unbounded_vector))]
+    public fun register_suppressed(registry: &mut Registry, entry: address) {
+        vector::push_back(&mut registry.entries, entry);
+    }
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module a::test {
+    use sui::tx_context::{Self, TxContext};
+
+    public fun unused(_ctx: &mut TxContext): u64 {
+        0
+    }
+
+    public fun unused_immutable(_x: u64, _ctx: &TxContext) {}
+
+    public fun used(ctx: &TxContext): address {
+        tx_context::sender(ctx)
+    }
+
+    fun init(_ctx: &mut TxContext) {}
+
+    #[allow(lint(unused_tx_context))]
+    public fun unused_suppressed(_ctx: &mut TxContext) {}
+}
+
+module sui::tx_context {
+    struct TxContext has drop {}
+    public fun sender(_: &TxContext): address {
+        @0
+    }
+}
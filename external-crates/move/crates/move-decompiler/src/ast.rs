@@ -0,0 +1,363 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A small source-level AST that decompiled function bodies are built into before printing.
+
+use std::fmt::{self, Write};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Exp {
+    /// A literal or a named constant, printed verbatim.
+    Value(String),
+    /// A local, parameter or stack temporary.
+    Var(String),
+    /// `&x` or `&mut x`
+    Borrow {
+        mutable: bool,
+        exp: Box<Exp>,
+    },
+    /// `&e.f` or `&mut e.f`, where `e` is a reference to a struct.
+    Field {
+        mutable: bool,
+        base: Box<Exp>,
+        field: String,
+    },
+    /// `*e`
+    Deref(Box<Exp>),
+    /// `freeze(e)`
+    Freeze(Box<Exp>),
+    Call {
+        function: String,
+        type_args: Vec<String>,
+        args: Vec<Exp>,
+    },
+    Pack {
+        name: String,
+        fields: Vec<(String, Exp)>,
+    },
+    Vector {
+        ty: String,
+        elems: Vec<Exp>,
+    },
+    Not(Box<Exp>),
+    Binary {
+        op: &'static str,
+        lhs: Box<Exp>,
+        rhs: Box<Exp>,
+    },
+    Cast {
+        exp: Box<Exp>,
+        ty: &'static str,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Stmt {
+    /// `x = e;` or `(x, _, y) = e;`
+    Assign(Vec<String>, Exp),
+    /// `S { f: x, g: y } = e;`
+    Unpack {
+        name: String,
+        fields: Vec<(String, String)>,
+        exp: Exp,
+    },
+    /// `*r = e;`
+    Mutate(Exp, Exp),
+    /// `e;`
+    Exp(Exp),
+    Return(Vec<Exp>),
+    Abort(Exp),
+    Break(Option<String>),
+    Continue(Option<String>),
+    If {
+        cond: Exp,
+        then: Vec<Stmt>,
+        else_: Vec<Stmt>,
+    },
+    Loop {
+        label: Option<String>,
+        body: Vec<Stmt>,
+    },
+    While {
+        label: Option<String>,
+        cond: Exp,
+        body: Vec<Stmt>,
+    },
+}
+
+impl Exp {
+    /// Whether evaluating the expression can have an observable effect (other than aborting), or
+    /// depends on state that statements can change. Such expressions must not be reordered with
+    /// respect to statements.
+    pub fn is_pure(&self) -> bool {
+        match self {
+            Exp::Value(_) | Exp::Var(_) => true,
+            Exp::Call { .. } | Exp::Deref(_) => false,
+            Exp::Borrow { exp, .. } | Exp::Freeze(exp) | Exp::Not(exp) | Exp::Cast { exp, .. } => {
+                exp.is_pure()
+            }
+            Exp::Field { base, .. } => base.is_pure(),
+            Exp::Pack { fields, .. } => fields.iter().all(|(_, e)| e.is_pure()),
+            Exp::Vector { elems, .. } => elems.iter().all(Exp::is_pure),
+            Exp::Binary { lhs, rhs, .. } => lhs.is_pure() && rhs.is_pure(),
+        }
+    }
+
+    /// Whether the expression reads variable `var`.
+    pub fn mentions(&self, var: &str) -> bool {
+        match self {
+            Exp::Value(_) => false,
+            Exp::Var(v) => v == var,
+            Exp::Borrow { exp, .. }
+            | Exp::Deref(exp)
+            | Exp::Freeze(exp)
+            | Exp::Not(exp)
+            | Exp::Cast { exp, .. } => exp.mentions(var),
+            Exp::Field { base, .. } => base.mentions(var),
+            Exp::Call { args, .. } | Exp::Vector { elems: args, .. } => {
+                args.iter().any(|e| e.mentions(var))
+            }
+            Exp::Pack { fields, .. } => fields.iter().any(|(_, e)| e.mentions(var)),
+            Exp::Binary { lhs, rhs, .. } => lhs.mentions(var) || rhs.mentions(var),
+        }
+    }
+
+    /// The logical negation of the expression, simplifying where possible.
+    pub fn negate(self) -> Exp {
+        match self {
+            Exp::Not(exp) => *exp,
+            Exp::Value(v) if v == "true" => Exp::Value("false".to_string()),
+            Exp::Value(v) if v == "false" => Exp::Value("true".to_string()),
+            Exp::Binary { op, lhs, rhs } if negated_comparison(op).is_some() => Exp::Binary {
+                op: negated_comparison(op).unwrap(),
+                lhs,
+                rhs,
+            },
+            exp => Exp::Not(Box::new(exp)),
+        }
+    }
+}
+
+fn negated_comparison(op: &str) -> Option<&'static str> {
+    Some(match op {
+        "==" => "!=",
+        "!=" => "==",
+        "<" => ">=",
+        ">=" => "<",
+        ">" => "<=",
+        "<=" => ">",
+        _ => return None,
+    })
+}
+
+impl fmt::Display for Exp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exp::Value(v) | Exp::Var(v) => write!(f, "{v}"),
+            Exp::Borrow { mutable, exp } => write!(f, "{}{}", borrow(*mutable), Operand(exp)),
+            Exp::Field { mutable, .. } => write!(f, "{}{}", borrow(*mutable), Path(self)),
+            Exp::Deref(exp) => match exp.as_ref() {
+                // Reading a field or a local through a borrow of it is just a copy.
+                Exp::Field { .. } => write!(f, "{}", Path(exp)),
+                Exp::Borrow { exp, .. } => write!(f, "{}", Operand(exp)),
+                exp => write!(f, "*{}", Operand(exp)),
+            },
+            Exp::Freeze(exp) => match exp.as_ref() {
+                Exp::Field { .. } | Exp::Borrow { .. } => write!(f, "&{}", Path(exp)),
+                exp => write!(f, "freeze({exp})"),
+            },
+            Exp::Call {
+                function,
+                type_args,
+                args,
+            } => {
+                write!(f, "{function}{}(", TypeArgs(type_args))?;
+                comma_separated(f, args)?;
+                write!(f, ")")
+            }
+            Exp::Pack { name, fields } => {
+                if fields.is_empty() {
+                    return write!(f, "{name} {{}}");
+                }
+                write!(f, "{name} {{ ")?;
+                for (i, (field, exp)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{field}: {exp}")?;
+                }
+                write!(f, " }}")
+            }
+            Exp::Vector { ty, elems } => {
+                write!(f, "vector<{ty}>[")?;
+                comma_separated(f, elems)?;
+                write!(f, "]")
+            }
+            Exp::Not(exp) => write!(f, "!{}", Operand(exp)),
+            Exp::Binary { op, lhs, rhs } => write!(f, "{} {op} {}", Operand(lhs), Operand(rhs)),
+            Exp::Cast { exp, ty } => write!(f, "({} as {ty})", Operand(exp)),
+        }
+    }
+}
+
+fn borrow(mutable: bool) -> &'static str {
+    if mutable {
+        "&mut "
+    } else {
+        "&"
+    }
+}
+
+/// Prints an expression in operand position, parenthesizing it unless it is atomic.
+struct Operand<'a>(&'a Exp);
+
+impl fmt::Display for Operand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            exp @ (Exp::Value(_)
+            | Exp::Var(_)
+            | Exp::Call { .. }
+            | Exp::Pack { .. }
+            | Exp::Vector { .. }
+            | Exp::Cast { .. }) => write!(f, "{exp}"),
+            exp @ Exp::Deref(inner)
+                if matches!(inner.as_ref(), Exp::Borrow { .. } | Exp::Field { .. }) =>
+            {
+                write!(f, "{exp}")
+            }
+            exp => write!(f, "({exp})"),
+        }
+    }
+}
+
+/// Prints the place a (field) borrow refers to, e.g. `x.f.g` for `&(&x).f.g`.
+struct Path<'a>(&'a Exp);
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Exp::Field { base, field, .. } => write!(f, "{}.{field}", Path(base)),
+            Exp::Borrow { exp, .. } => write!(f, "{}", Operand(exp)),
+            Exp::Freeze(exp) => write!(f, "{}", Path(exp)),
+            exp => write!(f, "{}", Operand(exp)),
+        }
+    }
+}
+
+struct TypeArgs<'a>(&'a [String]);
+
+impl fmt::Display for TypeArgs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.0.is_empty() {
+            write!(f, "<{}>", self.0.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn comma_separated(f: &mut fmt::Formatter<'_>, exps: &[Exp]) -> fmt::Result {
+    for (i, exp) in exps.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{exp}")?;
+    }
+    Ok(())
+}
+
+const INDENT: &str = "    ";
+
+/// Print `stmts` as a block body, indented by `depth` levels.
+pub(crate) fn write_stmts(out: &mut String, stmts: &[Stmt], depth: usize) -> fmt::Result {
+    for stmt in stmts {
+        write_stmt(out, stmt, depth)?;
+    }
+    Ok(())
+}
+
+fn write_stmt(out: &mut String, stmt: &Stmt, depth: usize) -> fmt::Result {
+    let indent = INDENT.repeat(depth);
+    match stmt {
+        Stmt::Assign(lhs, exp) if lhs.len() == 1 => writeln!(out, "{indent}{} = {exp};", lhs[0]),
+        Stmt::Assign(lhs, exp) => writeln!(out, "{indent}({}) = {exp};", lhs.join(", ")),
+        Stmt::Unpack { name, fields, exp } => {
+            let fields = fields
+                .iter()
+                .map(|(field, var)| format!("{field}: {var}"))
+                .collect::<Vec<_>>();
+            if fields.is_empty() {
+                writeln!(out, "{indent}{name} {{}} = {exp};")
+            } else {
+                writeln!(out, "{indent}{name} {{ {} }} = {exp};", fields.join(", "))
+            }
+        }
+        Stmt::Mutate(reference, exp) => writeln!(out, "{indent}*{} = {exp};", Operand(reference)),
+        Stmt::Exp(exp) => writeln!(out, "{indent}{exp};"),
+        Stmt::Return(exps) => match exps.as_slice() {
+            [] => writeln!(out, "{indent}return"),
+            [exp] => writeln!(out, "{indent}return {exp}"),
+            exps => {
+                write!(out, "{indent}return (")?;
+                for (i, exp) in exps.iter().enumerate() {
+                    if i > 0 {
+                        write!(out, ", ")?;
+                    }
+                    write!(out, "{exp}")?;
+                }
+                writeln!(out, ")")
+            }
+        },
+        Stmt::Abort(exp) => writeln!(out, "{indent}abort {}", Operand(exp)),
+        Stmt::Break(label) => writeln!(out, "{indent}break{}", Label(label)),
+        Stmt::Continue(label) => writeln!(out, "{indent}continue{}", Label(label)),
+        Stmt::If { cond, then, else_ } => {
+            writeln!(out, "{indent}if ({cond}) {{")?;
+            write_stmts(out, then, depth + 1)?;
+            if !else_.is_empty() {
+                // Print `else if` chains without nesting them further.
+                if let [nested @ Stmt::If { .. }] = else_.as_slice() {
+                    write!(out, "{indent}}} else ")?;
+                    let mut nested_out = String::new();
+                    write_stmt(&mut nested_out, nested, depth)?;
+                    out.push_str(nested_out.trim_start());
+                    return Ok(());
+                }
+                writeln!(out, "{indent}}} else {{")?;
+                write_stmts(out, else_, depth + 1)?;
+            }
+            writeln!(out, "{indent}}};")
+        }
+        Stmt::Loop { label, body } => {
+            writeln!(out, "{indent}{}loop {{", LabelDecl(label))?;
+            write_stmts(out, body, depth + 1)?;
+            writeln!(out, "{indent}}};")
+        }
+        Stmt::While { label, cond, body } => {
+            writeln!(out, "{indent}{}while ({cond}) {{", LabelDecl(label))?;
+            write_stmts(out, body, depth + 1)?;
+            writeln!(out, "{indent}}};")
+        }
+    }
+}
+
+struct Label<'a>(&'a Option<String>);
+
+impl fmt::Display for Label<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(label) => write!(f, " '{label}"),
+            None => Ok(()),
+        }
+    }
+}
+
+struct LabelDecl<'a>(&'a Option<String>);
+
+impl fmt::Display for LabelDecl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(label) => write!(f, "'{label}: "),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decompilation of a `CompiledModule` back into Move source (2024 edition syntax).
+//!
+//! Names of declarations and fields are preserved by the bytecode, but names of parameters,
+//! locals and constants are not: parameters are named `arg0`, `arg1`, ..., other locals `l0`,
+//! `l1`, ..., and constants `CONST_0`, `CONST_1`, .... Function bodies whose control flow cannot
+//! be structured are replaced by `abort 0`, preceded by their bytecode in a comment.
+
+use std::fmt::Write;
+
+use anyhow::Result;
+use move_binary_format::{
+    file_format::{
+        AbilitySet, Bytecode, ConstantPoolIndex, FunctionDefinition, StructFieldInformation,
+        StructTypeParameter, Visibility,
+    },
+    CompiledModule,
+};
+use move_core_types::runtime_value::MoveValue;
+
+use crate::{
+    ast::write_stmts,
+    names::{self, Names},
+    structure::structure,
+    translate::translate,
+};
+
+pub struct Decompiler<'a> {
+    names: Names<'a>,
+}
+
+impl<'a> Decompiler<'a> {
+    pub fn new(module: &'a CompiledModule) -> Self {
+        Self {
+            names: Names::new(module),
+        }
+    }
+
+    pub fn decompile(&self) -> Result<String> {
+        let module = self.names.module;
+        let mut out = String::new();
+        writeln!(
+            out,
+            "module {}::{} {{",
+            module.address().to_hex_literal(),
+            module.name()
+        )?;
+
+        let mut sections = vec![];
+
+        let constants: Vec<_> = module
+            .constant_pool()
+            .iter()
+            .enumerate()
+            .map(|(i, constant)| {
+                let value = constant
+                    .deserialize_constant()
+                    .and_then(|v| constant_value(&v))
+                    .unwrap_or_else(|| format!("/* 0x{} */", hex::encode(&constant.data)));
+                format!(
+                    "    const {}: {} = {value};\n",
+                    names::constant(ConstantPoolIndex(i as u16)),
+                    self.names.type_(&constant.type_),
+                )
+            })
+            .collect();
+        if !constants.is_empty() {
+            sections.push(constants.concat());
+        }
+
+        for def in module.struct_defs() {
+            let handle = module.struct_handle_at(def.struct_handle);
+            let mut decl = String::new();
+            let name = module.identifier_at(handle.name);
+            let type_params = struct_type_parameters(&handle.type_parameters);
+            let abilities = if handle.abilities == AbilitySet::EMPTY {
+                String::new()
+            } else {
+                format!(" has {}", names::abilities(handle.abilities, ", "))
+            };
+            match &def.field_information {
+                StructFieldInformation::Native => {
+                    writeln!(decl, "    native struct {name}{type_params}{abilities};")?
+                }
+                StructFieldInformation::Declared(fields) => {
+                    writeln!(decl, "    public struct {name}{type_params}{abilities} {{")?;
+                    for field in fields {
+                        writeln!(
+                            decl,
+                            "        {}: {},",
+                            module.identifier_at(field.name),
+                            self.names.type_(&field.signature.0)
+                        )?;
+                    }
+                    writeln!(decl, "    }}")?;
+                }
+            }
+            sections.push(decl);
+        }
+
+        for def in module.function_defs() {
+            sections.push(self.function(def)?);
+        }
+
+        out.push_str(&sections.join("\n"));
+        writeln!(out, "}}")?;
+        Ok(out)
+    }
+
+    fn function(&self, def: &FunctionDefinition) -> Result<String> {
+        let module = self.names.module;
+        let handle = module.function_handle_at(def.function);
+        let params = &module.signature_at(handle.parameters).0;
+        let returns = &module.signature_at(handle.return_).0;
+
+        let mut out = String::from("    ");
+        match def.visibility {
+            Visibility::Private => (),
+            Visibility::Public => out.push_str("public "),
+            Visibility::Friend => out.push_str("public(package) "),
+        }
+        if def.is_entry {
+            out.push_str("entry ");
+        }
+        if def.code.is_none() {
+            out.push_str("native ");
+        }
+        write!(out, "fun {}", module.identifier_at(handle.name))?;
+        if !handle.type_parameters.is_empty() {
+            let type_params: Vec<_> = handle
+                .type_parameters
+                .iter()
+                .enumerate()
+                .map(|(i, constraints)| {
+                    type_parameter_decl(names::type_parameter(i as u16), *constraints)
+                })
+                .collect();
+            write!(out, "<{}>", type_params.join(", "))?;
+        }
+
+        let local_names: Vec<_> = match &def.code {
+            Some(code) => (0..params.len() + module.signature_at(code.locals).len())
+                .map(|i| local_name(i, params.len()))
+                .collect(),
+            None => (0..params.len()).map(|i| local_name(i, 0)).collect(),
+        };
+        let mutated = match &def.code {
+            Some(code) => mutated_locals(&code.code, local_names.len(), params.len()),
+            None => vec![false; params.len()],
+        };
+
+        let param_decls: Vec<_> = params
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                let mut_ = if mutated[i] { "mut " } else { "" };
+                format!("{mut_}{}: {}", local_names[i], self.names.type_(ty))
+            })
+            .collect();
+        write!(out, "({})", param_decls.join(", "))?;
+        match returns.as_slice() {
+            [] => (),
+            [ty] => write!(out, ": {}", self.names.type_(ty))?,
+            tys => {
+                let tys: Vec<_> = tys.iter().map(|t| self.names.type_(t)).collect();
+                write!(out, ": ({})", tys.join(", "))?
+            }
+        }
+
+        let Some(code) = &def.code else {
+            writeln!(out, ";")?;
+            return Ok(out);
+        };
+        writeln!(out, " {{")?;
+
+        let body = translate(&self.names, code, &local_names, returns.len())
+            .and_then(|body| Ok((body.temps, structure(body.blocks)?)));
+        match body {
+            Ok((temps, stmts)) => {
+                let locals = &module.signature_at(code.locals).0;
+                for (i, ty) in locals.iter().enumerate() {
+                    let idx = params.len() + i;
+                    let mut_ = if mutated[idx] { "mut " } else { "" };
+                    writeln!(
+                        out,
+                        "        let {mut_}{}: {};",
+                        local_names[idx],
+                        self.names.type_(ty)
+                    )?;
+                }
+                for temp in temps {
+                    writeln!(out, "        let mut {temp};")?;
+                }
+                write_stmts(&mut out, &stmts, 2)?;
+            }
+            Err(e) => {
+                writeln!(out, "        // Failed to decompile function body: {e}")?;
+                for (offset, instr) in code.code.iter().enumerate() {
+                    writeln!(out, "        // {offset}: {instr:?}")?;
+                }
+                writeln!(out, "        abort 0")?;
+            }
+        }
+        writeln!(out, "    }}")?;
+        Ok(out)
+    }
+}
+
+fn local_name(idx: usize, params: usize) -> String {
+    if idx < params {
+        format!("arg{idx}")
+    } else {
+        format!("l{}", idx - params)
+    }
+}
+
+/// Which locals need to be declared `mut`: parameters that are assigned to or mutably borrowed,
+/// and locals that are assigned to more than once or mutably borrowed.
+fn mutated_locals(code: &[Bytecode], locals: usize, params: usize) -> Vec<bool> {
+    let mut stores = vec![0; locals];
+    let mut mutated = vec![false; locals];
+    for instr in code {
+        match instr {
+            Bytecode::StLoc(idx) => stores[*idx as usize] += 1,
+            Bytecode::MutBorrowLoc(idx) => mutated[*idx as usize] = true,
+            _ => (),
+        }
+    }
+    for (idx, stores) in stores.into_iter().enumerate() {
+        let initialized = if idx < params { 1 } else { 0 };
+        mutated[idx] |= stores + initialized > 1;
+    }
+    mutated
+}
+
+fn type_parameter_decl(name: String, constraints: AbilitySet) -> String {
+    if constraints == AbilitySet::EMPTY {
+        name
+    } else {
+        format!("{name}: {}", names::abilities(constraints, " + "))
+    }
+}
+
+fn struct_type_parameters(params: &[StructTypeParameter]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let params: Vec<_> = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let decl = type_parameter_decl(names::type_parameter(i as u16), param.constraints);
+            if param.is_phantom {
+                format!("phantom {decl}")
+            } else {
+                decl
+            }
+        })
+        .collect();
+    format!("<{}>", params.join(", "))
+}
+
+/// The source representation of a constant, or `None` if it cannot be expressed as a literal.
+fn constant_value(value: &MoveValue) -> Option<String> {
+    Some(match value {
+        MoveValue::Bool(b) => b.to_string(),
+        MoveValue::U8(n) => n.to_string(),
+        MoveValue::U16(n) => n.to_string(),
+        MoveValue::U32(n) => n.to_string(),
+        MoveValue::U64(n) => n.to_string(),
+        MoveValue::U128(n) => n.to_string(),
+        MoveValue::U256(n) => n.to_string(),
+        MoveValue::Address(a) => format!("@{}", a.to_hex_literal()),
+        MoveValue::Vector(elems) => {
+            let bytes: Option<Vec<u8>> = elems
+                .iter()
+                .map(|e| match e {
+                    MoveValue::U8(b) => Some(*b),
+                    _ => None,
+                })
+                .collect();
+            match bytes {
+                Some(bytes) if !bytes.is_empty() => byte_string(&bytes),
+                _ => {
+                    let elems: Option<Vec<_>> = elems.iter().map(constant_value).collect();
+                    format!("vector[{}]", elems?.join(", "))
+                }
+            }
+        }
+        MoveValue::Struct(_) | MoveValue::Signer(_) => return None,
+    })
+}
+
+/// A `b"..."` literal for printable ASCII, otherwise an `x"..."` literal.
+fn byte_string(bytes: &[u8]) -> String {
+    if bytes.iter().all(|b| b.is_ascii_graphic() || *b == b' ') {
+        let escaped: String = bytes
+            .iter()
+            .map(|b| match *b {
+                b'"' => "\\\"".to_string(),
+                b'\\' => "\\\\".to_string(),
+                b => (b as char).to_string(),
+            })
+            .collect();
+        format!("b\"{escaped}\"")
+    } else {
+        format!("x\"{}\"", hex::encode(bytes))
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+mod ast;
+pub mod decompiler;
+mod names;
+mod structure;
+mod translate;
@@ -0,0 +1,44 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use clap::Parser;
+use move_binary_format::file_format::CompiledModule;
+use move_command_line_common::files::MOVE_COMPILED_EXTENSION;
+use move_decompiler::decompiler::Decompiler;
+use std::{fs, path::Path};
+
+#[derive(Debug, Parser)]
+#[clap(author, version, about)]
+struct Args {
+    /// The path to the bytecode file to decompile.
+    #[clap(short = 'b', long = "bytecode")]
+    pub bytecode_file_path: String,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let source_path = Path::new(&args.bytecode_file_path);
+    let extension = source_path
+        .extension()
+        .expect("Missing file extension for bytecode file");
+    if extension != MOVE_COMPILED_EXTENSION {
+        println!(
+            "Bad source file extension {:?}; expected {}",
+            extension, MOVE_COMPILED_EXTENSION
+        );
+        std::process::exit(1);
+    }
+
+    let bytecode_bytes = fs::read(&args.bytecode_file_path).expect("Unable to read bytecode file");
+    let module = CompiledModule::deserialize_with_defaults(&bytecode_bytes)
+        .expect("Module blob can't be deserialized");
+
+    let source = Decompiler::new(&module)
+        .decompile()
+        .expect("Unable to decompile");
+
+    print!("{}", source);
+}
@@ -0,0 +1,156 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Source-level names for the entities referred to by a compiled module. Entities declared in the
+//! module being decompiled are referred to by their plain name, while entities from other modules
+//! are fully qualified with their address and module name.
+
+use move_binary_format::{
+    file_format::{
+        Ability, AbilitySet, ConstantPoolIndex, FieldHandleIndex, FunctionHandleIndex,
+        ModuleHandleIndex, SignatureIndex, SignatureToken, StructDefinitionIndex,
+        StructHandleIndex, TypeParameterIndex,
+    },
+    CompiledModule,
+};
+
+pub(crate) struct Names<'a> {
+    pub module: &'a CompiledModule,
+}
+
+impl<'a> Names<'a> {
+    pub fn new(module: &'a CompiledModule) -> Self {
+        Self { module }
+    }
+
+    /// The prefix to use when referring to a member of the module at `idx`.
+    fn module_prefix(&self, idx: ModuleHandleIndex) -> String {
+        if idx == self.module.self_handle_idx() {
+            return String::new();
+        }
+        let handle = self.module.module_handle_at(idx);
+        format!(
+            "{}::{}::",
+            self.module
+                .address_identifier_at(handle.address)
+                .to_hex_literal(),
+            self.module.identifier_at(handle.name)
+        )
+    }
+
+    pub fn type_(&self, token: &SignatureToken) -> String {
+        use SignatureToken as S;
+        match token {
+            S::Bool => "bool".to_string(),
+            S::U8 => "u8".to_string(),
+            S::U16 => "u16".to_string(),
+            S::U32 => "u32".to_string(),
+            S::U64 => "u64".to_string(),
+            S::U128 => "u128".to_string(),
+            S::U256 => "u256".to_string(),
+            S::Address => "address".to_string(),
+            S::Signer => "signer".to_string(),
+            S::Vector(inner) => format!("vector<{}>", self.type_(inner)),
+            S::Struct(idx) => self.struct_type(*idx, &[]),
+            S::StructInstantiation(inst) => {
+                let (idx, type_args) = &**inst;
+                self.struct_type(*idx, type_args)
+            }
+            S::Reference(inner) => format!("&{}", self.type_(inner)),
+            S::MutableReference(inner) => format!("&mut {}", self.type_(inner)),
+            S::TypeParameter(idx) => type_parameter(*idx),
+        }
+    }
+
+    pub fn types(&self, idx: SignatureIndex) -> Vec<String> {
+        self.module
+            .signature_at(idx)
+            .0
+            .iter()
+            .map(|t| self.type_(t))
+            .collect()
+    }
+
+    pub fn struct_type(&self, idx: StructHandleIndex, type_args: &[SignatureToken]) -> String {
+        let handle = self.module.struct_handle_at(idx);
+        let name = format!(
+            "{}{}",
+            self.module_prefix(handle.module),
+            self.module.identifier_at(handle.name)
+        );
+        if type_args.is_empty() {
+            name
+        } else {
+            let args: Vec<_> = type_args.iter().map(|t| self.type_(t)).collect();
+            format!("{name}<{}>", args.join(", "))
+        }
+    }
+
+    /// Name of the struct defined at `idx`, instantiated with the types in `type_args` (if any).
+    pub fn struct_def(
+        &self,
+        idx: StructDefinitionIndex,
+        type_args: Option<SignatureIndex>,
+    ) -> String {
+        let handle = self.module.struct_def_at(idx).struct_handle;
+        match type_args {
+            Some(sig) => self.struct_type(handle, &self.module.signature_at(sig).0),
+            None => self.struct_type(handle, &[]),
+        }
+    }
+
+    pub fn struct_fields(&self, idx: StructDefinitionIndex) -> Vec<String> {
+        self.module
+            .struct_def_at(idx)
+            .fields()
+            .unwrap_or_default()
+            .iter()
+            .map(|f| self.module.identifier_at(f.name).to_string())
+            .collect()
+    }
+
+    pub fn field(&self, idx: FieldHandleIndex) -> String {
+        let handle = self.module.field_handle_at(idx);
+        self.struct_fields(handle.owner)[handle.field as usize].clone()
+    }
+
+    pub fn function(&self, idx: FunctionHandleIndex) -> String {
+        let handle = self.module.function_handle_at(idx);
+        format!(
+            "{}{}",
+            self.module_prefix(handle.module),
+            self.module.identifier_at(handle.name)
+        )
+    }
+
+    /// Number of parameters and return values of the function at `idx`.
+    pub fn function_arity(&self, idx: FunctionHandleIndex) -> (usize, usize) {
+        let handle = self.module.function_handle_at(idx);
+        (
+            self.module.signature_at(handle.parameters).len(),
+            self.module.signature_at(handle.return_).len(),
+        )
+    }
+}
+
+pub(crate) fn type_parameter(idx: TypeParameterIndex) -> String {
+    format!("T{idx}")
+}
+
+pub(crate) fn constant(idx: ConstantPoolIndex) -> String {
+    format!("CONST_{}", idx.0)
+}
+
+pub(crate) fn ability(ability: Ability) -> &'static str {
+    match ability {
+        Ability::Copy => "copy",
+        Ability::Drop => "drop",
+        Ability::Store => "store",
+        Ability::Key => "key",
+    }
+}
+
+/// Abilities separated by `sep`, e.g. `copy, drop` or `copy + drop`.
+pub(crate) fn abilities(set: AbilitySet, sep: &str) -> String {
+    set.into_iter().map(ability).collect::<Vec<_>>().join(sep)
+}
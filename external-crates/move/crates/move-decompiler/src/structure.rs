@@ -0,0 +1,351 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recovery of structured control flow (`if`, `while`, `loop`, `break` and `continue`) from the
+//! control flow graph of a function.
+//!
+//! This relies on the block layout produced by the Move compiler, in which code appears in source
+//! order: the body of a loop is laid out contiguously after its header and the only backward jumps
+//! are loop back edges, while the two arms of an `if` are laid out one after the other, with the
+//! `then` arm ending in a jump over the `else` arm to the join point. Control flow that does not fit
+//! these patterns is reported as an error, rather than decompiled incorrectly.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, Result};
+use move_binary_format::{control_flow_graph::BlockId, file_format::CodeOffset};
+
+use crate::{
+    ast::Stmt,
+    translate::{Block, Terminator},
+};
+
+/// Structure the translated `blocks` of a function into a single statement list.
+pub(crate) fn structure(blocks: BTreeMap<BlockId, Block>) -> Result<Vec<Stmt>> {
+    let mut successors = BTreeMap::new();
+    let mut predecessors: BTreeMap<BlockId, Vec<BlockId>> = BTreeMap::new();
+    for (id, block) in &blocks {
+        let succs = match &block.term {
+            Terminator::Exit => vec![],
+            Terminator::Jump(target) => vec![*target],
+            Terminator::Cond {
+                target,
+                fallthrough,
+                ..
+            } => vec![*target, *fallthrough],
+        };
+        for succ in &succs {
+            predecessors.entry(*succ).or_default().push(*id);
+        }
+        successors.insert(*id, succs);
+    }
+
+    let mut structurer = Structurer {
+        layout: blocks.keys().copied().collect(),
+        blocks,
+        successors,
+        predecessors,
+        loops: vec![],
+        next_label: 0,
+    };
+
+    let mut stmts = structurer.region(
+        0,
+        Region {
+            bound: CodeOffset::MAX,
+            follow: None,
+        },
+    )?;
+    if let Some(id) = structurer.blocks.keys().next() {
+        bail!("Could not structure control flow reaching offset {id}");
+    }
+
+    // An explicit return at the end of a function without return values is redundant
+    if matches!(stmts.last(), Some(Stmt::Return(exps)) if exps.is_empty()) {
+        stmts.pop();
+    }
+    Ok(stmts)
+}
+
+/// A range of blocks being structured: blocks laid out before `bound` belong to the region, and
+/// control leaving the region continues at `follow` (`None` for the end of the function).
+#[derive(Clone, Copy)]
+struct Region {
+    bound: CodeOffset,
+    follow: Option<BlockId>,
+}
+
+struct Loop {
+    header: BlockId,
+    /// Blocks that a jump to exits the loop, i.e. that `break` continues at.
+    exits: Vec<BlockId>,
+    label: String,
+    /// Whether the loop is exited from by a `break`, or needs a label because it is the target of
+    /// a `break` or `continue` within a nested loop.
+    has_break: bool,
+    labelled: bool,
+}
+
+struct Structurer {
+    /// Reachable blocks in layout order.
+    layout: BTreeSet<BlockId>,
+    /// Blocks that have not been placed yet.
+    blocks: BTreeMap<BlockId, Block>,
+    successors: BTreeMap<BlockId, Vec<BlockId>>,
+    predecessors: BTreeMap<BlockId, Vec<BlockId>>,
+    /// Loops being structured, innermost last.
+    loops: Vec<Loop>,
+    next_label: usize,
+}
+
+impl Structurer {
+    fn region(&mut self, start: BlockId, region: Region) -> Result<Vec<Stmt>> {
+        let mut out = vec![];
+        let mut cur = start;
+        loop {
+            if cur >= region.bound {
+                if Some(cur) != region.follow {
+                    out.push(self.special_jump(cur)?);
+                }
+                return Ok(out);
+            }
+
+            if self.is_loop_header(cur) && !self.loops.iter().any(|l| l.header == cur) {
+                let (bound, exits) = self.loop_extent(cur, region)?;
+                let label = format!("loop{}", self.next_label);
+                self.next_label += 1;
+                self.loops.push(Loop {
+                    header: cur,
+                    exits,
+                    label,
+                    has_break: false,
+                    labelled: false,
+                });
+                let body = self.region(
+                    cur,
+                    Region {
+                        bound,
+                        follow: Some(cur),
+                    },
+                )?;
+                let lp = self.loops.pop().unwrap();
+                let has_break = lp.has_break;
+                out.push(make_loop(lp, body));
+                if !has_break {
+                    // Control never continues after the loop
+                    return Ok(out);
+                }
+                cur = bound;
+                continue;
+            }
+
+            let Some(block) = self.blocks.remove(&cur) else {
+                bail!("Block at offset {cur} is reached more than once");
+            };
+            out.extend(block.stmts);
+            match block.term {
+                Terminator::Exit => return Ok(out),
+                Terminator::Jump(target) => {
+                    if Some(target) == self.next_block(cur) {
+                        cur = target;
+                        continue;
+                    }
+                    if Some(target) != region.follow {
+                        out.push(self.special_jump(target)?);
+                    }
+                    return Ok(out);
+                }
+                Terminator::Cond {
+                    cond,
+                    target,
+                    fallthrough,
+                } => {
+                    if Some(target) != region.follow {
+                        if let Some(jump) = self.loop_jump(target) {
+                            out.push(Stmt::If {
+                                cond,
+                                then: vec![jump],
+                                else_: vec![],
+                            });
+                            cur = fallthrough;
+                            continue;
+                        }
+                    }
+
+                    if target <= cur {
+                        bail!("Unstructured backward jump from offset {cur} to {target}");
+                    }
+
+                    if target >= region.bound {
+                        if Some(target) != region.follow {
+                            bail!("Unstructured jump from offset {cur} to {target}");
+                        }
+                        // The rest of the region is only executed if the condition does not hold
+                        let then = self.region(fallthrough, region)?;
+                        out.push(Stmt::If {
+                            cond: cond.negate(),
+                            then,
+                            else_: vec![],
+                        });
+                        return Ok(out);
+                    }
+
+                    if let Some(join) = self.else_join(cur, target, region) {
+                        let then = self.region(
+                            fallthrough,
+                            Region {
+                                bound: target,
+                                follow: Some(join),
+                            },
+                        )?;
+                        let else_ = self.region(
+                            target,
+                            Region {
+                                bound: join.min(region.bound),
+                                follow: Some(join),
+                            },
+                        )?;
+                        out.push(Stmt::If {
+                            cond: cond.negate(),
+                            then,
+                            else_,
+                        });
+                        cur = join;
+                        continue;
+                    }
+
+                    let then = self.region(
+                        fallthrough,
+                        Region {
+                            bound: target,
+                            follow: Some(target),
+                        },
+                    )?;
+                    out.push(Stmt::If {
+                        cond: cond.negate(),
+                        then,
+                        else_: vec![],
+                    });
+                    cur = target;
+                }
+            }
+        }
+    }
+
+    /// If the conditional jump at `cur` to `target` is the start of an `if` with an `else` arm
+    /// starting at `target`, returns the block the two arms join at.
+    fn else_join(&self, cur: BlockId, target: BlockId, region: Region) -> Option<BlockId> {
+        let last = *self.layout.range(..target).next_back()?;
+        if last == cur {
+            return None;
+        }
+        let Terminator::Jump(join) = self.blocks.get(&last)?.term else {
+            return None;
+        };
+        (join > target && (join < region.bound || Some(join) == region.follow)).then_some(join)
+    }
+
+    fn next_block(&self, id: BlockId) -> Option<BlockId> {
+        self.layout.range(id + 1..).next().copied()
+    }
+
+    fn is_loop_header(&self, id: BlockId) -> bool {
+        self.predecessors
+            .get(&id)
+            .is_some_and(|preds| preds.iter().any(|p| *p >= id))
+    }
+
+    /// The bound of the loop headed by `header`, and the blocks that exit it.
+    fn loop_extent(&self, header: BlockId, region: Region) -> Result<(CodeOffset, Vec<BlockId>)> {
+        // The natural loop: all blocks that reach a back edge without going through the header
+        let mut body = BTreeSet::from([header]);
+        let mut worklist: Vec<_> = self.predecessors[&header]
+            .iter()
+            .copied()
+            .filter(|p| *p >= header)
+            .collect();
+        while let Some(id) = worklist.pop() {
+            if id < header {
+                bail!("Irreducible control flow into loop at offset {header}");
+            }
+            if body.insert(id) {
+                worklist.extend(self.predecessors.get(&id).into_iter().flatten().copied());
+            }
+        }
+
+        let last = *body.last().unwrap();
+        let mut bound = self.next_block(last).unwrap_or(CodeOffset::MAX);
+
+        // Blocks laid out after the loop that only return or abort from within it (e.g. the
+        // `return` in `loop { if (c) return x; ... }`) belong to its body too.
+        while bound < region.bound {
+            let preds = &self.predecessors[&bound];
+            if !self.successors[&bound].is_empty()
+                || self.successors[&header].contains(&bound)
+                || !preds.iter().all(|p| *p >= header && *p < bound)
+            {
+                break;
+            }
+            bound = self.next_block(bound).unwrap_or(CodeOffset::MAX);
+        }
+
+        if bound > region.bound {
+            bail!("Loop at offset {header} is not nested in its enclosing region");
+        }
+
+        let mut exits = vec![bound];
+        if bound == region.bound {
+            exits.extend(region.follow);
+        }
+        Ok((bound, exits))
+    }
+
+    /// A `break` or `continue` for a jump to `target`, if it exits or restarts an enclosing loop.
+    fn loop_jump(&mut self, target: BlockId) -> Option<Stmt> {
+        let innermost = self.loops.len().checked_sub(1)?;
+        for (depth, lp) in self.loops.iter_mut().enumerate().rev() {
+            let label = if depth == innermost {
+                None
+            } else {
+                Some(lp.label.clone())
+            };
+            if lp.header == target {
+                lp.labelled |= label.is_some();
+                return Some(Stmt::Continue(label));
+            }
+            if lp.exits.contains(&target) {
+                lp.labelled |= label.is_some();
+                lp.has_break = true;
+                return Some(Stmt::Break(label));
+            }
+        }
+        None
+    }
+
+    fn special_jump(&mut self, target: BlockId) -> Result<Stmt> {
+        match self.loop_jump(target) {
+            Some(jump) => Ok(jump),
+            None => bail!("Unstructured jump to offset {target}"),
+        }
+    }
+}
+
+/// Build the statement for a loop, turning it into a `while` loop if it starts by breaking out of
+/// itself on a condition.
+fn make_loop(lp: Loop, mut body: Vec<Stmt>) -> Stmt {
+    let label = lp.labelled.then_some(lp.label);
+    if let Some(Stmt::If { then, else_, .. }) = body.first() {
+        let breaks_self = matches!(then.as_slice(), [Stmt::Break(l)] if l.is_none() || *l == label);
+        if breaks_self && else_.is_empty() {
+            let Stmt::If { cond, .. } = body.remove(0) else {
+                unreachable!()
+            };
+            return Stmt::While {
+                label,
+                cond: cond.negate(),
+                body,
+            };
+        }
+    }
+    Stmt::Loop { label, body }
+}
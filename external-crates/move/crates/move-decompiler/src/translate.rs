@@ -0,0 +1,654 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Translation of the basic blocks of a function from stack-based bytecode into statements over
+//! expression trees. Values are kept on a symbolic stack as expressions for as long as possible,
+//! so that e.g. `CopyLoc(0); LdU64(1); Add; StLoc(0)` becomes `l0 = l0 + 1`. Values that are still
+//! on the stack at the end of a block, or that would otherwise be evaluated out of order, are
+//! spilled into stack temporaries (`s0`, `s1`, ...), one per stack slot.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, Result};
+use move_binary_format::{
+    control_flow_graph::{BlockId, ControlFlowGraph, VMControlFlowGraph},
+    file_format::{Bytecode, CodeOffset, CodeUnit, SignatureIndex, StructDefinitionIndex},
+};
+
+use crate::{
+    ast::{Exp, Stmt},
+    names::{self, Names},
+};
+
+/// How control leaves a basic block.
+#[derive(Clone, Debug)]
+pub(crate) enum Terminator {
+    /// Return or abort, already part of the block's statements.
+    Exit,
+    /// Unconditional jump, including falling through to the next block.
+    Jump(BlockId),
+    /// Jump to `target` if `cond` holds, otherwise fall through to `fallthrough`.
+    Cond {
+        cond: Exp,
+        target: BlockId,
+        fallthrough: BlockId,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Block {
+    pub stmts: Vec<Stmt>,
+    pub term: Terminator,
+}
+
+pub(crate) struct FunctionBody {
+    /// Translated blocks, keyed by their first instruction. Unreachable blocks are omitted.
+    pub blocks: BTreeMap<BlockId, Block>,
+    /// Stack temporaries used by the blocks.
+    pub temps: BTreeSet<String>,
+}
+
+pub(crate) fn stack_temp(slot: usize) -> String {
+    format!("s{slot}")
+}
+
+/// Translate every reachable block of `code`, where `locals` holds the names of all parameters and
+/// locals, and `returns` is the number of values returned by the function.
+pub(crate) fn translate(
+    names: &Names,
+    code: &CodeUnit,
+    locals: &[String],
+    returns: usize,
+) -> Result<FunctionBody> {
+    let cfg = VMControlFlowGraph::new(&code.code);
+    let heights = stack_heights(names, code, &cfg)?;
+
+    let mut blocks = BTreeMap::new();
+    let mut temps = BTreeSet::new();
+    for (block_id, height) in heights {
+        temps.extend((0..height).map(stack_temp));
+        let translator = BlockTranslator {
+            names,
+            code: &code.code,
+            locals,
+            returns,
+            stack: (0..height).map(|i| Exp::Var(stack_temp(i))).collect(),
+            stmts: vec![],
+            temps: &mut temps,
+        };
+        let block = translator.translate(cfg.block_start(block_id), cfg.block_end(block_id))?;
+        blocks.insert(block_id, block);
+    }
+
+    Ok(FunctionBody { blocks, temps })
+}
+
+/// The height of the stack on entry to every reachable block.
+fn stack_heights(
+    names: &Names,
+    code: &CodeUnit,
+    cfg: &VMControlFlowGraph,
+) -> Result<BTreeMap<BlockId, usize>> {
+    let mut heights = BTreeMap::from([(cfg.entry_block_id(), 0)]);
+    let mut worklist = vec![cfg.entry_block_id()];
+    while let Some(block_id) = worklist.pop() {
+        let mut height = heights[&block_id];
+        for pc in cfg.instr_indexes(block_id) {
+            let (pops, pushes) = stack_effect(names, &code.code[pc as usize]);
+            let Some(popped) = height.checked_sub(pops) else {
+                bail!("Stack underflow at offset {pc}");
+            };
+            height = popped + pushes;
+        }
+
+        for succ in cfg.successors(block_id) {
+            match heights.get(succ) {
+                Some(h) if *h != height => {
+                    bail!("Inconsistent stack height on entry to block at offset {succ}")
+                }
+                Some(_) => (),
+                None => {
+                    heights.insert(*succ, height);
+                    worklist.push(*succ);
+                }
+            }
+        }
+    }
+    Ok(heights)
+}
+
+/// Number of values `instr` pops off and pushes onto the stack.
+fn stack_effect(names: &Names, instr: &Bytecode) -> (usize, usize) {
+    use Bytecode as B;
+    let module = names.module;
+    match instr {
+        B::Nop | B::Branch(_) | B::Ret => (0, 0),
+        B::Pop | B::BrTrue(_) | B::BrFalse(_) | B::StLoc(_) | B::Abort => (1, 0),
+        B::LdU8(_)
+        | B::LdU16(_)
+        | B::LdU32(_)
+        | B::LdU64(_)
+        | B::LdU128(_)
+        | B::LdU256(_)
+        | B::LdConst(_)
+        | B::LdTrue
+        | B::LdFalse
+        | B::CopyLoc(_)
+        | B::MoveLoc(_)
+        | B::MutBorrowLoc(_)
+        | B::ImmBorrowLoc(_) => (0, 1),
+        B::CastU8
+        | B::CastU16
+        | B::CastU32
+        | B::CastU64
+        | B::CastU128
+        | B::CastU256
+        | B::Not
+        | B::ReadRef
+        | B::FreezeRef
+        | B::MutBorrowField(_)
+        | B::MutBorrowFieldGeneric(_)
+        | B::ImmBorrowField(_)
+        | B::ImmBorrowFieldGeneric(_)
+        | B::VecLen(_)
+        | B::VecPopBack(_)
+        | B::ExistsDeprecated(_)
+        | B::ExistsGenericDeprecated(_)
+        | B::MoveFromDeprecated(_)
+        | B::MoveFromGenericDeprecated(_)
+        | B::MutBorrowGlobalDeprecated(_)
+        | B::MutBorrowGlobalGenericDeprecated(_)
+        | B::ImmBorrowGlobalDeprecated(_)
+        | B::ImmBorrowGlobalGenericDeprecated(_) => (1, 1),
+        B::Add
+        | B::Sub
+        | B::Mul
+        | B::Mod
+        | B::Div
+        | B::BitOr
+        | B::BitAnd
+        | B::Xor
+        | B::Or
+        | B::And
+        | B::Eq
+        | B::Neq
+        | B::Lt
+        | B::Gt
+        | B::Le
+        | B::Ge
+        | B::Shl
+        | B::Shr
+        | B::VecImmBorrow(_)
+        | B::VecMutBorrow(_) => (2, 1),
+        B::WriteRef
+        | B::VecPushBack(_)
+        | B::MoveToDeprecated(_)
+        | B::MoveToGenericDeprecated(_) => (2, 0),
+        B::VecSwap(_) => (3, 0),
+        B::Call(idx) => names.function_arity(*idx),
+        B::CallGeneric(idx) => names.function_arity(module.function_instantiation_at(*idx).handle),
+        B::Pack(idx) => (names.struct_fields(*idx).len(), 1),
+        B::PackGeneric(idx) => (
+            names
+                .struct_fields(module.struct_instantiation_at(*idx).def)
+                .len(),
+            1,
+        ),
+        B::Unpack(idx) => (1, names.struct_fields(*idx).len()),
+        B::UnpackGeneric(idx) => (
+            1,
+            names
+                .struct_fields(module.struct_instantiation_at(*idx).def)
+                .len(),
+        ),
+        B::VecPack(_, n) => (*n as usize, 1),
+        B::VecUnpack(_, n) => (1, *n as usize),
+    }
+}
+
+struct BlockTranslator<'a, 'b> {
+    names: &'a Names<'a>,
+    code: &'a [Bytecode],
+    locals: &'a [String],
+    returns: usize,
+    stack: Vec<Exp>,
+    stmts: Vec<Stmt>,
+    temps: &'b mut BTreeSet<String>,
+}
+
+impl BlockTranslator<'_, '_> {
+    fn translate(mut self, start: CodeOffset, end: CodeOffset) -> Result<Block> {
+        use Bytecode as B;
+        let module = self.names.module;
+        let code = self.code;
+        let mut pc = start;
+        while pc <= end {
+            let instr = &code[pc as usize];
+            match instr {
+                B::Nop => (),
+                B::Pop => {
+                    let exp = self.pop()?;
+                    if !exp.is_pure() {
+                        self.flush(&[]);
+                        self.stmts.push(Stmt::Exp(exp));
+                    }
+                }
+                B::Ret => {
+                    let exps = self.pop_n(self.returns)?;
+                    self.stmts.push(Stmt::Return(exps));
+                    return self.finish(Terminator::Exit);
+                }
+                B::Abort => {
+                    let exp = self.pop()?;
+                    self.flush(&[]);
+                    self.stmts.push(Stmt::Abort(exp));
+                    return self.finish(Terminator::Exit);
+                }
+                B::Branch(target) => {
+                    self.spill_all();
+                    return self.finish(Terminator::Jump(*target));
+                }
+                B::BrTrue(target) | B::BrFalse(target) => {
+                    let cond = self.pop()?;
+                    self.spill_all();
+                    let cond = if matches!(instr, B::BrTrue(_)) {
+                        cond
+                    } else {
+                        cond.negate()
+                    };
+                    return self.finish(Terminator::Cond {
+                        cond,
+                        target: *target,
+                        fallthrough: pc + 1,
+                    });
+                }
+
+                B::LdU8(v) => self.push_value(format!("{v}u8")),
+                B::LdU16(v) => self.push_value(format!("{v}u16")),
+                B::LdU32(v) => self.push_value(format!("{v}u32")),
+                B::LdU64(v) => self.push_value(format!("{v}")),
+                B::LdU128(v) => self.push_value(format!("{v}u128")),
+                B::LdU256(v) => self.push_value(format!("{v}u256")),
+                B::LdTrue => self.push_value("true".to_string()),
+                B::LdFalse => self.push_value("false".to_string()),
+                B::LdConst(idx) => self.push_value(names::constant(*idx)),
+
+                B::CopyLoc(idx) | B::MoveLoc(idx) => self
+                    .stack
+                    .push(Exp::Var(self.locals[*idx as usize].clone())),
+                B::MutBorrowLoc(idx) | B::ImmBorrowLoc(idx) => self.stack.push(Exp::Borrow {
+                    mutable: matches!(instr, B::MutBorrowLoc(_)),
+                    exp: Box::new(Exp::Var(self.locals[*idx as usize].clone())),
+                }),
+                B::StLoc(idx) => {
+                    let exp = self.pop()?;
+                    let local = self.locals[*idx as usize].clone();
+                    self.flush(&[local.clone()]);
+                    self.stmts.push(Stmt::Assign(vec![local], exp));
+                }
+
+                B::Call(idx) => {
+                    let (params, returns) = self.names.function_arity(*idx);
+                    let args = self.pop_n(params)?;
+                    let call = Exp::Call {
+                        function: self.names.function(*idx),
+                        type_args: vec![],
+                        args,
+                    };
+                    pc = self.results(call, returns, pc)?;
+                }
+                B::CallGeneric(idx) => {
+                    let inst = module.function_instantiation_at(*idx);
+                    let (params, returns) = self.names.function_arity(inst.handle);
+                    let args = self.pop_n(params)?;
+                    let call = Exp::Call {
+                        function: self.names.function(inst.handle),
+                        type_args: self.names.types(inst.type_parameters),
+                        args,
+                    };
+                    pc = self.results(call, returns, pc)?;
+                }
+
+                B::Pack(idx) => {
+                    let name = self.names.struct_def(*idx, None);
+                    self.pack(name, self.names.struct_fields(*idx))?
+                }
+                B::PackGeneric(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    let name = self.names.struct_def(inst.def, Some(inst.type_parameters));
+                    self.pack(name, self.names.struct_fields(inst.def))?
+                }
+                B::Unpack(idx) => {
+                    let name = self.names.struct_def(*idx, None);
+                    pc = self.unpack(name, self.names.struct_fields(*idx), pc)?;
+                }
+                B::UnpackGeneric(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    let name = self.names.struct_def(inst.def, Some(inst.type_parameters));
+                    pc = self.unpack(name, self.names.struct_fields(inst.def), pc)?;
+                }
+
+                B::ReadRef => {
+                    let exp = self.pop()?;
+                    self.stack.push(Exp::Deref(Box::new(exp)))
+                }
+                B::WriteRef => {
+                    let reference = self.pop()?;
+                    let exp = self.pop()?;
+                    self.flush(&[]);
+                    self.stmts.push(Stmt::Mutate(reference, exp));
+                }
+                B::FreezeRef => {
+                    let exp = self.pop()?;
+                    self.stack.push(Exp::Freeze(Box::new(exp)))
+                }
+                B::MutBorrowField(idx) | B::ImmBorrowField(idx) => {
+                    let field = self.names.field(*idx);
+                    let mutable = matches!(instr, B::MutBorrowField(_));
+                    self.borrow_field(mutable, field)?
+                }
+                B::MutBorrowFieldGeneric(idx) | B::ImmBorrowFieldGeneric(idx) => {
+                    let field = self.names.field(module.field_instantiation_at(*idx).handle);
+                    let mutable = matches!(instr, B::MutBorrowFieldGeneric(_));
+                    self.borrow_field(mutable, field)?
+                }
+
+                B::Add => self.binary("+")?,
+                B::Sub => self.binary("-")?,
+                B::Mul => self.binary("*")?,
+                B::Mod => self.binary("%")?,
+                B::Div => self.binary("/")?,
+                B::BitOr => self.binary("|")?,
+                B::BitAnd => self.binary("&")?,
+                B::Xor => self.binary("^")?,
+                B::Or => self.binary("||")?,
+                B::And => self.binary("&&")?,
+                B::Eq => self.binary("==")?,
+                B::Neq => self.binary("!=")?,
+                B::Lt => self.binary("<")?,
+                B::Gt => self.binary(">")?,
+                B::Le => self.binary("<=")?,
+                B::Ge => self.binary(">=")?,
+                B::Shl => self.binary("<<")?,
+                B::Shr => self.binary(">>")?,
+                B::Not => {
+                    let exp = self.pop()?;
+                    self.stack.push(exp.negate())
+                }
+                B::CastU8 => self.cast("u8")?,
+                B::CastU16 => self.cast("u16")?,
+                B::CastU32 => self.cast("u32")?,
+                B::CastU64 => self.cast("u64")?,
+                B::CastU128 => self.cast("u128")?,
+                B::CastU256 => self.cast("u256")?,
+
+                B::VecPack(sig, n) => {
+                    let elems = self.pop_n(*n as usize)?;
+                    let ty = self.element_type(*sig);
+                    self.stack.push(Exp::Vector { ty, elems })
+                }
+                B::VecLen(_) => self.vector_call("length", 1, true)?,
+                B::VecImmBorrow(_) => self.vector_call("borrow", 2, true)?,
+                B::VecMutBorrow(_) => self.vector_call("borrow_mut", 2, true)?,
+                B::VecPopBack(_) => self.vector_call("pop_back", 1, true)?,
+                B::VecPushBack(_) => self.vector_call("push_back", 2, false)?,
+                B::VecSwap(_) => self.vector_call("swap", 3, false)?,
+                B::VecUnpack(_, 0) => self.vector_call("destroy_empty", 1, false)?,
+                B::VecUnpack(_, _) => bail!("Unsupported instruction {instr:?} at offset {pc}"),
+
+                B::ExistsDeprecated(idx) => self.global("exists", *idx, None, 1, true)?,
+                B::ExistsGenericDeprecated(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    self.global("exists", inst.def, Some(inst.type_parameters), 1, true)?
+                }
+                B::MoveFromDeprecated(idx) => self.global("move_from", *idx, None, 1, true)?,
+                B::MoveFromGenericDeprecated(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    self.global("move_from", inst.def, Some(inst.type_parameters), 1, true)?
+                }
+                B::MoveToDeprecated(idx) => self.global("move_to", *idx, None, 2, false)?,
+                B::MoveToGenericDeprecated(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    self.global("move_to", inst.def, Some(inst.type_parameters), 2, false)?
+                }
+                B::MutBorrowGlobalDeprecated(idx) => {
+                    self.global("borrow_global_mut", *idx, None, 1, true)?
+                }
+                B::MutBorrowGlobalGenericDeprecated(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    let type_args = Some(inst.type_parameters);
+                    self.global("borrow_global_mut", inst.def, type_args, 1, true)?
+                }
+                B::ImmBorrowGlobalDeprecated(idx) => {
+                    self.global("borrow_global", *idx, None, 1, true)?
+                }
+                B::ImmBorrowGlobalGenericDeprecated(idx) => {
+                    let inst = module.struct_instantiation_at(*idx);
+                    let type_args = Some(inst.type_parameters);
+                    self.global("borrow_global", inst.def, type_args, 1, true)?
+                }
+            }
+            pc += 1;
+        }
+
+        // The block falls through into the next one.
+        self.spill_all();
+        self.finish(Terminator::Jump(end + 1))
+    }
+
+    fn finish(self, term: Terminator) -> Result<Block> {
+        Ok(Block {
+            stmts: self.stmts,
+            term,
+        })
+    }
+
+    fn pop(&mut self) -> Result<Exp> {
+        match self.stack.pop() {
+            Some(exp) => Ok(exp),
+            None => bail!("Stack underflow"),
+        }
+    }
+
+    /// Pop `n` values, returned in the order they were pushed.
+    fn pop_n(&mut self, n: usize) -> Result<Vec<Exp>> {
+        let Some(len) = self.stack.len().checked_sub(n) else {
+            bail!("Stack underflow");
+        };
+        Ok(self.stack.split_off(len))
+    }
+
+    fn push_value(&mut self, value: String) {
+        self.stack.push(Exp::Value(value))
+    }
+
+    fn spill(&mut self, slot: usize) {
+        let temp = stack_temp(slot);
+        if self.stack[slot] == Exp::Var(temp.clone()) {
+            return;
+        }
+        let exp = std::mem::replace(&mut self.stack[slot], Exp::Var(temp.clone()));
+        self.temps.insert(temp.clone());
+        self.stmts.push(Stmt::Assign(vec![temp], exp));
+    }
+
+    /// Spill all values that are still on the stack into temporaries, in stack order, before a
+    /// statement that may change their value is emitted. Values that are evaluated later than the
+    /// statement in bytecode order must have been popped already.
+    fn flush(&mut self, assigned: &[String]) {
+        for slot in 0..self.stack.len() {
+            let exp = &self.stack[slot];
+            if !exp.is_pure() || assigned.iter().any(|v| exp.mentions(v)) {
+                self.spill(slot);
+            }
+        }
+    }
+
+    /// Spill all values on the stack, so that they can be read by successor blocks.
+    fn spill_all(&mut self) {
+        for slot in 0..self.stack.len() {
+            self.spill(slot);
+        }
+    }
+
+    /// Handle the `n` results of `exp`, evaluated by the instruction at `pc`. Multiple results
+    /// that are immediately stored into locals (or popped) are assigned to them directly, and
+    /// spilled into temporaries otherwise. Returns the offset of the last instruction consumed.
+    fn results(&mut self, exp: Exp, n: usize, pc: CodeOffset) -> Result<CodeOffset> {
+        match n {
+            0 => {
+                self.flush(&[]);
+                self.stmts.push(Stmt::Exp(exp));
+                Ok(pc)
+            }
+            1 => {
+                self.stack.push(exp);
+                Ok(pc)
+            }
+            n => {
+                let (lhs, last) = self.result_targets(n, pc);
+                self.flush(&lhs);
+                self.stmts.push(Stmt::Assign(lhs, exp));
+                Ok(last)
+            }
+        }
+    }
+
+    /// The variables that the `n` values produced by the instruction at `pc` are assigned to.
+    fn result_targets(&mut self, n: usize, pc: CodeOffset) -> (Vec<String>, CodeOffset) {
+        let start = pc as usize + 1;
+        let following = self.code.get(start..start + n).unwrap_or_default();
+        let direct = following.len() == n
+            && following
+                .iter()
+                .all(|i| matches!(i, Bytecode::StLoc(_) | Bytecode::Pop))
+            // None of the stores may be a jump target, i.e. start a new block
+            && !self.code.iter().any(|i| match i {
+                Bytecode::Branch(t) | Bytecode::BrTrue(t) | Bytecode::BrFalse(t) => {
+                    (start..start + n).contains(&(*t as usize))
+                }
+                _ => false,
+            });
+
+        if direct {
+            // The first store pops the last value
+            let lhs = following
+                .iter()
+                .rev()
+                .map(|i| match i {
+                    Bytecode::StLoc(idx) => self.locals[*idx as usize].clone(),
+                    _ => "_".to_string(),
+                })
+                .collect();
+            return (lhs, pc + n as CodeOffset);
+        }
+
+        let base = self.stack.len();
+        let lhs: Vec<_> = (base..base + n).map(stack_temp).collect();
+        self.temps.extend(lhs.iter().cloned());
+        self.stack.extend(lhs.iter().cloned().map(Exp::Var));
+        (lhs, pc)
+    }
+
+    fn pack(&mut self, name: String, fields: Vec<String>) -> Result<()> {
+        let exps = self.pop_n(fields.len())?;
+        self.stack.push(Exp::Pack {
+            name,
+            fields: fields.into_iter().zip(exps).collect(),
+        });
+        Ok(())
+    }
+
+    fn unpack(&mut self, name: String, fields: Vec<String>, pc: CodeOffset) -> Result<CodeOffset> {
+        let exp = self.pop()?;
+        let (vars, last) = if fields.is_empty() {
+            (vec![], pc)
+        } else {
+            self.result_targets(fields.len(), pc)
+        };
+        self.flush(&vars);
+        self.stmts.push(Stmt::Unpack {
+            name,
+            fields: fields.into_iter().zip(vars).collect(),
+            exp,
+        });
+        Ok(last)
+    }
+
+    fn borrow_field(&mut self, mutable: bool, field: String) -> Result<()> {
+        let base = self.pop()?;
+        self.stack.push(Exp::Field {
+            mutable,
+            base: Box::new(base),
+            field,
+        });
+        Ok(())
+    }
+
+    fn binary(&mut self, op: &'static str) -> Result<()> {
+        let rhs = self.pop()?;
+        let lhs = self.pop()?;
+        self.stack.push(Exp::Binary {
+            op,
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        });
+        Ok(())
+    }
+
+    fn cast(&mut self, ty: &'static str) -> Result<()> {
+        let exp = self.pop()?;
+        self.stack.push(Exp::Cast {
+            exp: Box::new(exp),
+            ty,
+        });
+        Ok(())
+    }
+
+    fn element_type(&self, sig: SignatureIndex) -> String {
+        self.names
+            .types(sig)
+            .pop()
+            .unwrap_or_else(|| "_".to_string())
+    }
+
+    /// Calls to functions in `std::vector`, which are implemented as bytecode instructions.
+    fn vector_call(&mut self, function: &str, arity: usize, returns: bool) -> Result<()> {
+        let args = self.pop_n(arity)?;
+        let call = Exp::Call {
+            function: format!("0x1::vector::{function}"),
+            type_args: vec![],
+            args,
+        };
+        self.call_builtin(call, returns);
+        Ok(())
+    }
+
+    /// Global storage operations, which are not supported on all platforms.
+    fn global(
+        &mut self,
+        function: &str,
+        def: StructDefinitionIndex,
+        type_args: Option<SignatureIndex>,
+        arity: usize,
+        returns: bool,
+    ) -> Result<()> {
+        let args = self.pop_n(arity)?;
+        let call = Exp::Call {
+            function: function.to_string(),
+            type_args: vec![self.names.struct_def(def, type_args)],
+            args,
+        };
+        self.call_builtin(call, returns);
+        Ok(())
+    }
+
+    fn call_builtin(&mut self, call: Exp, returns: bool) {
+        if returns {
+            self.stack.push(call);
+        } else {
+            self.flush(&[]);
+            self.stmts.push(Stmt::Exp(call));
+        }
+    }
+}
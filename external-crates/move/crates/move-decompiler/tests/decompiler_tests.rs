@@ -0,0 +1,97 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeMap, fs};
+
+use move_binary_format::file_format::CompiledModule;
+use move_compiler::Compiler;
+use move_decompiler::decompiler::Decompiler;
+
+const SOURCE: &str = r#"
+module 0x42::m {
+    struct Point has copy, drop {
+        x: u64,
+        y: u64,
+    }
+
+    const LIMIT: u64 = 100;
+
+    public fun sum(n: u64): u64 {
+        let i = 0;
+        let total = 0;
+        while (i < n) {
+            if (i % 2 == 0) {
+                total = total + i;
+            } else {
+                total = total + 1;
+            };
+            i = i + 1;
+        };
+        total
+    }
+
+    public fun new(x: u64, y: u64): Point {
+        assert!(x < LIMIT, 0);
+        Point { x, y }
+    }
+
+    public fun coords(p: &Point): (u64, u64) {
+        (p.x, p.y)
+    }
+
+    fun find(v: u64): bool {
+        let i = 0;
+        loop {
+            if (i == v) return true;
+            if (i > 10) break;
+            i = i + 1;
+        };
+        false
+    }
+}
+"#;
+
+fn compile(source: &str) -> CompiledModule {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("m.move");
+    fs::write(&path, source).unwrap();
+    let (_, mut units) = Compiler::from_files(
+        None,
+        vec![path.to_str().unwrap().to_string()],
+        vec![],
+        BTreeMap::<String, _>::new(),
+    )
+    .build_and_report()
+    .unwrap();
+    units.pop().unwrap().named_module.module
+}
+
+#[test]
+fn test_decompile_module() {
+    let module = compile(SOURCE);
+    let decompiled = Decompiler::new(&module).decompile().unwrap();
+
+    for expected in [
+        "module 0x42::m {",
+        "const CONST_0: u64 = 100;",
+        "public struct Point has copy, drop {",
+        "x: u64,",
+        "public fun sum(arg0: u64): u64 {",
+        "public fun new(arg0: u64, arg1: u64): Point {",
+        "public fun coords(arg0: &Point): (u64, u64) {",
+        "fun find(arg0: u64): bool {",
+        "while (",
+        "} else {",
+        "return true",
+        "break",
+    ] {
+        assert!(
+            decompiled.contains(expected),
+            "Missing {expected:?} in:\n{decompiled}"
+        );
+    }
+    assert!(
+        !decompiled.contains("Failed to decompile"),
+        "Unstructured function in:\n{decompiled}"
+    );
+}
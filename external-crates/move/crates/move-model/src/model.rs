@@ -125,6 +125,11 @@ impl Loc {
         )
     }
 
+    /// Delivers a location for the given byte range in the file of this one.
+    pub fn with_span(&self, start: u32, end: u32) -> Loc {
+        Loc::new(self.file_id, Span::new(start, end))
+    }
+
     /// Creates a location which encloses all the locations in the provided slice,
     /// which must not be empty. All locations are expected to be in the same file.
     pub fn enclosing(locs: &[&Loc]) -> Loc {
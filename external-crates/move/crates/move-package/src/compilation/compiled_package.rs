@@ -28,7 +28,7 @@ use move_compiler::{
     compiled_unit::{AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule},
     diagnostics::FilesSourceText,
     editions::Flavor,
-    linters,
+    linters::{self, LintLevel},
     shared::{NamedAddressMap, NumericalAddress, PackageConfig, PackagePaths},
     sui_mode::{self},
     Compiler,
@@ -484,6 +484,14 @@ impl CompiledPackage {
         paths.push(sources_package_paths.clone());
 
         let lint_level = resolution_graph.build_options.lint_flag.get();
+        // lint severities are only taken from the root package, as lints are not reported for
+        // dependencies
+        let lint_config = resolved_package
+            .source_package
+            .build
+            .as_ref()
+            .map(|build| build.lints.clone())
+            .unwrap_or_default();
         let sui_mode = resolution_graph
             .build_options
             .default_flavor
@@ -496,12 +504,18 @@ impl CompiledPackage {
             let (filter_attr_name, filters) = sui_mode::linters::known_filters();
             compiler = compiler
                 .add_custom_known_filters(filter_attr_name, filters)
-                .add_visitors(sui_mode::linters::linter_visitors(lint_level))
+                .add_visitors(sui_mode::linters::linter_visitors_with_config(
+                    lint_level,
+                    &lint_config,
+                ))
         }
         let (filter_attr_name, filters) = linters::known_filters();
         compiler = compiler
             .add_custom_known_filters(filter_attr_name, filters)
             .add_visitors(linters::linter_visitors(lint_level));
+        if lint_level != LintLevel::None {
+            compiler = compiler.set_lint_config(lint_config);
+        }
         Ok(BuildResult {
             root_package_name,
             sources_package_paths,
@@ -4,7 +4,10 @@
 
 use crate::{package_hooks, source_package::parsed_manifest as PM};
 use anyhow::{anyhow, bail, format_err, Context, Result};
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    linters::{LintConfig, LintSeverity},
+};
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -24,6 +27,7 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const LINTS_NAME: &str = "lints";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
@@ -222,12 +226,17 @@ pub fn parse_dependencies(tval: TV) -> Result<PM::Dependencies> {
 pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     match tval {
         TV::Table(mut table) => {
-            warn_if_unknown_field_names(&table, &["language_version", "arch"]);
+            warn_if_unknown_field_names(&table, &["language_version", "arch", LINTS_NAME]);
             Ok(PM::BuildInfo {
                 language_version: table
                     .remove("language_version")
                     .map(parse_version)
                     .transpose()?,
+                lints: table
+                    .remove(LINTS_NAME)
+                    .map(parse_lints)
+                    .transpose()?
+                    .unwrap_or_default(),
             })
         }
         x => bail!(
@@ -238,6 +247,30 @@ pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     }
 }
 
+pub fn parse_lints(tval: TV) -> Result<LintConfig> {
+    match tval {
+        TV::Table(table) => table
+            .into_iter()
+            .map(|(name, severity)| {
+                let severity = severity.as_str().ok_or_else(|| {
+                    format_err!(
+                        "Invalid severity for lint '{name}' of type {} found. Expected a string.",
+                        severity.type_str()
+                    )
+                })?;
+                let severity = LintSeverity::from_str(severity)
+                    .with_context(|| format!("Invalid severity for lint '{name}'"))?;
+                Ok((Symbol::from(name), severity))
+            })
+            .collect(),
+        x => bail!(
+            "Malformed 'lints' in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_addresses(tval: TV) -> Result<PM::AddressDeclarations> {
     match tval {
         TV::Table(table) => {
@@ -4,7 +4,10 @@
 
 use anyhow::{bail, Result};
 
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    linters::LintConfig,
+};
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -93,6 +96,8 @@ pub struct CustomDepInfo {
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct BuildInfo {
     pub language_version: Option<Version>,
+    /// Severity of individual lints, e.g. `lints = { share_owned = "error" }`
+    pub lints: LintConfig,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -0,0 +1,118 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_compiler::{diagnostics::report_diagnostics_to_buffer, editions::Flavor};
+use move_package::{compilation::build_plan::BuildPlan, BuildConfig, LintFlag};
+use std::{fs, path::Path};
+use tempfile::tempdir;
+
+const UNBOUNDED_VECTOR_LINT: &str = "Lint W08001";
+
+const SOURCE: &str = r#"
+module a::registry {
+    use std::vector;
+    use sui::object::UID;
+
+    struct Registry has key {
+        id: UID,
+        entries: vector<address>,
+    }
+
+    public fun register(registry: &mut Registry, entry: address) {
+        vector::push_back(&mut registry.entries, entry);
+    }
+}
+
+module std::vector {
+    native public fun push_back<Element>(v: &mut vector<Element>, e: Element);
+}
+
+module sui::object {
+    struct UID has store {
+        id: address,
+    }
+}
+"#;
+
+fn write_package(root: &Path, lints: &str) {
+    fs::create_dir_all(root.join("sources")).unwrap();
+    fs::write(
+        root.join("Move.toml"),
+        format!(
+            r#"[package]
+name = "lints"
+
+[build]
+lints = {{ {lints} }}
+
+[addresses]
+a = "0x0"
+std = "0x1"
+sui = "0x2"
+"#
+        ),
+    )
+    .unwrap();
+    fs::write(root.join("sources").join("registry.move"), SOURCE).unwrap();
+}
+
+/// Builds the package with the given lints table, and returns whether the build succeeded along
+/// with the rendered diagnostics.
+fn build_with_lints(lints: &str) -> (bool, String) {
+    let dir = tempdir().unwrap();
+    let root = dir.path().join("lints");
+    write_package(&root, lints);
+
+    let config = BuildConfig {
+        dev_mode: true,
+        install_dir: Some(dir.path().join("build")),
+        default_flavor: Some(Flavor::Sui),
+        lint_flag: LintFlag::LEVEL_ALL,
+        ..Default::default()
+    };
+    let resolved = config
+        .resolution_graph_for_package(&root, &mut Vec::new())
+        .unwrap();
+
+    let mut succeeded = false;
+    let mut output = String::new();
+    let _ = BuildPlan::create(resolved)
+        .unwrap()
+        .compile_with_driver(&mut Vec::new(), |compiler| {
+            let (files, units_res) = compiler.build()?;
+            let (units, diags) = match units_res {
+                Ok((units, warnings)) => {
+                    succeeded = true;
+                    (units, warnings)
+                }
+                Err(errors) => (vec![], errors),
+            };
+            output = String::from_utf8(report_diagnostics_to_buffer(&files, diags, false))?;
+            if !succeeded {
+                anyhow::bail!("Compilation error");
+            }
+            Ok((files, units))
+        });
+    (succeeded, output)
+}
+
+#[test]
+fn lint_reported_as_warning_by_default() {
+    let (succeeded, output) = build_with_lints("");
+    assert!(succeeded, "{output}");
+    assert!(output.contains(&format!("warning[{UNBOUNDED_VECTOR_LINT}]")));
+}
+
+#[test]
+fn lint_escalated_to_error() {
+    let (succeeded, output) = build_with_lints(r#"unbounded_vector = "error""#);
+    assert!(!succeeded, "{output}");
+    assert!(output.contains(&format!("error[{UNBOUNDED_VECTOR_LINT}]")));
+}
+
+#[test]
+fn lint_suppressed_by_allow() {
+    let (succeeded, output) = build_with_lints(r#"unbounded_vector = "allow""#);
+    assert!(succeeded, "{output}");
+    assert!(!output.contains(UNBOUNDED_VECTOR_LINT), "{output}");
+}
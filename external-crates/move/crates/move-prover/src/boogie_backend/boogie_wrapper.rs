@@ -0,0 +1,114 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs Boogie on a generated program and reports its findings as diagnostics.
+
+use crate::boogie_backend::{options::BoogieOptions, BoogieOutput};
+use anyhow::{anyhow, bail};
+use log::{debug, info};
+use move_model::model::GlobalEnv;
+use std::{collections::BTreeSet, fs, process::Command};
+
+/// The line Boogie ends its output with if it ran to completion.
+const VERIFIER_FINISHED: &str = "Boogie program verifier finished";
+
+/// Writes `output` to `boogie_file`, runs Boogie on it, and reports failed assertions and
+/// timeouts as errors in `env`. Returns an error if Boogie could not be run or rejected the
+/// program.
+pub fn verify_boogie(
+    env: &GlobalEnv,
+    options: &BoogieOptions,
+    output: &BoogieOutput,
+    boogie_file: &str,
+) -> anyhow::Result<()> {
+    fs::write(boogie_file, &output.code)?;
+    let args = options.get_boogie_command(boogie_file)?;
+    info!("running solver");
+    debug!("command line: {}", args.join(" "));
+    let result = Command::new(&args[0]).args(&args[1..]).output();
+    if !options.keep_artifacts {
+        let _ = fs::remove_file(boogie_file);
+    }
+    let result = result.map_err(|e| anyhow!("cannot run Boogie `{}`: {}", args[0], e))?;
+    let out = String::from_utf8_lossy(&result.stdout).to_string()
+        + &String::from_utf8_lossy(&result.stderr);
+    debug!("Boogie output:\n{}", out);
+
+    let mut reported = BTreeSet::new();
+    let mut timed_out = BTreeSet::new();
+    for line in out.lines() {
+        if let Some(id) = failed_assert(line) {
+            match output.asserts.get(id) {
+                Some((loc, msg)) if reported.insert(id) => env.error(loc, msg),
+                Some(_) => {}
+                None => bail!("[internal] Boogie reported an unknown assertion:\n{}", out),
+            }
+        } else if line.contains("timed out") {
+            let function = output
+                .verified_functions
+                .iter()
+                .filter(|(name, _)| line.contains(name.as_str()))
+                .max_by_key(|(name, _)| name.len());
+            if let Some((name, (loc, fun_name))) = function {
+                if timed_out.insert(name) {
+                    env.error(
+                        loc,
+                        &format!(
+                            "verification of `{}` timed out after {} seconds",
+                            fun_name, options.vc_timeout
+                        ),
+                    );
+                }
+            }
+        } else if is_boogie_error(line) {
+            bail!("[internal] Boogie rejected the generated program:\n{}", out);
+        }
+    }
+    if !out.contains(VERIFIER_FINISHED) {
+        bail!("[internal] Boogie did not run to completion:\n{}", out);
+    }
+    Ok(())
+}
+
+/// The index of the assertion a line of Boogie output reports as failed, if any.
+fn failed_assert(line: &str) -> Option<usize> {
+    let (_, rest) = line.split_once("assert_failed(")?;
+    let (id, _) = rest.split_once(')')?;
+    id.parse().ok()
+}
+
+/// Whether a line of Boogie output reports a problem with the program itself, rather than a
+/// verification failure.
+fn is_boogie_error(line: &str) -> bool {
+    line.contains("parse errors detected")
+        || line.contains("type checking errors detected")
+        || line.contains("name resolution errors detected")
+        || (line.contains(": Error") && !line.contains("assert_failed("))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_boogie_output() {
+        assert_eq!(
+            failed_assert("out.bpl(7,5): Error: assert_failed(3): post-condition does not hold"),
+            Some(3)
+        );
+        assert_eq!(
+            failed_assert("Boogie program verifier finished with 1 verified"),
+            None
+        );
+        assert!(is_boogie_error(
+            "1 type checking errors detected in out.bpl"
+        ));
+        assert!(is_boogie_error(
+            "out.bpl(3,1): Error: undeclared identifier: $x"
+        ));
+        assert!(!is_boogie_error(
+            "out.bpl(7,5): Error: assert_failed(3): post-condition does not hold"
+        ));
+    }
+}
@@ -0,0 +1,1567 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Translates the processed bytecode of functions, and the specifications of the functions being
+//! verified, into a Boogie program.
+//!
+//! Every function reachable from a verified function is translated, once per type instantiation,
+//! into an inlined procedure. A mutable reference is a `$Mutation` value, which is passed into
+//! and returned out of a procedure (after its regular results) if it is a parameter. Aborts set
+//! the global `$abort_flag`. Each verified function gets a `$verify` procedure which calls its
+//! implementation and asserts its specification.
+
+use crate::boogie_backend::{
+    options::BoogieOptions,
+    spec::{BinOp, ConditionKind, Exp, ExpKind, FunSpec},
+    template::{self, TypeInstance},
+    BoogieOutput,
+};
+use itertools::Itertools;
+use move_model::{
+    ast::{TempIndex, Value},
+    code_writer::CodeWriter,
+    emitln,
+    model::{FunId, FunctionEnv, GlobalEnv, Loc, ModuleEnv, ModuleId, QualifiedId, StructId},
+    ty::{PrimitiveType, Type},
+};
+use move_stackless_bytecode::{
+    function_target::FunctionTarget,
+    function_target_pipeline::{FunctionTargetsHolder, FunctionVariant},
+    stackless_bytecode::{
+        AbortAction, BorrowEdge, BorrowNode, Bytecode, Constant, HavocKind, IndexEdgeKind,
+        Operation,
+    },
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+const PRELUDE_TEMPLATE: &str = include_str!("prelude.bpl");
+
+/// The instance list of the vector natives in the prelude.
+const VEC_INSTANCES: &str = "vec_instances";
+
+/// The integer types, with the suffix of the operations on them in the prelude.
+const INT_TYPES: &[(&str, &str)] = &[
+    ("u8", "U8"),
+    ("u16", "U16"),
+    ("u32", "U32"),
+    ("u64", "U64"),
+    ("u128", "U128"),
+    ("u256", "U256"),
+];
+
+pub struct BoogieTranslator<'env> {
+    env: &'env GlobalEnv,
+    options: &'env BoogieOptions,
+    targets: &'env FunctionTargetsHolder,
+    writer: CodeWriter,
+    /// Function instances which have been scheduled for translation.
+    scheduled: BTreeSet<(QualifiedId<FunId>, Vec<Type>)>,
+    todo: Vec<(QualifiedId<FunId>, Vec<Type>)>,
+    /// Types which need a Boogie declaration.
+    types: BTreeSet<Type>,
+    /// Types native models are instantiated with, by the name of their instance list.
+    native_instances: BTreeMap<String, BTreeSet<Type>>,
+    /// Procedures of native models which are called, with the location of a call and the name
+    /// of the native function.
+    native_calls: BTreeMap<String, (Loc, String)>,
+    /// Maps native functions (`0x2::transfer::transfer_impl`) and modules (`0x2::event`) to
+    /// their instance list and whether only structs are valid instances.
+    native_lists: BTreeMap<String, (String, bool)>,
+    output: BoogieOutput,
+}
+
+impl<'env> BoogieTranslator<'env> {
+    pub fn new(
+        env: &'env GlobalEnv,
+        options: &'env BoogieOptions,
+        targets: &'env FunctionTargetsHolder,
+    ) -> Self {
+        let mut native_lists = BTreeMap::from([(
+            "0x1::vector".to_string(),
+            (VEC_INSTANCES.to_string(), false),
+        )]);
+        if let Some(custom) = &options.custom_natives {
+            for (name, list, objects_only) in &custom.native_instances {
+                native_lists.insert(name.clone(), (list.clone(), *objects_only));
+            }
+        }
+        Self {
+            env,
+            options,
+            targets,
+            writer: CodeWriter::new(env.internal_loc()),
+            scheduled: BTreeSet::new(),
+            todo: vec![],
+            types: BTreeSet::new(),
+            native_instances: BTreeMap::new(),
+            native_calls: BTreeMap::new(),
+            native_lists,
+            output: BoogieOutput::default(),
+        }
+    }
+
+    /// Translates the given functions with their specifications, and everything they depend on.
+    pub fn translate(
+        mut self,
+        specs: &BTreeMap<QualifiedId<FunId>, FunSpec>,
+    ) -> anyhow::Result<BoogieOutput> {
+        for (qid, spec) in specs {
+            let fun_env = self.env.get_function_qid(*qid);
+            if fun_env.is_native() {
+                self.env.error(
+                    &spec.loc,
+                    "specifications of native functions cannot be verified",
+                );
+                continue;
+            }
+            self.translate_verify(&fun_env, spec);
+        }
+        while let Some((qid, inst)) = self.todo.pop() {
+            self.translate_function(qid, &inst);
+        }
+
+        let types = self.type_closure();
+        let mut instances: BTreeMap<String, Vec<TypeInstance>> = BTreeMap::new();
+        instances.insert(
+            "int_instances".to_string(),
+            INT_TYPES
+                .iter()
+                .map(|(name, suffix)| TypeInstance {
+                    name: name.to_string(),
+                    suffix: suffix.to_string(),
+                })
+                .collect(),
+        );
+        let vec_elems = types.iter().filter_map(|ty| match ty {
+            Type::Vector(elem) => Some(elem.as_ref().clone()),
+            _ => None,
+        });
+        let mut native_instances = std::mem::take(&mut self.native_instances);
+        native_instances
+            .entry(VEC_INSTANCES.to_string())
+            .or_default()
+            .extend(vec_elems);
+        for (list, tys) in native_instances {
+            let list_instances = tys
+                .iter()
+                .map(|ty| TypeInstance {
+                    name: boogie_type(self.env, ty),
+                    suffix: boogie_type_suffix(self.env, ty),
+                })
+                .collect();
+            instances.insert(list, list_instances);
+        }
+
+        let mut code = String::new();
+        code.push_str("\n// ** Expanded prelude\n\n");
+        code.push_str(&template::render(PRELUDE_TEMPLATE, &instances)?);
+        if let Some(custom) = &self.options.custom_natives {
+            code.push_str("\n\n// ** Native models\n\n");
+            let custom_template = String::from_utf8_lossy(&custom.template_bytes);
+            code.push_str(&template::render(&custom_template, &instances)?);
+        }
+        let declared = declared_procedures(&code);
+        for (name, (loc, native)) in &self.native_calls {
+            if !declared.contains(name) {
+                self.env.error(
+                    loc,
+                    &format!("no Boogie model for native function `{}`", native),
+                );
+            }
+        }
+
+        code.push_str("\n\n// ** Types\n\n");
+        let type_writer = CodeWriter::new(self.env.internal_loc());
+        for ty in &types {
+            self.translate_type(&type_writer, ty);
+        }
+        code.push_str(&type_writer.extract_result());
+        code.push_str("\n// ** Functions\n\n");
+        code.push_str(&self.writer.extract_result());
+        self.output.code = code;
+        Ok(self.output)
+    }
+
+    /// Schedules a function instance for translation and returns the name of its procedure.
+    fn schedule(&mut self, qid: QualifiedId<FunId>, inst: Vec<Type>) -> String {
+        let fun_env = self.env.get_function_qid(qid);
+        let name = boogie_function_name(&fun_env, &inst);
+        if !fun_env.is_native() && self.scheduled.insert((qid, inst.clone())) {
+            self.todo.push((qid, inst));
+        }
+        name
+    }
+
+    /// Records a type, returning its Boogie type.
+    fn add_type(&mut self, ty: &Type) -> String {
+        self.types.insert(ty.clone());
+        boogie_type(self.env, ty)
+    }
+
+    /// Records the call of a native function, returning the name of its procedure.
+    fn add_native_call(&mut self, fun_env: &FunctionEnv, inst: &[Type], loc: Loc) -> String {
+        let module_env = &fun_env.module_env;
+        let module_name = format!(
+            "0x{:x}::{}",
+            module_env.get_name().addr(),
+            module_env.get_name().name().display(self.env.symbol_pool())
+        );
+        let fun_name = format!(
+            "{}::{}",
+            module_name,
+            fun_env.get_name().display(self.env.symbol_pool())
+        );
+        let list = self
+            .native_lists
+            .get(&fun_name)
+            .or_else(|| self.native_lists.get(&module_name))
+            .cloned();
+        if let (Some((list, objects_only)), [ty]) = (list, inst) {
+            if !objects_only || ty.is_struct() {
+                self.add_type(ty);
+                self.native_instances
+                    .entry(list)
+                    .or_default()
+                    .insert(ty.clone());
+            }
+        }
+        let name = boogie_function_name(fun_env, inst);
+        self.native_calls
+            .entry(name.clone())
+            .or_insert((loc, fun_name));
+        name
+    }
+
+    /// Computes the types to declare: all recorded types and their components.
+    fn type_closure(&self) -> BTreeSet<Type> {
+        let mut result = BTreeSet::new();
+        let mut todo = self.types.iter().cloned().collect_vec();
+        while let Some(ty) = todo.pop() {
+            if !result.insert(ty.clone()) {
+                continue;
+            }
+            match &ty {
+                Type::Vector(elem) => todo.push(elem.as_ref().clone()),
+                Type::Reference(_, inner) => todo.push(inner.as_ref().clone()),
+                Type::Struct(mid, sid, inst) => {
+                    let struct_env = self.env.get_struct_qid(mid.qualified(*sid));
+                    todo.extend(
+                        struct_env
+                            .get_fields()
+                            .map(|f| f.get_type().instantiate(inst)),
+                    );
+                    todo.extend(inst.iter().cloned());
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    fn translate_type(&self, writer: &CodeWriter, ty: &Type) {
+        let env = self.env;
+        let name = boogie_type(env, ty);
+        let suffix = boogie_type_suffix(env, ty);
+        match ty {
+            Type::TypeParameter(_) => {
+                emitln!(writer, "type {};", name);
+                emitln!(
+                    writer,
+                    "function {{:inline}} $IsValid'{}'(v: {}): bool {{ true }}",
+                    suffix,
+                    name
+                );
+                emitln!(
+                    writer,
+                    "function {{:inline}} $IsEqual'{}'(x: {}, y: {}): bool {{ x == y }}",
+                    suffix,
+                    name,
+                    name
+                );
+                emitln!(writer);
+            }
+            Type::Vector(elem) => {
+                let elem_suffix = boogie_type_suffix(env, elem);
+                emitln!(
+                    writer,
+                    "function {{:inline}} $IsValid'{}'(v: {}): bool {{",
+                    suffix,
+                    name
+                );
+                emitln!(
+                    writer,
+                    "    $IsValid'u64'(LenVec(v)) && \
+                     (forall i: int :: InRangeVec(v, i) ==> $IsValid'{}'(ReadVec(v, i)))",
+                    elem_suffix
+                );
+                emitln!(writer, "}");
+                emitln!(
+                    writer,
+                    "function {{:inline}} $IsEqual'{}'(v1: {}, v2: {}): bool {{",
+                    suffix,
+                    name,
+                    name
+                );
+                emitln!(
+                    writer,
+                    "    LenVec(v1) == LenVec(v2) && (forall i: int :: InRangeVec(v1, i) ==> \
+                     $IsEqual'{}'(ReadVec(v1, i), ReadVec(v2, i)))",
+                    elem_suffix
+                );
+                emitln!(writer, "}");
+                emitln!(writer);
+            }
+            Type::Struct(mid, sid, inst) => {
+                let struct_env = env.get_struct_qid(mid.qualified(*sid));
+                let mut fields = struct_env.get_fields().collect_vec();
+                fields.sort_by_key(|f| f.get_offset());
+                let fields = fields
+                    .iter()
+                    .map(|f| {
+                        let ty = f.get_type().instantiate(inst);
+                        (
+                            f.get_name().display(env.symbol_pool()).to_string(),
+                            boogie_type(env, &ty),
+                            boogie_type_suffix(env, &ty),
+                        )
+                    })
+                    .collect_vec();
+                emitln!(
+                    writer,
+                    "// struct {}",
+                    ty.display(&env.get_type_display_ctx())
+                );
+                emitln!(writer, "type {{:datatype}} {};", name);
+                emitln!(
+                    writer,
+                    "function {{:constructor}} {}({}): {};",
+                    name,
+                    fields
+                        .iter()
+                        .map(|(f, t, _)| format!("${}: {}", f, t))
+                        .join(", "),
+                    name
+                );
+                for (f, t, _) in &fields {
+                    let args = fields
+                        .iter()
+                        .map(|(g, _, _)| {
+                            if g == f {
+                                "x".to_string()
+                            } else {
+                                format!("${}#{}(s)", g, name)
+                            }
+                        })
+                        .join(", ");
+                    emitln!(
+                        writer,
+                        "function {{:inline}} $Update'{}'_{}(s: {}, x: {}): {} {{ {}({}) }}",
+                        suffix,
+                        f,
+                        name,
+                        t,
+                        name,
+                        name,
+                        args
+                    );
+                }
+                emitln!(
+                    writer,
+                    "function {{:inline}} $IsValid'{}'(s: {}): bool {{",
+                    suffix,
+                    name
+                );
+                emitln!(
+                    writer,
+                    "    {}",
+                    fields
+                        .iter()
+                        .map(|(f, _, s)| format!("$IsValid'{}'(${}#{}(s))", s, f, name))
+                        .join(" && ")
+                );
+                emitln!(writer, "}");
+                emitln!(
+                    writer,
+                    "function {{:inline}} $IsEqual'{}'(s1: {}, s2: {}): bool {{",
+                    suffix,
+                    name,
+                    name
+                );
+                emitln!(
+                    writer,
+                    "    {}",
+                    fields
+                        .iter()
+                        .map(|(f, _, s)| format!(
+                            "$IsEqual'{}'(${}#{}(s1), ${}#{}(s2))",
+                            s, f, name, f, name
+                        ))
+                        .join(" && ")
+                );
+                emitln!(writer, "}");
+                emitln!(writer);
+            }
+            // Primitive types are declared in the prelude, references have no declarations.
+            _ => {}
+        }
+    }
+
+    fn translate_function(&mut self, qid: QualifiedId<FunId>, inst: &[Type]) {
+        let fun_env = self.env.get_function_qid(qid);
+        let target = self
+            .targets
+            .get_target(&fun_env, &FunctionVariant::Baseline);
+        let mut_params = (0..target.get_parameter_count())
+            .filter(|i| target.get_local_type(*i).is_mutable_reference())
+            .collect_vec();
+        let mut translator = FunctionTranslator {
+            parent: self,
+            target: &target,
+            inst,
+            mut_params: &mut_params,
+            stmts: vec![],
+            extra_locals: vec![],
+        };
+        translator.translate();
+        let FunctionTranslator {
+            stmts,
+            extra_locals,
+            ..
+        } = translator;
+
+        let local_type = |this: &mut Self, i: TempIndex| {
+            this.add_type(&target.get_local_type(i).instantiate(inst))
+        };
+        let params = (0..target.get_parameter_count())
+            .map(|i| format!("_$t{}: {}", i, local_type(self, i)))
+            .collect_vec();
+        let rets = target
+            .get_return_types()
+            .iter()
+            .map(|ty| ty.instantiate(inst))
+            .chain(
+                mut_params
+                    .iter()
+                    .map(|i| target.get_local_type(*i).instantiate(inst)),
+            )
+            .collect_vec()
+            .into_iter()
+            .enumerate()
+            .map(|(i, ty)| format!("$ret{}: {}", i, self.add_type(&ty)))
+            .collect_vec();
+        let mut locals = (0..target.get_local_count())
+            .map(|i| format!("$t{}: {}", i, local_type(self, i)))
+            .collect_vec();
+        for (name, ty) in extra_locals {
+            locals.push(format!("{}: {}", name, self.add_type(&ty)));
+        }
+
+        let writer = &self.writer;
+        emitln!(writer, "// fun {}", fun_env.get_full_name_str());
+        let returns = if rets.is_empty() {
+            String::new()
+        } else {
+            format!(" returns ({})", rets.join(", "))
+        };
+        emitln!(
+            writer,
+            "procedure {{:inline 1}} {}({}){} {{",
+            boogie_function_name(&fun_env, inst),
+            params.join(", "),
+            returns
+        );
+        writer.indent();
+        for local in locals {
+            emitln!(writer, "var {};", local);
+        }
+        for i in 0..target.get_parameter_count() {
+            emitln!(writer, "$t{} := _$t{};", i, i);
+        }
+        for stmt in stmts {
+            emitln!(writer, "{}", stmt);
+        }
+        writer.unindent();
+        emitln!(writer, "}");
+        emitln!(writer);
+    }
+
+    /// Translates the verification procedure of a function, which asserts its specification.
+    fn translate_verify(&mut self, fun_env: &FunctionEnv, spec: &FunSpec) {
+        let env = self.env;
+        let inst = (0..fun_env.get_type_parameter_count())
+            .map(|i| Type::TypeParameter(i as u16))
+            .collect_vec();
+        let params = fun_env.get_parameters();
+        let return_types = fun_env.get_return_types();
+        let mut_params = params
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.1.is_mutable_reference())
+            .map(|(i, _)| i)
+            .collect_vec();
+        let ctx = SpecContext {
+            fun_env,
+            params: params
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let value = if p.1.is_mutable_reference() {
+                        let out =
+                            return_types.len() + mut_params.iter().position(|j| *j == i).unwrap();
+                        Some(format!("$Dereference($ret{})", out))
+                    } else {
+                        None
+                    };
+                    (
+                        p.0.display(env.symbol_pool()).to_string(),
+                        p.1.skip_reference().clone(),
+                        value,
+                    )
+                })
+                .collect(),
+            results: return_types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| {
+                    let exp = if ty.is_mutable_reference() {
+                        format!("$Dereference($ret{})", i)
+                    } else {
+                        format!("$ret{}", i)
+                    };
+                    (exp, ty.skip_reference().clone())
+                })
+                .collect(),
+        };
+
+        let mut requires = vec![];
+        let mut aborts_if = vec![];
+        let mut ensures = vec![];
+        let mut ok = true;
+        for cond in &spec.conditions {
+            let in_post = cond.kind == ConditionKind::Ensures;
+            match self.translate_spec_exp(&ctx, &cond.exp, in_post) {
+                Some((exp, Type::Primitive(PrimitiveType::Bool))) => match cond.kind {
+                    ConditionKind::Requires => requires.push(exp),
+                    ConditionKind::AbortsIf => aborts_if.push((cond.loc.clone(), exp)),
+                    ConditionKind::Ensures => ensures.push((cond.loc.clone(), exp)),
+                },
+                Some(_) => {
+                    env.error(&cond.exp.loc, "expected a boolean condition");
+                    ok = false;
+                }
+                None => ok = false,
+            }
+        }
+        if !ok {
+            return;
+        }
+
+        let impl_name = self.schedule(fun_env.get_qualified_id(), inst);
+        let verify_name = format!("{}$verify", impl_name);
+        self.output.verified_functions.insert(
+            verify_name.clone(),
+            (fun_env.get_loc(), fun_env.get_full_name_str()),
+        );
+        let param_types = params
+            .iter()
+            .map(|p| self.add_type(p.1.skip_reference()))
+            .collect_vec();
+        let ret_types = return_types
+            .iter()
+            .chain(mut_params.iter().map(|i| &params[*i].1))
+            .map(|ty| {
+                if ty.is_mutable_reference() {
+                    self.add_type(ty)
+                } else {
+                    self.add_type(ty.skip_reference())
+                }
+            })
+            .collect_vec();
+
+        let writer = &self.writer;
+        emitln!(
+            writer,
+            "// verification of fun {}",
+            fun_env.get_full_name_str()
+        );
+        emitln!(
+            writer,
+            "procedure {}({}) {{",
+            verify_name,
+            param_types
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| format!("_$t{}: {}", i, ty))
+                .join(", ")
+        );
+        writer.indent();
+        for i in &mut_params {
+            emitln!(writer, "var $m{}: $Mutation ({});", i, param_types[*i]);
+        }
+        for (i, ty) in ret_types.iter().enumerate() {
+            emitln!(writer, "var $ret{}: {};", i, ty);
+        }
+        for (i, p) in params.iter().enumerate() {
+            emitln!(
+                writer,
+                "assume $IsValid'{}'(_$t{});",
+                boogie_type_suffix(env, p.1.skip_reference()),
+                i
+            );
+        }
+        for exp in requires {
+            emitln!(writer, "assume {};", exp);
+        }
+        for i in &mut_params {
+            emitln!(
+                writer,
+                "$m{} := $Mutation($Param({}), EmptyVec(), _$t{});",
+                i,
+                i,
+                i
+            );
+        }
+        emitln!(writer, "$abort_flag := false;");
+        let args = (0..params.len())
+            .map(|i| {
+                if mut_params.contains(&i) {
+                    format!("$m{}", i)
+                } else {
+                    format!("_$t{}", i)
+                }
+            })
+            .join(", ");
+        if ret_types.is_empty() {
+            emitln!(writer, "call {}({});", impl_name, args);
+        } else {
+            emitln!(
+                writer,
+                "call {} := {}({});",
+                (0..ret_types.len())
+                    .map(|i| format!("$ret{}", i))
+                    .join(", "),
+                impl_name,
+                args
+            );
+        }
+        // Without `aborts_if` conditions, only the post-conditions of returns are checked.
+        let asserts = &mut self.output.asserts;
+        if aborts_if.is_empty() {
+            emitln!(writer, "if (!$abort_flag) {");
+        } else {
+            let msg = "abort not covered by any of the `aborts_if` clauses";
+            let id = add_assert(asserts, spec.loc.clone(), msg);
+            let exp = aborts_if.iter().map(|(_, e)| e).join(" || ");
+            emitln!(writer, "if ($abort_flag) {");
+            writer.indent();
+            emitln!(
+                writer,
+                "assert {{:msg \"assert_failed({}): {}\"}} {};",
+                id,
+                msg,
+                exp
+            );
+            writer.unindent();
+            emitln!(writer, "} else {");
+        }
+        writer.indent();
+        for (loc, exp) in aborts_if {
+            let msg = "function does not abort under this condition";
+            let id = add_assert(asserts, loc, msg);
+            emitln!(
+                writer,
+                "assert {{:msg \"assert_failed({}): {}\"}} !({});",
+                id,
+                msg,
+                exp
+            );
+        }
+        for (loc, exp) in ensures {
+            let msg = "post-condition does not hold";
+            let id = add_assert(asserts, loc, msg);
+            emitln!(
+                writer,
+                "assert {{:msg \"assert_failed({}): {}\"}} {};",
+                id,
+                msg,
+                exp
+            );
+        }
+        writer.unindent();
+        emitln!(writer, "}");
+        writer.unindent();
+        emitln!(writer, "}");
+        emitln!(writer);
+    }
+
+    /// Translates a specification expression into a Boogie expression and its type. Reports an
+    /// error and returns `None` if the expression is not well-typed.
+    fn translate_spec_exp(
+        &mut self,
+        ctx: &SpecContext,
+        exp: &Exp,
+        in_post: bool,
+    ) -> Option<(String, Type)> {
+        let env = self.env;
+        let num_ty = Type::Primitive(PrimitiveType::Num);
+        let bool_ty = Type::Primitive(PrimitiveType::Bool);
+        let error = |msg: &str| {
+            env.error(&exp.loc, msg);
+            None
+        };
+        match &exp.kind {
+            ExpKind::Bool(b) => Some((b.to_string(), bool_ty)),
+            ExpKind::Number(n) => Some((n.clone(), num_ty)),
+            ExpKind::Address(n) => Some((n.clone(), Type::Primitive(PrimitiveType::Address))),
+            ExpKind::Name(name) => self.translate_spec_name(ctx, exp, name, in_post),
+            ExpKind::Old(e) => self.translate_spec_exp(ctx, e, false),
+            ExpKind::Len(e) => match self.translate_spec_exp(ctx, e, in_post)? {
+                (e, Type::Vector(_)) => Some((format!("LenVec({})", e), num_ty)),
+                _ => error("expected a vector"),
+            },
+            ExpKind::Field(e, field) => {
+                let (e, ty) = self.translate_spec_exp(ctx, e, in_post)?;
+                let Type::Struct(mid, sid, inst) = &ty else {
+                    return error("expected a struct");
+                };
+                let struct_env = env.get_struct_qid(mid.qualified(*sid));
+                let field_env = struct_env
+                    .get_fields()
+                    .find(|f| f.get_name().display(env.symbol_pool()).to_string() == *field);
+                let Some(field_env) = field_env else {
+                    return error(&format!(
+                        "struct `{}` has no field `{}`",
+                        struct_env.get_full_name_str(),
+                        field
+                    ));
+                };
+                let field_ty = field_env.get_type().instantiate(inst);
+                let struct_name = self.add_type(&ty);
+                self.add_type(&field_ty);
+                Some((format!("${}#{}({})", field, struct_name, e), field_ty))
+            }
+            ExpKind::Index(e, i) => {
+                let (e, ty) = self.translate_spec_exp(ctx, e, in_post)?;
+                let (i, index_ty) = self.translate_spec_exp(ctx, i, in_post)?;
+                match ty {
+                    Type::Vector(elem) if index_ty.is_number() => {
+                        self.add_type(&elem);
+                        Some((format!("ReadVec({}, {})", e, i), *elem))
+                    }
+                    Type::Vector(_) => error("expected a number as index"),
+                    _ => error("expected a vector"),
+                }
+            }
+            ExpKind::Not(e) => match self.translate_spec_exp(ctx, e, in_post)? {
+                (e, ty) if ty == bool_ty => Some((format!("!({})", e), bool_ty)),
+                _ => error("expected a boolean"),
+            },
+            ExpKind::Binary(op, lhs, rhs) => {
+                let (l, lty) = self.translate_spec_exp(ctx, lhs, in_post)?;
+                let (r, rty) = self.translate_spec_exp(ctx, rhs, in_post)?;
+                use BinOp::*;
+                match op {
+                    Iff | Implies | Or | And => {
+                        if lty != bool_ty || rty != bool_ty {
+                            return error("expected booleans");
+                        }
+                        let op = match op {
+                            Iff => "<==>",
+                            Implies => "==>",
+                            Or => "||",
+                            _ => "&&",
+                        };
+                        Some((format!("({} {} {})", l, op, r), bool_ty))
+                    }
+                    Eq | Neq => {
+                        let eq = if lty.is_number() && rty.is_number() {
+                            format!("({} == {})", l, r)
+                        } else if lty != rty {
+                            return error("cannot compare values of different types");
+                        } else if matches!(lty, Type::Primitive(_)) {
+                            format!("({} == {})", l, r)
+                        } else {
+                            self.add_type(&lty);
+                            format!("$IsEqual'{}'({}, {})", boogie_type_suffix(env, &lty), l, r)
+                        };
+                        let exp = if *op == Eq { eq } else { format!("!{}", eq) };
+                        Some((exp, bool_ty))
+                    }
+                    Lt | Gt | Le | Ge | Add | Sub | Mul | Div | Mod => {
+                        if !lty.is_number() || !rty.is_number() {
+                            return error("expected numbers");
+                        }
+                        let (op, ty) = match op {
+                            Lt => ("<", bool_ty),
+                            Gt => (">", bool_ty),
+                            Le => ("<=", bool_ty),
+                            Ge => (">=", bool_ty),
+                            Add => ("+", num_ty),
+                            Sub => ("-", num_ty),
+                            Mul => ("*", num_ty),
+                            Div => ("div", num_ty),
+                            _ => ("mod", num_ty),
+                        };
+                        Some((format!("({} {} {})", l, op, r), ty))
+                    }
+                }
+            }
+            ExpKind::IfElse(c, t, e) => {
+                let (c, cty) = self.translate_spec_exp(ctx, c, in_post)?;
+                let (t, tty) = self.translate_spec_exp(ctx, t, in_post)?;
+                let (e, ety) = self.translate_spec_exp(ctx, e, in_post)?;
+                if cty != bool_ty {
+                    return error("expected a boolean condition");
+                }
+                let ty = if tty.is_number() && ety.is_number() {
+                    num_ty
+                } else if tty == ety {
+                    tty
+                } else {
+                    return error("branches have different types");
+                };
+                Some((format!("(if {} then {} else {})", c, t, e), ty))
+            }
+        }
+    }
+
+    fn translate_spec_name(
+        &mut self,
+        ctx: &SpecContext,
+        exp: &Exp,
+        name: &str,
+        in_post: bool,
+    ) -> Option<(String, Type)> {
+        let env = self.env;
+        if let Some(i) = ctx.params.iter().position(|(n, _, _)| n == name) {
+            let (_, ty, post_value) = &ctx.params[i];
+            self.add_type(ty);
+            let value = match post_value {
+                Some(value) if in_post => value.clone(),
+                _ => format!("_$t{}", i),
+            };
+            return Some((value, ty.clone()));
+        }
+        let result_index = match name.strip_prefix("result") {
+            Some("") if ctx.results.len() == 1 => Some(0),
+            Some(n) if ctx.results.len() > 1 => n
+                .strip_prefix('_')
+                .and_then(|n| n.parse::<usize>().ok())
+                .filter(|n| *n >= 1 && *n <= ctx.results.len())
+                .map(|n| n - 1),
+            _ => None,
+        };
+        if let Some(i) = result_index {
+            if !in_post {
+                env.error(
+                    &exp.loc,
+                    "`result` can only be used in `ensures` conditions",
+                );
+                return None;
+            }
+            let (value, ty) = ctx.results[i].clone();
+            self.add_type(&ty);
+            return Some((value, ty));
+        }
+        if let Some((_, suffix)) = INT_TYPES
+            .iter()
+            .find(|(_, suffix)| name.strip_prefix("MAX_") == Some(*suffix))
+        {
+            return Some((
+                format!("$MAX_{}()", suffix),
+                Type::Primitive(PrimitiveType::Num),
+            ));
+        }
+        let module_env = &ctx.fun_env.module_env;
+        let constant = module_env.find_named_constant(env.symbol_pool().make(name));
+        if let Some(constant) = constant {
+            let ty = constant.get_type();
+            let value = match (constant.get_value(), &ty) {
+                (Value::Number(n), _) => n.to_string(),
+                (Value::Bool(b), _) => b.to_string(),
+                (Value::Address(a), _) => a.to_string(),
+                (Value::ByteArray(bytes), _) => self.vector_literal(
+                    &Type::Primitive(PrimitiveType::U8),
+                    bytes.iter().map(|b| b.to_string()).collect(),
+                ),
+                (Value::AddressArray(addrs), _) => self.vector_literal(
+                    &Type::Primitive(PrimitiveType::Address),
+                    addrs.iter().map(|a| a.to_string()).collect(),
+                ),
+                (Value::Vector(_), _) => {
+                    env.error(
+                        &exp.loc,
+                        "vector constants are not supported in specifications",
+                    );
+                    return None;
+                }
+            };
+            return Some((value, ty));
+        }
+        env.error(&exp.loc, &format!("unbound name `{}`", name));
+        None
+    }
+
+    /// A vector with the given elements.
+    fn vector_literal(&mut self, elem_ty: &Type, elems: Vec<String>) -> String {
+        self.add_type(&Type::Vector(Box::new(elem_ty.clone())));
+        let empty = format!("$EmptyVec'{}'()", boogie_type_suffix(self.env, elem_ty));
+        elems
+            .into_iter()
+            .fold(empty, |v, e| format!("ExtendVec({}, {})", v, e))
+    }
+}
+
+/// The names, types, and (for `&mut` parameters) post-state values of the parameters of a
+/// verified function, and the values and types of its results.
+struct SpecContext<'a> {
+    fun_env: &'a FunctionEnv<'a>,
+    params: Vec<(String, Type, Option<String>)>,
+    results: Vec<(String, Type)>,
+}
+
+struct FunctionTranslator<'a, 'env> {
+    parent: &'a mut BoogieTranslator<'env>,
+    target: &'a FunctionTarget<'a>,
+    inst: &'a [Type],
+    mut_params: &'a [TempIndex],
+    stmts: Vec<String>,
+    /// Locals introduced by the translation, with their types.
+    extra_locals: Vec<(String, Type)>,
+}
+
+impl<'a, 'env> FunctionTranslator<'a, 'env> {
+    fn translate(&mut self) {
+        for bytecode in self.target.get_bytecode() {
+            self.translate_bytecode(bytecode);
+        }
+    }
+
+    fn local_type(&self, idx: TempIndex) -> Type {
+        self.target.get_local_type(idx).instantiate(self.inst)
+    }
+
+    fn emit(&mut self, stmt: String) {
+        self.stmts.push(stmt)
+    }
+
+    fn error(&self, attr_id: move_stackless_bytecode::stackless_bytecode::AttrId, msg: &str) {
+        let env = self.parent.env;
+        env.error(&self.target.get_bytecode_loc(attr_id), msg);
+    }
+
+    fn new_local(&mut self, ty: Type) -> String {
+        let name = format!("$tmp{}", self.extra_locals.len());
+        self.extra_locals.push((name.clone(), ty));
+        name
+    }
+
+    /// The value of a temporary, dereferenced if it is a mutable reference.
+    fn value(&self, idx: TempIndex) -> String {
+        if self.local_type(idx).is_mutable_reference() {
+            format!("$Dereference($t{})", idx)
+        } else {
+            format!("$t{}", idx)
+        }
+    }
+
+    fn emit_abort_check(&mut self, aa: &Option<AbortAction>) {
+        match aa {
+            Some(AbortAction(label, code)) => self.emit(format!(
+                "if ($abort_flag) {{ $t{} := $abort_code; goto L{}; }}",
+                code,
+                label.as_usize()
+            )),
+            None => self.emit("if ($abort_flag) { return; }".to_string()),
+        }
+    }
+
+    fn translate_bytecode(&mut self, bytecode: &Bytecode) {
+        use Bytecode::*;
+        match bytecode {
+            Assign(_, dest, src, _) => self.emit(format!("$t{} := $t{};", dest, src)),
+            Load(_, dest, constant) => {
+                let value = self.translate_constant(constant, &self.local_type(*dest));
+                self.emit(format!("$t{} := {};", dest, value))
+            }
+            Branch(_, then_label, else_label, cond) => self.emit(format!(
+                "if ($t{}) {{ goto L{}; }} else {{ goto L{}; }}",
+                cond,
+                then_label.as_usize(),
+                else_label.as_usize()
+            )),
+            Jump(_, label) => self.emit(format!("goto L{};", label.as_usize())),
+            Label(_, label) => self.emit(format!("L{}:", label.as_usize())),
+            Ret(_, rets) => {
+                let outs = rets
+                    .iter()
+                    .chain(self.mut_params.iter())
+                    .copied()
+                    .collect_vec();
+                for (i, temp) in outs.into_iter().enumerate() {
+                    self.emit(format!("$ret{} := $t{};", i, temp));
+                }
+                self.emit("return;".to_string())
+            }
+            Abort(_, code) => {
+                self.emit(format!("$abort_code := $t{};", code));
+                self.emit("$abort_flag := true;".to_string());
+                self.emit("return;".to_string())
+            }
+            Nop(_) => {}
+            Call(attr_id, dests, op, srcs, aa) => {
+                self.translate_call(*attr_id, dests, op, srcs, aa)
+            }
+        }
+    }
+
+    fn translate_call(
+        &mut self,
+        attr_id: move_stackless_bytecode::stackless_bytecode::AttrId,
+        dests: &[TempIndex],
+        op: &Operation,
+        srcs: &[TempIndex],
+        aa: &Option<AbortAction>,
+    ) {
+        use Operation::*;
+        let env = self.parent.env;
+        let src_list = || srcs.iter().map(|s| format!("$t{}", s)).join(", ");
+        match op {
+            Function(mid, fid, targs) => {
+                let callee_env = env.get_function_qid(mid.qualified(*fid));
+                let inst = Type::instantiate_slice(targs, self.inst);
+                let name = if callee_env.is_native() {
+                    let loc = self.target.get_bytecode_loc(attr_id);
+                    self.parent.add_native_call(&callee_env, &inst, loc)
+                } else {
+                    self.parent.schedule(callee_env.get_qualified_id(), inst)
+                };
+                let outs = dests
+                    .iter()
+                    .copied()
+                    .chain(
+                        callee_env
+                            .get_parameters()
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, p)| p.1.is_mutable_reference())
+                            .map(|(i, _)| srcs[i]),
+                    )
+                    .map(|t| format!("$t{}", t))
+                    .collect_vec();
+                if outs.is_empty() {
+                    self.emit(format!("call {}({});", name, src_list()));
+                } else {
+                    self.emit(format!(
+                        "call {} := {}({});",
+                        outs.join(", "),
+                        name,
+                        src_list()
+                    ));
+                }
+                self.emit_abort_check(aa)
+            }
+            Pack(mid, sid, targs) => {
+                let ty = Type::Struct(*mid, *sid, Type::instantiate_slice(targs, self.inst));
+                let name = self.parent.add_type(&ty);
+                self.emit(format!("$t{} := {}({});", dests[0], name, src_list()))
+            }
+            Unpack(mid, sid, targs) => {
+                let ty = Type::Struct(*mid, *sid, Type::instantiate_slice(targs, self.inst));
+                let name = self.parent.add_type(&ty);
+                for (offset, dest) in dests.iter().enumerate() {
+                    let field = field_name(env, *mid, *sid, offset);
+                    self.emit(format!("$t{} := ${}#{}($t{});", dest, field, name, srcs[0]))
+                }
+            }
+            BorrowLoc => self.emit(format!(
+                "$t{} := $Mutation($Local({}), EmptyVec(), $t{});",
+                dests[0], srcs[0], srcs[0]
+            )),
+            BorrowField(mid, sid, targs, offset) => {
+                let ty = Type::Struct(*mid, *sid, Type::instantiate_slice(targs, self.inst));
+                let name = self.parent.add_type(&ty);
+                let field = field_name(env, *mid, *sid, *offset);
+                self.emit(format!(
+                    "$t{} := $ChildMutation($t{}, {}, ${}#{}($Dereference($t{})));",
+                    dests[0], srcs[0], offset, field, name, srcs[0]
+                ))
+            }
+            GetField(mid, sid, targs, offset) => {
+                let ty = Type::Struct(*mid, *sid, Type::instantiate_slice(targs, self.inst));
+                let name = self.parent.add_type(&ty);
+                let field = field_name(env, *mid, *sid, *offset);
+                let value = self.value(srcs[0]);
+                self.emit(format!("$t{} := ${}#{}({});", dests[0], field, name, value))
+            }
+            ReadRef | FreezeRef => {
+                let value = self.value(srcs[0]);
+                self.emit(format!("$t{} := {};", dests[0], value))
+            }
+            WriteRef => self.emit(format!(
+                "$t{} := $UpdateMutation($t{}, $t{});",
+                srcs[0], srcs[0], srcs[1]
+            )),
+            Havoc(kind) => {
+                let idx = dests.first().or(srcs.first()).copied().unwrap();
+                let ty = self.local_type(idx);
+                let suffix = boogie_type_suffix(env, ty.skip_reference());
+                self.parent.add_type(&ty);
+                match kind {
+                    HavocKind::Value | HavocKind::MutationAll => {
+                        self.emit(format!("havoc $t{};", idx));
+                        let value = self.value(idx);
+                        self.emit(format!("assume $IsValid'{}'({});", suffix, value))
+                    }
+                    HavocKind::MutationValue => {
+                        let tmp = self.new_local(ty.skip_reference().clone());
+                        self.emit(format!("havoc {};", tmp));
+                        self.emit(format!("assume $IsValid'{}'({});", suffix, tmp));
+                        self.emit(format!("$t{} := $UpdateMutation($t{}, {});", idx, idx, tmp))
+                    }
+                }
+            }
+            Stop => {
+                self.emit("assume false;".to_string());
+                self.emit("return;".to_string())
+            }
+            IsParent(node, edge) => {
+                let cond = match node {
+                    BorrowNode::LocalRoot(root) => {
+                        format!("l#$Mutation($t{}) == $Local({})", srcs[0], root)
+                    }
+                    BorrowNode::Reference(parent) => {
+                        let edges = path_edges(edge);
+                        let mut conds = vec![format!(
+                            "$IsAncestorMutation($t{}, $t{}, {})",
+                            parent,
+                            srcs[0],
+                            edges.len()
+                        )];
+                        for (k, edge) in edges.iter().enumerate() {
+                            if let BorrowEdge::Field(_, offset) = edge {
+                                conds.push(format!(
+                                    "ReadVec(p#$Mutation($t{}), LenVec(p#$Mutation($t{})) + {}) \
+                                     == {}",
+                                    srcs[0], parent, k, offset
+                                ));
+                            }
+                        }
+                        conds.join(" && ")
+                    }
+                    _ => {
+                        self.error(attr_id, "global storage is not supported by the prover");
+                        return;
+                    }
+                };
+                self.emit(format!("$t{} := {};", dests[0], cond))
+            }
+            WriteBack(node, edge) => self.translate_write_back(attr_id, node, edge, srcs[0]),
+            Add | Sub | Mul | Div | Mod | Shl | Shr | BitOr | BitAnd | Xor => {
+                let Some(suffix) = int_suffix(&self.local_type(dests[0])) else {
+                    self.error(attr_id, "expected an integer operation");
+                    return;
+                };
+                let name = match op {
+                    Add => "Add",
+                    Sub => "Sub",
+                    Mul => "Mul",
+                    Div => "Div",
+                    Mod => "Mod",
+                    Shl => "Shl",
+                    Shr => "Shr",
+                    BitOr => "BitOr",
+                    BitAnd => "BitAnd",
+                    _ => "Xor",
+                };
+                self.emit(format!(
+                    "call $t{} := ${}{}($t{}, $t{});",
+                    dests[0], name, suffix, srcs[0], srcs[1]
+                ));
+                self.emit_abort_check(aa)
+            }
+            CastU8 | CastU16 | CastU32 | CastU64 | CastU128 | CastU256 => {
+                let suffix = match op {
+                    CastU8 => "U8",
+                    CastU16 => "U16",
+                    CastU32 => "U32",
+                    CastU64 => "U64",
+                    CastU128 => "U128",
+                    _ => "U256",
+                };
+                self.emit(format!(
+                    "call $t{} := $Cast{}($t{});",
+                    dests[0], suffix, srcs[0]
+                ));
+                self.emit_abort_check(aa)
+            }
+            Lt | Gt | Le | Ge | Or | And => {
+                let op = match op {
+                    Lt => "<",
+                    Gt => ">",
+                    Le => "<=",
+                    Ge => ">=",
+                    Or => "||",
+                    _ => "&&",
+                };
+                self.emit(format!(
+                    "$t{} := $t{} {} $t{};",
+                    dests[0], srcs[0], op, srcs[1]
+                ))
+            }
+            Not => self.emit(format!("$t{} := !$t{};", dests[0], srcs[0])),
+            Eq | Neq => {
+                let ty = self.local_type(srcs[0]).skip_reference().clone();
+                self.parent.add_type(&ty);
+                let eq = format!(
+                    "$IsEqual'{}'({}, {})",
+                    boogie_type_suffix(env, &ty),
+                    self.value(srcs[0]),
+                    self.value(srcs[1])
+                );
+                let exp = if matches!(op, Eq) {
+                    eq
+                } else {
+                    format!("!{}", eq)
+                };
+                self.emit(format!("$t{} := {};", dests[0], exp))
+            }
+            MoveTo(..) | MoveFrom(..) | Exists(..) | BorrowGlobal(..) | GetGlobal(..) => {
+                self.error(attr_id, "global storage is not supported by the prover")
+            }
+            OpaqueCallBegin(..) | OpaqueCallEnd(..) | Uninit | Destroy | TraceLocal(_)
+            | TraceReturn(_) | TraceAbort | UnpackRef | PackRef | UnpackRefDeep | PackRefDeep => {}
+        }
+    }
+
+    /// Writes the value of the reference `src` back into its parent `node`.
+    fn translate_write_back(
+        &mut self,
+        attr_id: move_stackless_bytecode::stackless_bytecode::AttrId,
+        node: &BorrowNode,
+        edge: &BorrowEdge,
+        src: TempIndex,
+    ) {
+        let env = self.parent.env;
+        let parent = match node {
+            BorrowNode::LocalRoot(root) => {
+                self.emit(format!("$t{} := $Dereference($t{});", root, src));
+                return;
+            }
+            BorrowNode::Reference(parent) => *parent,
+            _ => {
+                self.error(attr_id, "global storage is not supported by the prover");
+                return;
+            }
+        };
+        let edges = path_edges(edge);
+        let child = format!("$Dereference($t{})", src);
+        let parent_ty = self.local_type(parent).skip_reference().clone();
+        match edges.first() {
+            Some(BorrowEdge::Index(IndexEdgeKind::Custom(native))) => {
+                // The child is not part of the value of the parent but stored by the native
+                // model, which provides procedures to read and write it.
+                let child_ty = match edges.get(1) {
+                    Some(BorrowEdge::Field(qid, _)) => {
+                        let qid = qid.instantiate_ref(self.inst);
+                        Type::Struct(qid.module_id, qid.id, qid.inst)
+                    }
+                    None => self.local_type(src).skip_reference().clone(),
+                    Some(_) => {
+                        self.error(attr_id, "unsupported borrow through a native function");
+                        return;
+                    }
+                };
+                let Some(native_env) = find_function_by_name(env, native) else {
+                    self.error(attr_id, &format!("unknown native function `{}`", native));
+                    return;
+                };
+                let inst = [child_ty.clone()];
+                let loc = self.target.get_bytecode_loc(attr_id);
+                let base = self.parent.add_native_call(&native_env, &inst, loc.clone());
+                let (base, suffix) = base.split_at(base.len() - suffix_len(env, &inst));
+                let write_back = format!("{}$write_back{}", base, suffix);
+                let read = format!("{}$read{}", base, suffix);
+                for name in [&write_back, &read] {
+                    self.parent
+                        .native_calls
+                        .entry(name.clone())
+                        .or_insert((loc.clone(), native.clone()));
+                }
+                let id = format!(
+                    "ReadVec(p#$Mutation($t{}), LenVec(p#$Mutation($t{})))",
+                    src, parent
+                );
+                let value = if edges.len() == 1 {
+                    child
+                } else {
+                    let tmp = self.new_local(child_ty.clone());
+                    self.emit(format!("call {} := {}($t{}, {});", tmp, read, parent, id));
+                    match self.update_exp(attr_id, &child_ty, tmp, &edges[1..], 1, src, parent) {
+                        Some(value) => value,
+                        None => return,
+                    }
+                };
+                self.emit(format!(
+                    "call $t{} := {}($t{}, {}, {});",
+                    parent, write_back, parent, id, value
+                ))
+            }
+            _ => {
+                let current = format!("$Dereference($t{})", parent);
+                if let Some(value) =
+                    self.update_exp(attr_id, &parent_ty, current, &edges, 0, src, parent)
+                {
+                    self.emit(format!(
+                        "$t{} := $UpdateMutation($t{}, {});",
+                        parent, parent, value
+                    ))
+                }
+            }
+        }
+    }
+
+    /// The value `current` of type `ty`, updated at the path given by `edges` with the value of
+    /// the reference `src`. The `k`-th edge corresponds to the element of the path of `src`
+    /// which follows the path of `parent` by `k`.
+    #[allow(clippy::too_many_arguments)]
+    fn update_exp(
+        &mut self,
+        attr_id: move_stackless_bytecode::stackless_bytecode::AttrId,
+        ty: &Type,
+        current: String,
+        edges: &[BorrowEdge],
+        k: usize,
+        src: TempIndex,
+        parent: TempIndex,
+    ) -> Option<String> {
+        let env = self.parent.env;
+        let Some(edge) = edges.first() else {
+            return Some(format!("$Dereference($t{})", src));
+        };
+        match edge {
+            BorrowEdge::Field(qid, offset) => {
+                let qid = qid.instantiate_ref(self.inst);
+                let struct_ty = Type::Struct(qid.module_id, qid.id, qid.inst.clone());
+                let name = self.parent.add_type(&struct_ty);
+                let struct_env = env.get_struct_qid(qid.module_id.qualified(qid.id));
+                let field_env = struct_env.get_field_by_offset(*offset);
+                let field = field_env.get_name().display(env.symbol_pool()).to_string();
+                let field_ty = field_env.get_type().instantiate(&qid.inst);
+                let inner = self.update_exp(
+                    attr_id,
+                    &field_ty,
+                    format!("${}#{}({})", field, name, current),
+                    &edges[1..],
+                    k + 1,
+                    src,
+                    parent,
+                )?;
+                Some(format!(
+                    "$Update'{}'_{}({}, {})",
+                    boogie_type_suffix(env, &struct_ty),
+                    field,
+                    current,
+                    inner
+                ))
+            }
+            BorrowEdge::Index(IndexEdgeKind::Vector) => {
+                let Type::Vector(elem) = ty else {
+                    self.error(attr_id, "expected a vector");
+                    return None;
+                };
+                let index = format!(
+                    "ReadVec(p#$Mutation($t{}), LenVec(p#$Mutation($t{})) + {})",
+                    src, parent, k
+                );
+                let inner = self.update_exp(
+                    attr_id,
+                    elem,
+                    format!("ReadVec({}, {})", current, index),
+                    &edges[1..],
+                    k + 1,
+                    src,
+                    parent,
+                )?;
+                Some(format!("UpdateVec({}, {}, {})", current, index, inner))
+            }
+            _ => {
+                self.error(attr_id, "unsupported borrow edge");
+                None
+            }
+        }
+    }
+
+    fn translate_constant(&mut self, constant: &Constant, ty: &Type) -> String {
+        match constant {
+            Constant::Bool(b) => b.to_string(),
+            Constant::U8(n) => n.to_string(),
+            Constant::U16(n) => n.to_string(),
+            Constant::U32(n) => n.to_string(),
+            Constant::U64(n) => n.to_string(),
+            Constant::U128(n) => n.to_string(),
+            Constant::U256(n) => n.to_string(),
+            Constant::Address(a) => a.to_string(),
+            Constant::ByteArray(bytes) => self.parent.vector_literal(
+                &Type::Primitive(PrimitiveType::U8),
+                bytes.iter().map(|b| b.to_string()).collect(),
+            ),
+            Constant::AddressArray(addrs) => self.parent.vector_literal(
+                &Type::Primitive(PrimitiveType::Address),
+                addrs.iter().map(|a| a.to_string()).collect(),
+            ),
+            Constant::Vector(elems) => {
+                let elem_ty = match ty {
+                    Type::Vector(elem) => elem.as_ref().clone(),
+                    _ => Type::Primitive(PrimitiveType::Num),
+                };
+                let elems = elems
+                    .iter()
+                    .map(|c| self.translate_constant(c, &elem_ty))
+                    .collect();
+                self.parent.vector_literal(&elem_ty, elems)
+            }
+        }
+    }
+}
+
+/// Adds an assertion to the table of assertions, returning its index.
+fn add_assert(asserts: &mut Vec<(Loc, String)>, loc: Loc, msg: &str) -> usize {
+    asserts.push((loc, msg.to_string()));
+    asserts.len() - 1
+}
+
+/// The edges of a borrow which contribute to the path of a reference.
+fn path_edges(edge: &BorrowEdge) -> Vec<BorrowEdge> {
+    edge.flatten()
+        .into_iter()
+        .filter(|e| !matches!(e, BorrowEdge::Direct))
+        .cloned()
+        .collect()
+}
+
+fn int_suffix(ty: &Type) -> Option<&'static str> {
+    match ty {
+        Type::Primitive(PrimitiveType::U8) => Some("U8"),
+        Type::Primitive(PrimitiveType::U16) => Some("U16"),
+        Type::Primitive(PrimitiveType::U32) => Some("U32"),
+        Type::Primitive(PrimitiveType::U64) => Some("U64"),
+        Type::Primitive(PrimitiveType::U128) => Some("U128"),
+        Type::Primitive(PrimitiveType::U256) => Some("U256"),
+        _ => None,
+    }
+}
+
+fn field_name(env: &GlobalEnv, mid: ModuleId, sid: StructId, offset: usize) -> String {
+    let struct_env = env.get_struct_qid(mid.qualified(sid));
+    let name = struct_env.get_field_by_offset(offset).get_name();
+    name.display(env.symbol_pool()).to_string()
+}
+
+/// Finds a function by its name qualified with the module name, e.g. `dynamic_field::add`.
+fn find_function_by_name<'env>(env: &'env GlobalEnv, name: &str) -> Option<FunctionEnv<'env>> {
+    let (module_name, fun_name) = name.split_once("::")?;
+    let module_sym = env.symbol_pool().make(module_name);
+    let fun_sym = env.symbol_pool().make(fun_name);
+    env.get_modules()
+        .filter(|m| m.get_name().name() == module_sym)
+        .find_map(|m| m.find_function(fun_sym))
+}
+
+/// The length of the suffix a function instantiation adds to the name of its procedure.
+fn suffix_len(env: &GlobalEnv, inst: &[Type]) -> usize {
+    boogie_inst_suffix(env, inst).len()
+}
+
+/// The names of the procedures declared in Boogie code.
+fn declared_procedures(code: &str) -> BTreeSet<String> {
+    code.match_indices("procedure ")
+        .filter_map(|(pos, _)| {
+            let rest = code[pos + "procedure ".len()..].trim_start();
+            let rest = match rest.strip_prefix('{') {
+                Some(attrs) => attrs[attrs.find('}')? + 1..].trim_start(),
+                None => rest,
+            };
+            let name = &rest[..rest.find('(')?];
+            name.starts_with('$').then(|| name.trim().to_string())
+        })
+        .collect()
+}
+
+fn boogie_module_prefix(module_env: &ModuleEnv) -> String {
+    format!(
+        "${:x}_{}",
+        module_env.get_name().addr(),
+        module_env
+            .get_name()
+            .name()
+            .display(module_env.symbol_pool())
+    )
+}
+
+/// The name of the procedure of a function instance, e.g. `$2_transfer_transfer'u64'`.
+fn boogie_function_name(fun_env: &FunctionEnv, inst: &[Type]) -> String {
+    format!(
+        "{}_{}{}",
+        boogie_module_prefix(&fun_env.module_env),
+        fun_env.get_name().display(fun_env.symbol_pool()),
+        boogie_inst_suffix(fun_env.module_env.env, inst)
+    )
+}
+
+fn boogie_inst_suffix(env: &GlobalEnv, inst: &[Type]) -> String {
+    if inst.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "'{}'",
+            inst.iter().map(|ty| boogie_type_suffix(env, ty)).join("_")
+        )
+    }
+}
+
+/// The suffix of names specific to a type, e.g. `$IsValid'vec'u8''`.
+fn boogie_type_suffix(env: &GlobalEnv, ty: &Type) -> String {
+    match ty {
+        Type::Primitive(p) => match p {
+            PrimitiveType::Bool => "bool",
+            PrimitiveType::U8 => "u8",
+            PrimitiveType::U16 => "u16",
+            PrimitiveType::U32 => "u32",
+            PrimitiveType::U64 => "u64",
+            PrimitiveType::U128 => "u128",
+            PrimitiveType::U256 => "u256",
+            PrimitiveType::Address => "address",
+            PrimitiveType::Signer => "signer",
+            _ => "num",
+        }
+        .to_string(),
+        Type::Vector(elem) => format!("vec{}", boogie_inst_suffix(env, &[elem.as_ref().clone()])),
+        Type::Struct(..) => boogie_type(env, ty),
+        Type::TypeParameter(i) => format!("#{}", i),
+        Type::Reference(_, inner) => boogie_type_suffix(env, inner),
+        _ => "num".to_string(),
+    }
+}
+
+/// The Boogie type of a type.
+fn boogie_type(env: &GlobalEnv, ty: &Type) -> String {
+    match ty {
+        Type::Primitive(PrimitiveType::Bool) => "bool".to_string(),
+        Type::Vector(elem) => format!("Vec ({})", boogie_type(env, elem)),
+        Type::Struct(mid, sid, inst) => {
+            let struct_env = env.get_struct_qid(mid.qualified(*sid));
+            format!(
+                "{}_{}{}",
+                boogie_module_prefix(&struct_env.module_env),
+                struct_env.get_name().display(env.symbol_pool()),
+                boogie_inst_suffix(env, inst)
+            )
+        }
+        Type::TypeParameter(i) => format!("#{}", i),
+        Type::Reference(true, inner) => format!("$Mutation ({})", boogie_type(env, inner)),
+        Type::Reference(false, inner) => boogie_type(env, inner),
+        _ => "int".to_string(),
+    }
+}
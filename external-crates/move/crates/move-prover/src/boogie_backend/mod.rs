@@ -0,0 +1,46 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Boogie backend of the prover: translates the functions with specifications in the target
+//! modules into a Boogie program, and runs Boogie on it to verify them. Loop invariants are not
+//! supported, so a function with a loop is only verified if its specification holds for any
+//! values the variables modified by the loop may take.
+
+use crate::boogie_backend::{bytecode_translator::BoogieTranslator, options::BoogieOptions};
+use move_model::model::{GlobalEnv, Loc};
+use move_stackless_bytecode::function_target_pipeline::FunctionTargetsHolder;
+use std::collections::BTreeMap;
+
+pub mod boogie_wrapper;
+mod bytecode_translator;
+pub mod options;
+mod spec;
+pub mod template;
+
+/// A generated Boogie program.
+#[derive(Debug, Default)]
+pub struct BoogieOutput {
+    /// The Boogie code.
+    pub code: String,
+    /// The assertions in the code, with the location and message to report if they fail. An
+    /// assertion refers to its index in `{:msg "assert_failed(<index>): .."}`.
+    pub asserts: Vec<(Loc, String)>,
+    /// The verification procedures in the code, with the location and name of the function
+    /// they verify.
+    pub verified_functions: BTreeMap<String, (Loc, String)>,
+}
+
+/// Generates the Boogie program verifying the specifications of the functions in the target
+/// modules. Unsupported specifications and code are reported as errors in `env`.
+pub fn generate_boogie(
+    env: &GlobalEnv,
+    options: &BoogieOptions,
+    targets: &FunctionTargetsHolder,
+) -> anyhow::Result<BoogieOutput> {
+    let mut specs = BTreeMap::new();
+    for module_env in env.get_modules().filter(|m| m.is_target()) {
+        specs.extend(spec::parse_module_specs(&module_env));
+    }
+    BoogieTranslator::new(env, options, targets).translate(&specs)
+}
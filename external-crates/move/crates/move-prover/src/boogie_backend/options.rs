@@ -0,0 +1,85 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// Default for the timeout (in seconds) of each verification condition.
+const DEFAULT_VC_TIMEOUT: usize = 40;
+
+/// Boogie models for native functions, supplied by the client of the prover (e.g. for the natives
+/// of a framework). The template is rendered together with the prelude, see `template.rs`.
+#[derive(Debug, Clone, Default)]
+pub struct CustomNativeOptions {
+    /// The template, in the same syntax as the prelude.
+    pub template_bytes: Vec<u8>,
+    /// Native functions modeled by the template, each with the name of the instance list the
+    /// template iterates over for them, and whether only struct types are valid instances.
+    /// Functions are given by their qualified name, e.g. `0x2::transfer::transfer_impl`, or a
+    /// whole module by its name, e.g. `0x2::event`.
+    pub native_instances: Vec<(String, String, bool)>,
+}
+
+/// Options for the Boogie backend of the prover.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BoogieOptions {
+    /// Path to the Boogie executable. Defaults to the `BOOGIE_EXE` environment variable.
+    pub boogie_exe: String,
+    /// Path to the Z3 executable. Defaults to the `Z3_EXE` environment variable.
+    pub z3_exe: String,
+    /// Additional flags passed on to Boogie.
+    pub boogie_flags: Vec<String>,
+    /// Timeout (in seconds) for each verification condition.
+    pub vc_timeout: usize,
+    /// Number of cores Boogie may use to verify conditions in parallel.
+    pub proc_cores: usize,
+    /// Whether to keep the generated Boogie file after verification.
+    pub keep_artifacts: bool,
+    /// Boogie models for natives which are not part of the prelude.
+    #[serde(skip)]
+    pub custom_natives: Option<CustomNativeOptions>,
+}
+
+impl Default for BoogieOptions {
+    fn default() -> Self {
+        let get_env = |s| std::env::var(s).unwrap_or_default();
+        Self {
+            boogie_exe: get_env("BOOGIE_EXE"),
+            z3_exe: get_env("Z3_EXE"),
+            boogie_flags: vec![],
+            vc_timeout: DEFAULT_VC_TIMEOUT,
+            proc_cores: 4,
+            keep_artifacts: false,
+            custom_natives: None,
+        }
+    }
+}
+
+impl BoogieOptions {
+    /// Returns the command line to run Boogie on `boogie_file`, or an error if no Boogie or Z3
+    /// executable is configured.
+    pub fn get_boogie_command(&self, boogie_file: &str) -> anyhow::Result<Vec<String>> {
+        if self.boogie_exe.is_empty() {
+            anyhow::bail!(
+                "no Boogie executable configured: set `BOOGIE_EXE` or `backend.boogie_exe`"
+            );
+        }
+        if self.z3_exe.is_empty() {
+            anyhow::bail!("no Z3 executable configured: set `Z3_EXE` or `backend.z3_exe`");
+        }
+        let mut result = vec![
+            self.boogie_exe.clone(),
+            "-doModSetAnalysis".to_string(),
+            "-monomorphize".to_string(),
+            "-printVerifiedProceduresCount:0".to_string(),
+            "-errorTrace:0".to_string(),
+            format!("-proverOpt:PROVER_PATH={}", self.z3_exe),
+            format!("-timeLimit:{}", self.vc_timeout),
+            format!("-vcsCores:{}", self.proc_cores.max(1)),
+        ];
+        result.extend(self.boogie_flags.iter().cloned());
+        result.push(boogie_file.to_string());
+        Ok(result)
+    }
+}
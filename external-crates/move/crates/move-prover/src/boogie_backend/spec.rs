@@ -0,0 +1,696 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parses the function specifications the Boogie backend verifies. The compiler in this tree
+//! drops `spec` blocks, so they are read from the module source. The supported subset is
+//!
+//! ```text
+//! spec fun_name {
+//!     requires <exp>;
+//!     ensures <exp>;
+//!     aborts_if <exp>;
+//! }
+//! ```
+//!
+//! where expressions consist of literals, parameters, `result` (`result_1`, `result_2`, .. for
+//! multiple return values), module constants, `MAX_U8` .. `MAX_U256`, `old(e)`, `len(v)`,
+//! field selection, vector indexing, `if (c) e1 else e2`, and the usual logical, comparison
+//! and arithmetic operators. Arithmetic in specifications is unbounded.
+
+use codespan_reporting::diagnostic::Severity;
+use move_model::model::{FunId, Loc, ModuleEnv, QualifiedId};
+use std::collections::BTreeMap;
+
+/// The specification of a function.
+#[derive(Debug, Clone)]
+pub struct FunSpec {
+    pub loc: Loc,
+    pub conditions: Vec<Condition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionKind {
+    Requires,
+    Ensures,
+    AbortsIf,
+}
+
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub kind: ConditionKind,
+    pub loc: Loc,
+    pub exp: Exp,
+}
+
+#[derive(Debug, Clone)]
+pub struct Exp {
+    pub loc: Loc,
+    pub kind: ExpKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExpKind {
+    Bool(bool),
+    /// A number in decimal notation.
+    Number(String),
+    /// An address in decimal notation.
+    Address(String),
+    Name(String),
+    Old(Box<Exp>),
+    Len(Box<Exp>),
+    Field(Box<Exp>, String),
+    Index(Box<Exp>, Box<Exp>),
+    Not(Box<Exp>),
+    Binary(BinOp, Box<Exp>, Box<Exp>),
+    IfElse(Box<Exp>, Box<Exp>, Box<Exp>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Iff,
+    Implies,
+    Or,
+    And,
+    Eq,
+    Neq,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl BinOp {
+    /// Binding strength, and whether the operator associates to the right.
+    fn precedence(self) -> (u8, bool) {
+        use BinOp::*;
+        match self {
+            Iff => (1, false),
+            Implies => (2, true),
+            Or => (3, false),
+            And => (4, false),
+            Eq | Neq | Lt | Gt | Le | Ge => (5, false),
+            Add | Sub => (6, false),
+            Mul | Div | Mod => (7, false),
+        }
+    }
+
+    fn from_symbol(s: &str) -> Option<BinOp> {
+        use BinOp::*;
+        Some(match s {
+            "<==>" => Iff,
+            "==>" => Implies,
+            "||" => Or,
+            "&&" => And,
+            "==" => Eq,
+            "!=" => Neq,
+            "<" => Lt,
+            ">" => Gt,
+            "<=" => Le,
+            ">=" => Ge,
+            "+" => Add,
+            "-" => Sub,
+            "*" => Mul,
+            "/" => Div,
+            "%" => Mod,
+            _ => return None,
+        })
+    }
+}
+
+/// Parses the specifications of the functions in `module_env`, reporting errors for
+/// unsupported constructs to the environment.
+pub fn parse_module_specs(module_env: &ModuleEnv) -> BTreeMap<QualifiedId<FunId>, FunSpec> {
+    let env = module_env.env;
+    let module_loc = module_env.get_loc();
+    let source = match env.get_source(&module_loc) {
+        Ok(source) => source,
+        Err(_) => return BTreeMap::new(),
+    };
+    let mut parser = Parser {
+        module_env,
+        module_loc: module_loc.clone(),
+        base: module_loc.span().start().to_usize(),
+        tokens: tokenize(source),
+        pos: 0,
+        specs: BTreeMap::new(),
+    };
+    parser.parse_module();
+    parser.specs
+}
+
+// =================================================================================================
+// Tokenizer
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokKind {
+    Ident,
+    Num,
+    Sym,
+    /// A string or byte string literal.
+    Str,
+}
+
+#[derive(Debug, Clone)]
+struct Token<'a> {
+    kind: TokKind,
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+const SYMBOLS: &[&str] = &[
+    "<==>", "==>", "==", "!=", "<=", ">=", "&&", "||", "::", "(", ")", "{", "}", "[", "]", "<",
+    ">", "+", "-", "*", "/", "%", "!", ",", ";", ".", "@", ":", "&", "|", "^", "=", "#",
+];
+
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let bytes = source.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if source[i..].starts_with("//") {
+            i = source[i..].find('\n').map_or(bytes.len(), |p| i + p);
+            continue;
+        }
+        if source[i..].starts_with("/*") {
+            i = source[i + 2..]
+                .find("*/")
+                .map_or(bytes.len(), |p| i + p + 4);
+            continue;
+        }
+        let kind = if c == b'"' || ((c == b'b' || c == b'x') && bytes.get(i + 1) == Some(&b'"')) {
+            i += if c == b'"' { 1 } else { 2 };
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(bytes.len());
+            TokKind::Str
+        } else if c.is_ascii_alphabetic() || c == b'_' {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            TokKind::Ident
+        } else if c.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            TokKind::Num
+        } else {
+            let sym = SYMBOLS.iter().find(|s| source[i..].starts_with(**s));
+            // Characters which are not symbols of the specification language (like `'` of
+            // loop labels) are passed on as single character symbols.
+            i += sym.map_or_else(
+                || source[i..].chars().next().unwrap().len_utf8(),
+                |s| s.len(),
+            );
+            TokKind::Sym
+        };
+        tokens.push(Token {
+            kind,
+            text: &source[start..i],
+            start,
+            end: i,
+        });
+    }
+    tokens
+}
+
+/// Converts a number literal (decimal or hexadecimal, with optional `_` separators and type
+/// suffix) into decimal notation.
+fn number_to_decimal(text: &str) -> Option<String> {
+    let text = text.replace('_', "");
+    let text = ["u8", "u16", "u32", "u64", "u128", "u256"]
+        .iter()
+        .find_map(|suffix| text.strip_suffix(suffix))
+        .unwrap_or(&text);
+    let (digits, radix) = match text.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (text, 10),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    // Little-endian decimal digits of the result.
+    let mut result = vec![0u32];
+    for c in digits.chars() {
+        let mut carry = c.to_digit(radix)?;
+        for d in result.iter_mut() {
+            let v = *d * radix + carry;
+            *d = v % 10;
+            carry = v / 10;
+        }
+        while carry > 0 {
+            result.push(carry % 10);
+            carry /= 10;
+        }
+    }
+    while result.len() > 1 && result.last() == Some(&0) {
+        result.pop();
+    }
+    Some(
+        result
+            .iter()
+            .rev()
+            .map(|d| char::from_digit(*d, 10).unwrap())
+            .collect(),
+    )
+}
+
+// =================================================================================================
+// Parser
+
+struct Parser<'env, 'src> {
+    module_env: &'env ModuleEnv<'env>,
+    module_loc: Loc,
+    base: usize,
+    tokens: Vec<Token<'src>>,
+    pos: usize,
+    specs: BTreeMap<QualifiedId<FunId>, FunSpec>,
+}
+
+/// Marks a parse error which has already been reported.
+struct Reported;
+
+type ParseResult<T> = Result<T, Reported>;
+
+impl<'env, 'src> Parser<'env, 'src> {
+    fn peek(&self) -> Option<&Token<'src>> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_text(&self) -> &str {
+        self.peek().map_or("", |t| t.text)
+    }
+
+    fn is_sym(&self, sym: &str) -> bool {
+        matches!(self.peek(), Some(t) if t.kind == TokKind::Sym && t.text == sym)
+    }
+
+    fn advance(&mut self) -> Option<Token<'src>> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// The location from the start of token `from` to the end of the last consumed token.
+    fn loc_from(&self, from: usize) -> Loc {
+        let start = self.tokens.get(from).map_or(0, |t| t.start);
+        let end = self.tokens[..self.pos.min(self.tokens.len())]
+            .last()
+            .map_or(start, |t| t.end.max(start));
+        self.loc(start, end)
+    }
+
+    fn loc(&self, start: usize, end: usize) -> Loc {
+        self.module_loc
+            .with_span((self.base + start) as u32, (self.base + end) as u32)
+    }
+
+    fn current_loc(&self) -> Loc {
+        match self.peek() {
+            Some(t) => self.loc(t.start, t.end),
+            None => self.loc_from(self.tokens.len().saturating_sub(1)),
+        }
+    }
+
+    fn error<T>(&self, loc: &Loc, msg: &str) -> ParseResult<T> {
+        self.module_env.env.error(loc, msg);
+        Err(Reported)
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> ParseResult<()> {
+        if self.is_sym(sym) {
+            self.advance();
+            Ok(())
+        } else {
+            let msg = format!("expected `{}` but found `{}`", sym, self.peek_text());
+            self.error(&self.current_loc(), &msg)
+        }
+    }
+
+    /// Skips tokens up to and including the `}` which closes the `{` just consumed.
+    fn skip_block(&mut self) {
+        let mut depth = 1;
+        while let Some(token) = self.advance() {
+            match (token.kind, token.text) {
+                (TokKind::Sym, "{") => depth += 1,
+                (TokKind::Sym, "}") => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips tokens up to and including the next `;`, or up to the `}` closing the current
+    /// block.
+    fn skip_member(&mut self) {
+        while let Some(token) = self.peek() {
+            match (token.kind, token.text) {
+                (TokKind::Sym, ";") => {
+                    self.advance();
+                    return;
+                }
+                (TokKind::Sym, "}") => return,
+                (TokKind::Sym, "{") => {
+                    self.advance();
+                    self.skip_block();
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    fn parse_module(&mut self) {
+        // Find the start of the module body, which is either a block or, for a module declared
+        // with `module a::m;`, the rest of the file.
+        while let Some(token) = self.advance() {
+            if token.kind == TokKind::Sym && (token.text == "{" || token.text == ";") {
+                break;
+            }
+        }
+        let mut depth = 0;
+        while let Some(token) = self.peek().cloned() {
+            match (token.kind, token.text) {
+                (TokKind::Sym, "{") => depth += 1,
+                (TokKind::Sym, "}") => depth -= 1,
+                (TokKind::Ident, "spec") if depth == 0 => {
+                    let start = self.pos;
+                    self.advance();
+                    // Errors are reported where they occur; continue with the next member.
+                    let _ = self.parse_spec_member(start);
+                    continue;
+                }
+                (TokKind::Ident, "spec")
+                    if self.tokens.get(self.pos + 1).map(|t| t.text) == Some("{") =>
+                {
+                    self.advance();
+                    let loc = self.loc_from(self.pos - 1);
+                    self.module_env
+                        .env
+                        .error(&loc, "inline spec blocks are not supported by the prover");
+                    continue;
+                }
+                _ => {}
+            }
+            self.advance();
+        }
+    }
+
+    fn parse_spec_member(&mut self, start: usize) -> ParseResult<()> {
+        let env = self.module_env.env;
+        let name = match self.peek() {
+            Some(t) if t.kind == TokKind::Ident => t.text,
+            _ => {
+                let loc = self.current_loc();
+                self.skip_spec_block();
+                return self.error(
+                    &loc,
+                    "module specifications are not supported by the prover",
+                );
+            }
+        };
+        if matches!(name, "module" | "schema" | "fun" | "native") {
+            let loc = self.current_loc();
+            self.skip_spec_block();
+            let msg = format!("`spec {}` is not supported by the prover", name);
+            return self.error(&loc, &msg);
+        }
+        let name_loc = self.current_loc();
+        self.advance();
+        // Skip an optional signature.
+        while !self.is_sym("{") && self.peek().is_some() {
+            self.advance();
+        }
+        self.expect_sym("{")?;
+        let symbol = env.symbol_pool().make(name);
+        let fun_env = match self.module_env.find_function(symbol) {
+            Some(fun_env) => fun_env,
+            None => {
+                self.skip_block();
+                let msg = if self.module_env.find_struct(symbol).is_some() {
+                    format!(
+                        "struct specifications are not supported by the prover (`{}`)",
+                        name
+                    )
+                } else {
+                    format!("no function named `{}` in this module", name)
+                };
+                return self.error(&name_loc, &msg);
+            }
+        };
+        let mut conditions = vec![];
+        while !self.is_sym("}") {
+            let Some(token) = self.peek().cloned() else {
+                return self.error(&self.loc_from(start), "unterminated spec block");
+            };
+            let cond_start = self.pos;
+            let kind = match token.text {
+                "requires" => ConditionKind::Requires,
+                "ensures" => ConditionKind::Ensures,
+                "aborts_if" => ConditionKind::AbortsIf,
+                "pragma" => {
+                    self.skip_member();
+                    env.diag(
+                        Severity::Warning,
+                        &self.loc_from(cond_start),
+                        "pragmas are ignored by the prover",
+                    );
+                    continue;
+                }
+                _ => {
+                    self.skip_member();
+                    let msg = format!("`{}` is not supported in specifications", token.text);
+                    env.error(&self.loc_from(cond_start), &msg);
+                    continue;
+                }
+            };
+            self.advance();
+            match self.parse_condition(kind, cond_start) {
+                Ok(cond) => conditions.push(cond),
+                Err(Reported) => self.skip_member(),
+            }
+        }
+        self.advance();
+        let loc = self.loc_from(start);
+        self.specs
+            .entry(fun_env.get_qualified_id())
+            .or_insert_with(|| FunSpec {
+                loc,
+                conditions: vec![],
+            })
+            .conditions
+            .extend(conditions);
+        Ok(())
+    }
+
+    /// Skips to the end of the spec block at the current position.
+    fn skip_spec_block(&mut self) {
+        while !self.is_sym("{") && self.peek().is_some() {
+            self.advance();
+        }
+        self.advance();
+        self.skip_block();
+    }
+
+    fn parse_condition(&mut self, kind: ConditionKind, start: usize) -> ParseResult<Condition> {
+        let exp = self.parse_exp(kind)?;
+        if self.peek_text() == "with" {
+            return self.error(
+                &self.current_loc(),
+                "`aborts_if .. with` is not supported by the prover",
+            );
+        }
+        self.expect_sym(";")?;
+        Ok(Condition {
+            kind,
+            loc: self.loc_from(start),
+            exp,
+        })
+    }
+
+    fn parse_exp(&mut self, kind: ConditionKind) -> ParseResult<Exp> {
+        self.parse_binary(kind, 1)
+    }
+
+    fn parse_binary(&mut self, kind: ConditionKind, min_prec: u8) -> ParseResult<Exp> {
+        let start = self.pos;
+        let mut lhs = self.parse_unary(kind)?;
+        loop {
+            let op = match self.peek() {
+                Some(t) if t.kind == TokKind::Sym => BinOp::from_symbol(t.text),
+                _ => None,
+            };
+            let Some(op) = op else { break };
+            let (prec, right_assoc) = op.precedence();
+            if prec < min_prec {
+                break;
+            }
+            self.advance();
+            let rhs = self.parse_binary(kind, if right_assoc { prec } else { prec + 1 })?;
+            lhs = Exp {
+                loc: self.loc_from(start),
+                kind: ExpKind::Binary(op, Box::new(lhs), Box::new(rhs)),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self, kind: ConditionKind) -> ParseResult<Exp> {
+        let start = self.pos;
+        if self.is_sym("!") {
+            self.advance();
+            let exp = self.parse_unary(kind)?;
+            return Ok(Exp {
+                loc: self.loc_from(start),
+                kind: ExpKind::Not(Box::new(exp)),
+            });
+        }
+        let mut exp = self.parse_primary(kind)?;
+        loop {
+            if self.is_sym(".") {
+                self.advance();
+                match self.advance() {
+                    Some(t) if t.kind == TokKind::Ident => {
+                        exp = Exp {
+                            loc: self.loc_from(start),
+                            kind: ExpKind::Field(Box::new(exp), t.text.to_string()),
+                        }
+                    }
+                    _ => return self.error(&self.loc_from(start), "expected a field name"),
+                }
+            } else if self.is_sym("[") {
+                self.advance();
+                let index = self.parse_exp(kind)?;
+                self.expect_sym("]")?;
+                exp = Exp {
+                    loc: self.loc_from(start),
+                    kind: ExpKind::Index(Box::new(exp), Box::new(index)),
+                }
+            } else {
+                return Ok(exp);
+            }
+        }
+    }
+
+    fn parse_primary(&mut self, kind: ConditionKind) -> ParseResult<Exp> {
+        let start = self.pos;
+        let loc = self.current_loc();
+        let Some(token) = self.advance() else {
+            return self.error(&loc, "unexpected end of specification");
+        };
+        let exp_kind = match (token.kind, token.text) {
+            (TokKind::Num, text) => match number_to_decimal(text) {
+                Some(n) => ExpKind::Number(n),
+                None => return self.error(&loc, &format!("invalid number `{}`", text)),
+            },
+            (TokKind::Ident, "true") => ExpKind::Bool(true),
+            (TokKind::Ident, "false") => ExpKind::Bool(false),
+            (TokKind::Sym, "@") => match self.advance() {
+                Some(t) if t.kind == TokKind::Num => match number_to_decimal(t.text) {
+                    Some(n) => ExpKind::Address(n),
+                    None => return self.error(&loc, &format!("invalid address `{}`", t.text)),
+                },
+                _ => {
+                    return self.error(
+                        &self.loc_from(start),
+                        "only numerical addresses are supported in specifications",
+                    )
+                }
+            },
+            (TokKind::Sym, "(") => {
+                let exp = self.parse_exp(kind)?;
+                self.expect_sym(")")?;
+                return Ok(Exp {
+                    loc: self.loc_from(start),
+                    kind: exp.kind,
+                });
+            }
+            (TokKind::Ident, "if") => {
+                self.expect_sym("(")?;
+                let cond = self.parse_exp(kind)?;
+                self.expect_sym(")")?;
+                let then = self.parse_exp(kind)?;
+                if self.peek_text() != "else" {
+                    return self.error(&self.current_loc(), "expected `else`");
+                }
+                self.advance();
+                let els = self.parse_exp(kind)?;
+                ExpKind::IfElse(Box::new(cond), Box::new(then), Box::new(els))
+            }
+            (TokKind::Ident, name) if self.is_sym("(") => {
+                self.advance();
+                let arg = self.parse_exp(kind)?;
+                self.expect_sym(")")?;
+                match name {
+                    "old" if kind == ConditionKind::Ensures => ExpKind::Old(Box::new(arg)),
+                    "old" => {
+                        return self.error(
+                            &self.loc_from(start),
+                            "`old` can only be used in `ensures` conditions",
+                        )
+                    }
+                    "len" => ExpKind::Len(Box::new(arg)),
+                    _ => {
+                        let msg = format!(
+                            "function `{}` is not supported in specifications \
+                             (only `old` and `len` are)",
+                            name
+                        );
+                        return self.error(&self.loc_from(start), &msg);
+                    }
+                }
+            }
+            (TokKind::Ident, name) => {
+                if self.is_sym("::") || self.is_sym("<") {
+                    return self.error(
+                        &self.current_loc(),
+                        "qualified names are not supported in specifications",
+                    );
+                }
+                ExpKind::Name(name.to_string())
+            }
+            (_, text) => {
+                return self.error(&loc, &format!("unexpected `{}` in specification", text))
+            }
+        };
+        Ok(Exp {
+            loc: self.loc_from(start),
+            kind: exp_kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_to_decimal() {
+        assert_eq!(number_to_decimal("0").unwrap(), "0");
+        assert_eq!(number_to_decimal("1_000u64").unwrap(), "1000");
+        assert_eq!(number_to_decimal("0xff").unwrap(), "255");
+        assert_eq!(
+            number_to_decimal("0xffffffffffffffffffffffffffffffff").unwrap(),
+            "340282366920938463463374607431768211455"
+        );
+        assert!(number_to_decimal("0x").is_none());
+        assert!(number_to_decimal("12a").is_none());
+    }
+}
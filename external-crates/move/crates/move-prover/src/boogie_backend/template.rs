@@ -0,0 +1,215 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Renders the Boogie prelude and native templates. Templates use the subset of the Tera syntax
+//! needed to instantiate a native model once per type it is used with:
+//!
+//! ```text
+//! {%- for instance in vec_instances %}
+//! {%- set S = "'" ~ instance.suffix ~ "'" -%}
+//! procedure {:inline 1} $1_vector_length{{S}}(v: Vec ({{instance.name}})) returns (l: int);
+//! {%- endfor %}
+//! ```
+//!
+//! A `-` next to a delimiter removes the whitespace (including newlines) on that side of it.
+
+use anyhow::{anyhow, bail};
+use std::collections::BTreeMap;
+
+/// A type a template is instantiated with.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TypeInstance {
+    /// The Boogie type.
+    pub name: String,
+    /// The suffix of names instantiated with the type.
+    pub suffix: String,
+}
+
+enum Segment {
+    Text(String),
+    Tag { statement: bool, content: String },
+}
+
+/// Renders `template`, where each `for` loop iterates over the named list in `instances`.
+/// Lists which are not given are empty.
+pub fn render(
+    template: &str,
+    instances: &BTreeMap<String, Vec<TypeInstance>>,
+) -> anyhow::Result<String> {
+    let segments = parse(template)?;
+    let mut out = String::new();
+    let mut i = 0;
+    while i < segments.len() {
+        match &segments[i] {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Tag {
+                statement: true,
+                content,
+            } => {
+                let words: Vec<_> = content.split_whitespace().collect();
+                let (var, list) = match words.as_slice() {
+                    ["for", var, "in", list] => (*var, *list),
+                    _ => bail!("unexpected statement `{{% {} %}}` in template", content),
+                };
+                let end = segments[i + 1..]
+                    .iter()
+                    .position(|s| match s {
+                        Segment::Tag { statement, content } => *statement && content == "endfor",
+                        _ => false,
+                    })
+                    .map(|p| i + 1 + p)
+                    .ok_or_else(|| anyhow!("`for` without `endfor` in template"))?;
+                for instance in instances.get(list).into_iter().flatten() {
+                    let mut vars = BTreeMap::new();
+                    for segment in &segments[i + 1..end] {
+                        match segment {
+                            Segment::Text(text) => out.push_str(text),
+                            Segment::Tag {
+                                statement: true,
+                                content,
+                            } => {
+                                let (name, exp) = content
+                                    .strip_prefix("set ")
+                                    .and_then(|s| s.split_once('='))
+                                    .ok_or_else(|| {
+                                        anyhow!(
+                                            "unexpected statement `{{% {} %}}` in loop",
+                                            content
+                                        )
+                                    })?;
+                                let value = eval(exp, var, instance, &vars)?;
+                                vars.insert(name.trim().to_string(), value);
+                            }
+                            Segment::Tag {
+                                statement: false,
+                                content,
+                            } => out.push_str(&eval(content, var, instance, &vars)?),
+                        }
+                    }
+                }
+                i = end;
+            }
+            Segment::Tag {
+                statement: false,
+                content,
+            } => bail!(
+                "expression `{{{{{}}}}}` outside of a loop in template",
+                content
+            ),
+        }
+        i += 1;
+    }
+    Ok(out)
+}
+
+/// Splits the template into text and tags, applying whitespace control.
+fn parse(template: &str) -> anyhow::Result<Vec<Segment>> {
+    let mut segments = vec![];
+    let mut rest = template;
+    let mut trim_next = false;
+    while let Some(start) = rest.find("{%").into_iter().chain(rest.find("{{")).min() {
+        let statement = rest[start..].starts_with("{%");
+        let close = if statement { "%}" } else { "}}" };
+        let end = rest[start..]
+            .find(close)
+            .map(|p| start + p)
+            .ok_or_else(|| anyhow!("unterminated tag in template"))?;
+        let mut content = &rest[start + 2..end];
+        let mut text = &rest[..start];
+        if trim_next {
+            text = text.trim_start();
+        }
+        if let Some(c) = content.strip_prefix('-') {
+            text = text.trim_end();
+            content = c;
+        }
+        trim_next = false;
+        if let Some(c) = content.strip_suffix('-') {
+            trim_next = true;
+            content = c;
+        }
+        segments.push(Segment::Text(text.to_string()));
+        segments.push(Segment::Tag {
+            statement,
+            content: content.trim().to_string(),
+        });
+        rest = &rest[end + 2..];
+    }
+    segments.push(Segment::Text(if trim_next {
+        rest.trim_start().to_string()
+    } else {
+        rest.to_string()
+    }));
+    Ok(segments)
+}
+
+/// Evaluates a concatenation (`~`) of string literals, variables and instance fields.
+fn eval(
+    exp: &str,
+    loop_var: &str,
+    instance: &TypeInstance,
+    vars: &BTreeMap<String, String>,
+) -> anyhow::Result<String> {
+    let mut result = String::new();
+    for term in exp.split('~').map(str::trim) {
+        if let Some(s) = term.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            result.push_str(s);
+        } else if let Some(field) = term
+            .strip_prefix(loop_var)
+            .and_then(|s| s.strip_prefix('.'))
+        {
+            match field {
+                "name" => result.push_str(&instance.name),
+                "suffix" => result.push_str(&instance.suffix),
+                _ => bail!("unknown field `{}` of `{}` in template", field, loop_var),
+            }
+        } else if let Some(value) = vars.get(term) {
+            result.push_str(value);
+        } else {
+            bail!("undefined variable `{}` in template", term)
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_instances() {
+        let template = "\
+header
+{%- for instance in vec_instances %}
+{%- set S = \"'\" ~ instance.suffix ~ \"'\" -%}
+
+// instance {{instance.suffix}}
+procedure $len{{S}}(v: Vec ({{instance.name}}));
+{%- endfor %}
+footer
+";
+        let instances = BTreeMap::from([(
+            "vec_instances".to_string(),
+            vec![
+                TypeInstance {
+                    name: "int".to_string(),
+                    suffix: "u64".to_string(),
+                },
+                TypeInstance {
+                    name: "bool".to_string(),
+                    suffix: "bool".to_string(),
+                },
+            ],
+        )]);
+        assert_eq!(
+            render(template, &instances).unwrap(),
+            "header// instance u64\nprocedure $len'u64'(v: Vec (int));\
+             // instance bool\nprocedure $len'bool'(v: Vec (bool));\nfooter\n"
+        );
+        assert_eq!(
+            render(template, &BTreeMap::new()).unwrap(),
+            "header\nfooter\n"
+        );
+    }
+}
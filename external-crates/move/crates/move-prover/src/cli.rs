@@ -22,6 +22,7 @@ use simplelog::{
     CombinedLogger, Config, ConfigBuilder, LevelPadding, SimpleLogger, TermLogger, TerminalMode,
 };
 
+use crate::boogie_backend::options::BoogieOptions;
 use codespan_reporting::diagnostic::Severity;
 use move_docgen::DocgenOptions;
 use move_errmapgen::ErrmapOptions;
@@ -70,6 +71,8 @@ pub struct Options {
     pub docgen: DocgenOptions,
     /// Options for the prover.
     pub prover: ProverOptions,
+    /// Options for the prover backend.
+    pub backend: BoogieOptions,
     /// Options for the error map generator.
     /// TODO: this currently create errors during deserialization, so skip them for this.
     #[serde(skip_serializing)]
@@ -89,6 +92,7 @@ impl Default for Options {
             move_named_address_values: vec![],
             model_builder: ModelBuilderOptions::default(),
             prover: ProverOptions::default(),
+            backend: BoogieOptions::default(),
             docgen: DocgenOptions::default(),
             errmapgen: ErrmapOptions::default(),
             experimental_pipeline: false,
@@ -604,6 +608,21 @@ impl Options {
         if matches.get_flag("generate-only") {
             options.prover.generate_only = true;
         }
+        if matches.get_flag("keep") {
+            options.backend.keep_artifacts = true;
+        }
+        if matches.contains_id("cores") {
+            options.backend.proc_cores = matches
+                .get_one::<String>("cores")
+                .unwrap()
+                .parse::<usize>()?;
+        }
+        if matches.contains_id("timeout") {
+            options.backend.vc_timeout = matches
+                .get_one::<String>("timeout")
+                .unwrap()
+                .parse::<usize>()?;
+        }
         if let Some(m) = matches.get_many::<String>("sources") {
             options.move_sources = m.cloned().collect();
         }
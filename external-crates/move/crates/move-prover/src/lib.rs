@@ -4,7 +4,10 @@
 
 #![forbid(unsafe_code)]
 
-use crate::cli::Options;
+use crate::{
+    boogie_backend::{boogie_wrapper::verify_boogie, generate_boogie},
+    cli::Options,
+};
 use anyhow::anyhow;
 use codespan_reporting::{
     diagnostic::Severity,
@@ -28,6 +31,7 @@ use std::{
     time::Instant,
 };
 
+pub mod boogie_backend;
 pub mod cli;
 
 // =================================================================================================
@@ -94,6 +98,11 @@ pub fn run_move_prover_with_model<W: WriteColor>(
     )?;
     env.report_diag(error_writer, options.prover.report_severity);
 
+    // Loop invariants are not supported, so loops are left to Boogie, which havocs the
+    // variables a loop modifies at its head.
+    let mut options = options;
+    options.prover.skip_loop_analysis = true;
+
     // Add the prover options as an extension to the environment, so they can be accessed
     // from there.
     env.set_extension(options.prover.clone());
@@ -120,8 +129,45 @@ pub fn run_move_prover_with_model<W: WriteColor>(
         };
     }
 
+    // Create and process bytecode
+    let now = Instant::now();
+    let targets = create_and_process_bytecode(&options, env);
+    let trafo_duration = now.elapsed();
+    check_errors(
+        env,
+        &options,
+        error_writer,
+        "exiting with bytecode transformation errors",
+    )?;
+
+    // Generate boogie code
+    let now = Instant::now();
+    let output = generate_boogie(env, &options.backend, &targets)?;
+    let gen_duration = now.elapsed();
+    check_errors(
+        env,
+        &options,
+        error_writer,
+        "exiting with condition generation errors",
+    )?;
+
+    // Verify boogie code
+    let now = Instant::now();
+    if options.prover.generate_only {
+        fs::write(&options.output_path, &output.code)?;
+    } else if !output.verified_functions.is_empty() {
+        verify_boogie(env, &options.backend, &output, &options.output_path)?;
+    }
+    let verify_duration = now.elapsed();
+
     // Report durations.
-    info!("{:.3}s build", build_duration.as_secs_f64(),);
+    info!(
+        "{:.3}s build, {:.3}s trafo, {:.3}s gen, {:.3}s verify",
+        build_duration.as_secs_f64(),
+        trafo_duration.as_secs_f64(),
+        gen_duration.as_secs_f64(),
+        verify_duration.as_secs_f64(),
+    );
     check_errors(
         env,
         &options,